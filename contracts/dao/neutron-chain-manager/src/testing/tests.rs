@@ -1,8 +1,7 @@
 use crate::contract::{
-    execute_add_strategy, execute_execute_messages, execute_remove_strategy, instantiate,
+    execute, execute_add_strategy, execute_execute_messages, execute_remove_strategy, instantiate,
 };
 use crate::error::ContractError::{InvalidDemotion, Unauthorized};
-use crate::msg::InstantiateMsg;
 use crate::msg::Permission::{
     CronPermission, ParamChangePermission, UpdateCronParamsPermission,
     UpdateTokenfactoryParamsPermission,
@@ -11,10 +10,12 @@ use crate::msg::{
     CronPermission as CronPermissionType, CronUpdateParamsPermission, StrategyMsg,
     TokenfactoryUpdateParamsPermission,
 };
+use crate::msg::{ExecuteMsg, InstantiateMsg};
 use crate::msg::{ParamChangePermission as ParamChangePermissionType, ParamPermission};
+use crate::state::STRATEGIES;
 use crate::testing::mock_querier::mock_dependencies;
 use cosmwasm_std::testing::{message_info, mock_env};
-use cosmwasm_std::{Addr, BankMsg, Coin, CosmosMsg, Uint128};
+use cosmwasm_std::{from_json, Addr, BankMsg, Coin, CosmosMsg, Uint128};
 use neutron_sdk::bindings::msg::{
     AdminProposal, NeutronMsg, ParamChange, ParamChangeProposal, ProposalExecuteMessage,
 };
@@ -269,6 +270,54 @@ fn test_remove_strategy_invalid_demotion() {
     assert_eq!(err, InvalidDemotion {});
 }
 
+/// Checks that the `RemoveStrategy` emergency action proposed by the security
+/// subDAO is executed once the security subDAO core has an `AllowAll` strategy.
+#[test]
+fn test_remove_strategy_by_security_subdao() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let info = message_info(&Addr::unchecked("neutron_dao_address"), &[]);
+
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            initial_strategy_address: Addr::unchecked("neutron_dao_address".to_string()),
+        },
+    )
+    .unwrap();
+    execute_add_strategy(
+        deps.as_mut(),
+        info,
+        Addr::unchecked("addr1".to_string()),
+        StrategyMsg::AllowAll,
+    )
+    .unwrap();
+
+    // The message as the security subDAO pre-propose module accepts it.
+    let msg: ExecuteMsg = from_json(br#"{"remove_strategy": {"address": "addr1"}}"#).unwrap();
+    let security_subdao = message_info(&Addr::unchecked("security_subdao_core"), &[]);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        security_subdao.clone(),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, Unauthorized {});
+
+    execute_add_strategy(
+        deps.as_mut(),
+        message_info(&Addr::unchecked("neutron_dao_address"), &[]),
+        Addr::unchecked("security_subdao_core".to_string()),
+        StrategyMsg::AllowAll,
+    )
+    .unwrap();
+    execute(deps.as_mut(), env, security_subdao, msg).unwrap();
+    assert!(!STRATEGIES.has(deps.as_ref().storage, Addr::unchecked("addr1")));
+}
+
 /// Checks that if you have permissions, you can change both parameters of the cron
 /// module (new style parameter changes). NOTE: this does not check that the
 /// parameters have actually been changed.
@@ -105,6 +105,7 @@ pub fn execute(
             proposal_id,
             new_status,
         },
+        ExecuteBase::Extension { msg } => ExecuteInternal::Extension { msg },
     };
 
     PrePropose::default().execute(deps, env, info, internalized)
@@ -1,8 +1,8 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
-    WasmMsg,
+    from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env,
+    MessageInfo, Response, StdResult, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 use error::PreProposeOverruleError;
@@ -14,20 +14,44 @@ use cwd_pre_propose_base::{
     state::PreProposeContract,
 };
 use neutron_dao_pre_propose_overrule::msg::{
-    ExecuteMsg, InstantiateMsg, MigrateMsg, ProposeMessage, QueryExt, QueryMsg,
+    ExecuteExt, ExecuteMsg, InstantiateMsg, MigrateMsg, OverruleRule, ProposeMessage, QueryExt,
+    QueryMsg,
 };
 
-use crate::state::PROPOSALS;
+use crate::state::{FIRED_RULES, OVERRULE_RULES, PARTIAL_OVERRULE_PROPOSALS, PROPOSALS};
 use cwd_core::msg::QueryMsg as MainDaoQueryMsg;
+use cwd_interface::voting::{Query as CwCoreQuery, VotingPowerAtHeightResponse};
 use cwd_proposal_single::{
     msg::ExecuteMsg as ProposeMessageInternal, msg::QueryMsg as ProposalSingleQueryMsg,
+    query::ProposalResponse,
+};
+use cwd_voting::{pre_propose::ProposalCreationPolicy, status::Status};
+use neutron_sdk::bindings::msg::NeutronMsg;
+use neutron_subdao_core::{
+    msg::ExecuteMsg as SubdaoExecuteMsg, msg::QueryMsg as SubdaoQueryMsg, types as SubdaoTypes,
+};
+use neutron_subdao_pre_propose_single::msg::{
+    QueryExt as SubdaoPreProposeQueryExt, QueryMsg as SubdaoPreProposeQueryMsg,
+};
+use neutron_subdao_proposal_single::{
+    msg::QueryMsg as SubdaoProposalQueryMsg, proposal::SingleChoiceProposal as SubdaoProposal,
+    types::ProposalResponse as SubdaoProposalResponse,
 };
-use neutron_subdao_core::{msg::QueryMsg as SubdaoQueryMsg, types as SubdaoTypes};
 use neutron_subdao_timelock_single::{msg as TimelockMsg, types as TimelockTypes};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-pre-propose-single-overrule";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Maximum length (in characters) of the subdao proposal title quoted in the overrule
+/// proposal description.
+const MAX_SUBDAO_TITLE_LEN: usize = 200;
+/// Maximum length (in characters) of the subdao proposal description quoted in the
+/// overrule proposal description.
+const MAX_SUBDAO_DESCRIPTION_LEN: usize = 1000;
+/// Maximum length of the subdao proposal messages summary in the overrule proposal
+/// description.
+const MAX_MSGS_SUMMARY_LEN: usize = 2000;
+
 type PrePropose = PreProposeContract<ProposeMessageInternal, QueryExt>;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -79,42 +103,180 @@ pub fn execute(
                 return Err(PreProposeOverruleError::AlreadyExists { id });
             }
 
-            let subdao_address = get_subdao_from_timelock(&deps, &timelock_contract_addr)?;
+            let subdao_address = get_verified_subdao(&deps, &timelock_contract_addr)?;
 
-            // We need this check since the timelock contract might be an impostor
-            // E.g. the timelock contract might be a malicious contract that is not a part of
-            // the subdao but pretends to be.
-            if !verify_is_timelock_from_subdao(
+            let proposal: TimelockTypes::SingleChoiceProposal = deps.querier.query_wasm_smart(
+                &timelock_contract_addr,
+                &TimelockMsg::QueryMsg::Proposal { proposal_id },
+            )?;
+            if proposal.status != TimelockTypes::ProposalStatus::Timelocked {
+                return Err(PreProposeOverruleError::ProposalWrongState {});
+            }
+
+            let subdao_name = get_subdao_name(&deps, &subdao_address)?;
+            let proposal_details = describe_subdao_proposal(
                 &deps,
                 &subdao_address,
-                timelock_contract_addr.clone(),
-            )? {
-                return Err(PreProposeOverruleError::SubdaoMisconfigured {});
+                &timelock_contract_addr,
+                &proposal,
+            );
+
+            let fired_rule = find_fired_rule(&deps, &subdao_address, &proposal.msgs)?;
+            let (prop_name, prop_desc, msgs) = match &fired_rule {
+                // The proposal is overruled by default, so the DAO has to approve it instead.
+                // The approval proposal has no messages, the timelock contract checks its status.
+                Some(rule) => (
+                    format!(
+                        "Approve the proposal #{} of the '{}' subdao",
+                        proposal_id, subdao_name
+                    ),
+                    format!(
+                        "The proposal #{} of '{}' subdao (address {}) matches \
+the '{}' overrule rule. Unless this proposal will be accepted, \
+the subdao proposal is not going to be executed{}",
+                        proposal_id, subdao_name, subdao_address, rule, proposal_details
+                    ),
+                    vec![],
+                ),
+                None => (
+                    format!(
+                        "Reject the proposal #{} of the '{}' subdao",
+                        proposal_id, subdao_name
+                    ),
+                    format!(
+                        "If this proposal will be accepted, the DAO is going to \
+overrule the proposal #{} of '{}' subdao (address {}){}",
+                        proposal_id, subdao_name, subdao_address, proposal_details
+                    ),
+                    vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: timelock_contract_addr.to_string(),
+                        msg: to_json_binary(&TimelockMsg::ExecuteMsg::OverruleProposal {
+                            proposal_id,
+                        })?,
+                        funds: vec![],
+                    })],
+                ),
+            };
+
+            let internal_msg = ExecuteInternal::Propose {
+                msg: ProposeMessageInternal::Propose {
+                    // Fill in proposer based on message sender.
+                    proposer: Some(info.sender.to_string()),
+                    title: prop_name,
+                    description: prop_desc,
+                    msgs,
+                },
+            };
+
+            let next_proposal_id = &get_next_proposal_id(&deps)?;
+
+            PROPOSALS.save(
+                deps.storage,
+                (proposal_id, timelock_contract_addr.clone()),
+                next_proposal_id,
+            )?;
+
+            if let Some(rule) = fired_rule {
+                FIRED_RULES.save(deps.storage, (proposal_id, timelock_contract_addr), &rule)?;
+                return PrePropose::default()
+                    .execute(deps, env, info, internal_msg)
+                    .map(|res| res.add_attribute("fired_rule", rule.to_string()))
+                    .map_err(PreProposeOverruleError::PreProposeBase);
+            }
+
+            Ok(internal_msg)
+        }
+        ExecuteMsg::Propose {
+            msg:
+                ProposeMessage::ProposePartialOverrule {
+                    timelock_contract,
+                    proposal_id,
+                    msg_indices,
+                },
+        } => {
+            // Unlike the overrule proposals, which the timelock contract creates for
+            // every timelocked proposal, the partial ones are created by hand, so
+            // only the main DAO members can create them.
+            let dao = PrePropose::default().dao.load(deps.storage)?;
+            let voting_power: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+                dao,
+                &CwCoreQuery::VotingPowerAtHeight {
+                    address: info.sender.to_string(),
+                    height: None,
+                },
+            )?;
+            if voting_power.power.is_zero() {
+                return Err(PreProposeOverruleError::PreProposeBase(
+                    PreProposeError::NotMember {},
+                ));
             }
 
-            if !is_subdao_legit(&deps, &subdao_address)? {
-                return Err(PreProposeOverruleError::ForbiddenSubdao {});
+            let timelock_contract_addr = deps.api.addr_validate(&timelock_contract)?;
+
+            // Only one partial overrule of the proposal can be voted on at a time.
+            let proposal_module = PrePropose::default().proposal_module.load(deps.storage)?;
+            let overrule_proposal_ids = PARTIAL_OVERRULE_PROPOSALS
+                .may_load(deps.storage, (proposal_id, timelock_contract_addr.clone()))?
+                .unwrap_or_default();
+            for id in overrule_proposal_ids {
+                let overrule_proposal: ProposalResponse = deps.querier.query_wasm_smart(
+                    &proposal_module,
+                    &ProposalSingleQueryMsg::Proposal { proposal_id: id },
+                )?;
+                if overrule_proposal.proposal.status == Status::Open {
+                    return Err(PreProposeOverruleError::PartialOverruleAlreadyOpen { id });
+                }
             }
 
-            if !is_proposal_timelocked(&deps, &timelock_contract_addr, proposal_id)? {
+            let subdao_address = get_verified_subdao(&deps, &timelock_contract_addr)?;
+
+            let proposal: TimelockTypes::SingleChoiceProposal = deps.querier.query_wasm_smart(
+                &timelock_contract_addr,
+                &TimelockMsg::QueryMsg::Proposal { proposal_id },
+            )?;
+            if proposal.status != TimelockTypes::ProposalStatus::Timelocked
+                && proposal.status != TimelockTypes::ProposalStatus::PartiallyOverruled
+            {
                 return Err(PreProposeOverruleError::ProposalWrongState {});
             }
 
+            if msg_indices.is_empty() {
+                return Err(PreProposeOverruleError::NoMsgIndices {});
+            }
+            let surviving: TimelockTypes::SurvivingMsgsResponse = deps.querier.query_wasm_smart(
+                &timelock_contract_addr,
+                &TimelockMsg::QueryMsg::SurvivingMsgs { proposal_id },
+            )?;
+            for index in &msg_indices {
+                if !surviving.msgs.iter().any(|msg| msg.index == *index) {
+                    return Err(PreProposeOverruleError::NoSuchMsg { index: *index });
+                }
+            }
+
             let overrule_msg = CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: timelock_contract_addr.to_string(),
-                msg: to_json_binary(&TimelockMsg::ExecuteMsg::OverruleProposal { proposal_id })?,
+                msg: to_json_binary(&TimelockMsg::ExecuteMsg::OverruleProposalMsgs {
+                    proposal_id,
+                    msg_indices: msg_indices.clone(),
+                })?,
                 funds: vec![],
             });
 
             let subdao_name = get_subdao_name(&deps, &subdao_address)?;
+            let msg_list = msg_indices
+                .iter()
+                .map(|index| format!("#{}", index))
+                .collect::<Vec<String>>()
+                .join(", ");
             let prop_name: String = format!(
-                "Reject the proposal #{} of the '{}' subdao",
-                proposal_id, subdao_name
+                "Reject the messages {} of the proposal #{} of the '{}' subdao",
+                msg_list, proposal_id, subdao_name
             );
             let prop_desc: String = format!(
                 "If this proposal will be accepted, the DAO is going to \
-overrule the proposal #{} of '{}' subdao (address {})",
-                proposal_id, subdao_name, subdao_address
+overrule the messages {} of the proposal #{} of '{}' subdao (address {}). \
+The rest of the proposal messages will still be executed",
+                msg_list, proposal_id, subdao_name, subdao_address
             );
 
             let internal_msg = ExecuteInternal::Propose {
@@ -127,16 +289,26 @@ overrule the proposal #{} of '{}' subdao (address {})",
                 },
             };
 
-            let next_proposal_id = &get_next_proposal_id(&deps)?;
+            let next_proposal_id = get_next_proposal_id(&deps)?;
 
-            PROPOSALS.save(
+            PARTIAL_OVERRULE_PROPOSALS.update(
                 deps.storage,
                 (proposal_id, timelock_contract_addr),
-                next_proposal_id,
+                |ids| -> StdResult<Vec<u64>> {
+                    let mut ids = ids.unwrap_or_default();
+                    ids.push(next_proposal_id);
+                    Ok(ids)
+                },
             )?;
 
             Ok(internal_msg)
         }
+        ExecuteMsg::Extension {
+            msg: ExecuteExt::CloseOverruleProposals { proposal_id },
+        } => return execute_close_overrule_proposals(deps, info, proposal_id),
+        ExecuteMsg::Extension {
+            msg: ExecuteExt::SetOverruleRules { subdao, rules },
+        } => return execute_set_overrule_rules(deps, info, subdao, rules),
         // The following messages are forwarded to the base contract
         ExecuteMsg::ProposalCreatedHook {
             proposal_id,
@@ -163,6 +335,328 @@ overrule the proposal #{} of '{}' subdao (address {})",
         .map_err(PreProposeOverruleError::PreProposeBase)
 }
 
+fn execute_close_overrule_proposals(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, PreProposeOverruleError> {
+    // Only the timelock contract of one of the subdaos can close the
+    // overrule proposals of its proposal.
+    get_verified_subdao(&deps, &info.sender)
+        .map_err(|_| PreProposeOverruleError::Unauthorized {})?;
+
+    let mut overrule_proposal_ids = PARTIAL_OVERRULE_PROPOSALS
+        .may_load(deps.storage, (proposal_id, info.sender.clone()))?
+        .unwrap_or_default();
+    // Approval proposals of the proposals that matched an overrule rule
+    // are left open, since the proposals need them to pass.
+    if !FIRED_RULES.has(deps.storage, (proposal_id, info.sender.clone())) {
+        if let Some(id) = PROPOSALS.may_load(deps.storage, (proposal_id, info.sender.clone()))? {
+            overrule_proposal_ids.insert(0, id);
+        }
+    }
+
+    let proposal_module = PrePropose::default().proposal_module.load(deps.storage)?;
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    for id in overrule_proposal_ids {
+        let overrule_proposal: ProposalResponse = deps.querier.query_wasm_smart(
+            &proposal_module,
+            &ProposalSingleQueryMsg::Proposal { proposal_id: id },
+        )?;
+        // The proposals that are already decided are left as they are
+        if overrule_proposal.proposal.status == Status::Open {
+            msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: proposal_module.to_string(),
+                msg: to_json_binary(&ProposeMessageInternal::Cancel { proposal_id: id })?,
+                funds: vec![],
+            }));
+        }
+    }
+
+    Ok(Response::default()
+        .add_messages(msgs)
+        .add_attribute("action", "close_overrule_proposals")
+        .add_attribute("timelock_contract", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+fn execute_set_overrule_rules(
+    deps: DepsMut,
+    info: MessageInfo,
+    subdao: String,
+    rules: Vec<OverruleRule>,
+) -> Result<Response, PreProposeOverruleError> {
+    if info.sender != PrePropose::default().dao.load(deps.storage)? {
+        return Err(PreProposeOverruleError::Unauthorized {});
+    }
+
+    let subdao = deps.api.addr_validate(&subdao)?;
+    if rules.is_empty() {
+        OVERRULE_RULES.remove(deps.storage, subdao.clone());
+    } else {
+        OVERRULE_RULES.save(deps.storage, subdao.clone(), &rules)?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "set_overrule_rules")
+        .add_attribute("subdao", subdao)
+        .add_attribute(
+            "rules",
+            rules
+                .iter()
+                .map(|rule| rule.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        ))
+}
+
+// Returns the first of the subdao's overrule rules matched by the
+// messages wrapped into the timelocked proposal messages.
+fn find_fired_rule(
+    deps: &DepsMut,
+    subdao: &Addr,
+    msgs: &[CosmosMsg<NeutronMsg>],
+) -> Result<Option<OverruleRule>, PreProposeOverruleError> {
+    let rules = OVERRULE_RULES
+        .may_load(deps.storage, subdao.clone())?
+        .unwrap_or_default();
+    if rules.is_empty() {
+        return Ok(None);
+    }
+
+    let mut inner_msgs: Vec<CosmosMsg<NeutronMsg>> = vec![];
+    for msg in msgs {
+        if let CosmosMsg::Wasm(WasmMsg::Execute { msg: wrapped, .. }) = msg {
+            if let Ok(SubdaoExecuteMsg::ExecuteTimelockedMsgs { msgs }) =
+                from_json::<SubdaoExecuteMsg>(wrapped)
+            {
+                inner_msgs.extend(msgs);
+            }
+        }
+    }
+
+    Ok(rules
+        .into_iter()
+        .find(|rule| rule_matches(rule, &inner_msgs)))
+}
+
+pub(crate) fn rule_matches(rule: &OverruleRule, msgs: &[CosmosMsg<NeutronMsg>]) -> bool {
+    match rule {
+        // The amounts are summed over the whole proposal, so that a large
+        // transfer can't slip through split into several smaller ones.
+        OverruleRule::BankSendAbove { denom, amount } => {
+            let total = msgs
+                .iter()
+                .filter_map(|msg| match msg {
+                    CosmosMsg::Bank(BankMsg::Send { amount: coins, .. }) => Some(coins),
+                    _ => None,
+                })
+                .flatten()
+                .filter(|coin| coin.denom == *denom)
+                .fold(Uint128::zero(), |total, coin| {
+                    total.saturating_add(coin.amount)
+                });
+            total > *amount
+        }
+        OverruleRule::WasmMigrate {} => msgs
+            .iter()
+            .any(|msg| matches!(msg, CosmosMsg::Wasm(WasmMsg::Migrate { .. }))),
+        OverruleRule::WasmAdminChange {} => msgs.iter().any(|msg| {
+            matches!(
+                msg,
+                CosmosMsg::Wasm(WasmMsg::UpdateAdmin { .. } | WasmMsg::ClearAdmin { .. })
+            )
+        }),
+    }
+}
+
+// Builds a human-readable description of the subdao proposal for the main DAO voters:
+// the title and description of the subdao proposal followed by a summary of its messages.
+fn describe_subdao_proposal(
+    deps: &DepsMut,
+    subdao: &Addr,
+    timelock_contract: &Addr,
+    proposal: &TimelockTypes::SingleChoiceProposal,
+) -> String {
+    let mut details = String::new();
+    // The overrule proposal has to be created even if the subdao proposal can't be
+    // found, e.g. if the subdao uses some custom proposal modules.
+    if let Ok(Some(subdao_proposal)) =
+        get_subdao_proposal(deps, subdao, timelock_contract, proposal.id)
+    {
+        details.push_str(&format!(
+            "\n\nTitle: {}\n\nDescription: {}",
+            truncate(&subdao_proposal.title, MAX_SUBDAO_TITLE_LEN),
+            truncate(&subdao_proposal.description, MAX_SUBDAO_DESCRIPTION_LEN)
+        ));
+    }
+    details.push_str("\n\nMessages:\n");
+    details.push_str(&summarize_msgs(&proposal.msgs));
+    details
+}
+
+// Looks for the subdao proposal module which timelocks its proposals with the timelock
+// contract and returns the proposal from it.
+fn get_subdao_proposal(
+    deps: &DepsMut,
+    subdao: &Addr,
+    timelock_contract: &Addr,
+    proposal_id: u64,
+) -> StdResult<Option<SubdaoProposal>> {
+    let proposal_modules: Vec<SubdaoTypes::ProposalModule> = deps.querier.query_wasm_smart(
+        subdao,
+        &SubdaoQueryMsg::ProposalModules {
+            start_after: None,
+            limit: None,
+        },
+    )?;
+
+    for proposal_module in proposal_modules {
+        let creation_policy: StdResult<ProposalCreationPolicy> = deps.querier.query_wasm_smart(
+            &proposal_module.address,
+            &SubdaoProposalQueryMsg::ProposalCreationPolicy {},
+        );
+        let pre_propose = match creation_policy {
+            Ok(ProposalCreationPolicy::Module { addr }) => addr,
+            _ => continue,
+        };
+
+        let timelock: StdResult<Addr> = deps.querier.query_wasm_smart(
+            pre_propose,
+            &SubdaoPreProposeQueryMsg::QueryExtension {
+                msg: SubdaoPreProposeQueryExt::TimelockAddress {},
+            },
+        );
+        if timelock.ok().as_ref() == Some(timelock_contract) {
+            let response: SubdaoProposalResponse = deps.querier.query_wasm_smart(
+                &proposal_module.address,
+                &SubdaoProposalQueryMsg::Proposal { proposal_id },
+            )?;
+            return Ok(Some(response.proposal));
+        }
+    }
+
+    Ok(None)
+}
+
+// Decodes the messages wrapped into `ExecuteTimelockedMsgs` and lists them one per line.
+// The list is cut short once it gets longer than `MAX_MSGS_SUMMARY_LEN`.
+pub(crate) fn summarize_msgs(msgs: &[CosmosMsg<NeutronMsg>]) -> String {
+    let summaries: Vec<String> = msgs
+        .iter()
+        .flat_map(|msg| match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg: wrapped, .. }) => {
+                match from_json::<SubdaoExecuteMsg>(wrapped) {
+                    Ok(SubdaoExecuteMsg::ExecuteTimelockedMsgs { msgs }) => {
+                        msgs.iter().map(summarize_msg).collect()
+                    }
+                    _ => vec![summarize_msg(msg)],
+                }
+            }
+            _ => vec![summarize_msg(msg)],
+        })
+        .collect();
+
+    let mut lines: Vec<String> = vec![];
+    let mut len = 0;
+    for (i, summary) in summaries.iter().enumerate() {
+        let line = format!("{}. {}", i + 1, summary);
+        len += line.len() + 1;
+        if len > MAX_MSGS_SUMMARY_LEN {
+            lines.push(format!("... and {} more messages", summaries.len() - i));
+            break;
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+fn summarize_msg(msg: &CosmosMsg<NeutronMsg>) -> String {
+    match msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+            format!("bank send of {} to {}", format_coins(amount), to_address)
+        }
+        CosmosMsg::Bank(BankMsg::Burn { amount }) => {
+            format!("bank burn of {}", format_coins(amount))
+        }
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            funds,
+            ..
+        }) => with_funds(format!("wasm execute on {}", contract_addr), funds),
+        CosmosMsg::Wasm(WasmMsg::Instantiate {
+            code_id,
+            label,
+            funds,
+            ..
+        }) => with_funds(
+            format!("wasm instantiate of code {} labeled '{}'", code_id, label),
+            funds,
+        ),
+        CosmosMsg::Wasm(WasmMsg::Migrate {
+            contract_addr,
+            new_code_id,
+            ..
+        }) => format!("wasm migrate of {} to code {}", contract_addr, new_code_id),
+        CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+            contract_addr,
+            admin,
+        }) => format!("wasm admin change of {} to {}", contract_addr, admin),
+        CosmosMsg::Wasm(WasmMsg::ClearAdmin { contract_addr }) => {
+            format!("wasm admin clear of {}", contract_addr)
+        }
+        CosmosMsg::Custom(_) => "neutron custom message".to_string(),
+        _ => "other message".to_string(),
+    }
+}
+
+fn with_funds(summary: String, funds: &[Coin]) -> String {
+    if funds.is_empty() {
+        summary
+    } else {
+        format!("{} with {}", summary, format_coins(funds))
+    }
+}
+
+fn format_coins(coins: &[Coin]) -> String {
+    coins
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+// Cuts the text down to `max_len` characters, never splitting a character.
+pub(crate) fn truncate(text: &str, max_len: usize) -> String {
+    match text.char_indices().nth(max_len) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+// Returns the subdao of the timelock contract, verifying that the
+// timelock belongs to the subdao and that the subdao is one of the
+// main DAO's subdaos.
+fn get_verified_subdao(
+    deps: &DepsMut,
+    timelock_contract: &Addr,
+) -> Result<Addr, PreProposeOverruleError> {
+    let subdao_address = get_subdao_from_timelock(deps, timelock_contract)?;
+
+    // We need this check since the timelock contract might be an impostor
+    // E.g. the timelock contract might be a malicious contract that is not a part of
+    // the subdao but pretends to be.
+    if !verify_is_timelock_from_subdao(deps, &subdao_address, timelock_contract.clone())? {
+        return Err(PreProposeOverruleError::SubdaoMisconfigured {});
+    }
+
+    if !is_subdao_legit(deps, &subdao_address)? {
+        return Err(PreProposeOverruleError::ForbiddenSubdao {});
+    }
+
+    Ok(subdao_address)
+}
+
 fn get_subdao_from_timelock(
     deps: &DepsMut,
     timelock_contract: &Addr,
@@ -223,17 +717,6 @@ fn get_next_proposal_id(deps: &DepsMut) -> Result<u64, PreProposeOverruleError>
     Ok(last_proposal_id + 1)
 }
 
-fn is_proposal_timelocked(
-    deps: &DepsMut,
-    timelock: &Addr,
-    proposal_id: u64,
-) -> Result<bool, PreProposeOverruleError> {
-    let proposal: TimelockTypes::SingleChoiceProposal = deps
-        .querier
-        .query_wasm_smart(timelock, &TimelockMsg::QueryMsg::Proposal { proposal_id })?;
-    Ok(proposal.status == TimelockTypes::ProposalStatus::Timelocked)
-}
-
 fn get_subdao_name(deps: &DepsMut, subdao: &Addr) -> Result<String, PreProposeOverruleError> {
     let subdao_config: SubdaoTypes::Config = deps
         .querier
@@ -260,6 +743,48 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             )?;
             to_json_binary(&overrule_proposal_id)
         }
+        QueryMsg::QueryExtension {
+            msg:
+                QueryExt::PartialOverruleProposalIds {
+                    timelock_address,
+                    subdao_proposal_id,
+                },
+        } => {
+            let ids = PARTIAL_OVERRULE_PROPOSALS
+                .may_load(
+                    deps.storage,
+                    (
+                        subdao_proposal_id,
+                        deps.api.addr_validate(&timelock_address)?,
+                    ),
+                )?
+                .unwrap_or_default();
+            to_json_binary(&ids)
+        }
+        QueryMsg::QueryExtension {
+            msg: QueryExt::OverruleRules { subdao },
+        } => {
+            let rules = OVERRULE_RULES
+                .may_load(deps.storage, deps.api.addr_validate(&subdao)?)?
+                .unwrap_or_default();
+            to_json_binary(&rules)
+        }
+        QueryMsg::QueryExtension {
+            msg:
+                QueryExt::FiredRule {
+                    timelock_address,
+                    subdao_proposal_id,
+                },
+        } => {
+            let rule = FIRED_RULES.may_load(
+                deps.storage,
+                (
+                    subdao_proposal_id,
+                    deps.api.addr_validate(&timelock_address)?,
+                ),
+            )?;
+            to_json_binary(&rule)
+        }
         _ => PrePropose::default().query(deps, env, msg),
     }
 }
@@ -10,6 +10,9 @@ pub enum PreProposeOverruleError {
     #[error(transparent)]
     PreProposeBase(PreProposeError),
 
+    #[error("Unauthorized")]
+    Unauthorized {},
+
     #[error("Base pre propose messages aren't supported")]
     MessageUnsupported {},
 
@@ -24,4 +27,13 @@ pub enum PreProposeOverruleError {
 
     #[error("Overrule proposal for this subdao proposal already created with id ({id})")]
     AlreadyExists { id: u64 },
+
+    #[error("Partial overrule proposal for this subdao proposal is already open with id ({id})")]
+    PartialOverruleAlreadyOpen { id: u64 },
+
+    #[error("No message indices to overrule given")]
+    NoMsgIndices {},
+
+    #[error("Subdao proposal has no message with index {index} that isn't overruled yet")]
+    NoSuchMsg { index: u64 },
 }
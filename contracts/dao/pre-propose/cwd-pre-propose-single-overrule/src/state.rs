@@ -1,4 +1,16 @@
 use cosmwasm_std::Addr;
 use cw_storage_plus::Map;
+use neutron_dao_pre_propose_overrule::msg::OverruleRule;
 
 pub const PROPOSALS: Map<(u64, Addr), u64> = Map::new("overrule_proposals");
+
+/// IDs of the partial overrule proposals created for a subdao proposal.
+pub const PARTIAL_OVERRULE_PROPOSALS: Map<(u64, Addr), Vec<u64>> =
+    Map::new("partial_overrule_proposals");
+
+/// Overrule rules set by the main DAO for subdaos.
+pub const OVERRULE_RULES: Map<Addr, Vec<OverruleRule>> = Map::new("overrule_rules");
+
+/// The overrule rules subdao proposals matched. The proposals stored in
+/// PROPOSALS for them are approval proposals rather than overrule ones.
+pub const FIRED_RULES: Map<(u64, Addr), OverruleRule> = Map::new("fired_rules");
@@ -2,15 +2,34 @@ use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
 
 use cosmwasm_std::{
-    from_json,
+    coins, from_json,
     testing::{MockApi, MockQuerier, MockStorage},
-    to_json_binary, Addr, Binary, ContractResult, Empty, OwnedDeps, Querier, QuerierResult,
-    QueryRequest, SystemError, SystemResult, WasmQuery,
+    to_json_binary, Addr, BankMsg, Binary, ContractResult, CosmosMsg, Empty, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmMsg, WasmQuery,
 };
+use cw_utils::{Duration, Expiration};
 use cwd_core::msg::QueryMsg as MainDaoQueryMsg;
+use cwd_interface::voting::VotingPowerAtHeightResponse;
 use cwd_proposal_single::msg::QueryMsg as ProposalSingleQueryMsg;
+use cwd_proposal_single::{
+    proposal::SingleChoiceProposal as MainDaoProposal, query::ProposalResponse,
+};
+use cwd_voting::{
+    pre_propose::ProposalCreationPolicy, status::Status, threshold::Threshold, voting::Votes,
+};
 
-use neutron_subdao_core::{msg::QueryMsg as SubdaoQueryMsg, types as SubdaoTypes};
+use neutron_sdk::bindings::msg::NeutronMsg;
+use neutron_subdao_core::{
+    msg::{ExecuteMsg as SubdaoExecuteMsg, QueryMsg as SubdaoQueryMsg},
+    types as SubdaoTypes,
+};
+use neutron_subdao_pre_propose_single::msg::{
+    QueryExt as SubdaoPreProposeQueryExt, QueryMsg as SubdaoPreProposeQueryMsg,
+};
+use neutron_subdao_proposal_single::{
+    msg::QueryMsg as SubdaoProposalQueryMsg, proposal::SingleChoiceProposal as SubdaoProposal,
+    types::ProposalResponse as SubdaoProposalResponse,
+};
 use neutron_subdao_timelock_single::types::{ProposalStatus, SingleChoiceProposal};
 use neutron_subdao_timelock_single::{msg as TimelockMsg, types as TimelockTypes};
 
@@ -18,13 +37,20 @@ pub const MOCK_DAO_CORE: &str = "neutron1dao_core_contract";
 pub const MOCK_DAO_PROPOSE_MODULE: &str = "neutron1propose_module";
 pub const MOCK_TIMELOCK_CONTRACT: &str = "neutron1timelock_contract";
 pub const MOCK_SUBDAO_CORE: &str = "neutron1subdao_core";
+pub const MOCK_SUBDAO_PROPOSE_MODULE: &str = "neutron1subdao_propose_module";
+pub const MOCK_SUBDAO_PRE_PROPOSE: &str = "neutron1subdao_pre_propose";
 
 pub const MOCK_IMPOSTOR_TIMELOCK_CONTRACT: &str = "neutron1timelock_contract_impostor";
+pub const MOCK_NON_MEMBER: &str = "neutron1non_member";
 
 pub const SUBDAO_NAME: &str = "Based DAO";
 pub const TIMELOCKED_PROPOSAL_ID: u64 = 42;
 pub const NON_TIMELOCKED_PROPOSAL_ID: u64 = 24;
 pub const PROPOSALS_COUNT: u64 = 61;
+pub const SURVIVING_MSGS_COUNT: u64 = 3;
+pub const DECIDED_OVERRULE_PROPOSAL_ID: u64 = 3;
+pub const SUBDAO_PROPOSAL_TITLE: &str = "Upgrade the contract";
+pub const SUBDAO_PROPOSAL_DESCRIPTION: &str = "Migrate the contract to the new code";
 
 pub fn mock_dependencies(
     contracts: HashMap<String, Box<dyn ContractQuerier>>,
@@ -103,6 +129,15 @@ impl ContractQuerier for MockDaoQueries {
                 }
                 false => SystemResult::Err(SystemError::Unknown {}),
             },
+            MainDaoQueryMsg::VotingPowerAtHeight { address, .. } => SystemResult::Ok(
+                ContractResult::from(to_json_binary(&VotingPowerAtHeightResponse {
+                    power: match address.as_str() {
+                        MOCK_NON_MEMBER => Uint128::zero(),
+                        _ => Uint128::new(10),
+                    },
+                    height: 0,
+                })),
+            ),
             _ => SystemResult::Err(SystemError::Unknown {}),
         }
     }
@@ -122,23 +157,66 @@ impl ContractQuerier for MockTimelockQueries {
                     owner: Addr::unchecked(self.owner.clone()),
                     overrule_pre_propose: Addr::unchecked(""),
                     subdao: Addr::unchecked(self.subdao.clone()),
+                    timelock_duration: Duration::Height(10),
+                    retry_policy: None,
+                    fast_track_approvers: vec![],
                 }),
             )),
             TimelockMsg::QueryMsg::Proposal { proposal_id } => SystemResult::Ok(
                 ContractResult::from(to_json_binary(&SingleChoiceProposal {
                     id: proposal_id,
-                    msgs: vec![],
+                    msgs: match proposal_id {
+                        TIMELOCKED_PROPOSAL_ID => vec![subdao_migrate_msg()],
+                        _ => vec![],
+                    },
                     status: match proposal_id {
                         TIMELOCKED_PROPOSAL_ID => ProposalStatus::Timelocked,
                         _ => ProposalStatus::Executed,
                     },
+                    timelocked_at: None,
+                    executable_at: None,
+                    overruled_msgs: vec![],
+                    fast_tracked_by: None,
                 })),
             ),
+            TimelockMsg::QueryMsg::SurvivingMsgs { proposal_id } => {
+                let msgs = match proposal_id {
+                    TIMELOCKED_PROPOSAL_ID => (0..SURVIVING_MSGS_COUNT)
+                        .map(|index| TimelockTypes::IndexedMsg {
+                            index,
+                            msg: CosmosMsg::Bank(BankMsg::Burn {
+                                amount: coins(index as u128 + 1, "untrn"),
+                            }),
+                        })
+                        .collect(),
+                    _ => vec![],
+                };
+                SystemResult::Ok(ContractResult::from(to_json_binary(
+                    &TimelockTypes::SurvivingMsgsResponse { msgs },
+                )))
+            }
             _ => SystemResult::Err(SystemError::Unknown {}),
         }
     }
 }
 
+/// The subDAO proposal message wrapping a contract migration, as the
+/// timelock contract stores it.
+pub fn subdao_migrate_msg() -> CosmosMsg<NeutronMsg> {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: MOCK_SUBDAO_CORE.to_string(),
+        msg: to_json_binary(&SubdaoExecuteMsg::ExecuteTimelockedMsgs {
+            msgs: vec![CosmosMsg::Wasm(WasmMsg::Migrate {
+                contract_addr: "neutron1some_contract".to_string(),
+                new_code_id: 2,
+                msg: to_json_binary(&Empty {}).unwrap(),
+            })],
+        })
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
 pub struct MockDaoProposalQueries {
     dao_core: String,
 }
@@ -153,6 +231,30 @@ impl ContractQuerier for MockDaoProposalQueries {
             ProposalSingleQueryMsg::ProposalCount {} => {
                 SystemResult::Ok(ContractResult::from(to_json_binary(&PROPOSALS_COUNT)))
             }
+            ProposalSingleQueryMsg::Proposal { proposal_id } => {
+                SystemResult::Ok(ContractResult::from(to_json_binary(&ProposalResponse {
+                    id: proposal_id,
+                    proposal: MainDaoProposal {
+                        title: "".to_string(),
+                        description: "".to_string(),
+                        proposer: Addr::unchecked(""),
+                        start_height: 0,
+                        min_voting_period: None,
+                        expiration: Expiration::Never {},
+                        threshold: Threshold::AbsoluteCount {
+                            threshold: Uint128::one(),
+                        },
+                        total_power: Uint128::one(),
+                        msgs: vec![],
+                        status: match proposal_id {
+                            DECIDED_OVERRULE_PROPOSAL_ID => Status::Rejected,
+                            _ => Status::Open,
+                        },
+                        votes: Votes::zero(),
+                        allow_revoting: false,
+                    },
+                })))
+            }
             _ => SystemResult::Err(SystemError::Unknown {}),
         }
     }
@@ -179,6 +281,70 @@ impl ContractQuerier for MockSubdaoCoreQueries {
                     security_dao: Addr::unchecked(""),
                 })))
             }
+            SubdaoQueryMsg::ProposalModules { .. } => {
+                SystemResult::Ok(ContractResult::from(to_json_binary(&vec![
+                    SubdaoTypes::ProposalModule {
+                        address: Addr::unchecked(MOCK_SUBDAO_PROPOSE_MODULE),
+                        prefix: "A".to_string(),
+                        status: SubdaoTypes::ProposalModuleStatus::Enabled,
+                    },
+                ])))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        }
+    }
+}
+
+pub struct MockSubdaoProposalQueries {}
+
+impl ContractQuerier for MockSubdaoProposalQueries {
+    fn query(&self, msg: &Binary) -> QuerierResult {
+        let q: SubdaoProposalQueryMsg = from_json(msg).unwrap();
+        match q {
+            SubdaoProposalQueryMsg::ProposalCreationPolicy {} => SystemResult::Ok(
+                ContractResult::from(to_json_binary(&ProposalCreationPolicy::Module {
+                    addr: Addr::unchecked(MOCK_SUBDAO_PRE_PROPOSE),
+                })),
+            ),
+            SubdaoProposalQueryMsg::Proposal { proposal_id } => SystemResult::Ok(
+                ContractResult::from(to_json_binary(&SubdaoProposalResponse {
+                    id: proposal_id,
+                    proposal: SubdaoProposal {
+                        title: SUBDAO_PROPOSAL_TITLE.to_string(),
+                        description: SUBDAO_PROPOSAL_DESCRIPTION.to_string(),
+                        proposer: Addr::unchecked(""),
+                        start_height: 0,
+                        min_voting_period: None,
+                        expiration: Expiration::Never {},
+                        threshold: Threshold::AbsoluteCount {
+                            threshold: Uint128::one(),
+                        },
+                        total_power: Uint128::one(),
+                        msgs: vec![],
+                        status: Status::Executed,
+                        votes: Votes::zero(),
+                        allow_revoting: false,
+                    },
+                })),
+            ),
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        }
+    }
+}
+
+pub struct MockSubdaoPreProposeQueries {
+    timelock: String,
+}
+
+impl ContractQuerier for MockSubdaoPreProposeQueries {
+    fn query(&self, msg: &Binary) -> QuerierResult {
+        let q: SubdaoPreProposeQueryMsg = from_json(msg).unwrap();
+        match q {
+            SubdaoPreProposeQueryMsg::QueryExtension {
+                msg: SubdaoPreProposeQueryExt::TimelockAddress {},
+            } => SystemResult::Ok(ContractResult::from(to_json_binary(&Addr::unchecked(
+                self.timelock.clone(),
+            )))),
             _ => SystemResult::Err(SystemError::Unknown {}),
         }
     }
@@ -212,6 +378,16 @@ pub fn get_properly_initialized_dao() -> HashMap<String, Box<dyn ContractQuerier
             dao_core: MOCK_DAO_CORE.to_string(),
         }),
     );
+    contracts.insert(
+        MOCK_SUBDAO_PROPOSE_MODULE.to_string(),
+        Box::new(MockSubdaoProposalQueries {}),
+    );
+    contracts.insert(
+        MOCK_SUBDAO_PRE_PROPOSE.to_string(),
+        Box::new(MockSubdaoPreProposeQueries {
+            timelock: MOCK_TIMELOCK_CONTRACT.to_string(),
+        }),
+    );
     contracts
 }
 
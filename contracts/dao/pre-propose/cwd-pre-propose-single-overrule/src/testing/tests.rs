@@ -1,25 +1,30 @@
 use cosmwasm_std::{
-    from_json,
+    coins, from_json,
     testing::{mock_env, mock_info},
-    to_json_binary, Addr, CosmosMsg, DepsMut, Empty, SubMsg, WasmMsg,
+    to_json_binary, Addr, Attribute, BankMsg, CosmosMsg, DepsMut, Empty, SubMsg, Uint128, WasmMsg,
 };
+use neutron_sdk::bindings::msg::NeutronMsg;
+use neutron_subdao_core::msg::ExecuteMsg as SubdaoExecuteMsg;
 use std::collections::HashMap;
 
 use crate::{
-    contract::{execute, instantiate, query},
+    contract::{execute, instantiate, query, rule_matches, summarize_msgs, truncate},
     testing::mock_querier::{mock_dependencies, MOCK_DAO_CORE, MOCK_TIMELOCK_CONTRACT},
 };
 use neutron_dao_pre_propose_overrule::msg::{
-    ExecuteMsg, InstantiateMsg, ProposeMessage, QueryExt, QueryMsg,
+    ExecuteExt, ExecuteMsg, InstantiateMsg, OverruleRule, ProposeMessage, QueryExt, QueryMsg,
 };
 
 use crate::error::PreProposeOverruleError;
+use crate::state::{PARTIAL_OVERRULE_PROPOSALS, PROPOSALS};
 use crate::testing::mock_querier::{
     get_dao_with_impostor_subdao, get_dao_with_impostor_timelock, get_properly_initialized_dao,
-    ContractQuerier, MOCK_DAO_PROPOSE_MODULE, MOCK_IMPOSTOR_TIMELOCK_CONTRACT, MOCK_SUBDAO_CORE,
-    NON_TIMELOCKED_PROPOSAL_ID, PROPOSALS_COUNT, SUBDAO_NAME, TIMELOCKED_PROPOSAL_ID,
+    ContractQuerier, DECIDED_OVERRULE_PROPOSAL_ID, MOCK_DAO_PROPOSE_MODULE,
+    MOCK_IMPOSTOR_TIMELOCK_CONTRACT, MOCK_NON_MEMBER, MOCK_SUBDAO_CORE, NON_TIMELOCKED_PROPOSAL_ID,
+    PROPOSALS_COUNT, SUBDAO_NAME, SUBDAO_PROPOSAL_DESCRIPTION, SUBDAO_PROPOSAL_TITLE,
+    SURVIVING_MSGS_COUNT, TIMELOCKED_PROPOSAL_ID,
 };
-use cwd_pre_propose_base::state::Config;
+use cwd_pre_propose_base::{error::PreProposeError, state::Config};
 use cwd_proposal_single::msg::ExecuteMsg as ProposeMessageInternal;
 use neutron_subdao_timelock_single::msg as TimelockMsg;
 
@@ -29,6 +34,14 @@ pub fn init_base_contract(deps: DepsMut<Empty>) {
     instantiate(deps, mock_env(), info, msg).unwrap();
 }
 
+fn subdao_proposal_details() -> String {
+    format!(
+        "\n\nTitle: {}\n\nDescription: {}\n\nMessages:\n\
+1. wasm migrate of neutron1some_contract to code 2",
+        SUBDAO_PROPOSAL_TITLE, SUBDAO_PROPOSAL_DESCRIPTION
+    )
+}
+
 #[test]
 fn test_create_overrule_proposal() {
     let contracts: HashMap<String, Box<dyn ContractQuerier>> = get_properly_initialized_dao();
@@ -55,8 +68,11 @@ fn test_create_overrule_proposal() {
     );
     let prop_desc: String = format!(
         "If this proposal will be accepted, the DAO is going to \
-overrule the proposal #{} of '{}' subdao (address {})",
-        PROPOSAL_ID, SUBDAO_NAME, MOCK_SUBDAO_CORE
+overrule the proposal #{} of '{}' subdao (address {}){}",
+        PROPOSAL_ID,
+        SUBDAO_NAME,
+        MOCK_SUBDAO_CORE,
+        subdao_proposal_details()
     );
     assert_eq!(
         res.unwrap().messages,
@@ -272,3 +288,453 @@ fn test_double_creation() {
         })
     );
 }
+
+#[test]
+fn test_create_partial_overrule_proposal() {
+    let contracts: HashMap<String, Box<dyn ContractQuerier>> = get_properly_initialized_dao();
+    let mut deps = mock_dependencies(contracts);
+    init_base_contract(deps.as_mut());
+    const PROPOSAL_ID: u64 = TIMELOCKED_PROPOSAL_ID;
+    const PROPOSER_ADDR: &str = "whatever";
+    let msg = ExecuteMsg::Propose {
+        msg: ProposeMessage::ProposePartialOverrule {
+            timelock_contract: MOCK_TIMELOCK_CONTRACT.to_string(),
+            proposal_id: PROPOSAL_ID,
+            msg_indices: vec![0, 2],
+        },
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(PROPOSER_ADDR, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    let prop_name: String = format!(
+        "Reject the messages #0, #2 of the proposal #{} of the '{}' subdao",
+        PROPOSAL_ID, SUBDAO_NAME
+    );
+    let prop_desc: String = format!(
+        "If this proposal will be accepted, the DAO is going to \
+overrule the messages #0, #2 of the proposal #{} of '{}' subdao (address {}). \
+The rest of the proposal messages will still be executed",
+        PROPOSAL_ID, SUBDAO_NAME, MOCK_SUBDAO_CORE
+    );
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_DAO_PROPOSE_MODULE.to_string(),
+            msg: to_json_binary(&ProposeMessageInternal::Propose {
+                title: prop_name,
+                description: prop_desc,
+                msgs: vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: MOCK_TIMELOCK_CONTRACT.to_string(),
+                    msg: to_json_binary(&TimelockMsg::ExecuteMsg::OverruleProposalMsgs {
+                        proposal_id: PROPOSAL_ID,
+                        msg_indices: vec![0, 2],
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                })],
+                proposer: Some(PROPOSER_ADDR.to_string()),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    // only one partial overrule of the proposal can be open at a time
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(PROPOSER_ADDR, &[]),
+        msg.clone(),
+    );
+    assert_eq!(
+        res,
+        Err(PreProposeOverruleError::PartialOverruleAlreadyOpen {
+            id: PROPOSALS_COUNT + 1
+        })
+    );
+
+    // but a new one can be created once the previous ones are decided
+    PARTIAL_OVERRULE_PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            (PROPOSAL_ID, Addr::unchecked(MOCK_TIMELOCK_CONTRACT)),
+            &vec![DECIDED_OVERRULE_PROPOSAL_ID],
+        )
+        .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(PROPOSER_ADDR, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+
+    let query_msg = QueryMsg::QueryExtension {
+        msg: QueryExt::PartialOverruleProposalIds {
+            timelock_address: MOCK_TIMELOCK_CONTRACT.to_string(),
+            subdao_proposal_id: PROPOSAL_ID,
+        },
+    };
+    let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+    let ids: Vec<u64> = from_json(res).unwrap();
+    assert_eq!(ids, vec![DECIDED_OVERRULE_PROPOSAL_ID, PROPOSALS_COUNT + 1]);
+
+    // only the main DAO members can create partial overrules
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_NON_MEMBER, &[]),
+        msg,
+    );
+    assert_eq!(
+        res,
+        Err(PreProposeOverruleError::PreProposeBase(
+            PreProposeError::NotMember {}
+        ))
+    );
+
+    // the rest of the checks are done on a proposal with no open partial overrules
+    PARTIAL_OVERRULE_PROPOSALS.remove(
+        deps.as_mut().storage,
+        (PROPOSAL_ID, Addr::unchecked(MOCK_TIMELOCK_CONTRACT)),
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(PROPOSER_ADDR, &[]),
+        ExecuteMsg::Propose {
+            msg: ProposeMessage::ProposePartialOverrule {
+                timelock_contract: MOCK_TIMELOCK_CONTRACT.to_string(),
+                proposal_id: PROPOSAL_ID,
+                msg_indices: vec![],
+            },
+        },
+    );
+    assert_eq!(res, Err(PreProposeOverruleError::NoMsgIndices {}));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(PROPOSER_ADDR, &[]),
+        ExecuteMsg::Propose {
+            msg: ProposeMessage::ProposePartialOverrule {
+                timelock_contract: MOCK_TIMELOCK_CONTRACT.to_string(),
+                proposal_id: PROPOSAL_ID,
+                msg_indices: vec![1, SURVIVING_MSGS_COUNT],
+            },
+        },
+    );
+    assert_eq!(
+        res,
+        Err(PreProposeOverruleError::NoSuchMsg {
+            index: SURVIVING_MSGS_COUNT
+        })
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(PROPOSER_ADDR, &[]),
+        ExecuteMsg::Propose {
+            msg: ProposeMessage::ProposePartialOverrule {
+                timelock_contract: MOCK_TIMELOCK_CONTRACT.to_string(),
+                proposal_id: NON_TIMELOCKED_PROPOSAL_ID,
+                msg_indices: vec![0],
+            },
+        },
+    );
+    assert_eq!(res, Err(PreProposeOverruleError::ProposalWrongState {}));
+}
+
+#[test]
+fn test_close_overrule_proposals() {
+    let contracts: HashMap<String, Box<dyn ContractQuerier>> = get_properly_initialized_dao();
+    let mut deps = mock_dependencies(contracts);
+    init_base_contract(deps.as_mut());
+    const PROPOSAL_ID: u64 = TIMELOCKED_PROPOSAL_ID;
+    let timelock = Addr::unchecked(MOCK_TIMELOCK_CONTRACT);
+    PROPOSALS
+        .save(deps.as_mut().storage, (PROPOSAL_ID, timelock.clone()), &1)
+        .unwrap();
+    PARTIAL_OVERRULE_PROPOSALS
+        .save(
+            deps.as_mut().storage,
+            (PROPOSAL_ID, timelock),
+            &vec![2, DECIDED_OVERRULE_PROPOSAL_ID],
+        )
+        .unwrap();
+
+    let msg = ExecuteMsg::Extension {
+        msg: ExecuteExt::CloseOverruleProposals {
+            proposal_id: PROPOSAL_ID,
+        },
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("whatever", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(PreProposeOverruleError::Unauthorized {}));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_TIMELOCK_CONTRACT, &[]),
+        msg,
+    )
+    .unwrap();
+    // the overrule proposals that are already decided are left as they are
+    assert_eq!(
+        res.messages,
+        vec![1, 2]
+            .into_iter()
+            .map(|proposal_id| SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_DAO_PROPOSE_MODULE.to_string(),
+                msg: to_json_binary(&ProposeMessageInternal::Cancel { proposal_id }).unwrap(),
+                funds: vec![],
+            })))
+            .collect::<Vec<SubMsg>>()
+    );
+}
+
+#[test]
+fn test_overrule_rules() {
+    let contracts: HashMap<String, Box<dyn ContractQuerier>> = get_properly_initialized_dao();
+    let mut deps = mock_dependencies(contracts);
+    init_base_contract(deps.as_mut());
+    const PROPOSAL_ID: u64 = TIMELOCKED_PROPOSAL_ID;
+    const PROPOSER_ADDR: &str = "whatever";
+    let rules = vec![
+        OverruleRule::BankSendAbove {
+            denom: "untrn".to_string(),
+            amount: Uint128::new(1_000_000),
+        },
+        OverruleRule::WasmMigrate {},
+    ];
+
+    let msg = ExecuteMsg::Extension {
+        msg: ExecuteExt::SetOverruleRules {
+            subdao: MOCK_SUBDAO_CORE.to_string(),
+            rules: rules.clone(),
+        },
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(PROPOSER_ADDR, &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(PreProposeOverruleError::Unauthorized {}));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_DAO_CORE, &[]),
+        msg,
+    )
+    .unwrap();
+    let query_res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::QueryExtension {
+            msg: QueryExt::OverruleRules {
+                subdao: MOCK_SUBDAO_CORE.to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let stored_rules: Vec<OverruleRule> = from_json(query_res).unwrap();
+    assert_eq!(stored_rules, rules);
+
+    // the timelocked proposal migrates a contract, so the DAO has to approve it
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(PROPOSER_ADDR, &[]),
+        ExecuteMsg::Propose {
+            msg: ProposeMessage::ProposeOverrule {
+                timelock_contract: MOCK_TIMELOCK_CONTRACT.to_string(),
+                proposal_id: PROPOSAL_ID,
+            },
+        },
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&Attribute::new("fired_rule", "wasm migrate")));
+    let prop_name: String = format!(
+        "Approve the proposal #{} of the '{}' subdao",
+        PROPOSAL_ID, SUBDAO_NAME
+    );
+    let prop_desc: String = format!(
+        "The proposal #{} of '{}' subdao (address {}) matches \
+the 'wasm migrate' overrule rule. Unless this proposal will be accepted, \
+the subdao proposal is not going to be executed{}",
+        PROPOSAL_ID,
+        SUBDAO_NAME,
+        MOCK_SUBDAO_CORE,
+        subdao_proposal_details()
+    );
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_DAO_PROPOSE_MODULE.to_string(),
+            msg: to_json_binary(&ProposeMessageInternal::Propose {
+                title: prop_name,
+                description: prop_desc,
+                msgs: vec![],
+                proposer: Some(PROPOSER_ADDR.to_string()),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    let query_res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::QueryExtension {
+            msg: QueryExt::FiredRule {
+                timelock_address: MOCK_TIMELOCK_CONTRACT.to_string(),
+                subdao_proposal_id: PROPOSAL_ID,
+            },
+        },
+    )
+    .unwrap();
+    let fired_rule: Option<OverruleRule> = from_json(query_res).unwrap();
+    assert_eq!(fired_rule, Some(OverruleRule::WasmMigrate {}));
+
+    // an empty list removes the rules of the subdao
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_DAO_CORE, &[]),
+        ExecuteMsg::Extension {
+            msg: ExecuteExt::SetOverruleRules {
+                subdao: MOCK_SUBDAO_CORE.to_string(),
+                rules: vec![],
+            },
+        },
+    )
+    .unwrap();
+    let query_res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::QueryExtension {
+            msg: QueryExt::OverruleRules {
+                subdao: MOCK_SUBDAO_CORE.to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let stored_rules: Vec<OverruleRule> = from_json(query_res).unwrap();
+    assert!(stored_rules.is_empty());
+}
+
+#[test]
+fn test_summarize_msgs() {
+    let msgs: Vec<CosmosMsg<NeutronMsg>> = vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_SUBDAO_CORE.to_string(),
+            msg: to_json_binary(&SubdaoExecuteMsg::ExecuteTimelockedMsgs {
+                msgs: vec![
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: "neutron1recipient".to_string(),
+                        amount: coins(100, "untrn"),
+                    }),
+                    CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: "neutron1some_contract".to_string(),
+                        msg: to_json_binary(&Empty {}).unwrap(),
+                        funds: coins(5, "untrn"),
+                    }),
+                    CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+                        contract_addr: "neutron1some_contract".to_string(),
+                        admin: "neutron1new_admin".to_string(),
+                    }),
+                ],
+            })
+            .unwrap(),
+            funds: vec![],
+        }),
+        CosmosMsg::Bank(BankMsg::Burn {
+            amount: coins(7, "untrn"),
+        }),
+    ];
+    assert_eq!(
+        summarize_msgs(&msgs),
+        "1. bank send of 100untrn to neutron1recipient\n\
+2. wasm execute on neutron1some_contract with 5untrn\n\
+3. wasm admin change of neutron1some_contract to neutron1new_admin\n\
+4. bank burn of 7untrn"
+    );
+
+    // the summary is cut short if there are too many messages
+    let msgs: Vec<CosmosMsg<NeutronMsg>> = (0..1000)
+        .map(|_| {
+            CosmosMsg::Bank(BankMsg::Burn {
+                amount: coins(1, "untrn"),
+            })
+        })
+        .collect();
+    let summary = summarize_msgs(&msgs);
+    assert!(summary.len() <= 2100);
+    assert!(summary.ends_with("more messages"));
+}
+
+#[test]
+fn test_rule_matches() {
+    let rule = OverruleRule::BankSendAbove {
+        denom: "untrn".to_string(),
+        amount: Uint128::new(100),
+    };
+    let send = |amount: u128, denom: &str| -> CosmosMsg<NeutronMsg> {
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "neutron1recipient".to_string(),
+            amount: coins(amount, denom),
+        })
+    };
+
+    assert!(!rule_matches(&rule, &[send(100, "untrn")]));
+    assert!(rule_matches(&rule, &[send(101, "untrn")]));
+    assert!(!rule_matches(&rule, &[send(1000, "uatom")]));
+    // the amounts are summed over all the messages of the proposal
+    assert!(!rule_matches(
+        &rule,
+        &[send(50, "untrn"), send(50, "untrn")]
+    ));
+    assert!(rule_matches(&rule, &[send(60, "untrn"), send(60, "untrn")]));
+    assert!(!rule_matches(
+        &rule,
+        &[send(60, "untrn"), send(60, "uatom")]
+    ));
+
+    let migrate: CosmosMsg<NeutronMsg> = CosmosMsg::Wasm(WasmMsg::Migrate {
+        contract_addr: "neutron1some_contract".to_string(),
+        new_code_id: 1,
+        msg: to_json_binary(&Empty {}).unwrap(),
+    });
+    assert!(rule_matches(
+        &OverruleRule::WasmMigrate {},
+        &[send(1, "untrn"), migrate.clone()]
+    ));
+    assert!(!rule_matches(
+        &OverruleRule::WasmAdminChange {},
+        &[send(1, "untrn"), migrate]
+    ));
+}
+
+#[test]
+fn test_truncate() {
+    assert_eq!(truncate("title", 5), "title");
+    assert_eq!(truncate("long title", 4), "long...");
+    // the length is counted in characters rather than bytes
+    assert_eq!(truncate("ñññ", 3), "ñññ");
+    assert_eq!(truncate("ñññ", 2), "ññ...");
+    assert_eq!(truncate("日本語のタイトル", 3), "日本語...");
+}
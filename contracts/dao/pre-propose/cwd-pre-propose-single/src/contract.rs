@@ -1,7 +1,7 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+    to_json_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
 };
 use cw2::set_contract_version;
 use neutron_sdk::bindings::msg::NeutronMsg;
@@ -14,9 +14,12 @@ use cwd_pre_propose_base::{
     },
     state::PreProposeContract,
 };
+use cwd_voting::deposit::UncheckedDepositInfo;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::{error::PreProposeSingleError, policy::MessagePolicy, state::MESSAGE_POLICY};
+
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-pre-propose-single";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -30,9 +33,41 @@ pub enum ProposeMessage {
     },
 }
 
-pub type InstantiateMsg = InstantiateBase;
-pub type ExecuteMsg = ExecuteBase<ProposeMessage>;
-pub type QueryMsg = QueryBase<Empty>;
+#[derive(Serialize, JsonSchema, Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct InstantiateMsg {
+    /// Information about the deposit requirements for this
+    /// module. None if no deposit.
+    pub deposit_info: Option<UncheckedDepositInfo>,
+    /// If false, only members (addresses with voting power) may create
+    /// proposals in the DAO. Otherwise, any address may create a
+    /// proposal so long as they pay the deposit.
+    pub open_proposal_submission: bool,
+    /// Restrictions on the messages of submitted proposals. None if
+    /// proposals may contain any messages.
+    pub message_policy: Option<MessagePolicy>,
+}
+
+#[derive(Serialize, JsonSchema, Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteExt {
+    /// Replaces the message policy applied to proposals created after
+    /// the update. Only the DAO may execute this message.
+    UpdateMessagePolicy {
+        message_policy: Option<MessagePolicy>,
+    },
+}
+
+#[derive(Serialize, JsonSchema, Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryExt {
+    /// Gets the message policy of this module. Returns
+    /// `Option<MessagePolicy>`.
+    MessagePolicy {},
+}
+
+pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
+pub type QueryMsg = QueryBase<QueryExt>;
 
 /// Internal version of the propose message that includes the
 /// `proposer` field. The module will fill this in based on the sender
@@ -48,7 +83,7 @@ enum ProposeMessageInternal {
     },
 }
 
-type PrePropose = PreProposeContract<ProposeMessageInternal, Empty>;
+type PrePropose = PreProposeContract<ProposeMessageInternal, QueryExt>;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -56,8 +91,21 @@ pub fn instantiate(
     env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
-) -> Result<Response, PreProposeError> {
-    let resp = PrePropose::default().instantiate(deps.branch(), env, info, msg)?;
+) -> Result<Response, PreProposeSingleError> {
+    if let Some(message_policy) = msg.message_policy {
+        message_policy.validate(deps.api)?;
+        MESSAGE_POLICY.save(deps.storage, &message_policy)?;
+    }
+
+    let resp = PrePropose::default().instantiate(
+        deps.branch(),
+        env,
+        info,
+        InstantiateBase {
+            deposit_info: msg.deposit_info,
+            open_proposal_submission: msg.open_proposal_submission,
+        },
+    )?;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(resp)
 }
@@ -68,7 +116,7 @@ pub fn execute(
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> Result<Response, PreProposeError> {
+) -> Result<Response, PreProposeSingleError> {
     // We don't want to expose the `proposer` field on the propose
     // message externally as that is to be set by this module. Here,
     // we transform an external message which omits that field into an
@@ -82,15 +130,21 @@ pub fn execute(
                     description,
                     msgs,
                 },
-        } => ExecuteInternal::Propose {
-            msg: ProposeMessageInternal::Propose {
-                // Fill in proposer based on message sender.
-                proposer: Some(info.sender.to_string()),
-                title,
-                description,
-                msgs,
-            },
-        },
+        } => {
+            if let Some(message_policy) = MESSAGE_POLICY.may_load(deps.storage)? {
+                message_policy.check(deps.api, &msgs)?;
+            }
+
+            ExecuteInternal::Propose {
+                msg: ProposeMessageInternal::Propose {
+                    // Fill in proposer based on message sender.
+                    proposer: Some(info.sender.to_string()),
+                    title,
+                    description,
+                    msgs,
+                },
+            }
+        }
         ExecuteMsg::Withdraw { denom } => ExecuteInternal::Withdraw { denom },
         ExecuteMsg::UpdateConfig {
             deposit_info,
@@ -113,14 +167,45 @@ pub fn execute(
             proposal_id,
             new_status,
         },
+        ExecuteMsg::Extension {
+            msg: ExecuteExt::UpdateMessagePolicy { message_policy },
+        } => return execute_update_message_policy(deps, info, message_policy),
     };
 
-    PrePropose::default().execute(deps, env, info, internalized)
+    Ok(PrePropose::default().execute(deps, env, info, internalized)?)
+}
+
+pub fn execute_update_message_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    message_policy: Option<MessagePolicy>,
+) -> Result<Response, PreProposeSingleError> {
+    let dao = PrePropose::default().dao.load(deps.storage)?;
+    if info.sender != dao {
+        return Err(PreProposeError::NotDao {}.into());
+    }
+
+    match message_policy {
+        Some(message_policy) => {
+            message_policy.validate(deps.api)?;
+            MESSAGE_POLICY.save(deps.storage, &message_policy)?;
+        }
+        None => MESSAGE_POLICY.remove(deps.storage),
+    }
+
+    Ok(Response::default()
+        .add_attribute("method", "update_message_policy")
+        .add_attribute("sender", info.sender))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    PrePropose::default().query(deps, env, msg)
+    match msg {
+        QueryMsg::QueryExtension {
+            msg: QueryExt::MessagePolicy {},
+        } => to_json_binary(&MESSAGE_POLICY.may_load(deps.storage)?),
+        _ => PrePropose::default().query(deps, env, msg),
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
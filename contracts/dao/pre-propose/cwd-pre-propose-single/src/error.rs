@@ -0,0 +1,39 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use cwd_pre_propose_base::error::PreProposeError;
+use thiserror::Error;
+
+use crate::policy::MessageKind;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PreProposeSingleError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    PreProposeBase(#[from] PreProposeError),
+
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+
+    #[error("Proposal contains {count} messages while at most {max} are allowed")]
+    TooManyMessages { count: u64, max: u64 },
+
+    #[error("Messages of kind {kind:?} are not allowed by the message policy")]
+    MessageKindNotAllowed { kind: MessageKind },
+
+    #[error("Contract {contract} is not allowed to be targeted by proposal messages")]
+    ContractNotAllowed { contract: String },
+
+    #[error("Wasm messages that do not target an existing contract are not allowed by the message policy")]
+    UntargetedWasmMessage {},
+
+    #[error("Proposal sends {amount}{denom} via bank messages while the limit is {limit}{denom}")]
+    BankSendLimitExceeded {
+        denom: String,
+        amount: Uint128,
+        limit: Uint128,
+    },
+
+    #[error("Message policy contains more than one bank send limit for {denom}")]
+    DuplicateBankSendLimit { denom: String },
+}
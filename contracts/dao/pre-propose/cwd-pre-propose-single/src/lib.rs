@@ -1,6 +1,10 @@
 pub mod contract;
+pub mod error;
+pub mod policy;
+mod state;
 
-pub use contract::{ExecuteMsg, InstantiateMsg, ProposeMessage, QueryMsg};
+pub use contract::{ExecuteExt, ExecuteMsg, InstantiateMsg, ProposeMessage, QueryExt, QueryMsg};
+pub use policy::{MessageKind, MessagePolicy};
 
 // Exporting these means that contracts interacting with this one don't
 // need an explicit dependency on the base contract to read queries.
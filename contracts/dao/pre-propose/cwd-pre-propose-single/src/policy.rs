@@ -0,0 +1,193 @@
+use std::collections::HashSet;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api, BankMsg, Coin, CosmosMsg, IbcMsg, StdResult, Uint128, WasmMsg};
+use neutron_sdk::bindings::msg::NeutronMsg;
+
+use crate::error::PreProposeSingleError;
+
+/// Kinds of messages a proposal may contain.
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum MessageKind {
+    Bank,
+    Wasm,
+    /// Neutron custom messages (`NeutronMsg`).
+    Custom,
+    Stargate,
+    Ibc,
+    Gov,
+    /// Any other message kind, e.g. staking and distribution messages.
+    Other,
+}
+
+impl MessageKind {
+    pub fn of(msg: &CosmosMsg<NeutronMsg>) -> Self {
+        match msg {
+            CosmosMsg::Bank(_) => MessageKind::Bank,
+            CosmosMsg::Wasm(_) => MessageKind::Wasm,
+            CosmosMsg::Custom(_) => MessageKind::Custom,
+            CosmosMsg::Stargate { .. } => MessageKind::Stargate,
+            CosmosMsg::Ibc(_) => MessageKind::Ibc,
+            CosmosMsg::Gov(_) => MessageKind::Gov,
+            #[allow(unreachable_patterns)]
+            _ => MessageKind::Other,
+        }
+    }
+}
+
+/// Restrictions applied to the messages of every proposal submitted
+/// through this module. Every restriction is optional, `None` means
+/// that the corresponding property of a proposal is not restricted.
+#[cw_serde]
+pub struct MessagePolicy {
+    /// Message kinds proposals are allowed to contain.
+    pub allowed_kinds: Option<Vec<MessageKind>>,
+    /// Contracts `WasmMsg`s are allowed to target. If set, wasm
+    /// messages which do not target an existing contract (e.g.
+    /// instantiations) are rejected.
+    pub allowed_contracts: Option<Vec<String>>,
+    /// Maximum total amount of every denom a single proposal may send,
+    /// be it via `BankMsg::Send`, funds attached to wasm messages or
+    /// IBC transfers. If set, sending denoms which are not listed is
+    /// rejected.
+    pub max_bank_send: Option<Vec<Coin>>,
+    /// Maximum number of messages in a single proposal.
+    pub max_msgs: Option<u64>,
+}
+
+impl MessagePolicy {
+    /// Validates the addresses and limits of the policy.
+    pub fn validate(&self, api: &dyn Api) -> Result<(), PreProposeSingleError> {
+        if let Some(contracts) = &self.allowed_contracts {
+            for contract in contracts {
+                api.addr_validate(contract)?;
+            }
+        }
+
+        if let Some(limits) = &self.max_bank_send {
+            let mut denoms = HashSet::new();
+            for limit in limits {
+                if !denoms.insert(limit.denom.as_str()) {
+                    return Err(PreProposeSingleError::DuplicateBankSendLimit {
+                        denom: limit.denom.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the proposal messages satisfy the policy.
+    pub fn check(
+        &self,
+        api: &dyn Api,
+        msgs: &[CosmosMsg<NeutronMsg>],
+    ) -> Result<(), PreProposeSingleError> {
+        if let Some(max) = self.max_msgs {
+            let count = msgs.len() as u64;
+            if count > max {
+                return Err(PreProposeSingleError::TooManyMessages { count, max });
+            }
+        }
+
+        let allowed_contracts = self
+            .allowed_contracts
+            .as_ref()
+            .map(|contracts| {
+                contracts
+                    .iter()
+                    .map(|contract| api.addr_validate(contract))
+                    .collect::<StdResult<Vec<Addr>>>()
+            })
+            .transpose()?;
+
+        for msg in msgs {
+            let kind = MessageKind::of(msg);
+            if let Some(allowed_kinds) = &self.allowed_kinds {
+                if !allowed_kinds.contains(&kind) {
+                    return Err(PreProposeSingleError::MessageKindNotAllowed { kind });
+                }
+            }
+
+            if let (Some(allowed_contracts), CosmosMsg::Wasm(wasm_msg)) = (&allowed_contracts, msg)
+            {
+                match wasm_msg_target(wasm_msg) {
+                    Some(contract)
+                        if api
+                            .addr_validate(contract)
+                            .map_or(false, |addr| allowed_contracts.contains(&addr)) => {}
+                    Some(contract) => {
+                        return Err(PreProposeSingleError::ContractNotAllowed {
+                            contract: contract.to_string(),
+                        })
+                    }
+                    None => return Err(PreProposeSingleError::UntargetedWasmMessage {}),
+                }
+            }
+        }
+
+        if let Some(limits) = &self.max_bank_send {
+            for (denom, amount) in bank_send_totals(msgs)? {
+                let limit = limits
+                    .iter()
+                    .find(|l| l.denom == denom)
+                    .map(|l| l.amount)
+                    .unwrap_or_default();
+                if amount > limit {
+                    return Err(PreProposeSingleError::BankSendLimitExceeded {
+                        denom,
+                        amount,
+                        limit,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the address of the contract targeted by the wasm message,
+/// if any.
+fn wasm_msg_target(msg: &WasmMsg) -> Option<&str> {
+    match msg {
+        WasmMsg::Execute { contract_addr, .. }
+        | WasmMsg::Migrate { contract_addr, .. }
+        | WasmMsg::UpdateAdmin { contract_addr, .. }
+        | WasmMsg::ClearAdmin { contract_addr } => Some(contract_addr.as_str()),
+        _ => None,
+    }
+}
+
+/// Returns the coins the message moves out of the DAO, if any.
+fn sent_coins(msg: &CosmosMsg<NeutronMsg>) -> Vec<&Coin> {
+    match msg {
+        CosmosMsg::Bank(BankMsg::Send { amount, .. })
+        | CosmosMsg::Wasm(WasmMsg::Execute { funds: amount, .. })
+        | CosmosMsg::Wasm(WasmMsg::Instantiate { funds: amount, .. }) => amount.iter().collect(),
+        CosmosMsg::Ibc(IbcMsg::Transfer { amount, .. }) => vec![amount],
+        // IBC fees are paid by the sender as well
+        CosmosMsg::Custom(NeutronMsg::IbcTransfer { token, fee, .. }) => std::iter::once(token)
+            .chain(&fee.recv_fee)
+            .chain(&fee.ack_fee)
+            .chain(&fee.timeout_fee)
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Sums up the amounts sent by the messages per denom.
+fn bank_send_totals(
+    msgs: &[CosmosMsg<NeutronMsg>],
+) -> Result<Vec<(String, Uint128)>, PreProposeSingleError> {
+    let mut totals: Vec<(String, Uint128)> = vec![];
+    for coin in msgs.iter().flat_map(sent_coins) {
+        match totals.iter_mut().find(|(denom, _)| *denom == coin.denom) {
+            Some((_, total)) => *total = total.checked_add(coin.amount)?,
+            None => totals.push((coin.denom.clone(), coin.amount)),
+        }
+    }
+    Ok(totals)
+}
@@ -0,0 +1,5 @@
+use cw_storage_plus::Item;
+
+use crate::policy::MessagePolicy;
+
+pub const MESSAGE_POLICY: Item<MessagePolicy> = Item::new("message_policy");
@@ -8,7 +8,7 @@ use cosmwasm_std::{
 use cw2::set_contract_version;
 use cw_storage_plus::{Bound, Map};
 use cw_utils::{parse_reply_instantiate_data, Duration};
-use cwd_hooks::Hooks;
+use cwd_hooks::{HookFailure, HookFilter, Hooks};
 use cwd_interface::voting::IsActiveResponse;
 use cwd_pre_propose_multiple::contract::ExecuteMsg as PreProposeMsg;
 use cwd_proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
@@ -131,13 +131,19 @@ pub fn execute(
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
         }
-        ExecuteMsg::AddProposalHook { address } => {
-            execute_add_proposal_hook(deps, env, info, address)
-        }
+        ExecuteMsg::AddProposalHook {
+            address,
+            failures_tolerated,
+            filter,
+        } => execute_add_proposal_hook(deps, env, info, address, failures_tolerated, filter),
         ExecuteMsg::RemoveProposalHook { address } => {
             execute_remove_proposal_hook(deps, env, info, address)
         }
-        ExecuteMsg::AddVoteHook { address } => execute_add_vote_hook(deps, env, info, address),
+        ExecuteMsg::AddVoteHook {
+            address,
+            failures_tolerated,
+            filter,
+        } => execute_add_vote_hook(deps, env, info, address, failures_tolerated, filter),
         ExecuteMsg::RemoveVoteHook { address } => {
             execute_remove_vote_hook(deps, env, info, address)
         }
@@ -245,7 +251,13 @@ pub fn execute_propose(
 
     PROPOSALS.save(deps.storage, id, &proposal)?;
 
-    let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
+    let hooks = new_proposal_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        id,
+        proposer.as_str(),
+        proposal.msg_contracts(),
+    )?;
     // Add prepropose / deposit module hook which will save deposit info. This
     // needs to be called after execute_propose because we don't know the
     // proposal ID beforehand.
@@ -351,6 +363,7 @@ pub fn execute_vote(
         proposal_id,
         old_status.to_string(),
         new_status.to_string(),
+        prop.msg_contracts(),
     )?;
     let vote_hooks = new_vote_hooks(
         VOTE_HOOKS,
@@ -358,6 +371,8 @@ pub fn execute_vote(
         proposal_id,
         info.sender.to_string(),
         vote.to_string(),
+        vote_power,
+        prop.msg_contracts(),
     )?;
     Ok(Response::default()
         .add_submessages(change_hooks)
@@ -443,6 +458,7 @@ pub fn execute_execute(
                 proposal_id,
                 old_status.to_string(),
                 prop.status.to_string(),
+                prop.msg_contracts(),
             )?;
 
             // Add prepropose / deposit module hook which will handle deposit refunds.
@@ -502,6 +518,7 @@ pub fn execute_close(
         proposal_id,
         old_status.to_string(),
         prop.status.to_string(),
+        prop.msg_contracts(),
     )?;
 
     // Add prepropose / deposit module hook which will handle deposit refunds.
@@ -601,6 +618,8 @@ pub fn execute_add_proposal_hook(
     _env: Env,
     info: MessageInfo,
     address: String,
+    failures_tolerated: Option<u64>,
+    filter: Option<HookFilter>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.dao != info.sender {
@@ -609,8 +628,17 @@ pub fn execute_add_proposal_hook(
     }
 
     let validated_address = deps.api.addr_validate(&address)?;
+    if let Some(filter) = &filter {
+        filter.validate(deps.api)?;
+    }
 
-    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+    add_hook(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        validated_address,
+        failures_tolerated.unwrap_or_default(),
+        filter,
+    )?;
 
     Ok(Response::default()
         .add_attribute("action", "add_proposal_hook")
@@ -643,6 +671,8 @@ pub fn execute_add_vote_hook(
     _env: Env,
     info: MessageInfo,
     address: String,
+    failures_tolerated: Option<u64>,
+    filter: Option<HookFilter>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.dao != info.sender {
@@ -651,8 +681,17 @@ pub fn execute_add_vote_hook(
     }
 
     let validated_address = deps.api.addr_validate(&address)?;
+    if let Some(filter) = &filter {
+        filter.validate(deps.api)?;
+    }
 
-    add_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+    add_hook(
+        VOTE_HOOKS,
+        deps.storage,
+        validated_address,
+        failures_tolerated.unwrap_or_default(),
+        filter,
+    )?;
 
     Ok(Response::default()
         .add_attribute("action", "add_vote_hook")
@@ -684,9 +723,11 @@ pub fn add_hook(
     hooks: Hooks,
     storage: &mut dyn Storage,
     validated_address: Addr,
+    failures_tolerated: u64,
+    filter: Option<HookFilter>,
 ) -> Result<(), ContractError> {
     hooks
-        .add_hook(storage, validated_address)
+        .add_hook_with_config(storage, validated_address, failures_tolerated, filter)
         .map_err(ContractError::HookError)?;
     Ok(())
 }
@@ -731,6 +772,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ProposalCreationPolicy {} => query_creation_policy(deps),
         QueryMsg::ProposalHooks {} => to_json_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_json_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::ProposalHookStatus { address } => to_json_binary(
+            &PROPOSAL_HOOKS.query_hook_status(deps, deps.api.addr_validate(&address)?)?,
+        ),
+        QueryMsg::VoteHookStatus { address } => {
+            to_json_binary(&VOTE_HOOKS.query_hook_status(deps, deps.api.addr_validate(&address)?)?)
+        }
         QueryMsg::Dao {} => query_dao(deps),
         QueryMsg::ProposalExecutionError { proposal_id } => {
             query_proposal_execution_error(deps, proposal_id)
@@ -852,7 +899,7 @@ pub fn query_proposal_execution_error(deps: Deps, proposal_id: u64) -> StdResult
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     let repl = TaggedReplyId::new(msg.id)?;
     match repl {
         TaggedReplyId::FailedProposalExecution(proposal_id) => {
@@ -875,12 +922,55 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
             Ok(Response::new().add_attribute("proposal_execution_failed", proposal_id.to_string()))
         }
         TaggedReplyId::FailedProposalHook(idx) => {
-            let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
-            Ok(Response::new().add_attribute("removed_proposal_hook", format!("{addr}:{idx}")))
+            // Hooks that have failed before reply on success as well.
+            let error = match msg.result.into_result() {
+                Ok(_) => {
+                    // The hook may have been removed since the batch was prepared.
+                    let res = match PROPOSAL_HOOKS.record_hook_success(deps.storage, idx)? {
+                        Some(addr) => Response::new()
+                            .add_attribute("recovered_proposal_hook", format!("{addr}:{idx}")),
+                        None => Response::new(),
+                    };
+                    return Ok(res);
+                }
+                Err(error) => error,
+            };
+            match PROPOSAL_HOOKS.record_hook_failure(deps.storage, idx, error, env.block.height)? {
+                Some(HookFailure::Removed { addr }) => {
+                    Ok(Response::new()
+                        .add_attribute("removed_proposal_hook", format!("{addr}:{idx}")))
+                }
+                Some(HookFailure::Tolerated { addr, failures }) => Ok(Response::new()
+                    .add_attribute("failed_proposal_hook", format!("{addr}:{idx}"))
+                    .add_attribute("failures", failures.to_string())),
+                // The hook has been removed since the batch was prepared.
+                None => Ok(Response::new()),
+            }
         }
         TaggedReplyId::FailedVoteHook(idx) => {
-            let addr = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
-            Ok(Response::new().add_attribute("removed vote hook", format!("{addr}:{idx}")))
+            // Hooks that have failed before reply on success as well.
+            let error = match msg.result.into_result() {
+                Ok(_) => {
+                    // The hook may have been removed since the batch was prepared.
+                    let res = match VOTE_HOOKS.record_hook_success(deps.storage, idx)? {
+                        Some(addr) => Response::new()
+                            .add_attribute("recovered_vote_hook", format!("{addr}:{idx}")),
+                        None => Response::new(),
+                    };
+                    return Ok(res);
+                }
+                Err(error) => error,
+            };
+            match VOTE_HOOKS.record_hook_failure(deps.storage, idx, error, env.block.height)? {
+                Some(HookFailure::Removed { addr }) => {
+                    Ok(Response::new().add_attribute("removed vote hook", format!("{addr}:{idx}")))
+                }
+                Some(HookFailure::Tolerated { addr, failures }) => Ok(Response::new()
+                    .add_attribute("failed_vote_hook", format!("{addr}:{idx}"))
+                    .add_attribute("failures", failures.to_string())),
+                // The hook has been removed since the batch was prepared.
+                None => Ok(Response::new()),
+            }
         }
         TaggedReplyId::PreProposeModuleInstantiation => {
             let res = parse_reply_instantiate_data(msg)?;
@@ -1,7 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Addr;
 use cw_utils::Duration;
-use cwd_hooks::HooksResponse;
+use cwd_hooks::{HookFilter, HookStatus, HooksResponse};
 use cwd_interface::voting::InfoResponse;
 use cwd_macros::{info_query, proposal_module_query};
 use cwd_voting::{
@@ -121,14 +121,32 @@ pub enum ExecuteMsg {
     UpdatePreProposeInfo {
         info: PreProposeInfo,
     },
+    /// Adds an address as a consumer of proposal hooks. A consumer
+    /// failing to handle a hook message more than
+    /// `failures_tolerated` times in a row is removed.
     AddProposalHook {
         address: String,
+        /// The number of consecutive failed hook executions tolerated
+        /// before the consumer is removed. Defaults to zero.
+        failures_tolerated: Option<u64>,
+        /// Restricts the hook messages sent to the consumer. Defaults
+        /// to sending every hook message.
+        filter: Option<HookFilter>,
     },
     RemoveProposalHook {
         address: String,
     },
+    /// Adds an address as a consumer of vote hooks. A consumer
+    /// failing to handle a hook message more than
+    /// `failures_tolerated` times in a row is removed.
     AddVoteHook {
         address: String,
+        /// The number of consecutive failed hook executions tolerated
+        /// before the consumer is removed. Defaults to zero.
+        failures_tolerated: Option<u64>,
+        /// Restricts the hook messages sent to the consumer. Defaults
+        /// to sending every hook message.
+        filter: Option<HookFilter>,
     },
     RemoveVoteHook {
         address: String,
@@ -186,6 +204,16 @@ pub enum QueryMsg {
     /// module.
     #[returns(HooksResponse)]
     VoteHooks {},
+    /// Gets the subscription settings and failure tracking
+    /// information of a proposal hook consumer. `None` if the address is
+    /// not a consumer.
+    #[returns(Option<HookStatus>)]
+    ProposalHookStatus { address: String },
+    /// Gets the subscription settings and failure tracking
+    /// information of a vote hook consumer. `None` if the address is
+    /// not a consumer.
+    #[returns(Option<HookStatus>)]
+    VoteHookStatus { address: String },
     /// Returns errors of the failed proposal.
     /// Expected in the form of "codespace=? code=?".
     /// Returns `Option<String>`
@@ -80,6 +80,17 @@ impl Proposal for MultipleChoiceProposal {
 }
 
 impl MultipleChoiceProposal {
+    /// Returns the addresses of the contracts targeted by the messages
+    /// of all choices.
+    pub fn msg_contracts(&self) -> Vec<String> {
+        let msgs: Vec<_> = self
+            .choices
+            .iter()
+            .flat_map(|choice| choice.msgs.iter().flatten().cloned())
+            .collect();
+        cwd_hooks::msg_contracts(&msgs)
+    }
+
     /// Consumes the proposal and returns a version which may be used
     /// in a query response. The difference being that proposal
     /// statuses are only updated on vote, execute, and close
@@ -61,9 +61,13 @@ pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
 pub const PROPOSALS: Map<u64, MultipleChoiceProposal> = Map::new("proposals");
 pub const BALLOTS: Map<(u64, Addr), Ballot> = Map::new("ballots");
 /// Consumers of proposal state change hooks.
-pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new(
+    "proposal_hooks",
+    "proposal_hook_statuses",
+    "proposal_hook_batch",
+);
 /// Consumers of vote hooks.
-pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
+pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks", "vote_hook_statuses", "vote_hook_batch");
 /// The address of the pre-propose module associated with this
 /// proposal module (if any).
 pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
@@ -78,6 +78,8 @@ pub(crate) fn instantiate_with_native_bonded_balances_governance(
         description: "based neutron vault".to_string(),
         owner: DAO_ADDR.to_string(),
         denom: "ujuno".to_string(),
+        unbonding_period: None,
+        max_lock_duration: None,
     };
 
     let vault_addr = app
@@ -165,7 +167,7 @@ pub(crate) fn instantiate_with_native_bonded_balances_governance(
         app.execute_contract(
             Addr::unchecked(&address),
             vault_addr.clone(),
-            &neutron_vault::msg::ExecuteMsg::Bond {},
+            &neutron_vault::msg::ExecuteMsg::Bond { lock: None },
             &[Coin {
                 amount,
                 denom: "ujuno".to_string(),
@@ -1380,6 +1380,8 @@ fn test_hooks() {
 
     let msg = ExecuteMsg::AddProposalHook {
         address: "some_addr".to_string(),
+        failures_tolerated: None,
+        filter: None,
     };
 
     // Expect error as sender is not DAO
@@ -1428,6 +1430,8 @@ fn test_hooks() {
 
     let msg = ExecuteMsg::AddVoteHook {
         address: "some_addr".to_string(),
+        failures_tolerated: None,
+        filter: None,
     };
 
     // Expect error as sender is not DAO
@@ -7,7 +7,7 @@ use cosmwasm_std::{
 use cw2::set_contract_version;
 use cw_storage_plus::Bound;
 use cw_utils::{parse_reply_instantiate_data, Duration};
-use cwd_hooks::Hooks;
+use cwd_hooks::{msg_contracts, HookFailure, HookFilter, Hooks};
 use cwd_pre_propose_single::contract::ExecuteMsg as PreProposeMsg;
 use cwd_proposal_hooks::{new_proposal_hooks, proposal_status_changed_hooks};
 use cwd_vote_hooks::new_vote_hooks;
@@ -23,7 +23,9 @@ use neutron_sdk::bindings::msg::NeutronMsg;
 
 use crate::msg::MigrateMsg;
 use crate::proposal::SingleChoiceProposal;
-use crate::state::{Config, CREATION_POLICY, PROPOSAL_EXECUTION_ERRORS};
+use crate::state::{
+    Config, CREATION_POLICY, PROPOSAL_EXECUTION_ERRORS, PROPOSAL_PRE_PROPOSE_MODULES,
+};
 
 use crate::{
     error::ContractError,
@@ -95,6 +97,7 @@ pub fn execute(
         ExecuteMsg::Vote { proposal_id, vote } => execute_vote(deps, env, info, proposal_id, vote),
         ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
         ExecuteMsg::Close { proposal_id } => execute_close(deps, env, info, proposal_id),
+        ExecuteMsg::Cancel { proposal_id } => execute_cancel(deps, env, info, proposal_id),
         ExecuteMsg::UpdateConfig {
             threshold,
             max_voting_period,
@@ -115,13 +118,19 @@ pub fn execute(
         ExecuteMsg::UpdatePreProposeInfo { info: new_info } => {
             execute_update_proposal_creation_policy(deps, info, new_info)
         }
-        ExecuteMsg::AddProposalHook { address } => {
-            execute_add_proposal_hook(deps, env, info, address)
-        }
+        ExecuteMsg::AddProposalHook {
+            address,
+            failures_tolerated,
+            filter,
+        } => execute_add_proposal_hook(deps, env, info, address, failures_tolerated, filter),
         ExecuteMsg::RemoveProposalHook { address } => {
             execute_remove_proposal_hook(deps, env, info, address)
         }
-        ExecuteMsg::AddVoteHook { address } => execute_add_vote_hook(deps, env, info, address),
+        ExecuteMsg::AddVoteHook {
+            address,
+            failures_tolerated,
+            filter,
+        } => execute_add_vote_hook(deps, env, info, address, failures_tolerated, filter),
         ExecuteMsg::RemoveVoteHook { address } => {
             execute_remove_vote_hook(deps, env, info, address)
         }
@@ -210,8 +219,17 @@ pub fn execute_propose(
     }
 
     PROPOSALS.save(deps.storage, id, &proposal)?;
+    if let ProposalCreationPolicy::Module { addr } = &proposal_creation_policy {
+        PROPOSAL_PRE_PROPOSE_MODULES.save(deps.storage, id, addr)?;
+    }
 
-    let hooks = new_proposal_hooks(PROPOSAL_HOOKS, deps.storage, id, proposer.as_str())?;
+    let hooks = new_proposal_hooks(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        id,
+        proposer.as_str(),
+        msg_contracts(&proposal.msgs),
+    )?;
 
     // Add prepropose / deposit module hook which will save deposit info. This
     // needs to be called after execute_propose because we don't know the
@@ -269,6 +287,7 @@ pub fn execute_execute(
 
     PROPOSALS.save(deps.storage, proposal_id, &prop)?;
 
+    let contracts = msg_contracts(&prop.msgs);
     let response = {
         if !prop.msgs.is_empty() {
             let execute_message = WasmMsg::Execute {
@@ -297,6 +316,7 @@ pub fn execute_execute(
         proposal_id,
         old_status.to_string(),
         prop.status.to_string(),
+        contracts,
     )?;
 
     // Add prepropose / deposit module hook which will handle deposit refunds.
@@ -399,6 +419,7 @@ pub fn execute_vote(
         proposal_id,
         old_status.to_string(),
         new_status.to_string(),
+        msg_contracts(&prop.msgs),
     )?;
 
     let vote_hooks = new_vote_hooks(
@@ -407,6 +428,8 @@ pub fn execute_vote(
         proposal_id,
         info.sender.to_string(),
         vote.to_string(),
+        vote_power,
+        msg_contracts(&prop.msgs),
     )?;
 
     Ok(Response::default()
@@ -434,6 +457,55 @@ pub fn execute_close(
         return Err(ContractError::WrongCloseStatus {});
     }
 
+    let hooks = close_proposal(deps, proposal_id, prop)?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "close")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    // Only the current pre-propose module may cancel the proposals, and
+    // only the ones created through it.
+    match CREATION_POLICY.load(deps.storage)? {
+        ProposalCreationPolicy::Module { addr } if addr == info.sender => (),
+        _ => return Err(ContractError::Unauthorized {}),
+    }
+    if PROPOSAL_PRE_PROPOSE_MODULES.may_load(deps.storage, proposal_id)?
+        != Some(info.sender.clone())
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut prop = PROPOSALS.load(deps.storage, proposal_id)?;
+
+    prop.update_status(&env.block);
+    if prop.status != Status::Open {
+        return Err(ContractError::WrongCancelStatus {});
+    }
+
+    let hooks = close_proposal(deps, proposal_id, prop)?;
+
+    Ok(Response::default()
+        .add_submessages(hooks)
+        .add_attribute("action", "cancel")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+/// Closes the proposal and returns the hooks to fire.
+fn close_proposal(
+    deps: DepsMut,
+    proposal_id: u64,
+    mut prop: SingleChoiceProposal,
+) -> Result<Vec<SubMsg>, ContractError> {
     let old_status = prop.status;
 
     prop.status = Status::Closed;
@@ -445,6 +517,7 @@ pub fn execute_close(
         proposal_id,
         old_status.to_string(),
         prop.status.to_string(),
+        msg_contracts(&prop.msgs),
     )?;
 
     // Add prepropose / deposit module hook which will handle deposit refunds.
@@ -469,11 +542,7 @@ pub fn execute_close(
         }
     };
 
-    Ok(Response::default()
-        .add_submessages(hooks)
-        .add_attribute("action", "close")
-        .add_attribute("sender", info.sender)
-        .add_attribute("proposal_id", proposal_id.to_string()))
+    Ok(hooks)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -541,9 +610,11 @@ pub fn add_hook(
     hooks: Hooks,
     storage: &mut dyn Storage,
     validated_address: Addr,
+    failures_tolerated: u64,
+    filter: Option<HookFilter>,
 ) -> Result<(), ContractError> {
     hooks
-        .add_hook(storage, validated_address)
+        .add_hook_with_config(storage, validated_address, failures_tolerated, filter)
         .map_err(ContractError::HookError)?;
     Ok(())
 }
@@ -564,6 +635,8 @@ pub fn execute_add_proposal_hook(
     _env: Env,
     info: MessageInfo,
     address: String,
+    failures_tolerated: Option<u64>,
+    filter: Option<HookFilter>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.dao != info.sender {
@@ -572,8 +645,17 @@ pub fn execute_add_proposal_hook(
     }
 
     let validated_address = deps.api.addr_validate(&address)?;
+    if let Some(filter) = &filter {
+        filter.validate(deps.api)?;
+    }
 
-    add_hook(PROPOSAL_HOOKS, deps.storage, validated_address)?;
+    add_hook(
+        PROPOSAL_HOOKS,
+        deps.storage,
+        validated_address,
+        failures_tolerated.unwrap_or_default(),
+        filter,
+    )?;
 
     Ok(Response::default()
         .add_attribute("action", "add_proposal_hook")
@@ -606,6 +688,8 @@ pub fn execute_add_vote_hook(
     _env: Env,
     info: MessageInfo,
     address: String,
+    failures_tolerated: Option<u64>,
+    filter: Option<HookFilter>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if config.dao != info.sender {
@@ -614,8 +698,17 @@ pub fn execute_add_vote_hook(
     }
 
     let validated_address = deps.api.addr_validate(&address)?;
+    if let Some(filter) = &filter {
+        filter.validate(deps.api)?;
+    }
 
-    add_hook(VOTE_HOOKS, deps.storage, validated_address)?;
+    add_hook(
+        VOTE_HOOKS,
+        deps.storage,
+        validated_address,
+        failures_tolerated.unwrap_or_default(),
+        filter,
+    )?;
 
     Ok(Response::default()
         .add_attribute("action", "add_vote_hook")
@@ -667,6 +760,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::ProposalCreationPolicy {} => query_creation_policy(deps),
         QueryMsg::ProposalHooks {} => to_json_binary(&PROPOSAL_HOOKS.query_hooks(deps)?),
         QueryMsg::VoteHooks {} => to_json_binary(&VOTE_HOOKS.query_hooks(deps)?),
+        QueryMsg::ProposalHookStatus { address } => to_json_binary(
+            &PROPOSAL_HOOKS.query_hook_status(deps, deps.api.addr_validate(&address)?)?,
+        ),
+        QueryMsg::VoteHookStatus { address } => {
+            to_json_binary(&VOTE_HOOKS.query_hook_status(deps, deps.api.addr_validate(&address)?)?)
+        }
         QueryMsg::ProposalExecutionError { proposal_id } => {
             query_proposal_execution_error(deps, proposal_id)
         }
@@ -793,7 +892,7 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     let repl = TaggedReplyId::new(msg.id)?;
     match repl {
         TaggedReplyId::FailedProposalExecution(proposal_id) => {
@@ -818,12 +917,55 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
             Ok(Response::new().add_attribute("proposal_execution_failed", proposal_id.to_string()))
         }
         TaggedReplyId::FailedProposalHook(idx) => {
-            let addr = PROPOSAL_HOOKS.remove_hook_by_index(deps.storage, idx)?;
-            Ok(Response::new().add_attribute("removed_proposal_hook", format!("{addr}:{idx}")))
+            // Hooks that have failed before reply on success as well.
+            let error = match msg.result.into_result() {
+                Ok(_) => {
+                    // The hook may have been removed since the batch was prepared.
+                    let res = match PROPOSAL_HOOKS.record_hook_success(deps.storage, idx)? {
+                        Some(addr) => Response::new()
+                            .add_attribute("recovered_proposal_hook", format!("{addr}:{idx}")),
+                        None => Response::new(),
+                    };
+                    return Ok(res);
+                }
+                Err(error) => error,
+            };
+            match PROPOSAL_HOOKS.record_hook_failure(deps.storage, idx, error, env.block.height)? {
+                Some(HookFailure::Removed { addr }) => {
+                    Ok(Response::new()
+                        .add_attribute("removed_proposal_hook", format!("{addr}:{idx}")))
+                }
+                Some(HookFailure::Tolerated { addr, failures }) => Ok(Response::new()
+                    .add_attribute("failed_proposal_hook", format!("{addr}:{idx}"))
+                    .add_attribute("failures", failures.to_string())),
+                // The hook has been removed since the batch was prepared.
+                None => Ok(Response::new()),
+            }
         }
         TaggedReplyId::FailedVoteHook(idx) => {
-            let addr = VOTE_HOOKS.remove_hook_by_index(deps.storage, idx)?;
-            Ok(Response::new().add_attribute("removed_vote_hook", format!("{addr}:{idx}")))
+            // Hooks that have failed before reply on success as well.
+            let error = match msg.result.into_result() {
+                Ok(_) => {
+                    // The hook may have been removed since the batch was prepared.
+                    let res = match VOTE_HOOKS.record_hook_success(deps.storage, idx)? {
+                        Some(addr) => Response::new()
+                            .add_attribute("recovered_vote_hook", format!("{addr}:{idx}")),
+                        None => Response::new(),
+                    };
+                    return Ok(res);
+                }
+                Err(error) => error,
+            };
+            match VOTE_HOOKS.record_hook_failure(deps.storage, idx, error, env.block.height)? {
+                Some(HookFailure::Removed { addr }) => {
+                    Ok(Response::new().add_attribute("removed_vote_hook", format!("{addr}:{idx}")))
+                }
+                Some(HookFailure::Tolerated { addr, failures }) => Ok(Response::new()
+                    .add_attribute("failed_vote_hook", format!("{addr}:{idx}"))
+                    .add_attribute("failures", failures.to_string())),
+                // The hook has been removed since the batch was prepared.
+                None => Ok(Response::new()),
+            }
         }
         TaggedReplyId::PreProposeModuleInstantiation => {
             let res = parse_reply_instantiate_data(msg)?;
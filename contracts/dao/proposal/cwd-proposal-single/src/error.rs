@@ -50,6 +50,9 @@ pub enum ContractError {
     #[error("only rejected proposals may be closed")]
     WrongCloseStatus {},
 
+    #[error("only open proposals may be canceled")]
+    WrongCancelStatus {},
+
     #[error(
         "pre-propose modules must specify a proposer. lacking one, no proposer should be specified"
     )]
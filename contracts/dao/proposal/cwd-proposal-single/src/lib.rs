@@ -40,7 +40,16 @@
 //!
 //! To stop an invalid hook receiver from locking the proposal module
 //! receivers will be removed from the hook list if they error when
-//! handling a hook.
+//! handling a hook more times in a row than they are tolerated to (the
+//! `failures_tolerated` field on registration, zero by default). A
+//! successful call resets the count. The number of failures in a row,
+//! the last error and the height of the last failure of a receiver are
+//! available via the `ProposalHookStatus` and `VoteHookStatus` queries.
+//!
+//! Receivers may register with a `filter` to only be called for the
+//! events they care about: proposal status changes to the given
+//! statuses, votes cast with at least the given voting power, or
+//! proposals containing a message to the given contract.
 
 pub mod contract;
 mod error;
@@ -58,11 +58,18 @@ pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
 pub const PROPOSALS: Map<u64, SingleChoiceProposal> = Map::new("proposals_v2");
 pub const BALLOTS: Map<(u64, Addr), Ballot> = Map::new("ballots");
 /// Consumers of proposal state change hooks.
-pub const PROPOSAL_HOOKS: Hooks = Hooks::new("proposal_hooks");
+pub const PROPOSAL_HOOKS: Hooks = Hooks::new(
+    "proposal_hooks",
+    "proposal_hook_statuses",
+    "proposal_hook_batch",
+);
 /// Consumers of vote hooks.
-pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks");
+pub const VOTE_HOOKS: Hooks = Hooks::new("vote_hooks", "vote_hook_statuses", "vote_hook_batch");
 /// The address of the pre-propose module associated with this
 /// proposal module (if any).
 pub const CREATION_POLICY: Item<ProposalCreationPolicy> = Item::new("creation_policy");
+/// The pre-propose modules the proposals were created through. Only the
+/// module a proposal was created through may cancel it.
+pub const PROPOSAL_PRE_PROPOSE_MODULES: Map<u64, Addr> = Map::new("proposal_pre_propose_modules");
 /// Execution errors for proposals that do not close on failure (Config.close_proposal_on_execution_failure set to false)
 pub const PROPOSAL_EXECUTION_ERRORS: Map<u64, String> = Map::new("proposal_execution_errors");
@@ -238,6 +238,8 @@ pub(crate) fn add_proposal_hook(
         proposal_module.clone(),
         &ExecuteMsg::AddProposalHook {
             address: hook_addr.to_string(),
+            failures_tolerated: None,
+            filter: None,
         },
         &[],
     )
@@ -255,6 +257,8 @@ pub(crate) fn add_proposal_hook_should_fail(
         proposal_module.clone(),
         &ExecuteMsg::AddProposalHook {
             address: hook_addr.to_string(),
+            failures_tolerated: None,
+            filter: None,
         },
         &[],
     )
@@ -310,6 +314,8 @@ pub(crate) fn add_vote_hook(
         proposal_module.clone(),
         &ExecuteMsg::AddVoteHook {
             address: hook_addr.to_string(),
+            failures_tolerated: None,
+            filter: None,
         },
         &[],
     )
@@ -327,6 +333,8 @@ pub(crate) fn add_vote_hook_should_fail(
         proposal_module.clone(),
         &ExecuteMsg::AddVoteHook {
             address: hook_addr.to_string(),
+            failures_tolerated: None,
+            filter: None,
         },
         &[],
     )
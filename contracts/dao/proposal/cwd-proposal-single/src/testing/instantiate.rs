@@ -37,6 +37,7 @@ pub(crate) fn get_pre_propose_info(
             msg: to_json_binary(&cppbps::InstantiateMsg {
                 deposit_info,
                 open_proposal_submission,
+                message_policy: None,
             })
             .unwrap(),
             admin: Some(Admin::CoreModule {}),
@@ -81,6 +82,8 @@ pub(crate) fn instantiate_with_native_bonded_balances_governance(
         description: "based neutron vault".to_string(),
         owner: DAO_ADDR.to_string(),
         denom: "ujuno".to_string(),
+        unbonding_period: None,
+        max_lock_duration: None,
     };
 
     let vault_addr = app
@@ -168,7 +171,7 @@ pub(crate) fn instantiate_with_native_bonded_balances_governance(
         app.execute_contract(
             Addr::unchecked(&address),
             vault_addr.clone(),
-            &neutron_vault::msg::ExecuteMsg::Bond {},
+            &neutron_vault::msg::ExecuteMsg::Bond { lock: None },
             &[Coin {
                 amount,
                 denom: "ujuno".to_string(),
@@ -3,7 +3,7 @@ use cosmwasm_std::{
     coins, from_json,
     testing::{mock_dependencies, mock_env},
     to_json_binary, Addr, Attribute, BankMsg, ContractInfoResponse, CosmosMsg, Decimal, Empty,
-    Reply, StdError, StdResult, SubMsgResult, Uint128, WasmMsg, WasmQuery,
+    Reply, StdError, StdResult, SubMsgResponse, SubMsgResult, Uint128, WasmMsg, WasmQuery,
 };
 use cosmwasm_std::{Api, Storage};
 use cw2::ContractVersion;
@@ -11,8 +11,10 @@ use cw20::Cw20Coin;
 use cw_multi_test::{custom_app, BasicApp, Executor, Router};
 use cw_utils::Duration;
 use cwd_core::msg::{ExecuteMsg as DaoExecuteMsg, QueryMsg as DaoQueryMsg};
-use cwd_hooks::{HookError, HooksResponse};
+use cwd_hooks::{HookError, HookStatus, HooksResponse};
 use cwd_interface::voting::InfoResponse;
+use cwd_pre_propose_base::error::PreProposeError;
+use cwd_pre_propose_single::{self as cppbps, error::PreProposeSingleError};
 use cwd_voting::{
     pre_propose::{PreProposeInfo, ProposalCreationPolicy},
     proposal::MAX_PROPOSAL_SIZE,
@@ -42,7 +44,8 @@ use crate::{
             remove_vote_hook_should_fail, vote_on_proposal, vote_on_proposal_should_fail,
         },
         instantiate::{
-            get_proposal_module_instantiate, instantiate_with_native_bonded_balances_governance,
+            get_pre_propose_info, get_proposal_module_instantiate,
+            instantiate_with_native_bonded_balances_governance,
         },
         queries::{
             query_balance_native, query_creation_policy, query_list_proposals,
@@ -171,6 +174,88 @@ fn test_proposal_close_after_expiry() {
     assert_eq!(proposal.proposal.status, Status::Closed);
 }
 
+#[test]
+fn test_proposal_cancel() {
+    let CommonTest {
+        mut app,
+        core_addr,
+        proposal_module,
+        proposal_id,
+    } = setup_test(vec![]);
+
+    let pre_propose = match query_creation_policy(&app, &proposal_module) {
+        ProposalCreationPolicy::Module { addr } => addr,
+        ProposalCreationPolicy::Anyone {} => panic!("expected a pre-propose module"),
+    };
+
+    // Only the pre-propose module may cancel proposals.
+    let err: ContractError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            proposal_module.clone(),
+            &ExecuteMsg::Cancel { proposal_id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    app.execute_contract(
+        pre_propose.clone(),
+        proposal_module.clone(),
+        &ExecuteMsg::Cancel { proposal_id },
+        &[],
+    )
+    .unwrap();
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Closed);
+
+    // Closed proposals can't be canceled.
+    let err: ContractError = app
+        .execute_contract(
+            pre_propose,
+            proposal_module,
+            &ExecuteMsg::Cancel { proposal_id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::WrongCancelStatus {}));
+
+    // A new pre-propose module can't cancel the proposals created through
+    // the previous one.
+    mint_natives(&mut app, CREATOR_ADDR, coins(10000000, "ujuno"));
+    let proposal_id = make_proposal(&mut app, &proposal_module, CREATOR_ADDR, vec![]);
+    let info = get_pre_propose_info(&mut app, None, false);
+    app.execute_contract(
+        core_addr,
+        proposal_module.clone(),
+        &ExecuteMsg::UpdatePreProposeInfo { info },
+        &[],
+    )
+    .unwrap();
+    let new_pre_propose = match query_creation_policy(&app, &proposal_module) {
+        ProposalCreationPolicy::Module { addr } => addr,
+        ProposalCreationPolicy::Anyone {} => panic!("expected a pre-propose module"),
+    };
+    assert_ne!(new_pre_propose, pre_propose);
+    let err: ContractError = app
+        .execute_contract(
+            new_pre_propose,
+            proposal_module.clone(),
+            &ExecuteMsg::Cancel { proposal_id },
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    let proposal = query_proposal(&app, &proposal_module, proposal_id);
+    assert_eq!(proposal.proposal.status, Status::Open);
+}
+
 #[test]
 fn test_proposal_cant_close_after_expiry_is_passed() {
     let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
@@ -1202,6 +1287,265 @@ fn test_reply_hooks_mock() {
     );
 }
 
+#[test]
+fn test_reply_hooks_failures_tolerated() {
+    use crate::contract::{query, reply};
+    use crate::state::PROPOSAL_HOOKS;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    PROPOSAL_HOOKS
+        .add_hook_with_config(
+            deps.as_mut().storage,
+            Addr::unchecked(CREATOR_ADDR),
+            1,
+            None,
+        )
+        .unwrap();
+
+    let reply_msg = Reply {
+        id: mask_proposal_hook_index(0),
+        result: SubMsgResult::Err("error_msg".to_string()),
+    };
+
+    // The first failure is tolerated.
+    let res = reply(deps.as_mut(), env.clone(), reply_msg.clone()).unwrap();
+    assert_eq!(
+        res.attributes[0],
+        Attribute {
+            key: "failed_proposal_hook".to_string(),
+            value: format! {"{CREATOR_ADDR}:{}", 0}
+        }
+    );
+
+    let status: Option<HookStatus> = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ProposalHookStatus {
+                address: CREATOR_ADDR.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        status,
+        Some(HookStatus {
+            failures: 1,
+            failures_tolerated: 1,
+            last_error: Some("error_msg".to_string()),
+            last_failure_height: Some(env.block.height),
+            filter: None,
+        })
+    );
+
+    // A successful call resets the failure count.
+    let res = reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: mask_proposal_hook_index(0),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes[0],
+        Attribute {
+            key: "recovered_proposal_hook".to_string(),
+            value: format! {"{CREATOR_ADDR}:{}", 0}
+        }
+    );
+    let res = reply(deps.as_mut(), env.clone(), reply_msg.clone()).unwrap();
+    assert_eq!(
+        res.attributes[0],
+        Attribute {
+            key: "failed_proposal_hook".to_string(),
+            value: format! {"{CREATOR_ADDR}:{}", 0}
+        }
+    );
+
+    // The second failure in a row is not tolerated.
+    let res = reply(deps.as_mut(), env.clone(), reply_msg).unwrap();
+    assert_eq!(
+        res.attributes[0],
+        Attribute {
+            key: "removed_proposal_hook".to_string(),
+            value: format! {"{CREATOR_ADDR}:{}", 0}
+        }
+    );
+
+    let status: Option<HookStatus> = from_json(
+        query(
+            deps.as_ref(),
+            env,
+            QueryMsg::ProposalHookStatus {
+                address: CREATOR_ADDR.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(status, None);
+}
+
+#[test]
+fn test_filtered_proposal_hooks() {
+    use crate::contract::reply;
+    use crate::state::PROPOSAL_HOOKS;
+    use cwd_hooks::HookFilter;
+    use cwd_proposal_hooks::proposal_status_changed_hooks;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    // The first consumer only wants to hear about executed proposals,
+    // e.g. to refund deposits.
+    PROPOSAL_HOOKS
+        .add_hook_with_config(
+            deps.as_mut().storage,
+            Addr::unchecked("completions"),
+            0,
+            Some(HookFilter {
+                statuses: Some(vec![Status::Executed]),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+    PROPOSAL_HOOKS
+        .add_hook(deps.as_mut().storage, Addr::unchecked(CREATOR_ADDR))
+        .unwrap();
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.as_mut().storage,
+        1,
+        Status::Open.to_string(),
+        Status::Passed.to_string(),
+        vec![],
+    )
+    .unwrap();
+    assert_eq!(hooks.len(), 1);
+    // The reply ID still refers to the position of the consumer in the
+    // list of hooks.
+    assert_eq!(hooks[0].id, mask_proposal_hook_index(1));
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.as_mut().storage,
+        1,
+        Status::Passed.to_string(),
+        Status::Executed.to_string(),
+        vec![],
+    )
+    .unwrap();
+    assert_eq!(hooks.len(), 2);
+
+    // A failure of the unfiltered consumer removes it, not the filtered
+    // one.
+    let res = reply(
+        deps.as_mut(),
+        env,
+        Reply {
+            id: mask_proposal_hook_index(1),
+            result: SubMsgResult::Err("error_msg".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes[0],
+        Attribute {
+            key: "removed_proposal_hook".to_string(),
+            value: format! {"{CREATOR_ADDR}:{}", 1}
+        }
+    );
+    let HooksResponse { hooks } = PROPOSAL_HOOKS.query_hooks(deps.as_ref()).unwrap();
+    assert_eq!(hooks, vec!["completions".to_string()]);
+}
+
+#[test]
+fn test_proposal_hook_removed_during_batch() {
+    use crate::contract::reply;
+    use crate::state::PROPOSAL_HOOKS;
+    use cwd_proposal_hooks::proposal_status_changed_hooks;
+
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    for hook in ["removed", CREATOR_ADDR] {
+        PROPOSAL_HOOKS
+            .add_hook_with_config(deps.as_mut().storage, Addr::unchecked(hook), 1, None)
+            .unwrap();
+    }
+    // Both hooks have failed once, so they reply on success as well.
+    for index in 0..2 {
+        reply(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: mask_proposal_hook_index(index),
+                result: SubMsgResult::Err("error_msg".to_string()),
+            },
+        )
+        .unwrap();
+    }
+
+    let hooks = proposal_status_changed_hooks(
+        PROPOSAL_HOOKS,
+        deps.as_mut().storage,
+        1,
+        Status::Open.to_string(),
+        Status::Passed.to_string(),
+        vec![],
+    )
+    .unwrap();
+    assert_eq!(hooks.len(), 2);
+
+    // The first hook is removed before the replies of the batch arrive.
+    PROPOSAL_HOOKS
+        .remove_hook(deps.as_mut().storage, Addr::unchecked("removed"))
+        .unwrap();
+
+    let res = reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: mask_proposal_hook_index(1),
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes[0],
+        Attribute {
+            key: "recovered_proposal_hook".to_string(),
+            value: format! {"{CREATOR_ADDR}:{}", 1}
+        }
+    );
+
+    // The reply of the removed hook doesn't fail the transaction.
+    let res = reply(
+        deps.as_mut(),
+        env,
+        Reply {
+            id: mask_proposal_hook_index(0),
+            result: SubMsgResult::Err("error_msg".to_string()),
+        },
+    )
+    .unwrap();
+    assert!(res.attributes.is_empty());
+    let HooksResponse { hooks } = PROPOSAL_HOOKS.query_hooks(deps.as_ref()).unwrap();
+    assert_eq!(hooks, vec![CREATOR_ADDR.to_string()]);
+}
+
 #[test]
 fn test_query_info() {
     let CommonTest {
@@ -1357,6 +1701,201 @@ fn test_pre_propose_admin_is_dao() {
     assert_eq!(info.admin, Some(core_addr.into_string()));
 }
 
+#[test]
+fn test_pre_propose_message_policy() {
+    let CommonTest {
+        mut app,
+        core_addr,
+        proposal_module,
+        proposal_id: _,
+    } = setup_test(vec![]);
+
+    let pre_propose = match query_creation_policy(&app, &proposal_module) {
+        ProposalCreationPolicy::Anyone {} => panic!("expected a pre-propose module"),
+        ProposalCreationPolicy::Module { addr } => addr,
+    };
+
+    let update_policy = cppbps::ExecuteMsg::Extension {
+        msg: cppbps::ExecuteExt::UpdateMessagePolicy {
+            message_policy: Some(cppbps::MessagePolicy {
+                allowed_kinds: Some(vec![cppbps::MessageKind::Bank]),
+                allowed_contracts: None,
+                max_bank_send: Some(coins(10, "ujuno")),
+                max_msgs: Some(2),
+            }),
+        },
+    };
+
+    // Only the DAO may update the policy.
+    let err: PreProposeSingleError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            pre_propose.clone(),
+            &update_policy,
+            &[],
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeSingleError::PreProposeBase(PreProposeError::NotDao {})
+    );
+
+    app.execute_contract(core_addr, pre_propose.clone(), &update_policy, &[])
+        .unwrap();
+
+    let propose = |msgs: Vec<CosmosMsg<NeutronMsg>>| cppbps::ExecuteMsg::Propose {
+        msg: cppbps::ProposeMessage::Propose {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            msgs,
+        },
+    };
+    let send = |amount: u128| -> CosmosMsg<NeutronMsg> {
+        BankMsg::Send {
+            to_address: CREATOR_ADDR.to_string(),
+            amount: coins(amount, "ujuno"),
+        }
+        .into()
+    };
+
+    mint_natives(&mut app, CREATOR_ADDR, coins(10_000_000, "ujuno"));
+    let err: PreProposeSingleError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            pre_propose.clone(),
+            &propose(vec![send(6), send(5)]),
+            &coins(10_000_000, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeSingleError::BankSendLimitExceeded {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(11),
+            limit: Uint128::new(10),
+        }
+    );
+
+    let err: PreProposeSingleError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            pre_propose.clone(),
+            &propose(vec![send(1), send(1), send(1)]),
+            &coins(10_000_000, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeSingleError::TooManyMessages { count: 3, max: 2 }
+    );
+
+    let err: PreProposeSingleError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            pre_propose,
+            &propose(vec![WasmMsg::Execute {
+                contract_addr: proposal_module.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Close { proposal_id: 1 }).unwrap(),
+                funds: vec![],
+            }
+            .into()]),
+            &coins(10_000_000, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeSingleError::MessageKindNotAllowed {
+            kind: cppbps::MessageKind::Wasm
+        }
+    );
+
+    // Proposals within the policy are accepted.
+    make_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        vec![send(5), send(5)],
+    );
+
+    // Funds attached to wasm messages count towards the bank send limit.
+    app.execute_contract(
+        core_addr,
+        pre_propose.clone(),
+        &cppbps::ExecuteMsg::Extension {
+            msg: cppbps::ExecuteExt::UpdateMessagePolicy {
+                message_policy: Some(cppbps::MessagePolicy {
+                    allowed_kinds: None,
+                    allowed_contracts: Some(vec![proposal_module.to_string()]),
+                    max_bank_send: Some(coins(10, "ujuno")),
+                    max_msgs: None,
+                }),
+            },
+        },
+        &[],
+    )
+    .unwrap();
+    let execute = |contract_addr: &str, amount: u128| -> CosmosMsg<NeutronMsg> {
+        WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_json_binary(&ExecuteMsg::Close { proposal_id: 1 }).unwrap(),
+            funds: coins(amount, "ujuno"),
+        }
+        .into()
+    };
+
+    let err: PreProposeSingleError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            pre_propose.clone(),
+            &propose(vec![execute(proposal_module.as_str(), 6), send(5)]),
+            &coins(10_000_000, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeSingleError::BankSendLimitExceeded {
+            denom: "ujuno".to_string(),
+            amount: Uint128::new(11),
+            limit: Uint128::new(10),
+        }
+    );
+
+    // Targets are compared as validated addresses.
+    let err: PreProposeSingleError = app
+        .execute_contract(
+            Addr::unchecked(CREATOR_ADDR),
+            pre_propose,
+            &propose(vec![execute(&proposal_module.as_str().to_uppercase(), 1)]),
+            &coins(10_000_000, "ujuno"),
+        )
+        .unwrap_err()
+        .downcast()
+        .unwrap();
+    assert_eq!(
+        err,
+        PreProposeSingleError::ContractNotAllowed {
+            contract: proposal_module.as_str().to_uppercase(),
+        }
+    );
+
+    make_proposal(
+        &mut app,
+        &proposal_module,
+        CREATOR_ADDR,
+        vec![execute(proposal_module.as_str(), 5), send(5)],
+    );
+}
+
 #[test]
 fn test_subdao_queries() {
     let mut app = custom_app::<NeutronMsg, Empty, _>(no_init);
@@ -1,10 +1,16 @@
 use crate::error::ContractError;
-use crate::msg::{CreditsQueryMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{Config, TotalSupplyResponse, CONFIG, DAO};
+use crate::msg::{
+    CreditsQueryMsg, CreditsSourceMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    SourcePower, VotingPowerBreakdownResponse,
+};
+use crate::state::{
+    Config, CreditsSource, TotalSupplyResponse, CONFIG, CREDITS_SOURCES, DAO, MAX_CREDITS_SOURCES,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
+    to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdError, StdResult, Uint128,
 };
 use cw2::set_contract_version;
 use cwd_interface::voting::{
@@ -16,8 +22,8 @@ pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -40,6 +46,10 @@ pub fn instantiate(
     CONFIG.save(deps.storage, &config)?;
     DAO.save(deps.storage, &info.sender)?;
 
+    for source in msg.credits_sources {
+        add_credits_source(deps.branch(), &env, &config, source)?;
+    }
+
     Ok(Response::new()
         .add_attribute("action", "instantiate")
         .add_attribute("description", config.description)
@@ -71,6 +81,18 @@ pub fn execute(
             name,
             description,
         ),
+        ExecuteMsg::AddCreditsSource { source } => {
+            execute_add_credits_source(deps, env, info, source)
+        }
+        ExecuteMsg::ExpireCreditsSource {
+            credits_contract_address,
+            expiry_height,
+        } => {
+            execute_expire_credits_source(deps, env, info, credits_contract_address, expiry_height)
+        }
+        ExecuteMsg::RemoveCreditsSource {
+            credits_contract_address,
+        } => execute_remove_credits_source(deps, env, info, credits_contract_address),
     }
 }
 
@@ -121,8 +143,11 @@ pub fn execute_update_config(
     if let Some(description) = new_description {
         config.description = description;
     }
+    // the credits contract is not snapshotted, so switching it would rewrite historical power
     if let Some(new_credits_contract_address) = new_credits_contract_address {
-        config.credits_contract_address = new_credits_contract_address;
+        if new_credits_contract_address != config.credits_contract_address {
+            return Err(ContractError::CreditsContractAddressImmutable {});
+        }
     }
 
     config.validate()?;
@@ -135,6 +160,192 @@ pub fn execute_update_config(
         .add_attribute("owner", config.owner))
 }
 
+pub fn execute_add_credits_source(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    source: CreditsSourceMsg,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let source = add_credits_source(deps, &env, &config, source)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_credits_source")
+        .add_attribute("credits_contract_address", source.credits_contract_address)
+        .add_attribute("start_height", source.start_height.to_string())
+        .add_attribute("multiplier", source.multiplier.to_string()))
+}
+
+pub fn execute_expire_credits_source(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    credits_contract_address: String,
+    expiry_height: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let address = deps.api.addr_validate(&credits_contract_address)?;
+    let mut source = CREDITS_SOURCES
+        .may_load(deps.storage, &address)?
+        .ok_or_else(|| ContractError::CreditsSourceNotFound {
+            address: address.to_string(),
+        })?;
+
+    if source
+        .expiry_height
+        .map_or(false, |expiry_height| expiry_height <= env.block.height)
+    {
+        return Err(ContractError::CreditsSourceExpired {
+            address: address.to_string(),
+        });
+    }
+
+    // the source has never been counted yet, so it can be dropped without
+    // affecting historical voting power
+    if env.block.height < source.start_height {
+        CREDITS_SOURCES.remove(deps.storage, &address);
+        return Ok(Response::new()
+            .add_attribute("action", "remove_credits_source")
+            .add_attribute("credits_contract_address", address));
+    }
+
+    let expiry_height = expiry_height.unwrap_or(env.block.height);
+    if expiry_height < env.block.height {
+        return Err(ContractError::HeightInPast {
+            height: expiry_height,
+        });
+    }
+    source.expiry_height = Some(expiry_height);
+    source.validate()?;
+    CREDITS_SOURCES.save(deps.storage, &address, &source)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "expire_credits_source")
+        .add_attribute("credits_contract_address", address)
+        .add_attribute("expiry_height", expiry_height.to_string()))
+}
+
+pub fn execute_remove_credits_source(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    credits_contract_address: String,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let address = deps.api.addr_validate(&credits_contract_address)?;
+    let source = CREDITS_SOURCES
+        .may_load(deps.storage, &address)?
+        .ok_or_else(|| ContractError::CreditsSourceNotFound {
+            address: address.to_string(),
+        })?;
+
+    if !source
+        .expiry_height
+        .map_or(false, |expiry_height| expiry_height <= env.block.height)
+    {
+        return Err(ContractError::CreditsSourceNotExpired {
+            address: address.to_string(),
+        });
+    }
+    CREDITS_SOURCES.remove(deps.storage, &address);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_credits_source")
+        .add_attribute("credits_contract_address", address))
+}
+
+fn add_credits_source(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    msg: CreditsSourceMsg,
+) -> Result<CreditsSource, ContractError> {
+    let source = CreditsSource {
+        credits_contract_address: deps.api.addr_validate(&msg.credits_contract_address)?,
+        airdrop_contract_address: msg
+            .airdrop_contract_address
+            .map(|address| deps.api.addr_validate(&address))
+            .transpose()?,
+        start_height: msg.start_height,
+        expiry_height: msg.expiry_height,
+        multiplier: msg.multiplier,
+    };
+    source.validate()?;
+
+    // adding a source must not change voting power of already created proposals
+    if source.start_height < env.block.height {
+        return Err(ContractError::HeightInPast {
+            height: source.start_height,
+        });
+    }
+    if source.credits_contract_address == config.credits_contract_address
+        || CREDITS_SOURCES.has(deps.storage, &source.credits_contract_address)
+    {
+        return Err(ContractError::CreditsSourceAlreadyExists {
+            address: source.credits_contract_address.to_string(),
+        });
+    }
+
+    // expired sources are kept for historical voting power and are still iterated by the power
+    // queries, so they count towards the limit until they are removed
+    let count = CREDITS_SOURCES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count();
+    if count >= MAX_CREDITS_SOURCES {
+        return Err(ContractError::TooManyCreditsSources {
+            max: MAX_CREDITS_SOURCES,
+        });
+    }
+
+    CREDITS_SOURCES.save(deps.storage, &source.credits_contract_address, &source)?;
+    Ok(source)
+}
+
+/// Returns the primary credits contract from the config followed by the additional sources.
+fn credits_sources(deps: Deps, config: &Config) -> StdResult<Vec<CreditsSource>> {
+    let mut sources = vec![CreditsSource {
+        credits_contract_address: config.credits_contract_address.clone(),
+        airdrop_contract_address: Some(config.airdrop_contract_address.clone()),
+        start_height: 0,
+        expiry_height: None,
+        multiplier: Decimal::one(),
+    }];
+    for item in CREDITS_SOURCES.range(deps.storage, None, None, Order::Ascending) {
+        sources.push(item?.1);
+    }
+    Ok(sources)
+}
+
+fn query_credits_balance(
+    deps: Deps,
+    credits_contract_address: &Addr,
+    address: String,
+    height: u64,
+) -> StdResult<Uint128> {
+    Ok(deps
+        .querier
+        .query_wasm_smart::<cw20::BalanceResponse>(
+            credits_contract_address,
+            &CreditsQueryMsg::BalanceAtHeight {
+                height: Some(height),
+                address,
+            },
+        )?
+        .balance)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -149,6 +360,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Name {} => query_name(deps),
         QueryMsg::Description {} => query_description(deps),
         QueryMsg::Config {} => query_config(deps),
+        QueryMsg::CreditsSources {} => query_credits_sources(deps),
+        QueryMsg::VotingPowerBreakdown { address, height } => {
+            to_json_binary(&query_voting_power_breakdown(deps, env, address, height)?)
+        }
         QueryMsg::ListBonders { start_after, limit } => {
             query_list_bonders(deps, start_after, limit)
         }
@@ -164,27 +379,59 @@ pub fn query_voting_power_at_height(
     address: String,
     height: Option<u64>,
 ) -> StdResult<VotingPowerAtHeightResponse> {
+    let breakdown = query_voting_power_breakdown(deps, env, address, height)?;
+
+    Ok(VotingPowerAtHeightResponse {
+        power: breakdown.power,
+        height: breakdown.height,
+    })
+}
+
+pub fn query_voting_power_breakdown(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<VotingPowerBreakdownResponse> {
     let config = CONFIG.load(deps.storage)?;
 
     let height = height.unwrap_or(env.block.height);
 
-    let balance = if address == config.airdrop_contract_address {
-        Uint128::zero()
-    } else {
-        deps.querier
-            .query_wasm_smart::<cw20::BalanceResponse>(
-                config.credits_contract_address,
-                &CreditsQueryMsg::BalanceAtHeight {
-                    height: Some(height),
-                    address,
-                },
+    let mut power = Uint128::zero();
+    let mut sources = vec![];
+    for source in credits_sources(deps, &config)? {
+        let active = source.is_active(height);
+        let is_airdrop = source
+            .airdrop_contract_address
+            .as_ref()
+            .map_or(false, |airdrop| *airdrop == address);
+
+        let balance = if !active || is_airdrop {
+            Uint128::zero()
+        } else {
+            query_credits_balance(
+                deps,
+                &source.credits_contract_address,
+                address.clone(),
+                height,
             )?
-            .balance
-    };
+        };
+        let source_power = balance.mul_floor(source.multiplier);
+        power = power.checked_add(source_power)?;
+
+        sources.push(SourcePower {
+            credits_contract_address: source.credits_contract_address,
+            active,
+            balance,
+            multiplier: source.multiplier,
+            power: source_power,
+        });
+    }
 
-    Ok(VotingPowerAtHeightResponse {
-        power: balance,
+    Ok(VotingPowerBreakdownResponse {
+        power,
         height,
+        sources,
     })
 }
 
@@ -197,27 +444,38 @@ pub fn query_total_power_at_height(
 
     let height = height.unwrap_or(env.block.height);
 
-    let airdrop_balance: cw20::BalanceResponse = deps.querier.query_wasm_smart(
-        config.credits_contract_address.clone(),
-        &CreditsQueryMsg::BalanceAtHeight {
-            height: Some(height),
-            address: config.airdrop_contract_address.to_string(),
-        },
-    )?;
-
-    let total_supply: TotalSupplyResponse = deps.querier.query_wasm_smart(
-        config.credits_contract_address,
-        &CreditsQueryMsg::TotalSupplyAtHeight {
-            height: Some(height),
-        },
-    )?;
-
-    Ok(TotalPowerAtHeightResponse {
-        power: total_supply
-            .total_supply
-            .checked_sub(airdrop_balance.balance)?,
-        height,
-    })
+    let mut power = Uint128::zero();
+    for source in credits_sources(deps, &config)? {
+        if !source.is_active(height) {
+            continue;
+        }
+
+        let airdrop_balance = match source.airdrop_contract_address {
+            Some(airdrop) => query_credits_balance(
+                deps,
+                &source.credits_contract_address,
+                airdrop.to_string(),
+                height,
+            )?,
+            None => Uint128::zero(),
+        };
+
+        let total_supply: TotalSupplyResponse = deps.querier.query_wasm_smart(
+            source.credits_contract_address,
+            &CreditsQueryMsg::TotalSupplyAtHeight {
+                height: Some(height),
+            },
+        )?;
+
+        power = power.checked_add(
+            total_supply
+                .total_supply
+                .checked_sub(airdrop_balance)?
+                .mul_floor(source.multiplier),
+        )?;
+    }
+
+    Ok(TotalPowerAtHeightResponse { power, height })
 }
 
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
@@ -245,6 +503,14 @@ pub fn query_config(deps: Deps) -> StdResult<Binary> {
     to_json_binary(&config)
 }
 
+pub fn query_credits_sources(deps: Deps) -> StdResult<Binary> {
+    let sources = CREDITS_SOURCES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, source)| source))
+        .collect::<StdResult<Vec<_>>>()?;
+    to_json_binary(&sources)
+}
+
 pub fn query_list_bonders(
     _deps: Deps,
     _start_after: Option<String>,
@@ -20,4 +20,31 @@ pub enum ContractError {
 
     #[error("config description cannot be empty.")]
     DescriptionIsEmpty {},
+
+    #[error("credits source multiplier cannot be zero.")]
+    ZeroMultiplier {},
+
+    #[error("credits source expiry height must be greater than its start height {start_height}.")]
+    InvalidExpiryHeight { start_height: u64 },
+
+    #[error("credits source height {height} is in the past.")]
+    HeightInPast { height: u64 },
+
+    #[error("credits source {address} already exists.")]
+    CreditsSourceAlreadyExists { address: String },
+
+    #[error("credits source {address} not found.")]
+    CreditsSourceNotFound { address: String },
+
+    #[error("credits source {address} has already expired.")]
+    CreditsSourceExpired { address: String },
+
+    #[error("credits source {address} has not expired yet.")]
+    CreditsSourceNotExpired { address: String },
+
+    #[error("cannot have more than {max} credits sources, expired ones can be removed with RemoveCreditsSource.")]
+    TooManyCreditsSources { max: usize },
+
+    #[error("credits contract address cannot be changed, add the new contract as a credits source instead.")]
+    CreditsContractAddressImmutable {},
 }
@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Uint128};
 use cwd_interface::voting::{
     BondingStatusResponse, InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
@@ -19,6 +19,23 @@ pub struct InstantiateMsg {
     pub owner: String,
     /// Airdrop address is the address of the airdrop contract.
     pub airdrop_contract_address: String,
+    /// Additional credits contracts counted towards the voting power.
+    #[serde(default)]
+    pub credits_sources: Vec<CreditsSourceMsg>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct CreditsSourceMsg {
+    /// Credits contract address.
+    pub credits_contract_address: String,
+    /// Airdrop contract whose balance is excluded from the source's power.
+    pub airdrop_contract_address: Option<String>,
+    /// First height at which the source is counted, must not be in the past.
+    pub start_height: u64,
+    /// Height starting from which the source's power drops to zero.
+    pub expiry_height: Option<u64>,
+    /// Voting power per credits token.
+    pub multiplier: Decimal,
 }
 
 #[voting_vault]
@@ -26,11 +43,26 @@ pub struct InstantiateMsg {
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     UpdateConfig {
+        /// Can't be changed, only the current address is accepted. Historical voting power
+        /// relies on it, so new credits contracts are added with `AddCreditsSource`.
         credits_contract_address: Option<String>,
         owner: Option<String>,
         name: Option<String>,
         description: Option<String>,
     },
+    /// Adds an additional credits source. Can only be called by the owner.
+    AddCreditsSource { source: CreditsSourceMsg },
+    /// Sets the height starting from which the source has no voting power,
+    /// current height if not set. Can only be called by the owner.
+    ExpireCreditsSource {
+        credits_contract_address: String,
+        expiry_height: Option<u64>,
+    },
+    /// Removes an expired source, so that it no longer counts towards the sources limit.
+    /// The power the source gave before its expiry height is lost, so it should only be
+    /// removed once no proposal created before that height is open. Can only be called by
+    /// the owner.
+    RemoveCreditsSource { credits_contract_address: String },
 }
 
 #[voting_query]
@@ -41,6 +73,31 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(crate::state::Config)]
     Config {},
+    #[returns(Vec<crate::state::CreditsSource>)]
+    CreditsSources {},
+    #[returns(VotingPowerBreakdownResponse)]
+    VotingPowerBreakdown {
+        address: String,
+        height: Option<u64>,
+    },
+}
+
+#[cw_serde]
+pub struct SourcePower {
+    pub credits_contract_address: Addr,
+    /// Whether the source is counted at the queried height.
+    pub active: bool,
+    pub balance: Uint128,
+    pub multiplier: Decimal,
+    pub power: Uint128,
+}
+
+#[cw_serde]
+pub struct VotingPowerBreakdownResponse {
+    pub power: Uint128,
+    pub height: u64,
+    /// Power per source, the primary credits contract goes first.
+    pub sources: Vec<SourcePower>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
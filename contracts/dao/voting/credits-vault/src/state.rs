@@ -1,7 +1,7 @@
 use crate::error::ContractError;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -27,6 +27,46 @@ impl Config {
     }
 }
 
+/// An additional credits contract whose balances are counted towards the voting power.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct CreditsSource {
+    pub credits_contract_address: Addr,
+    /// Balance of this address is excluded from the source's total power and
+    /// the address itself has no power from the source.
+    pub airdrop_contract_address: Option<Addr>,
+    /// First height at which the source is counted.
+    pub start_height: u64,
+    /// Height starting from which the source's power drops to zero.
+    pub expiry_height: Option<u64>,
+    /// Voting power per credits token.
+    pub multiplier: Decimal,
+}
+
+impl CreditsSource {
+    /// checks whether the source fields are valid.
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.multiplier.is_zero() {
+            return Err(ContractError::ZeroMultiplier {});
+        }
+        if let Some(expiry_height) = self.expiry_height {
+            if expiry_height <= self.start_height {
+                return Err(ContractError::InvalidExpiryHeight {
+                    start_height: self.start_height,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// returns whether the source has voting power at the given height.
+    pub fn is_active(&self, height: u64) -> bool {
+        height >= self.start_height
+            && self
+                .expiry_height
+                .map_or(true, |expiry_height| height < expiry_height)
+    }
+}
+
 #[cw_serde]
 pub struct TotalSupplyResponse {
     // Total supply of ucNTRNs for specified block height
@@ -36,12 +76,17 @@ pub struct TotalSupplyResponse {
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const DAO: Item<Addr> = Item::new("dao");
 pub const DESCRIPTION: Item<String> = Item::new("description");
+/// Additional credits sources, keyed by credits contract address.
+pub const CREDITS_SOURCES: Map<&Addr, CreditsSource> = Map::new("credits_sources");
+
+/// Limits the number of additional sources so that power queries stay bounded.
+pub const MAX_CREDITS_SOURCES: usize = 10;
 
 #[cfg(test)]
 mod tests {
-    use super::Config;
+    use super::{Config, CreditsSource};
     use crate::error::ContractError;
-    use cosmwasm_std::Addr;
+    use cosmwasm_std::{Addr, Decimal};
 
     #[test]
     fn test_config_validate() {
@@ -78,4 +123,38 @@ mod tests {
             Err(ContractError::DescriptionIsEmpty {})
         );
     }
+
+    #[test]
+    fn test_credits_source_validate() {
+        let source = CreditsSource {
+            credits_contract_address: Addr::unchecked("credits_contract"),
+            airdrop_contract_address: None,
+            start_height: 10,
+            expiry_height: Some(20),
+            multiplier: Decimal::percent(50),
+        };
+        assert_eq!(source.validate(), Ok(()));
+        assert!(!source.is_active(9));
+        assert!(source.is_active(10));
+        assert!(source.is_active(19));
+        assert!(!source.is_active(20));
+
+        let zero_multiplier = CreditsSource {
+            multiplier: Decimal::zero(),
+            ..source.clone()
+        };
+        assert_eq!(
+            zero_multiplier.validate(),
+            Err(ContractError::ZeroMultiplier {})
+        );
+
+        let early_expiry = CreditsSource {
+            expiry_height: Some(10),
+            ..source
+        };
+        assert_eq!(
+            early_expiry.validate(),
+            Err(ContractError::InvalidExpiryHeight { start_height: 10 })
+        );
+    }
 }
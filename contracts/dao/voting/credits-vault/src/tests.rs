@@ -1,9 +1,14 @@
 use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
-use crate::msg::{CreditsQueryMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{Config, TotalSupplyResponse};
+use crate::msg::{
+    CreditsQueryMsg, CreditsSourceMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    SourcePower, VotingPowerBreakdownResponse,
+};
+use crate::state::{Config, CreditsSource, TotalSupplyResponse, MAX_CREDITS_SOURCES};
 use crate::ContractError;
 use cosmwasm_std::testing::{mock_dependencies, mock_env};
-use cosmwasm_std::{to_json_binary, Addr, Binary, Deps, Empty, Env, Response, StdResult, Uint128};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Decimal, Deps, Empty, Env, Response, StdResult, Uint128,
+};
 use cw_multi_test::{custom_app, App, AppResponse, Contract, ContractWrapper, Executor};
 use cwd_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
@@ -13,6 +18,7 @@ use serde::{Deserialize, Serialize};
 
 const DAO_ADDR: &str = "dao";
 const AIRDROP_ADDR: &str = "airdrop";
+const AIRDROP_ADDR_2: &str = "airdrop2";
 const NAME: &str = "name";
 const DESCRIPTION: &str = "description";
 const NEW_NAME: &str = "new name";
@@ -50,11 +56,42 @@ fn credits_query(_deps: Deps, _env: Env, msg: CreditsQueryMsg) -> StdResult<Bina
     }
 }
 
+fn other_credits_query(_deps: Deps, _env: Env, msg: CreditsQueryMsg) -> StdResult<Binary> {
+    match msg {
+        CreditsQueryMsg::BalanceAtHeight { address, height: _ } => {
+            let response = cw20::BalanceResponse {
+                balance: Uint128::from(if address == AIRDROP_ADDR_2 {
+                    500u64
+                } else {
+                    1000u64
+                }),
+            };
+            to_json_binary(&response)
+        }
+        CreditsQueryMsg::TotalSupplyAtHeight { height: _ } => {
+            let response = TotalSupplyResponse {
+                total_supply: Uint128::from(3000u64),
+            };
+            to_json_binary(&response)
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct EmptyMsg {}
 
 fn credits_contract() -> Box<dyn Contract<Empty>> {
+    mock_credits_contract(credits_query)
+}
+
+fn other_credits_contract() -> Box<dyn Contract<Empty>> {
+    mock_credits_contract(other_credits_query)
+}
+
+fn mock_credits_contract(
+    query_fn: fn(Deps, Env, CreditsQueryMsg) -> StdResult<Binary>,
+) -> Box<dyn Contract<Empty>> {
     let contract: ContractWrapper<
         EmptyMsg,
         EmptyMsg,
@@ -65,13 +102,22 @@ fn credits_contract() -> Box<dyn Contract<Empty>> {
     > = ContractWrapper::new(
         |_, _, _, _: EmptyMsg| Ok(Response::new()),
         |_, _, _, _: EmptyMsg| Ok(Response::new()),
-        credits_query,
+        query_fn,
     );
     Box::new(contract)
 }
 
 fn instantiate_credits_contract(app: &mut App) -> Addr {
     let contract_id = app.store_code(credits_contract());
+    instantiate_mock_credits_contract(app, contract_id)
+}
+
+fn instantiate_other_credits_contract(app: &mut App) -> Addr {
+    let contract_id = app.store_code(other_credits_contract());
+    instantiate_mock_credits_contract(app, contract_id)
+}
+
+fn instantiate_mock_credits_contract(app: &mut App, contract_id: u64) -> Addr {
     app.instantiate_contract(
         contract_id,
         Addr::unchecked(DAO_ADDR),
@@ -114,6 +160,74 @@ fn update_config(
     )
 }
 
+fn add_credits_source(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    source: CreditsSourceMsg,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::AddCreditsSource { source },
+        &[],
+    )
+}
+
+fn expire_credits_source(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    credits_contract_address: String,
+    expiry_height: Option<u64>,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::ExpireCreditsSource {
+            credits_contract_address,
+            expiry_height,
+        },
+        &[],
+    )
+}
+
+fn remove_credits_source(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    credits_contract_address: String,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::RemoveCreditsSource {
+            credits_contract_address,
+        },
+        &[],
+    )
+}
+
+fn get_credits_sources(app: &mut App, contract_addr: Addr) -> Vec<CreditsSource> {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::CreditsSources {})
+        .unwrap()
+}
+
+fn get_voting_power_breakdown(
+    app: &mut App,
+    contract_addr: Addr,
+    address: String,
+    height: Option<u64>,
+) -> VotingPowerBreakdownResponse {
+    app.wrap()
+        .query_wasm_smart(
+            contract_addr,
+            &QueryMsg::VotingPowerBreakdown { address, height },
+        )
+        .unwrap()
+}
+
 fn get_voting_power_at_height(
     app: &mut App,
     contract_addr: Addr,
@@ -165,6 +279,7 @@ fn test_instantiate() {
             name: NAME.to_string(),
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
+            credits_sources: vec![],
         },
     );
 }
@@ -185,6 +300,7 @@ fn test_update_config_unauthorized() {
             name: NAME.to_string(),
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
+            credits_sources: vec![],
         },
     );
 
@@ -216,6 +332,7 @@ fn test_update_config_as_owner() {
             name: NAME.to_string(),
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
+            credits_sources: vec![],
         },
     );
 
@@ -260,6 +377,7 @@ fn test_update_config_invalid_description() {
             name: NAME.to_string(),
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
+            credits_sources: vec![],
         },
     );
 
@@ -292,6 +410,7 @@ fn test_update_config_invalid_name() {
             name: NAME.to_string(),
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
+            credits_sources: vec![],
         },
     );
 
@@ -323,6 +442,7 @@ fn test_query_dao() {
             name: NAME.to_string(),
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
+            credits_sources: vec![],
         },
     );
 
@@ -346,6 +466,7 @@ fn test_query_info() {
             name: NAME.to_string(),
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
+            credits_sources: vec![],
         },
     );
 
@@ -369,6 +490,7 @@ fn test_query_get_config() {
             name: NAME.to_string(),
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
+            credits_sources: vec![],
         },
     );
 
@@ -400,6 +522,7 @@ fn test_query_get_description() {
             name: NAME.to_string(),
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
+            credits_sources: vec![],
         },
     );
 
@@ -422,6 +545,7 @@ fn test_voting_power_queries() {
             name: NAME.to_string(),
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
+            credits_sources: vec![],
         },
     );
 
@@ -438,6 +562,475 @@ fn test_voting_power_queries() {
     assert_eq!(Uint128::from(0u64), resp.power);
 }
 
+#[test]
+fn test_credits_sources() {
+    let mut app = mock_app();
+    let credits_contract = instantiate_credits_contract(&mut app);
+    let other_credits_contract = instantiate_other_credits_contract(&mut app);
+    let start_height = app.block_info().height + 10;
+
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            credits_contract_address: credits_contract.to_string(),
+            airdrop_contract_address: AIRDROP_ADDR.to_string(),
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            credits_sources: vec![CreditsSourceMsg {
+                credits_contract_address: other_credits_contract.to_string(),
+                airdrop_contract_address: Some(AIRDROP_ADDR_2.to_string()),
+                start_height,
+                expiry_height: Some(start_height + 10),
+                multiplier: Decimal::percent(150),
+            }],
+        },
+    );
+
+    assert_eq!(
+        get_credits_sources(&mut app, addr.clone()),
+        vec![CreditsSource {
+            credits_contract_address: other_credits_contract.clone(),
+            airdrop_contract_address: Some(Addr::unchecked(AIRDROP_ADDR_2)),
+            start_height,
+            expiry_height: Some(start_height + 10),
+            multiplier: Decimal::percent(150),
+        }]
+    );
+
+    // The additional source has not started yet
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(Uint128::from(6000u64), resp.power);
+    let resp = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(Uint128::from(8000u64), resp.power);
+
+    // 6000 + 1000 * 1.5 = 7500
+    let resp = get_voting_power_at_height(
+        &mut app,
+        addr.clone(),
+        ADDR1.to_string(),
+        Some(start_height),
+    );
+    assert_eq!(Uint128::from(7500u64), resp.power);
+    // 8000 + (3000 - 500) * 1.5 = 11750
+    let resp = get_total_power_at_height(&mut app, addr.clone(), Some(start_height));
+    assert_eq!(Uint128::from(11750u64), resp.power);
+
+    // Each airdrop contract only loses power from its own source
+    let resp = get_voting_power_at_height(
+        &mut app,
+        addr.clone(),
+        AIRDROP_ADDR.to_string(),
+        Some(start_height),
+    );
+    assert_eq!(Uint128::from(1500u64), resp.power);
+    let resp = get_voting_power_at_height(
+        &mut app,
+        addr.clone(),
+        AIRDROP_ADDR_2.to_string(),
+        Some(start_height),
+    );
+    assert_eq!(Uint128::from(6000u64), resp.power);
+
+    let resp = get_voting_power_breakdown(
+        &mut app,
+        addr.clone(),
+        ADDR1.to_string(),
+        Some(start_height),
+    );
+    assert_eq!(
+        resp,
+        VotingPowerBreakdownResponse {
+            power: Uint128::from(7500u64),
+            height: start_height,
+            sources: vec![
+                SourcePower {
+                    credits_contract_address: credits_contract,
+                    active: true,
+                    balance: Uint128::from(6000u64),
+                    multiplier: Decimal::one(),
+                    power: Uint128::from(6000u64),
+                },
+                SourcePower {
+                    credits_contract_address: other_credits_contract.clone(),
+                    active: true,
+                    balance: Uint128::from(1000u64),
+                    multiplier: Decimal::percent(150),
+                    power: Uint128::from(1500u64),
+                },
+            ],
+        }
+    );
+
+    // The additional source has expired
+    let resp = get_voting_power_at_height(
+        &mut app,
+        addr.clone(),
+        ADDR1.to_string(),
+        Some(start_height + 10),
+    );
+    assert_eq!(Uint128::from(6000u64), resp.power);
+    let resp = get_total_power_at_height(&mut app, addr.clone(), Some(start_height + 10));
+    assert_eq!(Uint128::from(8000u64), resp.power);
+
+    let resp =
+        get_voting_power_breakdown(&mut app, addr, ADDR1.to_string(), Some(start_height + 10));
+    assert_eq!(
+        resp.sources[1],
+        SourcePower {
+            credits_contract_address: other_credits_contract,
+            active: false,
+            balance: Uint128::zero(),
+            multiplier: Decimal::percent(150),
+            power: Uint128::zero(),
+        }
+    );
+}
+
+#[test]
+fn test_add_credits_source() {
+    let mut app = mock_app();
+    let credits_contract = instantiate_credits_contract(&mut app);
+    let other_credits_contract = instantiate_other_credits_contract(&mut app);
+    let height = app.block_info().height;
+
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            credits_contract_address: credits_contract.to_string(),
+            airdrop_contract_address: AIRDROP_ADDR.to_string(),
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            credits_sources: vec![],
+        },
+    );
+
+    let source = CreditsSourceMsg {
+        credits_contract_address: other_credits_contract.to_string(),
+        airdrop_contract_address: None,
+        start_height: height,
+        expiry_height: None,
+        multiplier: Decimal::percent(50),
+    };
+
+    let err = add_credits_source(&mut app, addr.clone(), ADDR1, source.clone()).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    let err = add_credits_source(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        CreditsSourceMsg {
+            start_height: height - 1,
+            ..source.clone()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::HeightInPast { height: height - 1 },
+        err.downcast().unwrap()
+    );
+
+    let err = add_credits_source(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        CreditsSourceMsg {
+            multiplier: Decimal::zero(),
+            ..source.clone()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::ZeroMultiplier {}, err.downcast().unwrap());
+
+    let err = add_credits_source(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        CreditsSourceMsg {
+            credits_contract_address: credits_contract.to_string(),
+            ..source.clone()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::CreditsSourceAlreadyExists {
+            address: credits_contract.to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    add_credits_source(&mut app, addr.clone(), DAO_ADDR, source.clone()).unwrap();
+    let err = add_credits_source(&mut app, addr.clone(), DAO_ADDR, source).unwrap_err();
+    assert_eq!(
+        ContractError::CreditsSourceAlreadyExists {
+            address: other_credits_contract.to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    // No airdrop contract is set, so the whole supply counts: 8000 + 3000 * 0.5
+    let resp = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(Uint128::from(9500u64), resp.power);
+
+    // The primary credits contract can't be switched
+    let err = update_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        Some(other_credits_contract.to_string()),
+        None,
+        None,
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::CreditsContractAddressImmutable {},
+        err.downcast().unwrap()
+    );
+
+    for i in 1..MAX_CREDITS_SOURCES {
+        add_credits_source(
+            &mut app,
+            addr.clone(),
+            DAO_ADDR,
+            CreditsSourceMsg {
+                credits_contract_address: format!("credits{}", i),
+                airdrop_contract_address: None,
+                start_height: height + 1,
+                expiry_height: if i == 1 { Some(height + 2) } else { None },
+                multiplier: Decimal::one(),
+            },
+        )
+        .unwrap();
+    }
+    // expired sources still count towards the limit
+    app.update_block(|b| b.height = height + 3);
+    let extra_source = CreditsSourceMsg {
+        credits_contract_address: "credits_extra".to_string(),
+        airdrop_contract_address: None,
+        start_height: height + 3,
+        expiry_height: None,
+        multiplier: Decimal::one(),
+    };
+    let err =
+        add_credits_source(&mut app, addr.clone(), DAO_ADDR, extra_source.clone()).unwrap_err();
+    assert_eq!(
+        ContractError::TooManyCreditsSources {
+            max: MAX_CREDITS_SOURCES
+        },
+        err.downcast().unwrap()
+    );
+
+    // until they are removed
+    remove_credits_source(&mut app, addr.clone(), DAO_ADDR, "credits1".to_string()).unwrap();
+    add_credits_source(&mut app, addr.clone(), DAO_ADDR, extra_source).unwrap();
+    assert_eq!(
+        get_credits_sources(&mut app, addr).len(),
+        MAX_CREDITS_SOURCES
+    );
+}
+
+#[test]
+fn test_remove_credits_source() {
+    let mut app = mock_app();
+    let credits_contract = instantiate_credits_contract(&mut app);
+    let other_credits_contract = instantiate_other_credits_contract(&mut app);
+    let height = app.block_info().height;
+
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            credits_contract_address: credits_contract.to_string(),
+            airdrop_contract_address: AIRDROP_ADDR.to_string(),
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            credits_sources: vec![CreditsSourceMsg {
+                credits_contract_address: other_credits_contract.to_string(),
+                airdrop_contract_address: None,
+                start_height: height,
+                expiry_height: Some(height + 5),
+                multiplier: Decimal::one(),
+            }],
+        },
+    );
+
+    let err = remove_credits_source(
+        &mut app,
+        addr.clone(),
+        ADDR1,
+        other_credits_contract.to_string(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    let err = remove_credits_source(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        credits_contract.to_string(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::CreditsSourceNotFound {
+            address: credits_contract.to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = remove_credits_source(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        other_credits_contract.to_string(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::CreditsSourceNotExpired {
+            address: other_credits_contract.to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    app.update_block(|block| block.height = height + 5);
+    remove_credits_source(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        other_credits_contract.to_string(),
+    )
+    .unwrap();
+    assert_eq!(get_credits_sources(&mut app, addr.clone()), vec![]);
+
+    // The removed source no longer adds power at the heights it was active at
+    let resp = get_voting_power_at_height(&mut app, addr, ADDR1.to_string(), Some(height + 4));
+    assert_eq!(Uint128::from(6000u64), resp.power);
+}
+
+#[test]
+fn test_expire_credits_source() {
+    let mut app = mock_app();
+    let credits_contract = instantiate_credits_contract(&mut app);
+    let other_credits_contract = instantiate_other_credits_contract(&mut app);
+    let height = app.block_info().height;
+
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            credits_contract_address: credits_contract.to_string(),
+            airdrop_contract_address: AIRDROP_ADDR.to_string(),
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            credits_sources: vec![CreditsSourceMsg {
+                credits_contract_address: other_credits_contract.to_string(),
+                airdrop_contract_address: None,
+                start_height: height,
+                expiry_height: None,
+                multiplier: Decimal::one(),
+            }],
+        },
+    );
+
+    let err = expire_credits_source(
+        &mut app,
+        addr.clone(),
+        ADDR1,
+        other_credits_contract.to_string(),
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    let err = expire_credits_source(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        credits_contract.to_string(),
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::CreditsSourceNotFound {
+            address: credits_contract.to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    app.update_block(|block| block.height += 5);
+    let err = expire_credits_source(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        other_credits_contract.to_string(),
+        Some(height + 4),
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::HeightInPast { height: height + 4 },
+        err.downcast().unwrap()
+    );
+
+    expire_credits_source(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        other_credits_contract.to_string(),
+        None,
+    )
+    .unwrap();
+
+    // Historical power is kept
+    let resp =
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), Some(height + 4));
+    assert_eq!(Uint128::from(7000u64), resp.power);
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(Uint128::from(6000u64), resp.power);
+
+    let err = expire_credits_source(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        other_credits_contract.to_string(),
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::CreditsSourceExpired {
+            address: other_credits_contract.to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    // A source that has not started yet is removed altogether
+    let future_source = "future_credits".to_string();
+    add_credits_source(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        CreditsSourceMsg {
+            credits_contract_address: future_source.clone(),
+            airdrop_contract_address: None,
+            start_height: height + 100,
+            expiry_height: None,
+            multiplier: Decimal::one(),
+        },
+    )
+    .unwrap();
+    expire_credits_source(&mut app, addr.clone(), DAO_ADDR, future_source, None).unwrap();
+    assert_eq!(get_credits_sources(&mut app, addr).len(), 1);
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
@@ -10,11 +10,20 @@ use cwd_interface::voting::{
 
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{Config, CONFIG, DAO};
+use crate::state::{Config, PowerPolicy, CONFIG, DAO};
+use neutron_voting_power::snapshot::load_at_height;
+use vesting_base::types::{VestingAccountResponse, VestingAccountsResponse, VestingSchedule};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:neutron-investors-vesting-vault";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The number of vesting accounts read at once when summing up the total power.
+const VESTING_ACCOUNTS_PAGE_LIMIT: u32 = 30;
+
+/// Limits the number of vesting accounts the total power is summed up over one by one, so that
+/// the query stays bounded.
+pub(crate) const MAX_WEIGHED_VESTING_ACCOUNTS: usize = 300;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -32,10 +41,11 @@ pub fn instantiate(
         description: msg.description,
         owner,
         name: msg.name,
+        power_policy: msg.power_policy,
     };
 
     config.validate()?;
-    CONFIG.save(deps.storage, &config)?;
+    CONFIG.save(deps.storage, &config, 0)?;
     DAO.save(deps.storage, &info.sender)?;
 
     Ok(Response::new()
@@ -60,13 +70,16 @@ pub fn execute(
             owner,
             description,
             name,
+            power_policy,
         } => execute_update_config(
             deps,
+            env,
             info,
             vesting_contract_address,
             owner,
             description,
             name,
+            power_policy,
         ),
     }
 }
@@ -90,11 +103,13 @@ pub fn execute_unbond(
 
 pub fn execute_update_config(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     new_vesting_contract_address: Option<String>,
     new_owner: Option<String>,
     new_description: Option<String>,
     new_name: Option<String>,
+    new_power_policy: Option<PowerPolicy>,
 ) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
     if info.sender != config.owner {
@@ -123,9 +138,12 @@ pub fn execute_update_config(
     if let Some(new_vesting_contract_address) = new_vesting_contract_address {
         config.vesting_contract_address = new_vesting_contract_address;
     }
+    if let Some(power_policy) = new_power_policy {
+        config.power_policy = power_policy;
+    }
 
     config.validate()?;
-    CONFIG.save(deps.storage, &config)?;
+    CONFIG.save(deps.storage, &config, env.block.height)?;
 
     Ok(Response::new()
         .add_attribute("action", "update_config")
@@ -189,19 +207,26 @@ pub fn query_voting_power_at_height(
 ) -> StdResult<VotingPowerAtHeightResponse> {
     let height = height.unwrap_or(env.block.height);
 
-    let config = CONFIG.load(deps.storage)?;
-
-    let unclaimed_amount: Uint128 = deps.querier.query_wasm_smart(
-        config.vesting_contract_address,
-        &vesting_base::msg::QueryMsg::HistoricalExtension {
-            msg: vesting_base::msg::QueryMsgHistorical::UnclaimedAmountAtHeight { address, height },
-        },
-    )?;
+    let config = load_at_height(deps.storage, &CONFIG, height)?;
+    if config.power_policy.before_cliff(height) {
+        return Ok(VotingPowerAtHeightResponse {
+            power: Uint128::zero(),
+            height,
+        });
+    }
 
-    Ok(VotingPowerAtHeightResponse {
-        power: unclaimed_amount,
-        height,
-    })
+    let unclaimed = query_unclaimed_amount(deps, &config, &address, height)?;
+    let power = account_power(&config.power_policy, height, unclaimed, || {
+        let account: VestingAccountResponse = deps.querier.query_wasm_smart(
+            &config.vesting_contract_address,
+            &vesting_base::msg::QueryMsg::VestingAccount {
+                address: address.clone(),
+            },
+        )?;
+        Ok(account.info.schedules)
+    })?;
+
+    Ok(VotingPowerAtHeightResponse { power, height })
 }
 
 pub fn query_total_power_at_height(
@@ -211,19 +236,94 @@ pub fn query_total_power_at_height(
 ) -> StdResult<TotalPowerAtHeightResponse> {
     let height = height.unwrap_or(env.block.height);
 
-    let config = CONFIG.load(deps.storage)?;
+    let config = load_at_height(deps.storage, &CONFIG, height)?;
+    let policy = &config.power_policy;
+    if policy.before_cliff(height) {
+        return Ok(TotalPowerAtHeightResponse {
+            power: Uint128::zero(),
+            height,
+        });
+    }
 
-    let unclaimed_amount_total: Uint128 = deps.querier.query_wasm_smart(
-        config.vesting_contract_address,
+    // all unclaimed tokens are weighed the same, so there is no need to look at the schedules
+    if policy.block_time.is_none() {
+        let unclaimed: Uint128 = deps.querier.query_wasm_smart(
+            &config.vesting_contract_address,
+            &vesting_base::msg::QueryMsg::HistoricalExtension {
+                msg: vesting_base::msg::QueryMsgHistorical::UnclaimedTotalAmountAtHeight { height },
+            },
+        )?;
+        let power = account_power(policy, height, unclaimed, || Ok(vec![]))?;
+        return Ok(TotalPowerAtHeightResponse { power, height });
+    }
+
+    // every account vests on its own schedule, so the total is summed up account by account.
+    // The vesting contract only returns the current schedules, so they are read at the current
+    // height even for historical queries
+    let mut power = Uint128::zero();
+    let mut accounts = 0;
+    let mut start_after = None;
+    loop {
+        let page: VestingAccountsResponse = deps.querier.query_wasm_smart(
+            &config.vesting_contract_address,
+            &vesting_base::msg::QueryMsg::VestingAccounts {
+                start_after,
+                limit: Some(VESTING_ACCOUNTS_PAGE_LIMIT),
+                order_by: None,
+            },
+        )?;
+        start_after = match page.vesting_accounts.last() {
+            Some(account) => Some(account.address.to_string()),
+            None => break,
+        };
+        accounts += page.vesting_accounts.len();
+        if accounts > MAX_WEIGHED_VESTING_ACCOUNTS {
+            return Err(StdError::generic_err(format!(
+                "{}",
+                ContractError::TooManyVestingAccounts {
+                    max: MAX_WEIGHED_VESTING_ACCOUNTS
+                }
+            )));
+        }
+        for account in page.vesting_accounts {
+            let unclaimed =
+                query_unclaimed_amount(deps, &config, account.address.as_str(), height)?;
+            power = power.checked_add(account_power(policy, height, unclaimed, || {
+                Ok(account.info.schedules)
+            })?)?;
+        }
+    }
+
+    Ok(TotalPowerAtHeightResponse { power, height })
+}
+
+fn query_unclaimed_amount(
+    deps: Deps,
+    config: &Config,
+    address: &str,
+    height: u64,
+) -> StdResult<Uint128> {
+    deps.querier.query_wasm_smart(
+        &config.vesting_contract_address,
         &vesting_base::msg::QueryMsg::HistoricalExtension {
-            msg: vesting_base::msg::QueryMsgHistorical::UnclaimedTotalAmountAtHeight { height },
+            msg: vesting_base::msg::QueryMsgHistorical::UnclaimedAmountAtHeight {
+                address: address.to_string(),
+                height,
+            },
         },
-    )?;
+    )
+}
 
-    Ok(TotalPowerAtHeightResponse {
-        power: unclaimed_amount_total,
-        height,
-    })
+/// Applies the power policy to the unclaimed amount of an account with the given vesting
+/// schedules. Both voting power queries go through here to apply the policy the same way.
+fn account_power(
+    policy: &PowerPolicy,
+    height: u64,
+    unclaimed: Uint128,
+    schedules: impl FnOnce() -> StdResult<Vec<VestingSchedule>>,
+) -> StdResult<Uint128> {
+    let unvested = policy.unvested_amount(height, unclaimed, schedules)?;
+    Ok(policy.power(unclaimed, unvested)?)
 }
 
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
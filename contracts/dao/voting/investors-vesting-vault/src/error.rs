@@ -20,6 +20,18 @@ pub enum ContractError {
 
     #[error("Direct unbonding is not available for this contract")]
     DirectUnbondingDisabled {},
+
+    #[error("power policy multipliers cannot both be zero.")]
+    ZeroPowerPolicy {},
+
+    #[error("average block time must be greater than zero.")]
+    InvalidBlockTime {},
+
+    #[error("block time is required when unvested and vested multipliers differ.")]
+    BlockTimeRequired {},
+
+    #[error("cannot weigh more than {max} vesting accounts separately, use equal multipliers.")]
+    TooManyVestingAccounts { max: usize },
 }
 
 pub type ContractResult<T> = Result<T, ContractError>;
@@ -17,6 +17,9 @@ pub struct InstantiateMsg {
     pub owner: String,
     // Name of the vault
     pub name: String,
+    // Defines how unclaimed vesting tokens are turned into voting power.
+    #[serde(default)]
+    pub power_policy: crate::state::PowerPolicy,
 }
 
 #[voting_vault]
@@ -28,6 +31,9 @@ pub enum ExecuteMsg {
         owner: Option<String>,
         description: Option<String>,
         name: Option<String>,
+        /// Only applies to the heights after the update.
+        #[serde(default)]
+        power_policy: Option<crate::state::PowerPolicy>,
     },
 }
 
@@ -1,8 +1,9 @@
 use crate::{ContractError, ContractResult};
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Decimal, OverflowError, Uint128};
+use cw_storage_plus::{Item, SnapshotItem, Strategy};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use vesting_base::types::VestingSchedule;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct Config {
@@ -10,6 +11,8 @@ pub struct Config {
     pub description: String,
     pub owner: Addr,
     pub name: String,
+    #[serde(default)]
+    pub power_policy: PowerPolicy,
 }
 
 impl Config {
@@ -21,9 +24,260 @@ impl Config {
         if self.description.is_empty() {
             return Err(ContractError::DescriptionIsEmpty {});
         }
-        Ok(())
+        self.power_policy.validate()
     }
 }
 
-pub const CONFIG: Item<Config> = Item::new("config");
+/// Defines how unclaimed tokens in the vesting contract are turned into voting power.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct PowerPolicy {
+    /// Voting power per token which has not vested yet.
+    pub unvested_multiplier: Decimal,
+    /// Voting power per token which has vested but has not been claimed yet.
+    pub vested_multiplier: Decimal,
+    /// Used to evaluate the accounts' vesting schedules at a height to split unclaimed tokens
+    /// into unvested and vested ones. Required if the multipliers differ.
+    pub block_time: Option<BlockTime>,
+    /// Voting power is zero before this height.
+    pub cliff_height: Option<u64>,
+}
+
+impl Default for PowerPolicy {
+    fn default() -> Self {
+        Self {
+            unvested_multiplier: Decimal::one(),
+            vested_multiplier: Decimal::one(),
+            block_time: None,
+            cliff_height: None,
+        }
+    }
+}
+
+/// Vesting contract schedules are time based, so the vault estimates the time of a historical
+/// height from a reference block and the average block time.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct BlockTime {
+    /// Height of the reference block.
+    pub height: u64,
+    /// Time of the reference block in seconds.
+    pub time: u64,
+    /// Average block time in milliseconds.
+    pub average_block_time_ms: u64,
+}
+
+impl BlockTime {
+    /// returns the estimated time of the block at the given height in seconds.
+    pub fn time_at(&self, height: u64) -> u64 {
+        let duration = |blocks: u64| blocks.saturating_mul(self.average_block_time_ms) / 1000;
+        if height >= self.height {
+            self.time.saturating_add(duration(height - self.height))
+        } else {
+            self.time.saturating_sub(duration(self.height - height))
+        }
+    }
+}
+
+impl PowerPolicy {
+    /// checks whether the policy fields are valid.
+    pub fn validate(&self) -> ContractResult<()> {
+        if self.unvested_multiplier.is_zero() && self.vested_multiplier.is_zero() {
+            return Err(ContractError::ZeroPowerPolicy {});
+        }
+        match &self.block_time {
+            Some(block_time) if block_time.average_block_time_ms == 0 => {
+                Err(ContractError::InvalidBlockTime {})
+            }
+            None if self.unvested_multiplier != self.vested_multiplier => {
+                Err(ContractError::BlockTimeRequired {})
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// returns whether the power is zeroed at the given height because of the cliff.
+    pub fn before_cliff(&self, height: u64) -> bool {
+        self.cliff_height.map_or(false, |cliff| height < cliff)
+    }
+
+    /// returns the part of the unclaimed amount which is still unvested at the given height
+    /// according to the account's vesting schedules. `schedules` is only called when the policy
+    /// tells vested tokens apart and there are unclaimed tokens.
+    pub fn unvested_amount<E: From<OverflowError>>(
+        &self,
+        height: u64,
+        unclaimed: Uint128,
+        schedules: impl FnOnce() -> Result<Vec<VestingSchedule>, E>,
+    ) -> Result<Uint128, E> {
+        let block_time = match &self.block_time {
+            Some(block_time) if !unclaimed.is_zero() => block_time,
+            _ => return Ok(unclaimed),
+        };
+        let time = block_time.time_at(height);
+        let mut unvested = Uint128::zero();
+        for schedule in schedules()? {
+            unvested = unvested.checked_add(unvested_in_schedule(&schedule, time))?;
+        }
+        Ok(unvested.min(unclaimed))
+    }
+
+    /// returns the voting power of the unclaimed amount of which `unvested` is not vested yet.
+    pub fn power(&self, unclaimed: Uint128, unvested: Uint128) -> Result<Uint128, OverflowError> {
+        let vested = unclaimed.checked_sub(unvested)?;
+        unvested
+            .mul_floor(self.unvested_multiplier)
+            .checked_add(vested.mul_floor(self.vested_multiplier))
+    }
+}
+
+/// returns the amount of the schedule which is not vested at the given time. The start point
+/// amount vests at the start time and the rest vests linearly until the end time, the same way
+/// the vesting contract releases it.
+fn unvested_in_schedule(schedule: &VestingSchedule, time: u64) -> Uint128 {
+    let start = &schedule.start_point;
+    match &schedule.end_point {
+        None if time < start.time => start.amount,
+        Some(end) if time < start.time => end.amount,
+        Some(end) if time < end.time => end
+            .amount
+            .saturating_sub(start.amount)
+            .multiply_ratio(end.time - time, end.time - start.time),
+        _ => Uint128::zero(),
+    }
+}
+
+pub const CONFIG: SnapshotItem<Config> = SnapshotItem::new(
+    "config",
+    "config__checkpoints",
+    "config__changelog",
+    Strategy::EveryBlock,
+);
 pub const DAO: Item<Addr> = Item::new("dao");
+
+#[cfg(test)]
+mod tests {
+    use super::{BlockTime, PowerPolicy};
+    use crate::ContractError;
+    use cosmwasm_std::{Decimal, StdResult, Uint128};
+    use vesting_base::types::{VestingSchedule, VestingSchedulePoint};
+
+    #[test]
+    fn test_power_policy_validate() {
+        assert_eq!(PowerPolicy::default().validate(), Ok(()));
+
+        let policy = PowerPolicy {
+            unvested_multiplier: Decimal::one(),
+            vested_multiplier: Decimal::zero(),
+            block_time: None,
+            cliff_height: None,
+        };
+        assert_eq!(policy.validate(), Err(ContractError::BlockTimeRequired {}));
+
+        let policy = PowerPolicy {
+            block_time: Some(BlockTime {
+                height: 10,
+                time: 1000,
+                average_block_time_ms: 0,
+            }),
+            ..policy
+        };
+        assert_eq!(policy.validate(), Err(ContractError::InvalidBlockTime {}));
+
+        let policy = PowerPolicy {
+            unvested_multiplier: Decimal::zero(),
+            vested_multiplier: Decimal::zero(),
+            block_time: None,
+            cliff_height: None,
+        };
+        assert_eq!(policy.validate(), Err(ContractError::ZeroPowerPolicy {}));
+    }
+
+    #[test]
+    fn test_block_time() {
+        let block_time = BlockTime {
+            height: 100,
+            time: 1000,
+            average_block_time_ms: 1500,
+        };
+        assert_eq!(block_time.time_at(100), 1000);
+        assert_eq!(block_time.time_at(110), 1015);
+        assert_eq!(block_time.time_at(90), 985);
+        assert_eq!(block_time.time_at(0), 850);
+    }
+
+    #[test]
+    fn test_power_policy_unvested_amount() {
+        let policy = PowerPolicy {
+            unvested_multiplier: Decimal::one(),
+            vested_multiplier: Decimal::percent(50),
+            block_time: Some(BlockTime {
+                height: 0,
+                time: 0,
+                average_block_time_ms: 1000,
+            }),
+            cliff_height: None,
+        };
+        // 200 tokens vest at 100, 800 more vest linearly until 200, and 500 vest at 300
+        let schedules = || -> StdResult<Vec<VestingSchedule>> {
+            Ok(vec![
+                VestingSchedule {
+                    start_point: VestingSchedulePoint {
+                        time: 100,
+                        amount: Uint128::new(200),
+                    },
+                    end_point: Some(VestingSchedulePoint {
+                        time: 200,
+                        amount: Uint128::new(1000),
+                    }),
+                },
+                VestingSchedule {
+                    start_point: VestingSchedulePoint {
+                        time: 300,
+                        amount: Uint128::new(500),
+                    },
+                    end_point: None,
+                },
+            ])
+        };
+
+        let unvested = policy
+            .unvested_amount(50, Uint128::new(1500), schedules)
+            .unwrap();
+        assert_eq!(unvested, Uint128::new(1500));
+
+        // 400 of the first schedule are vested, part of them are claimed already
+        let unvested = policy
+            .unvested_amount(125, Uint128::new(1400), schedules)
+            .unwrap();
+        assert_eq!(unvested, Uint128::new(1100));
+        assert_eq!(
+            policy.power(Uint128::new(1400), unvested).unwrap(),
+            Uint128::new(1250)
+        );
+
+        // unvested amount can't be greater than the unclaimed one
+        let unvested = policy
+            .unvested_amount(125, Uint128::new(500), schedules)
+            .unwrap();
+        assert_eq!(unvested, Uint128::new(500));
+
+        let unvested = policy
+            .unvested_amount(200, Uint128::new(900), schedules)
+            .unwrap();
+        assert_eq!(unvested, Uint128::new(500));
+
+        let unvested = policy
+            .unvested_amount(300, Uint128::new(300), schedules)
+            .unwrap();
+        assert_eq!(unvested, Uint128::zero());
+        assert_eq!(
+            policy.power(Uint128::new(300), unvested).unwrap(),
+            Uint128::new(150)
+        );
+
+        // schedules are not needed without unclaimed tokens
+        let unvested = policy
+            .unvested_amount(125, Uint128::zero(), || -> StdResult<_> { unreachable!() })
+            .unwrap();
+        assert_eq!(unvested, Uint128::zero());
+    }
+}
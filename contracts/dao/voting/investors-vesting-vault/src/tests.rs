@@ -1,15 +1,23 @@
-use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION, MAX_WEIGHED_VESTING_ACCOUNTS};
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::Config;
+use crate::state::{BlockTime, Config, PowerPolicy};
 use crate::ContractError;
 use cosmwasm_std::testing::{mock_dependencies, mock_env};
-use cosmwasm_std::{to_json_binary, Addr, Binary, Deps, Empty, Env, Response, StdResult, Uint128};
-use cw_multi_test::{custom_app, App, AppResponse, Contract, ContractWrapper, Executor};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Decimal, Deps, Empty, Env, Response, StdError, StdResult, Uint128,
+};
+use cw_multi_test::{
+    custom_app, next_block, App, AppResponse, Contract, ContractWrapper, Executor,
+};
 use cwd_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use vesting_base::types::{
+    VestingAccountResponse, VestingAccountsResponse, VestingInfo, VestingSchedule,
+    VestingSchedulePoint,
+};
 
 const DAO_ADDR: &str = "dao";
 const DESCRIPTION: &str = "description";
@@ -48,11 +56,148 @@ fn vesting_query(_deps: Deps, _env: Env, msg: vesting_base::msg::QueryMsg) -> St
     }
 }
 
+const VESTING_START: u64 = 100;
+const VESTING_END: u64 = 200;
+const LATE_VESTING_START: u64 = 150;
+const LATE_VESTING_END: u64 = 250;
+
+// Times equal heights with this block time
+const BLOCK_TIME: BlockTime = BlockTime {
+    height: 0,
+    time: 0,
+    average_block_time_ms: 1000,
+};
+
+fn linear_schedule(start: u64, end: u64, amount: u128) -> VestingSchedule {
+    VestingSchedule {
+        start_point: VestingSchedulePoint {
+            time: start,
+            amount: Uint128::zero(),
+        },
+        end_point: Some(VestingSchedulePoint {
+            time: end,
+            amount: Uint128::new(amount),
+        }),
+    }
+}
+
+fn vesting_accounts() -> Vec<VestingAccountResponse> {
+    vec![
+        VestingAccountResponse {
+            address: Addr::unchecked(ADDR1),
+            info: VestingInfo {
+                schedules: vec![linear_schedule(VESTING_START, VESTING_END, 10000)],
+                released_amount: Uint128::new(2000),
+            },
+        },
+        VestingAccountResponse {
+            address: Addr::unchecked(ADDR2),
+            info: VestingInfo {
+                schedules: vec![linear_schedule(LATE_VESTING_START, LATE_VESTING_END, 10000)],
+                released_amount: Uint128::zero(),
+            },
+        },
+    ]
+}
+
+// Each investor got 10000 tokens. The first one claimed 2000 of them right after the vesting
+// start, the second one vests later and has claimed nothing.
+fn vesting_schedule_query(
+    _deps: Deps,
+    _env: Env,
+    msg: vesting_base::msg::QueryMsg,
+) -> StdResult<Binary> {
+    let unclaimed = |address: &str, height: u64| match address {
+        ADDR1 if height > VESTING_START => 8000u64,
+        ADDR1 | ADDR2 => 10000u64,
+        _ => 0u64,
+    };
+    match msg {
+        vesting_base::msg::QueryMsg::HistoricalExtension {
+            msg: vesting_base::msg::QueryMsgHistorical::UnclaimedAmountAtHeight { address, height },
+        } => to_json_binary(&Uint128::from(unclaimed(&address, height))),
+        vesting_base::msg::QueryMsg::HistoricalExtension {
+            msg: vesting_base::msg::QueryMsgHistorical::UnclaimedTotalAmountAtHeight { height },
+        } => to_json_binary(&Uint128::from(
+            unclaimed(ADDR1, height) + unclaimed(ADDR2, height),
+        )),
+        vesting_base::msg::QueryMsg::VestingAccount { address } => to_json_binary(
+            &vesting_accounts()
+                .into_iter()
+                .find(|account| account.address == address)
+                .ok_or_else(|| StdError::not_found("vesting account"))?,
+        ),
+        vesting_base::msg::QueryMsg::VestingAccounts {
+            start_after,
+            limit: _,
+            order_by: _,
+        } => to_json_binary(&VestingAccountsResponse {
+            vesting_accounts: vesting_accounts()
+                .into_iter()
+                .filter(|account| {
+                    start_after.as_ref().map_or(true, |start_after| {
+                        account.address.as_str() > start_after.as_str()
+                    })
+                })
+                .collect(),
+        }),
+        _ => unimplemented!(),
+    }
+}
+
+// One more vesting account than the total power query goes through, each with one token
+fn many_accounts_query(
+    _deps: Deps,
+    _env: Env,
+    msg: vesting_base::msg::QueryMsg,
+) -> StdResult<Binary> {
+    let accounts = (0..=MAX_WEIGHED_VESTING_ACCOUNTS).map(|i| VestingAccountResponse {
+        address: Addr::unchecked(format!("investor{:04}", i)),
+        info: VestingInfo {
+            schedules: vec![linear_schedule(VESTING_START, VESTING_END, 1)],
+            released_amount: Uint128::zero(),
+        },
+    });
+    match msg {
+        vesting_base::msg::QueryMsg::HistoricalExtension {
+            msg: vesting_base::msg::QueryMsgHistorical::UnclaimedAmountAtHeight { .. },
+        } => to_json_binary(&Uint128::one()),
+        vesting_base::msg::QueryMsg::HistoricalExtension {
+            msg: vesting_base::msg::QueryMsgHistorical::UnclaimedTotalAmountAtHeight { .. },
+        } => to_json_binary(&Uint128::from(MAX_WEIGHED_VESTING_ACCOUNTS as u128 + 1)),
+        vesting_base::msg::QueryMsg::VestingAccounts {
+            start_after,
+            limit,
+            order_by: _,
+        } => to_json_binary(&VestingAccountsResponse {
+            vesting_accounts: accounts
+                .filter(|account| {
+                    start_after.as_ref().map_or(true, |start_after| {
+                        account.address.as_str() > start_after.as_str()
+                    })
+                })
+                .take(limit.unwrap_or(10) as usize)
+                .collect(),
+        }),
+        _ => unimplemented!(),
+    }
+}
+
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct EmptyMsg {}
 
 fn vesting_contract() -> Box<dyn Contract<Empty>> {
+    mock_vesting_contract(vesting_query)
+}
+
+fn vesting_schedule_contract() -> Box<dyn Contract<Empty>> {
+    mock_vesting_contract(vesting_schedule_query)
+}
+
+fn mock_vesting_contract(
+    query_fn: fn(Deps, Env, vesting_base::msg::QueryMsg) -> StdResult<Binary>,
+) -> Box<dyn Contract<Empty>> {
     let contract: ContractWrapper<
         EmptyMsg,
         EmptyMsg,
@@ -63,13 +208,22 @@ fn vesting_contract() -> Box<dyn Contract<Empty>> {
     > = ContractWrapper::new(
         |_, _, _, _: EmptyMsg| Ok(Response::new()),
         |_, _, _, _: EmptyMsg| Ok(Response::new()),
-        vesting_query,
+        query_fn,
     );
     Box::new(contract)
 }
 
 fn instantiate_vesting_contract(app: &mut App) -> Addr {
     let contract_id = app.store_code(vesting_contract());
+    instantiate_mock_vesting_contract(app, contract_id)
+}
+
+fn instantiate_vesting_schedule_contract(app: &mut App) -> Addr {
+    let contract_id = app.store_code(vesting_schedule_contract());
+    instantiate_mock_vesting_contract(app, contract_id)
+}
+
+fn instantiate_mock_vesting_contract(app: &mut App, contract_id: u64) -> Addr {
     app.instantiate_contract(
         contract_id,
         Addr::unchecked(DAO_ADDR),
@@ -90,6 +244,25 @@ fn instantiate_vault(app: &mut App, id: u64, msg: InstantiateMsg) -> Addr {
         .unwrap()
 }
 
+fn instantiate_power_policy_vault(
+    app: &mut App,
+    vault_id: u64,
+    vesting_contract: &Addr,
+    power_policy: PowerPolicy,
+) -> Addr {
+    instantiate_vault(
+        app,
+        vault_id,
+        InstantiateMsg {
+            vesting_contract_address: vesting_contract.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            name: "vesting vault".to_string(),
+            power_policy,
+        },
+    )
+}
+
 fn update_config(
     app: &mut App,
     contract_addr: Addr,
@@ -106,6 +279,27 @@ fn update_config(
             owner: Some(owner),
             description,
             name: None,
+            power_policy: None,
+        },
+        &[],
+    )
+}
+
+fn update_power_policy(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    power_policy: PowerPolicy,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::UpdateConfig {
+            vesting_contract_address: None,
+            owner: None,
+            description: None,
+            name: None,
+            power_policy: Some(power_policy),
         },
         &[],
     )
@@ -155,6 +349,7 @@ fn test_instantiate() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             name: "vesting vault".to_string(),
+            power_policy: PowerPolicy::default(),
         },
     );
 }
@@ -174,6 +369,7 @@ fn test_update_config_unauthorized() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             name: "vesting vault".to_string(),
+            power_policy: PowerPolicy::default(),
         },
     );
 
@@ -203,6 +399,7 @@ fn test_update_config_as_owner() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             name: "vesting vault".to_string(),
+            power_policy: PowerPolicy::default(),
         },
     );
 
@@ -224,6 +421,7 @@ fn test_update_config_as_owner() {
             description: NEW_DESCRIPTION.to_string(),
             owner: Addr::unchecked(ADDR1),
             name: "vesting vault".to_string(),
+            power_policy: PowerPolicy::default(),
         },
         config
     );
@@ -244,6 +442,7 @@ fn test_update_config_invalid_description() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             name: "vesting vault".to_string(),
+            power_policy: PowerPolicy::default(),
         },
     );
 
@@ -273,6 +472,7 @@ fn test_query_dao() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             name: "vesting vault".to_string(),
+            power_policy: PowerPolicy::default(),
         },
     );
 
@@ -295,6 +495,7 @@ fn test_query_info() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             name: "vesting vault".to_string(),
+            power_policy: PowerPolicy::default(),
         },
     );
 
@@ -320,6 +521,7 @@ fn test_query_get_config() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             name: "vesting vault".to_string(),
+            power_policy: PowerPolicy::default(),
         },
     );
 
@@ -331,6 +533,7 @@ fn test_query_get_config() {
             description: DESCRIPTION.to_string(),
             owner: Addr::unchecked(DAO_ADDR),
             name: "vesting vault".to_string(),
+            power_policy: PowerPolicy::default(),
         }
     )
 }
@@ -349,6 +552,7 @@ fn test_voting_power_queries() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             name: "vesting vault".to_string(),
+            power_policy: PowerPolicy::default(),
         },
     );
 
@@ -359,6 +563,237 @@ fn test_voting_power_queries() {
     assert_eq!(Uint128::from(10000u64), resp.power);
 }
 
+fn assert_powers(app: &mut App, addr: &Addr, height: u64, power: u64, total_power: u64) {
+    let resp = get_voting_power_at_height(app, addr.clone(), ADDR1.to_string(), Some(height));
+    assert_eq!(Uint128::from(power), resp.power);
+    let resp = get_total_power_at_height(app, addr.clone(), Some(height));
+    assert_eq!(Uint128::from(total_power), resp.power);
+}
+
+#[test]
+fn test_power_policy() {
+    let mut app = mock_app();
+    let vesting_contract = instantiate_vesting_schedule_contract(&mut app);
+
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            vesting_contract_address: vesting_contract.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            name: "vesting vault".to_string(),
+            power_policy: PowerPolicy {
+                unvested_multiplier: Decimal::one(),
+                vested_multiplier: Decimal::percent(50),
+                block_time: Some(BLOCK_TIME),
+                cliff_height: None,
+            },
+        },
+    );
+
+    // Nothing is vested yet
+    assert_powers(&mut app, &addr, VESTING_START, 10000, 20000);
+    // Half of 10000 is unvested, 8000 - 5000 = 3000 are vested but unclaimed: 5000 + 3000 * 0.5.
+    // The second investor's vesting only starts
+    assert_powers(&mut app, &addr, 150, 6500, 16500);
+    // Everything is vested: 8000 * 0.5. The second investor is halfway: 5000 + 5000 * 0.5
+    assert_powers(&mut app, &addr, VESTING_END, 4000, 11500);
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), Some(VESTING_END))
+            .power,
+        Uint128::new(7500)
+    );
+    // Addresses without a vesting account have no power
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), DAO_ADDR.to_string(), Some(150)).power,
+        Uint128::zero()
+    );
+
+    // Unvested tokens only
+    let addr = instantiate_power_policy_vault(
+        &mut app,
+        vault_id,
+        &vesting_contract,
+        PowerPolicy {
+            unvested_multiplier: Decimal::one(),
+            vested_multiplier: Decimal::zero(),
+            block_time: Some(BLOCK_TIME),
+            cliff_height: None,
+        },
+    );
+    assert_powers(&mut app, &addr, 150, 5000, 15000);
+    assert_powers(&mut app, &addr, VESTING_END, 0, 5000);
+    assert_powers(&mut app, &addr, LATE_VESTING_END, 0, 0);
+
+    // Vested but unclaimed tokens only
+    let addr = instantiate_power_policy_vault(
+        &mut app,
+        vault_id,
+        &vesting_contract,
+        PowerPolicy {
+            unvested_multiplier: Decimal::zero(),
+            vested_multiplier: Decimal::one(),
+            block_time: Some(BLOCK_TIME),
+            cliff_height: None,
+        },
+    );
+    assert_powers(&mut app, &addr, VESTING_START, 0, 0);
+    assert_powers(&mut app, &addr, 150, 3000, 3000);
+    assert_powers(&mut app, &addr, VESTING_END, 8000, 13000);
+    assert_powers(&mut app, &addr, LATE_VESTING_END, 8000, 18000);
+}
+
+#[test]
+fn test_power_policy_update() {
+    let mut app = mock_app();
+    let vesting_contract = instantiate_vesting_schedule_contract(&mut app);
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_power_policy_vault(
+        &mut app,
+        vault_id,
+        &vesting_contract,
+        PowerPolicy::default(),
+    );
+
+    app.update_block(next_block);
+    let update_height = app.block_info().height;
+    update_power_policy(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        PowerPolicy {
+            unvested_multiplier: Decimal::one(),
+            vested_multiplier: Decimal::zero(),
+            block_time: Some(BLOCK_TIME),
+            cliff_height: None,
+        },
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    // The old policy still applies at the heights before the update
+    assert_powers(&mut app, &addr, VESTING_END, 8000, 18000);
+    assert_powers(&mut app, &addr, update_height, 8000, 18000);
+    // Everything is vested by now, so unvested tokens only give no power
+    assert_powers(&mut app, &addr, update_height + 1, 0, 0);
+}
+
+#[test]
+fn test_total_power_too_many_vesting_accounts() {
+    let mut app = mock_app();
+    let contract_id = app.store_code(mock_vesting_contract(many_accounts_query));
+    let vesting_contract = instantiate_mock_vesting_contract(&mut app, contract_id);
+    let vault_id = app.store_code(vault_contract());
+
+    // Tokens are weighed the same, so the accounts are not read one by one
+    let addr = instantiate_power_policy_vault(
+        &mut app,
+        vault_id,
+        &vesting_contract,
+        PowerPolicy::default(),
+    );
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr, None).power,
+        Uint128::new(MAX_WEIGHED_VESTING_ACCOUNTS as u128 + 1)
+    );
+
+    let addr = instantiate_power_policy_vault(
+        &mut app,
+        vault_id,
+        &vesting_contract,
+        PowerPolicy {
+            unvested_multiplier: Decimal::one(),
+            vested_multiplier: Decimal::percent(50),
+            block_time: Some(BLOCK_TIME),
+            cliff_height: None,
+        },
+    );
+    let err = app
+        .wrap()
+        .query_wasm_smart::<TotalPowerAtHeightResponse>(
+            addr,
+            &QueryMsg::TotalPowerAtHeight { height: None },
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains(
+        &ContractError::TooManyVestingAccounts {
+            max: MAX_WEIGHED_VESTING_ACCOUNTS
+        }
+        .to_string()
+    ));
+}
+
+#[test]
+fn test_power_policy_cliff() {
+    let mut app = mock_app();
+    let vesting_contract = instantiate_vesting_schedule_contract(&mut app);
+
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            vesting_contract_address: vesting_contract.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            name: "vesting vault".to_string(),
+            power_policy: PowerPolicy {
+                cliff_height: Some(150),
+                ..PowerPolicy::default()
+            },
+        },
+    );
+
+    assert_powers(&mut app, &addr, VESTING_START, 0, 0);
+    assert_powers(&mut app, &addr, 149, 0, 0);
+    assert_powers(&mut app, &addr, 150, 8000, 18000);
+}
+
+#[test]
+fn test_power_policy_invalid() {
+    let mut app = mock_app();
+    let vesting_contract = instantiate_vesting_schedule_contract(&mut app);
+
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            vesting_contract_address: vesting_contract.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            name: "vesting vault".to_string(),
+            power_policy: PowerPolicy::default(),
+        },
+    );
+
+    let err = update_power_policy(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        PowerPolicy {
+            vested_multiplier: Decimal::zero(),
+            ..PowerPolicy::default()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::BlockTimeRequired {}, err.downcast().unwrap());
+
+    let err = update_power_policy(
+        &mut app,
+        addr,
+        ADDR1,
+        PowerPolicy {
+            cliff_height: Some(150),
+            ..PowerPolicy::default()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
@@ -15,6 +15,8 @@ use astroport_periphery::lockdrop::PoolType;
 use neutron_lockdrop_vault_for_cl_pools::error::{ContractError, ContractResult};
 use neutron_lockdrop_vault_for_cl_pools::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use neutron_lockdrop_vault_for_cl_pools::types::Config;
+use neutron_voting_power::snapshot::load_at_height;
+use neutron_voting_power::voting_power::{cl_pool_has_denom, ClPoolValuation};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:neutron-lockdrop-vault-for-cl-pools";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -37,9 +39,14 @@ pub fn instantiate(
         usdc_cl_pool_contract: deps.api.addr_validate(&msg.usdc_cl_pool_contract)?,
         atom_cl_pool_contract: deps.api.addr_validate(&msg.atom_cl_pool_contract)?,
         owner,
+        valuation: msg.valuation,
+        denom: msg.denom,
     };
     config.validate()?;
-    CONFIG.save(deps.storage, &config)?;
+    validate_denom(deps.as_ref(), &config)?;
+    // the vault only reads the lockdrop and pools' history, so the initial config counts at all the
+    // heights before the first update
+    CONFIG.save(deps.storage, &config, 0)?;
     DAO.save(deps.storage, &info.sender)?;
 
     Ok(Response::new()
@@ -49,7 +56,8 @@ pub fn instantiate(
         .add_attribute("owner", config.owner)
         .add_attribute("lockdrop_contract", config.lockdrop_contract)
         .add_attribute("oracle_usdc_contract", config.usdc_cl_pool_contract)
-        .add_attribute("oracle_atom_contract", config.atom_cl_pool_contract))
+        .add_attribute("oracle_atom_contract", config.atom_cl_pool_contract)
+        .add_attribute("denom", config.denom))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -69,8 +77,11 @@ pub fn execute(
             atom_cl_pool_contract,
             name,
             description,
+            valuation,
+            denom,
         } => execute_update_config(
             deps,
+            env,
             info,
             owner,
             lockdrop_contract,
@@ -78,6 +89,8 @@ pub fn execute(
             atom_cl_pool_contract,
             name,
             description,
+            valuation,
+            denom,
         ),
     }
 }
@@ -98,6 +111,7 @@ pub fn execute_unbond(
 #[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     new_owner: Option<String>,
     new_lockdrop_contract: Option<String>,
@@ -105,6 +119,8 @@ pub fn execute_update_config(
     new_atom_cl_pool_contract: Option<String>,
     new_name: Option<String>,
     new_description: Option<String>,
+    new_valuation: Option<ClPoolValuation>,
+    new_denom: Option<String>,
 ) -> ContractResult<Response> {
     let mut config: Config = CONFIG.load(deps.storage)?;
     if info.sender != config.owner {
@@ -146,9 +162,16 @@ pub fn execute_update_config(
     if let Some(description) = new_description {
         config.description = description;
     }
+    if let Some(valuation) = new_valuation {
+        config.valuation = valuation;
+    }
+    if let Some(denom) = new_denom {
+        config.denom = denom;
+    }
 
     config.validate()?;
-    CONFIG.save(deps.storage, &config)?;
+    validate_denom(deps.as_ref(), &config)?;
+    CONFIG.save(deps.storage, &config, env.block.height)?;
 
     Ok(Response::new()
         .add_attribute("action", "update_config")
@@ -156,7 +179,21 @@ pub fn execute_update_config(
         .add_attribute("owner", config.owner)
         .add_attribute("lockdrop_contract", config.lockdrop_contract)
         .add_attribute("oracle_usdc_contract", config.usdc_cl_pool_contract)
-        .add_attribute("oracle_atom_contract", config.atom_cl_pool_contract))
+        .add_attribute("oracle_atom_contract", config.atom_cl_pool_contract)
+        .add_attribute("denom", config.denom))
+}
+
+/// checks whether both CL pools hold the denom LP tokens are valued in.
+fn validate_denom(deps: Deps, config: &Config) -> ContractResult<()> {
+    for pool_contract in [&config.usdc_cl_pool_contract, &config.atom_cl_pool_contract] {
+        if !cl_pool_has_denom(deps, pool_contract, &config.denom)? {
+            return Err(ContractError::DenomNotInPair {
+                denom: config.denom.clone(),
+                contract: pool_contract.to_string(),
+            });
+        }
+    }
+    Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -188,9 +225,8 @@ pub fn query_voting_power_at_height(
     address: String,
     height: Option<u64>,
 ) -> ContractResult<VotingPowerAtHeightResponse> {
-    let config = CONFIG.load(deps.storage)?;
-
     let height = height.unwrap_or(env.block.height);
+    let config = load_at_height(deps.storage, &CONFIG, height)?;
 
     let atom_power = get_voting_power_for_address(
         deps,
@@ -198,7 +234,9 @@ pub fn query_voting_power_at_height(
         &config.atom_cl_pool_contract,
         PoolType::ATOM,
         address.clone(),
+        &config.denom,
         height,
+        &config.valuation,
     )?;
     let usdc_power = get_voting_power_for_address(
         deps,
@@ -206,7 +244,9 @@ pub fn query_voting_power_at_height(
         &config.usdc_cl_pool_contract,
         PoolType::USDC,
         address,
+        &config.denom,
         height,
+        &config.valuation,
     )?;
 
     let power = atom_power + usdc_power;
@@ -219,23 +259,26 @@ pub fn query_total_power_at_height(
     env: Env,
     height: Option<u64>,
 ) -> ContractResult<TotalPowerAtHeightResponse> {
-    let config = CONFIG.load(deps.storage)?;
-
     let height = height.unwrap_or(env.block.height);
+    let config = load_at_height(deps.storage, &CONFIG, height)?;
 
     let atom_power = get_voting_power_total(
         deps,
         &config.lockdrop_contract,
         &config.atom_cl_pool_contract,
         PoolType::ATOM,
+        &config.denom,
         height,
+        &config.valuation,
     )?;
     let usdc_power = get_voting_power_total(
         deps,
         &config.lockdrop_contract,
         &config.usdc_cl_pool_contract,
         PoolType::USDC,
+        &config.denom,
         height,
+        &config.valuation,
     )?;
 
     let power = atom_power.checked_add(usdc_power)?;
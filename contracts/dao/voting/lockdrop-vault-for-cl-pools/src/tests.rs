@@ -1,10 +1,18 @@
 use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
+use astroport::asset::{AssetInfo, PairInfo};
+use astroport::factory::PairType;
+use astroport::pair_concentrated::QueryMsg as ClPoolQueryMsg;
 use cosmwasm_std::testing::{mock_dependencies, mock_env};
-use cosmwasm_std::{coins, Addr, Coin, Empty, Uint128};
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, Binary, Coin, Deps, Empty, Env, Response, StdError, StdResult,
+    Uint128,
+};
 use cw_multi_test::{custom_app, App, AppResponse, Contract, ContractWrapper, Executor};
 use cwd_interface::voting::InfoResponse;
+use neutron_lockdrop_vault_for_cl_pools::error::ContractError;
 use neutron_lockdrop_vault_for_cl_pools::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use neutron_lockdrop_vault_for_cl_pools::types::Config;
+use neutron_voting_power::voting_power::ClPoolValuation;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -14,14 +22,14 @@ const NEW_NAME: &str = "new_name";
 const DESCRIPTION: &str = "description";
 const NEW_DESCRIPTION: &str = "new description";
 const LOCKDROP_ADDR: &str = "lockdrop";
-const USDC_CL_POOL_ADDR: &str = "usdc_cl_pool";
-const ATOM_CL_POOL_ADDR: &str = "atom_cl_pool";
 const NEW_LOCKDROP_ADDR: &str = "new_lockdrop";
 const NEW_USDC_CL_POOL_ADDR: &str = "new_usdc_cl_pool";
 const NEW_ATOM_CL_POOL_ADDR: &str = "new_atom_cl_pool";
 const ADDR1: &str = "addr1";
 const ADDR2: &str = "addr2";
 const DENOM: &str = "ujuno";
+const NTRN_DENOM: &str = "untrn";
+const SUBDAO_DENOM: &str = "usubdao";
 const INIT_BALANCE: Uint128 = Uint128::new(10000);
 
 fn vault_contract() -> Box<dyn Contract<Empty>> {
@@ -72,6 +80,54 @@ fn mock_app() -> App {
 #[serde(rename_all = "snake_case")]
 pub struct EmptyMsg {}
 
+fn cl_pool_query(_deps: Deps, env: Env, msg: ClPoolQueryMsg) -> StdResult<Binary> {
+    match msg {
+        ClPoolQueryMsg::Pair {} => to_json_binary(&PairInfo {
+            asset_infos: vec![
+                AssetInfo::NativeToken {
+                    denom: NTRN_DENOM.to_string(),
+                },
+                AssetInfo::NativeToken {
+                    denom: SUBDAO_DENOM.to_string(),
+                },
+            ],
+            contract_addr: env.contract.address,
+            liquidity_token: Addr::unchecked("lp_token"),
+            pair_type: PairType::Custom("concentrated".to_string()),
+        }),
+        _ => unreachable!(),
+    }
+}
+
+fn cl_pool_contract() -> Box<dyn Contract<Empty>> {
+    let contract: ContractWrapper<
+        EmptyMsg,
+        EmptyMsg,
+        ClPoolQueryMsg,
+        ContractError,
+        ContractError,
+        StdError,
+    > = ContractWrapper::new(
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        cl_pool_query,
+    );
+    Box::new(contract)
+}
+
+fn instantiate_cl_pool_contract(app: &mut App) -> Addr {
+    let contract_id = app.store_code(cl_pool_contract());
+    app.instantiate_contract(
+        contract_id,
+        Addr::unchecked(DAO_ADDR),
+        &EmptyMsg {},
+        &[],
+        "cl pool contract",
+        None,
+    )
+    .unwrap()
+}
+
 fn instantiate_vault(app: &mut App, id: u64, msg: InstantiateMsg) -> Addr {
     app.instantiate_contract(id, Addr::unchecked(DAO_ADDR), &msg, &[], "vault", None)
         .unwrap()
@@ -119,6 +175,8 @@ fn update_config(
     atom_cl_pool_contract: Option<String>,
     name: Option<String>,
     description: Option<String>,
+    valuation: Option<ClPoolValuation>,
+    denom: Option<String>,
 ) -> anyhow::Result<AppResponse> {
     app.execute_contract(
         Addr::unchecked(sender),
@@ -130,6 +188,8 @@ fn update_config(
             atom_cl_pool_contract,
             name,
             description,
+            valuation,
+            denom,
         },
         &[],
     )
@@ -150,6 +210,8 @@ fn get_dao(app: &App, contract_addr: &Addr) -> String {
 #[test]
 fn test_instantiate() {
     let mut app = mock_app();
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -159,8 +221,10 @@ fn test_instantiate() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
     assert_eq!(get_dao(&app, &addr), String::from(DAO_ADDR));
@@ -170,6 +234,8 @@ fn test_instantiate() {
 #[should_panic(expected = "Bonding is not available for this contract")]
 fn test_bond() {
     let mut app = mock_app();
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -179,8 +245,10 @@ fn test_bond() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -192,6 +260,8 @@ fn test_bond() {
 #[should_panic(expected = "Direct unbonding is not available for this contract")]
 fn test_unbond() {
     let mut app = mock_app();
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -201,8 +271,10 @@ fn test_unbond() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -213,6 +285,8 @@ fn test_unbond() {
 #[should_panic(expected = "Unauthorized")]
 fn test_update_config_unauthorized() {
     let mut app = mock_app();
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -222,8 +296,10 @@ fn test_update_config_unauthorized() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -238,6 +314,8 @@ fn test_update_config_unauthorized() {
         Some(NEW_ATOM_CL_POOL_ADDR.to_string()),
         Some(NEW_NAME.to_string()),
         Some(NEW_DESCRIPTION.to_string()),
+        None,
+        None,
     )
     .unwrap();
 }
@@ -245,6 +323,10 @@ fn test_update_config_unauthorized() {
 #[test]
 fn test_update_config_as_owner() {
     let mut app = mock_app();
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let new_usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let new_atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -254,22 +336,29 @@ fn test_update_config_as_owner() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
-    // Change owner, description, name and lockdrop contract
+    // Change owner, description, name, lockdrop contract, valuation and denom
     update_config(
         &mut app,
         addr.clone(),
         DAO_ADDR,
         Some(ADDR1.to_string()),
         Some(NEW_LOCKDROP_ADDR.to_string()),
-        Some(NEW_USDC_CL_POOL_ADDR.to_string()),
-        Some(NEW_ATOM_CL_POOL_ADDR.to_string()),
+        Some(new_usdc_cl_pool_contract.to_string()),
+        Some(new_atom_cl_pool_contract.to_string()),
         Some(NEW_NAME.to_string()),
         Some(NEW_DESCRIPTION.to_string()),
+        Some(ClPoolValuation::Median {
+            window: 10,
+            samples: 5,
+        }),
+        Some(SUBDAO_DENOM.to_string()),
     )
     .unwrap();
 
@@ -280,8 +369,13 @@ fn test_update_config_as_owner() {
             description: NEW_DESCRIPTION.to_string(),
             owner: Addr::unchecked(ADDR1),
             lockdrop_contract: Addr::unchecked(NEW_LOCKDROP_ADDR),
-            usdc_cl_pool_contract: Addr::unchecked(NEW_USDC_CL_POOL_ADDR),
-            atom_cl_pool_contract: Addr::unchecked(NEW_ATOM_CL_POOL_ADDR),
+            usdc_cl_pool_contract: new_usdc_cl_pool_contract,
+            atom_cl_pool_contract: new_atom_cl_pool_contract,
+            valuation: ClPoolValuation::Median {
+                window: 10,
+                samples: 5,
+            },
+            denom: SUBDAO_DENOM.to_string(),
         },
         config
     );
@@ -291,6 +385,8 @@ fn test_update_config_as_owner() {
 #[should_panic(expected = "config description cannot be empty.")]
 fn test_update_config_invalid_description() {
     let mut app = mock_app();
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -300,8 +396,10 @@ fn test_update_config_invalid_description() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -312,10 +410,12 @@ fn test_update_config_invalid_description() {
         DAO_ADDR,
         Some(DAO_ADDR.to_string()),
         Some(LOCKDROP_ADDR.to_string()),
-        Some(USDC_CL_POOL_ADDR.to_string()),
-        Some(ATOM_CL_POOL_ADDR.to_string()),
+        Some(usdc_cl_pool_contract.to_string()),
+        Some(atom_cl_pool_contract.to_string()),
         Some(NEW_NAME.to_string()),
         Some(String::from("")),
+        None,
+        None,
     )
     .unwrap();
 }
@@ -324,6 +424,8 @@ fn test_update_config_invalid_description() {
 #[should_panic(expected = "config name cannot be empty.")]
 fn test_update_config_invalid_name() {
     let mut app = mock_app();
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -333,8 +435,10 @@ fn test_update_config_invalid_name() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -345,10 +449,53 @@ fn test_update_config_invalid_name() {
         DAO_ADDR,
         Some(DAO_ADDR.to_string()),
         Some(LOCKDROP_ADDR.to_string()),
-        Some(USDC_CL_POOL_ADDR.to_string()),
-        Some(ATOM_CL_POOL_ADDR.to_string()),
+        Some(usdc_cl_pool_contract.to_string()),
+        Some(atom_cl_pool_contract.to_string()),
         Some(String::from("")),
         Some(NEW_DESCRIPTION.to_string()),
+        None,
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "valuation samples must be between 2 and 10")]
+fn test_update_config_invalid_valuation() {
+    let mut app = mock_app();
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            lockdrop_contract: LOCKDROP_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
+        },
+    );
+
+    update_config(
+        &mut app,
+        addr,
+        DAO_ADDR,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(ClPoolValuation::Twap {
+            window: 10,
+            samples: 1,
+        }),
+        None,
     )
     .unwrap();
 }
@@ -356,6 +503,8 @@ fn test_update_config_invalid_name() {
 #[test]
 fn test_query_dao() {
     let mut app = mock_app();
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -365,8 +514,10 @@ fn test_query_dao() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -378,6 +529,8 @@ fn test_query_dao() {
 #[test]
 fn test_query_info() {
     let mut app = mock_app();
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -387,8 +540,10 @@ fn test_query_info() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -403,6 +558,8 @@ fn test_query_info() {
 #[test]
 fn test_query_get_config() {
     let mut app = mock_app();
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -412,8 +569,10 @@ fn test_query_get_config() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -425,12 +584,75 @@ fn test_query_get_config() {
             description: DESCRIPTION.to_string(),
             owner: Addr::unchecked(DAO_ADDR),
             lockdrop_contract: Addr::unchecked(LOCKDROP_ADDR),
-            usdc_cl_pool_contract: Addr::unchecked(USDC_CL_POOL_ADDR),
-            atom_cl_pool_contract: Addr::unchecked(ATOM_CL_POOL_ADDR),
+            usdc_cl_pool_contract: usdc_cl_pool_contract,
+            atom_cl_pool_contract: atom_cl_pool_contract,
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         }
     )
 }
 
+#[test]
+#[should_panic(expected = "config denom uatom is not an asset of the contract0 pair.")]
+fn test_instantiate_denom_not_in_pair() {
+    let mut app = mock_app();
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let vault_id = app.store_code(vault_contract());
+    instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            lockdrop_contract: LOCKDROP_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: String::from("uatom"),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "config denom uatom is not an asset of the contract0 pair.")]
+fn test_update_config_denom_not_in_pair() {
+    let mut app = mock_app();
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            lockdrop_contract: LOCKDROP_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
+        },
+    );
+
+    update_config(
+        &mut app,
+        addr,
+        DAO_ADDR,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(String::from("uatom")),
+    )
+    .unwrap();
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
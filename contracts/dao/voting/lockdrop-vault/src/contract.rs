@@ -15,6 +15,8 @@ use astroport_periphery::lockdrop::PoolType;
 use neutron_lockdrop_vault::error::{ContractError, ContractResult};
 use neutron_lockdrop_vault::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use neutron_lockdrop_vault::types::Config;
+use neutron_oracle::voting_power::pair_has_denom;
+use neutron_voting_power::snapshot::load_at_height;
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:neutron-lockdrop-vault";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -22,7 +24,7 @@ pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> ContractResult<Response> {
@@ -37,9 +39,13 @@ pub fn instantiate(
         oracle_usdc_contract: deps.api.addr_validate(&msg.oracle_usdc_contract)?,
         oracle_atom_contract: deps.api.addr_validate(&msg.oracle_atom_contract)?,
         owner,
+        denom: msg.denom,
     };
     config.validate()?;
-    CONFIG.save(deps.storage, &config)?;
+    validate_denom(deps.as_ref(), &config, env.block.height)?;
+    // the vault only reads the lockdrop and oracles' history, so the initial config counts at all
+    // the heights before the first update
+    CONFIG.save(deps.storage, &config, 0)?;
     DAO.save(deps.storage, &info.sender)?;
 
     Ok(Response::new()
@@ -49,7 +55,8 @@ pub fn instantiate(
         .add_attribute("owner", config.owner)
         .add_attribute("lockdrop_contract", config.lockdrop_contract)
         .add_attribute("oracle_usdc_contract", config.oracle_usdc_contract)
-        .add_attribute("oracle_atom_contract", config.oracle_atom_contract))
+        .add_attribute("oracle_atom_contract", config.oracle_atom_contract)
+        .add_attribute("denom", config.denom))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -69,8 +76,10 @@ pub fn execute(
             oracle_atom_contract,
             name,
             description,
+            denom,
         } => execute_update_config(
             deps,
+            env,
             info,
             owner,
             lockdrop_contract,
@@ -78,6 +87,7 @@ pub fn execute(
             oracle_atom_contract,
             name,
             description,
+            denom,
         ),
     }
 }
@@ -98,6 +108,7 @@ pub fn execute_unbond(
 #[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     new_owner: Option<String>,
     new_lockdrop_contract: Option<String>,
@@ -105,6 +116,7 @@ pub fn execute_update_config(
     new_oracle_atom_contract: Option<String>,
     new_name: Option<String>,
     new_description: Option<String>,
+    new_denom: Option<String>,
 ) -> ContractResult<Response> {
     let mut config: Config = CONFIG.load(deps.storage)?;
     if info.sender != config.owner {
@@ -146,9 +158,13 @@ pub fn execute_update_config(
     if let Some(description) = new_description {
         config.description = description;
     }
+    if let Some(denom) = new_denom {
+        config.denom = denom;
+    }
 
     config.validate()?;
-    CONFIG.save(deps.storage, &config)?;
+    validate_denom(deps.as_ref(), &config, env.block.height)?;
+    CONFIG.save(deps.storage, &config, env.block.height)?;
 
     Ok(Response::new()
         .add_attribute("action", "update_config")
@@ -156,7 +172,21 @@ pub fn execute_update_config(
         .add_attribute("owner", config.owner)
         .add_attribute("lockdrop_contract", config.lockdrop_contract)
         .add_attribute("oracle_usdc_contract", config.oracle_usdc_contract)
-        .add_attribute("oracle_atom_contract", config.oracle_atom_contract))
+        .add_attribute("oracle_atom_contract", config.oracle_atom_contract)
+        .add_attribute("denom", config.denom))
+}
+
+/// checks whether both oracles' pairs hold the denom LP tokens are valued in.
+fn validate_denom(deps: Deps, config: &Config, height: u64) -> ContractResult<()> {
+    for oracle_contract in [&config.oracle_usdc_contract, &config.oracle_atom_contract] {
+        if !pair_has_denom(deps, oracle_contract, &config.denom, height)? {
+            return Err(ContractError::DenomNotInPair {
+                denom: config.denom.clone(),
+                contract: oracle_contract.to_string(),
+            });
+        }
+    }
+    Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -188,9 +218,8 @@ pub fn query_voting_power_at_height(
     address: String,
     height: Option<u64>,
 ) -> ContractResult<VotingPowerAtHeightResponse> {
-    let config = CONFIG.load(deps.storage)?;
-
     let height = height.unwrap_or(env.block.height);
+    let config = load_at_height(deps.storage, &CONFIG, height)?;
 
     let atom_power = get_voting_power_for_address(
         deps,
@@ -199,6 +228,7 @@ pub fn query_voting_power_at_height(
         config.oracle_atom_contract.as_ref(),
         PoolType::ATOM,
         address.clone(),
+        &config.denom,
         height,
     )?;
     let usdc_power = get_voting_power_for_address(
@@ -208,6 +238,7 @@ pub fn query_voting_power_at_height(
         config.oracle_atom_contract,
         PoolType::USDC,
         address,
+        &config.denom,
         height,
     )?;
 
@@ -224,9 +255,8 @@ pub fn query_total_power_at_height(
     env: Env,
     height: Option<u64>,
 ) -> ContractResult<TotalPowerAtHeightResponse> {
-    let config = CONFIG.load(deps.storage)?;
-
     let height = height.unwrap_or(env.block.height);
+    let config = load_at_height(deps.storage, &CONFIG, height)?;
 
     let atom_power = get_voting_power_total(
         deps,
@@ -234,6 +264,7 @@ pub fn query_total_power_at_height(
         config.oracle_usdc_contract.as_ref(),
         config.oracle_atom_contract.as_ref(),
         PoolType::ATOM,
+        &config.denom,
         height,
     )?;
     let usdc_power = get_voting_power_total(
@@ -242,6 +273,7 @@ pub fn query_total_power_at_height(
         config.oracle_usdc_contract,
         config.oracle_atom_contract,
         PoolType::USDC,
+        &config.denom,
         height,
     )?;
 
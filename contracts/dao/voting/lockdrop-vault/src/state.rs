@@ -1,6 +1,11 @@
 use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, SnapshotItem, Strategy};
 use neutron_lockdrop_vault::types::Config;
 
-pub const CONFIG: Item<Config> = Item::new("config");
+pub const CONFIG: SnapshotItem<Config> = SnapshotItem::new(
+    "config",
+    "config__checkpoints",
+    "config__changelog",
+    Strategy::EveryBlock,
+);
 pub const DAO: Item<Addr> = Item::new("dao");
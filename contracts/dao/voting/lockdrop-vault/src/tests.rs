@@ -4,10 +4,12 @@ use astroport::oracle::QueryMsg as OracleQueryMsg;
 use astroport_periphery::lockdrop::{PoolType, QueryMsg as LockdropQueryMsg};
 use cosmwasm_std::testing::{mock_dependencies, mock_env};
 use cosmwasm_std::{
-    coins, to_json_binary, Addr, Binary, Coin, Decimal256, Deps, Empty, Env, Response, StdResult,
-    Uint128,
+    coins, to_json_binary, Addr, Binary, Coin, Decimal256, Deps, Empty, Env, Response, StdError,
+    StdResult, Uint128,
+};
+use cw_multi_test::{
+    custom_app, next_block, App, AppResponse, Contract, ContractWrapper, Executor,
 };
-use cw_multi_test::{custom_app, App, AppResponse, Contract, ContractWrapper, Executor};
 use cwd_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
@@ -23,14 +25,14 @@ const NEW_NAME: &str = "new_name";
 const DESCRIPTION: &str = "description";
 const NEW_DESCRIPTION: &str = "new description";
 const LOCKDROP_ADDR: &str = "lockdrop";
-const ORACLE_USDC_ADDR: &str = "oracle_usdc";
-const ORACLE_ATOM_ADDR: &str = "oracle_atom";
 const NEW_LOCKDROP_ADDR: &str = "new_lockdrop";
 const NEW_ORACLE_USDC_ADDR: &str = "new_oracle_usdc";
 const NEW_ORACLE_ATOM_ADDR: &str = "new_oracle_atom";
 const ADDR1: &str = "addr1";
 const ADDR2: &str = "addr2";
 const DENOM: &str = "ujuno";
+const NTRN_DENOM: &str = "untrn";
+const SUBDAO_DENOM: &str = "usubdao";
 const INIT_BALANCE: Uint128 = Uint128::new(10000);
 
 fn vault_contract() -> Box<dyn Contract<Empty>> {
@@ -145,16 +147,21 @@ fn instantiate_lockdrop_contract(app: &mut App) -> Addr {
 }
 
 const NTRN_TWAP: u64 = 4;
+const SUBDAO_TWAP: u64 = 16;
 
 fn oracle_query(_deps: Deps, _env: Env, msg: OracleQueryMsg) -> StdResult<Binary> {
     match msg {
         OracleQueryMsg::TWAPAtHeight { token, height: _ } => {
             let twap = match token.clone() {
                 AssetInfo::NativeToken { denom } => match denom.as_str() {
-                    "untrn" => Decimal256::from_ratio(NTRN_TWAP, 1u64),
-                    _ => Decimal256::from_ratio(1u64, NTRN_TWAP),
+                    NTRN_DENOM => Decimal256::from_ratio(NTRN_TWAP, 1u64),
+                    SUBDAO_DENOM => Decimal256::from_ratio(SUBDAO_TWAP, 1u64),
+                    // the oracle doesn't price tokens outside of its pair
+                    _ => return Err(StdError::generic_err("Invalid Token")),
                 },
-                AssetInfo::Token { contract_addr: _ } => Decimal256::from_ratio(1u64, NTRN_TWAP),
+                AssetInfo::Token { contract_addr: _ } => {
+                    return Err(StdError::generic_err("Invalid Token"))
+                }
             };
 
             let response = vec![(token, twap)];
@@ -240,6 +247,7 @@ fn update_config(
     oracle_atom_contract: Option<String>,
     name: Option<String>,
     description: Option<String>,
+    denom: Option<String>,
 ) -> anyhow::Result<AppResponse> {
     app.execute_contract(
         Addr::unchecked(sender),
@@ -251,6 +259,7 @@ fn update_config(
             oracle_atom_contract,
             name,
             description,
+            denom,
         },
         &[],
     )
@@ -295,6 +304,8 @@ fn get_dao(app: &App, contract_addr: &Addr) -> String {
 #[test]
 fn test_instantiate() {
     let mut app = mock_app();
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -304,8 +315,9 @@ fn test_instantiate() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            oracle_usdc_contract: ORACLE_USDC_ADDR.to_string(),
-            oracle_atom_contract: ORACLE_ATOM_ADDR.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
     assert_eq!(get_dao(&app, &addr), String::from(DAO_ADDR));
@@ -315,6 +327,8 @@ fn test_instantiate() {
 #[should_panic(expected = "Bonding is not available for this contract")]
 fn test_bond() {
     let mut app = mock_app();
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -324,8 +338,9 @@ fn test_bond() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            oracle_usdc_contract: ORACLE_USDC_ADDR.to_string(),
-            oracle_atom_contract: ORACLE_ATOM_ADDR.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -337,6 +352,8 @@ fn test_bond() {
 #[should_panic(expected = "Direct unbonding is not available for this contract")]
 fn test_unbond() {
     let mut app = mock_app();
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -346,8 +363,9 @@ fn test_unbond() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            oracle_usdc_contract: ORACLE_USDC_ADDR.to_string(),
-            oracle_atom_contract: ORACLE_ATOM_ADDR.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -358,6 +376,8 @@ fn test_unbond() {
 #[should_panic(expected = "Unauthorized")]
 fn test_update_config_unauthorized() {
     let mut app = mock_app();
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -367,8 +387,9 @@ fn test_update_config_unauthorized() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            oracle_usdc_contract: ORACLE_USDC_ADDR.to_string(),
-            oracle_atom_contract: ORACLE_ATOM_ADDR.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -379,10 +400,11 @@ fn test_update_config_unauthorized() {
         ADDR2,
         Some(ADDR1.to_string()),
         Some(NEW_LOCKDROP_ADDR.to_string()),
-        Some(NEW_ORACLE_USDC_ADDR.to_string()),
-        Some(NEW_ORACLE_ATOM_ADDR.to_string()),
+        Some(NEW_oracle_usdc_contract.to_string()),
+        Some(NEW_oracle_atom_contract.to_string()),
         Some(NEW_NAME.to_string()),
         Some(NEW_DESCRIPTION.to_string()),
+        None,
     )
     .unwrap();
 }
@@ -390,6 +412,10 @@ fn test_update_config_unauthorized() {
 #[test]
 fn test_update_config_as_owner() {
     let mut app = mock_app();
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
+    let new_oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let new_oracle_atom_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -399,8 +425,9 @@ fn test_update_config_as_owner() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            oracle_usdc_contract: ORACLE_USDC_ADDR.to_string(),
-            oracle_atom_contract: ORACLE_ATOM_ADDR.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -411,10 +438,11 @@ fn test_update_config_as_owner() {
         DAO_ADDR,
         Some(ADDR1.to_string()),
         Some(NEW_LOCKDROP_ADDR.to_string()),
-        Some(NEW_ORACLE_USDC_ADDR.to_string()),
-        Some(NEW_ORACLE_ATOM_ADDR.to_string()),
+        Some(NEW_oracle_usdc_contract.to_string()),
+        Some(NEW_oracle_atom_contract.to_string()),
         Some(NEW_NAME.to_string()),
         Some(NEW_DESCRIPTION.to_string()),
+        None,
     )
     .unwrap();
 
@@ -425,8 +453,9 @@ fn test_update_config_as_owner() {
             description: NEW_DESCRIPTION.to_string(),
             owner: Addr::unchecked(ADDR1),
             lockdrop_contract: Addr::unchecked(NEW_LOCKDROP_ADDR),
-            oracle_usdc_contract: Addr::unchecked(NEW_ORACLE_USDC_ADDR),
-            oracle_atom_contract: Addr::unchecked(NEW_ORACLE_ATOM_ADDR),
+            oracle_usdc_contract: new_oracle_usdc_contract,
+            oracle_atom_contract: new_oracle_atom_contract,
+            denom: NTRN_DENOM.to_string(),
         },
         config
     );
@@ -436,6 +465,8 @@ fn test_update_config_as_owner() {
 #[should_panic(expected = "config description cannot be empty.")]
 fn test_update_config_invalid_description() {
     let mut app = mock_app();
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -445,8 +476,9 @@ fn test_update_config_invalid_description() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            oracle_usdc_contract: ORACLE_USDC_ADDR.to_string(),
-            oracle_atom_contract: ORACLE_ATOM_ADDR.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -457,10 +489,11 @@ fn test_update_config_invalid_description() {
         DAO_ADDR,
         Some(DAO_ADDR.to_string()),
         Some(LOCKDROP_ADDR.to_string()),
-        Some(ORACLE_USDC_ADDR.to_string()),
-        Some(ORACLE_ATOM_ADDR.to_string()),
+        Some(oracle_usdc_contract.to_string()),
+        Some(oracle_atom_contract.to_string()),
         Some(NEW_NAME.to_string()),
         Some(String::from("")),
+        None,
     )
     .unwrap();
 }
@@ -469,6 +502,8 @@ fn test_update_config_invalid_description() {
 #[should_panic(expected = "config name cannot be empty.")]
 fn test_update_config_invalid_name() {
     let mut app = mock_app();
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -478,8 +513,9 @@ fn test_update_config_invalid_name() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            oracle_usdc_contract: ORACLE_USDC_ADDR.to_string(),
-            oracle_atom_contract: ORACLE_ATOM_ADDR.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -490,10 +526,11 @@ fn test_update_config_invalid_name() {
         DAO_ADDR,
         Some(DAO_ADDR.to_string()),
         Some(LOCKDROP_ADDR.to_string()),
-        Some(ORACLE_USDC_ADDR.to_string()),
-        Some(ORACLE_ATOM_ADDR.to_string()),
+        Some(oracle_usdc_contract.to_string()),
+        Some(oracle_atom_contract.to_string()),
         Some(String::from("")),
         Some(NEW_DESCRIPTION.to_string()),
+        None,
     )
     .unwrap();
 }
@@ -501,6 +538,8 @@ fn test_update_config_invalid_name() {
 #[test]
 fn test_query_dao() {
     let mut app = mock_app();
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -510,8 +549,9 @@ fn test_query_dao() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            oracle_usdc_contract: ORACLE_USDC_ADDR.to_string(),
-            oracle_atom_contract: ORACLE_ATOM_ADDR.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -523,6 +563,8 @@ fn test_query_dao() {
 #[test]
 fn test_query_info() {
     let mut app = mock_app();
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -532,8 +574,9 @@ fn test_query_info() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            oracle_usdc_contract: ORACLE_USDC_ADDR.to_string(),
-            oracle_atom_contract: ORACLE_ATOM_ADDR.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -545,6 +588,8 @@ fn test_query_info() {
 #[test]
 fn test_query_get_config() {
     let mut app = mock_app();
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -554,8 +599,9 @@ fn test_query_get_config() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             lockdrop_contract: LOCKDROP_ADDR.to_string(),
-            oracle_usdc_contract: ORACLE_USDC_ADDR.to_string(),
-            oracle_atom_contract: ORACLE_ATOM_ADDR.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -567,8 +613,9 @@ fn test_query_get_config() {
             description: DESCRIPTION.to_string(),
             owner: Addr::unchecked(DAO_ADDR),
             lockdrop_contract: Addr::unchecked(LOCKDROP_ADDR),
-            oracle_usdc_contract: Addr::unchecked(ORACLE_USDC_ADDR),
-            oracle_atom_contract: Addr::unchecked(ORACLE_ATOM_ADDR),
+            oracle_usdc_contract: oracle_usdc_contract,
+            oracle_atom_contract: oracle_atom_contract,
+            denom: NTRN_DENOM.to_string(),
         }
     )
 }
@@ -592,6 +639,7 @@ fn test_voting_power_at_height() {
             lockdrop_contract: lockdrop_contract.to_string(),
             oracle_usdc_contract: oracle_usdc_contract.to_string(),
             oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -619,6 +667,7 @@ fn test_total_power_at_height() {
             lockdrop_contract: lockdrop_contract.to_string(),
             oracle_usdc_contract: oracle_usdc_contract.to_string(),
             oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -627,6 +676,187 @@ fn test_total_power_at_height() {
     assert_eq!(resp.power, Uint128::from(1_500_000u128 + 2_000_000u128));
 }
 
+#[test]
+fn test_power_at_height_non_default_denom() {
+    let mut app = mock_app();
+
+    let lockdrop_contract = instantiate_lockdrop_contract(&mut app);
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
+
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            lockdrop_contract: lockdrop_contract.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: SUBDAO_DENOM.to_string(),
+        },
+    );
+
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    // (USER_ATOM_LOCKUP_AT_HEIGHT / sqrt(SUBDAO_TWAP)) + (USER_USDC_LOCKUP_AT_HEIGHT / sqrt(SUBDAO_TWAP))
+    assert_eq!(resp.power, Uint128::from(250_000u128 + 500_000u128));
+
+    let resp = get_total_power_at_height(&mut app, addr, None);
+    // (TOTAL_ATOM_LOCKUP_AT_HEIGHT / sqrt(SUBDAO_TWAP)) + (TOTAL_USDC_LOCKUP_AT_HEIGHT / sqrt(SUBDAO_TWAP))
+    assert_eq!(resp.power, Uint128::from(750_000u128 + 1_000_000u128));
+}
+
+#[test]
+#[should_panic(expected = "config denom cannot be empty.")]
+fn test_update_config_invalid_denom() {
+    let mut app = mock_app();
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            lockdrop_contract: LOCKDROP_ADDR.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
+        },
+    );
+
+    update_config(
+        &mut app,
+        addr,
+        DAO_ADDR,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(String::from("")),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_update_config_denom() {
+    let mut app = mock_app();
+
+    let lockdrop_contract = instantiate_lockdrop_contract(&mut app);
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
+
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            lockdrop_contract: lockdrop_contract.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
+        },
+    );
+
+    let update_height = app.block_info().height;
+    update_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(SUBDAO_DENOM.to_string()),
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    // (USER_ATOM_LOCKUP_AT_HEIGHT / sqrt(SUBDAO_TWAP)) + (USER_USDC_LOCKUP_AT_HEIGHT / sqrt(SUBDAO_TWAP))
+    assert_eq!(resp.power, Uint128::from(250_000u128 + 500_000u128));
+
+    // the past heights keep being valued in the previous denom
+    let resp = get_voting_power_at_height(
+        &mut app,
+        addr.clone(),
+        ADDR1.to_string(),
+        Some(update_height),
+    );
+    assert_eq!(resp.power, Uint128::from(500_000u128 + 1_000_000u128));
+    let resp = get_total_power_at_height(&mut app, addr, Some(update_height));
+    assert_eq!(resp.power, Uint128::from(1_500_000u128 + 2_000_000u128));
+}
+
+#[test]
+#[should_panic(expected = "config denom uatom is not an asset of the contract0 pair.")]
+fn test_instantiate_denom_not_in_pair() {
+    let mut app = mock_app();
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
+    let vault_id = app.store_code(vault_contract());
+    instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            lockdrop_contract: LOCKDROP_ADDR.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: String::from("uatom"),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "config denom uatom is not an asset of the contract0 pair.")]
+fn test_update_config_denom_not_in_pair() {
+    let mut app = mock_app();
+    let oracle_usdc_contract = instantiate_oracle_contract(&mut app);
+    let oracle_atom_contract = instantiate_oracle_contract(&mut app);
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            lockdrop_contract: LOCKDROP_ADDR.to_string(),
+            oracle_usdc_contract: oracle_usdc_contract.to_string(),
+            oracle_atom_contract: oracle_atom_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
+        },
+    );
+
+    update_config(
+        &mut app,
+        addr,
+        DAO_ADDR,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(String::from("uatom")),
+    )
+    .unwrap();
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
@@ -0,0 +1,295 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+use cw2::set_contract_version;
+use cwd_interface::voting::{
+    BondingStatusResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use neutron_lp_vault::voting_power::{get_voting_power_for_address, get_voting_power_total};
+
+use crate::state::{CONFIG, DAO};
+
+use neutron_lp_vault::error::{ContractError, ContractResult};
+use neutron_lp_vault::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use neutron_lp_vault::types::{Config, LpPool, LpSource, PairKind};
+use neutron_oracle::voting_power::pair_has_denom;
+use neutron_voting_power::snapshot::load_at_height;
+use neutron_voting_power::voting_power::cl_pool_has_denom;
+
+pub(crate) const CONTRACT_NAME: &str = "crates.io:neutron-lp-vault";
+pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ContractResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = deps.api.addr_validate(&msg.owner)?;
+    for pool in &msg.pools {
+        pool.validate_addresses(deps.api)?;
+    }
+
+    let config = Config {
+        name: msg.name,
+        description: msg.description,
+        owner,
+        pools: msg.pools,
+        denom: msg.denom,
+    };
+    config.validate()?;
+    validate_denom(deps.as_ref(), &config, env.block.height)?;
+    // the vault only reads the LP sources' history, so the initial pools count at all the heights
+    // before the first update
+    CONFIG.save(deps.storage, &config, 0)?;
+    DAO.save(deps.storage, &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("name", config.name)
+        .add_attribute("description", config.description)
+        .add_attribute("owner", config.owner)
+        .add_attribute("pools", config.pools.len().to_string())
+        .add_attribute("denom", config.denom))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> ContractResult<Response> {
+    match msg {
+        ExecuteMsg::Bond {} => execute_bond(deps, env, info),
+        ExecuteMsg::Unbond { amount } => execute_unbond(deps, env, info, amount),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            name,
+            description,
+            add_pools,
+            remove_pools,
+            denom,
+        } => execute_update_config(
+            deps,
+            env,
+            info,
+            owner,
+            name,
+            description,
+            add_pools,
+            remove_pools,
+            denom,
+        ),
+    }
+}
+
+pub fn execute_bond(_deps: DepsMut, _env: Env, _info: MessageInfo) -> ContractResult<Response> {
+    Err(ContractError::BondingDisabled {})
+}
+
+pub fn execute_unbond(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _amount: Uint128,
+) -> ContractResult<Response> {
+    Err(ContractError::DirectUnbondingDisabled {})
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_owner: Option<String>,
+    new_name: Option<String>,
+    new_description: Option<String>,
+    add_pools: Option<Vec<LpPool>>,
+    remove_pools: Option<Vec<LpSource>>,
+    new_denom: Option<String>,
+) -> ContractResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let new_owner = new_owner
+        .map(|new_owner| deps.api.addr_validate(&new_owner))
+        .transpose()?;
+
+    if let Some(owner) = new_owner {
+        config.owner = owner;
+    }
+    if let Some(name) = new_name {
+        config.name = name;
+    }
+    if let Some(description) = new_description {
+        config.description = description;
+    }
+    if let Some(denom) = new_denom {
+        config.denom = denom;
+    }
+    for lp_source in remove_pools.unwrap_or_default() {
+        let index = config
+            .pools
+            .iter()
+            .position(|pool| pool.lp_source == lp_source)
+            .ok_or(ContractError::PoolNotFound {})?;
+        config.pools.remove(index);
+    }
+    for pool in add_pools.unwrap_or_default() {
+        pool.validate_addresses(deps.api)?;
+        config.pools.push(pool);
+    }
+
+    config.validate()?;
+    validate_denom(deps.as_ref(), &config, env.block.height)?;
+    CONFIG.save(deps.storage, &config, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("description", config.description)
+        .add_attribute("owner", config.owner)
+        .add_attribute("pools", config.pools.len().to_string())
+        .add_attribute("denom", config.denom))
+}
+
+/// checks whether the pairs of all the pools hold the denom LP tokens are valued in.
+fn validate_denom(deps: Deps, config: &Config, height: u64) -> ContractResult<()> {
+    for pool in &config.pools {
+        let (contract, has_denom) = match &pool.pair_kind {
+            PairKind::XykTwap { oracle_contract } => (
+                oracle_contract,
+                pair_has_denom(deps, oracle_contract, &config.denom, height)?,
+            ),
+            PairKind::ClPool { pool_contract, .. } => (
+                pool_contract,
+                cl_pool_has_denom(deps, pool_contract, &config.denom)?,
+            ),
+        };
+        if !has_denom {
+            return Err(ContractError::DenomNotInPair {
+                denom: config.denom.clone(),
+                contract: contract.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+    match msg {
+        QueryMsg::VotingPowerAtHeight { address, height } => Ok(to_json_binary(
+            &query_voting_power_at_height(deps, env, address, height)?,
+        )?),
+        QueryMsg::TotalPowerAtHeight { height } => Ok(to_json_binary(
+            &query_total_power_at_height(deps, env, height)?,
+        )?),
+        QueryMsg::Info {} => query_info(deps),
+        QueryMsg::Dao {} => query_dao(deps),
+        QueryMsg::Name {} => query_name(deps),
+        QueryMsg::Description {} => query_description(deps),
+        QueryMsg::Config {} => query_config(deps),
+        QueryMsg::ListBonders { start_after, limit } => {
+            query_list_bonders(deps, start_after, limit)
+        }
+        QueryMsg::BondingStatus { height, address } => Ok(to_json_binary(&query_bonding_status(
+            deps, env, height, address,
+        )?)?),
+    }
+}
+
+pub fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> ContractResult<VotingPowerAtHeightResponse> {
+    let height = height.unwrap_or(env.block.height);
+    let config = load_at_height(deps.storage, &CONFIG, height)?;
+
+    let mut power = Uint128::zero();
+    for pool in &config.pools {
+        power = power.checked_add(get_voting_power_for_address(
+            deps,
+            pool,
+            address.clone(),
+            &config.denom,
+            height,
+        )?)?;
+    }
+
+    Ok(VotingPowerAtHeightResponse { power, height })
+}
+
+pub fn query_total_power_at_height(
+    deps: Deps,
+    env: Env,
+    height: Option<u64>,
+) -> ContractResult<TotalPowerAtHeightResponse> {
+    let height = height.unwrap_or(env.block.height);
+    let config = load_at_height(deps.storage, &CONFIG, height)?;
+
+    let mut power = Uint128::zero();
+    for pool in &config.pools {
+        power = power.checked_add(get_voting_power_total(deps, pool, &config.denom, height)?)?;
+    }
+
+    Ok(TotalPowerAtHeightResponse { power, height })
+}
+
+pub fn query_info(deps: Deps) -> ContractResult<Binary> {
+    let info = cw2::get_contract_version(deps.storage)?;
+    Ok(to_json_binary(&cwd_interface::voting::InfoResponse {
+        info,
+    })?)
+}
+
+pub fn query_dao(deps: Deps) -> ContractResult<Binary> {
+    let dao = DAO.load(deps.storage)?;
+    Ok(to_json_binary(&dao)?)
+}
+
+pub fn query_name(deps: Deps) -> ContractResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(to_json_binary(&config.name)?)
+}
+
+pub fn query_description(deps: Deps) -> ContractResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(to_json_binary(&config.description)?)
+}
+
+pub fn query_config(deps: Deps) -> ContractResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(to_json_binary(&config)?)
+}
+
+pub fn query_list_bonders(
+    _deps: Deps,
+    _start_after: Option<String>,
+    _limit: Option<u32>,
+) -> ContractResult<Binary> {
+    Err(ContractError::BondingDisabled {})
+}
+
+pub fn query_bonding_status(
+    _deps: Deps,
+    _env: Env,
+    _height: Option<u64>,
+    _address: String,
+) -> ContractResult<BondingStatusResponse> {
+    Err(ContractError::BondingDisabled {})
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _: MigrateMsg) -> ContractResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::default())
+}
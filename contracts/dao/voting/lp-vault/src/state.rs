@@ -0,0 +1,11 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, SnapshotItem, Strategy};
+use neutron_lp_vault::types::Config;
+
+pub const CONFIG: SnapshotItem<Config> = SnapshotItem::new(
+    "config",
+    "config__checkpoints",
+    "config__changelog",
+    Strategy::EveryBlock,
+);
+pub const DAO: Item<Addr> = Item::new("dao");
@@ -0,0 +1,860 @@
+use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
+use astroport_periphery::lockdrop::{PoolType, QueryMsg as LockdropQueryMsg};
+use cosmwasm_std::testing::{mock_dependencies, mock_env};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Decimal256, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128,
+};
+use cw_multi_test::{
+    custom_app, next_block, App, AppResponse, Contract, ContractWrapper, Executor,
+};
+use cw_storage_plus::Item;
+use cwd_interface::voting::{
+    InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use neutron_lp_vault::error::ContractError;
+use neutron_lp_vault::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use neutron_lp_vault::types::{Config, LpPool, LpSource, PairKind};
+use neutron_voting_power::voting_power::ClPoolValuation;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+const DAO_ADDR: &str = "dao";
+const NAME: &str = "name";
+const DESCRIPTION: &str = "description";
+const NEW_DESCRIPTION: &str = "new description";
+const ADDR1: &str = "addr1";
+const ADDR2: &str = "addr2";
+const NTRN_DENOM: &str = "untrn";
+const SUBDAO_DENOM: &str = "usubdao";
+
+fn vault_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn mock_app() -> App {
+    custom_app(|_r, _a, _s| {})
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct EmptyMsg {}
+
+fn instantiate_mock_contract(app: &mut App, contract: Box<dyn Contract<Empty>>) -> Addr {
+    let contract_id = app.store_code(contract);
+    app.instantiate_contract(
+        contract_id,
+        Addr::unchecked(DAO_ADDR),
+        &EmptyMsg {},
+        &[],
+        "mock contract",
+        None,
+    )
+    .unwrap()
+}
+
+const USER_ATOM_LOCKUP_AT_HEIGHT: u64 = 1_000_000u64;
+const USER_USDC_LOCKUP_AT_HEIGHT: u64 = 2_000_000u64;
+const TOTAL_ATOM_LOCKUP_AT_HEIGHT: u64 = 3_000_000u64;
+const TOTAL_USDC_LOCKUP_AT_HEIGHT: u64 = 4_000_000u64;
+
+fn lockdrop_query(_deps: Deps, _env: Env, msg: LockdropQueryMsg) -> StdResult<Binary> {
+    match msg {
+        LockdropQueryMsg::QueryUserLockupTotalAtHeight {
+            pool_type,
+            user_address: _,
+            height: _,
+        } => {
+            let response = match pool_type {
+                PoolType::ATOM => Uint128::from(USER_ATOM_LOCKUP_AT_HEIGHT),
+                PoolType::USDC => Uint128::from(USER_USDC_LOCKUP_AT_HEIGHT),
+            };
+
+            to_json_binary(&response)
+        }
+        LockdropQueryMsg::QueryLockupTotalAtHeight {
+            pool_type,
+            height: _,
+        } => {
+            let response = match pool_type {
+                PoolType::ATOM => Uint128::from(TOTAL_ATOM_LOCKUP_AT_HEIGHT),
+                PoolType::USDC => Uint128::from(TOTAL_USDC_LOCKUP_AT_HEIGHT),
+            };
+
+            to_json_binary(&response)
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn lockdrop_contract() -> Box<dyn Contract<Empty>> {
+    let contract: ContractWrapper<
+        EmptyMsg,
+        EmptyMsg,
+        LockdropQueryMsg,
+        ContractError,
+        ContractError,
+        cosmwasm_std::StdError,
+    > = ContractWrapper::new(
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        lockdrop_query,
+    );
+    Box::new(contract)
+}
+
+const NTRN_TWAP: u64 = 4;
+const SUBDAO_TWAP: u64 = 16;
+
+fn oracle_query(
+    _deps: Deps,
+    _env: Env,
+    msg: astroport_main::oracle::QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        astroport_main::oracle::QueryMsg::TWAPAtHeight { token, height: _ } => {
+            let twap = match &token {
+                astroport_main::asset::AssetInfo::NativeToken { denom } => match denom.as_str() {
+                    NTRN_DENOM => NTRN_TWAP,
+                    SUBDAO_DENOM => SUBDAO_TWAP,
+                    // the oracle doesn't price tokens outside of its pair
+                    _ => return Err(StdError::generic_err("Invalid Token")),
+                },
+                _ => return Err(StdError::generic_err("Invalid Token")),
+            };
+            let response = vec![(token, Decimal256::from_ratio(twap, 1u64))];
+            to_json_binary(&response)
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn oracle_contract() -> Box<dyn Contract<Empty>> {
+    let contract: ContractWrapper<
+        EmptyMsg,
+        EmptyMsg,
+        astroport_main::oracle::QueryMsg,
+        ContractError,
+        ContractError,
+        cosmwasm_std::StdError,
+    > = ContractWrapper::new(
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        oracle_query,
+    );
+    Box::new(contract)
+}
+
+const USER_UNCLAIMED_LP: u64 = 1_000u64;
+const TOTAL_UNCLAIMED_LP: u64 = 4_000u64;
+
+fn vesting_lp_query(_deps: Deps, _env: Env, msg: vesting_base::msg::QueryMsg) -> StdResult<Binary> {
+    match msg {
+        vesting_base::msg::QueryMsg::HistoricalExtension {
+            msg:
+                vesting_base::msg::QueryMsgHistorical::UnclaimedAmountAtHeight {
+                    address: _,
+                    height: _,
+                },
+        } => to_json_binary(&Uint128::from(USER_UNCLAIMED_LP)),
+        vesting_base::msg::QueryMsg::HistoricalExtension {
+            msg: vesting_base::msg::QueryMsgHistorical::UnclaimedTotalAmountAtHeight { height: _ },
+        } => to_json_binary(&Uint128::from(TOTAL_UNCLAIMED_LP)),
+        _ => unreachable!(),
+    }
+}
+
+fn vesting_lp_contract() -> Box<dyn Contract<Empty>> {
+    let contract: ContractWrapper<
+        EmptyMsg,
+        EmptyMsg,
+        vesting_base::msg::QueryMsg,
+        ContractError,
+        ContractError,
+        cosmwasm_std::StdError,
+    > = ContractWrapper::new(
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        vesting_lp_query,
+    );
+    Box::new(contract)
+}
+
+const LP_TOTAL_SUPPLY: u64 = 10_000u64;
+const NTRN_IN_CL_POOL: u64 = 50_000u64;
+// a single block at which a large swap pushed the NTRN balance of the CL pool up
+const MANIPULATED_HEIGHT: u64 = 12_340u64;
+const NTRN_IN_MANIPULATED_CL_POOL: u64 = 500_000u64;
+const SUBDAO_IN_CL_POOL: u64 = 20_000u64;
+const LIQUIDITY_TOKEN: Item<Addr> = Item::new("liquidity_token");
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct MockClPoolInstantiateMsg {
+    liquidity_token: String,
+}
+
+fn cl_pool_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockClPoolInstantiateMsg,
+) -> Result<Response, ContractError> {
+    LIQUIDITY_TOKEN.save(deps.storage, &Addr::unchecked(msg.liquidity_token))?;
+    Ok(Response::new())
+}
+
+fn cl_pool_query(
+    deps: Deps,
+    env: Env,
+    msg: astroport_original::pair_concentrated::QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        astroport_original::pair_concentrated::QueryMsg::Pair {} => {
+            to_json_binary(&astroport_original::asset::PairInfo {
+                asset_infos: vec![
+                    astroport_original::asset::AssetInfo::NativeToken {
+                        denom: NTRN_DENOM.to_string(),
+                    },
+                    astroport_original::asset::AssetInfo::NativeToken {
+                        denom: SUBDAO_DENOM.to_string(),
+                    },
+                ],
+                contract_addr: env.contract.address,
+                liquidity_token: LIQUIDITY_TOKEN.load(deps.storage)?,
+                pair_type: astroport_original::factory::PairType::Custom(
+                    "concentrated".to_string(),
+                ),
+            })
+        }
+        astroport_original::pair_concentrated::QueryMsg::AssetBalanceAt {
+            asset_info: astroport_original::asset::AssetInfo::NativeToken { denom },
+            block_height,
+        } => to_json_binary(&Some(Uint128::from(match denom.as_str() {
+            SUBDAO_DENOM => SUBDAO_IN_CL_POOL,
+            _ if block_height.u64() == MANIPULATED_HEIGHT => NTRN_IN_MANIPULATED_CL_POOL,
+            _ => NTRN_IN_CL_POOL,
+        }))),
+        _ => unreachable!(),
+    }
+}
+
+fn cl_pool_contract() -> Box<dyn Contract<Empty>> {
+    let contract: ContractWrapper<
+        EmptyMsg,
+        MockClPoolInstantiateMsg,
+        astroport_original::pair_concentrated::QueryMsg,
+        ContractError,
+        ContractError,
+        cosmwasm_std::StdError,
+    > = ContractWrapper::new(
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        cl_pool_instantiate,
+        cl_pool_query,
+    );
+    Box::new(contract)
+}
+
+fn lp_token_query(
+    _deps: Deps,
+    _env: Env,
+    msg: astroport::xastro_token::QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        astroport::xastro_token::QueryMsg::TotalSupplyAt { block: _ } => {
+            to_json_binary(&Uint128::from(LP_TOTAL_SUPPLY))
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn lp_token_contract() -> Box<dyn Contract<Empty>> {
+    let contract: ContractWrapper<
+        EmptyMsg,
+        EmptyMsg,
+        astroport::xastro_token::QueryMsg,
+        ContractError,
+        ContractError,
+        cosmwasm_std::StdError,
+    > = ContractWrapper::new(
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        lp_token_query,
+    );
+    Box::new(contract)
+}
+
+struct MockPools {
+    atom: LpPool,
+    usdc: LpPool,
+    cl: LpPool,
+}
+
+fn instantiate_mock_pools(app: &mut App) -> MockPools {
+    let lockdrop = instantiate_mock_contract(app, lockdrop_contract());
+    let oracle = instantiate_mock_contract(app, oracle_contract());
+    let vesting_lp = instantiate_mock_contract(app, vesting_lp_contract());
+    let lp_token = instantiate_mock_contract(app, lp_token_contract());
+
+    let cl_pool_id = app.store_code(cl_pool_contract());
+    let cl_pool = app
+        .instantiate_contract(
+            cl_pool_id,
+            Addr::unchecked(DAO_ADDR),
+            &MockClPoolInstantiateMsg {
+                liquidity_token: lp_token.to_string(),
+            },
+            &[],
+            "cl pool",
+            None,
+        )
+        .unwrap();
+
+    MockPools {
+        atom: LpPool {
+            lp_source: LpSource::Lockdrop {
+                contract: lockdrop.clone(),
+                pool_type: PoolType::ATOM,
+            },
+            pair_kind: PairKind::XykTwap {
+                oracle_contract: oracle.clone(),
+            },
+        },
+        usdc: LpPool {
+            lp_source: LpSource::Lockdrop {
+                contract: lockdrop,
+                pool_type: PoolType::USDC,
+            },
+            pair_kind: PairKind::XykTwap {
+                oracle_contract: oracle,
+            },
+        },
+        cl: LpPool {
+            lp_source: LpSource::VestingLp {
+                contract: vesting_lp,
+            },
+            pair_kind: PairKind::ClPool {
+                pool_contract: cl_pool,
+                valuation: ClPoolValuation::default(),
+            },
+        },
+    }
+}
+
+fn instantiate_vault(app: &mut App, id: u64, msg: InstantiateMsg) -> anyhow::Result<Addr> {
+    app.instantiate_contract(id, Addr::unchecked(DAO_ADDR), &msg, &[], "vault", None)
+}
+
+fn update_config(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    description: Option<String>,
+    add_pools: Option<Vec<LpPool>>,
+    remove_pools: Option<Vec<LpSource>>,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::UpdateConfig {
+            owner: None,
+            name: None,
+            description,
+            add_pools,
+            remove_pools,
+            denom: None,
+        },
+        &[],
+    )
+}
+
+fn get_voting_power_at_height(
+    app: &mut App,
+    contract_addr: Addr,
+    address: String,
+    height: Option<u64>,
+) -> VotingPowerAtHeightResponse {
+    app.wrap()
+        .query_wasm_smart(
+            contract_addr,
+            &QueryMsg::VotingPowerAtHeight { address, height },
+        )
+        .unwrap()
+}
+
+fn get_total_power_at_height(
+    app: &mut App,
+    contract_addr: Addr,
+    height: Option<u64>,
+) -> TotalPowerAtHeightResponse {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::TotalPowerAtHeight { height })
+        .unwrap()
+}
+
+fn get_config(app: &mut App, contract_addr: Addr) -> Config {
+    app.wrap()
+        .query_wasm_smart(contract_addr, &QueryMsg::Config {})
+        .unwrap()
+}
+
+fn setup_vault(app: &mut App, pools: Vec<LpPool>) -> Addr {
+    let vault_id = app.store_code(vault_contract());
+    instantiate_vault(
+        app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            pools,
+            denom: NTRN_DENOM.to_string(),
+        },
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_instantiate() {
+    let mut app = mock_app();
+    let pools = instantiate_mock_pools(&mut app);
+    let addr = setup_vault(&mut app, vec![pools.atom.clone(), pools.cl.clone()]);
+
+    let dao: Addr = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::Dao {})
+        .unwrap();
+    assert_eq!(dao, Addr::unchecked(DAO_ADDR));
+
+    assert_eq!(
+        get_config(&mut app, addr),
+        Config {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: Addr::unchecked(DAO_ADDR),
+            pools: vec![pools.atom, pools.cl],
+            denom: NTRN_DENOM.to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_instantiate_invalid_pools() {
+    let mut app = mock_app();
+    let pools = instantiate_mock_pools(&mut app);
+    let vault_id = app.store_code(vault_contract());
+
+    let err = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            pools: vec![],
+            denom: NTRN_DENOM.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::NoPools {}, err.downcast().unwrap());
+
+    let err = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            pools: vec![pools.cl.clone(), pools.cl],
+            denom: NTRN_DENOM.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::DuplicatePool {}, err.downcast().unwrap());
+}
+
+#[test]
+fn test_bond_and_unbond_disabled() {
+    let mut app = mock_app();
+    let pools = instantiate_mock_pools(&mut app);
+    let addr = setup_vault(&mut app, vec![pools.atom]);
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            addr.clone(),
+            &ExecuteMsg::Bond {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::BondingDisabled {}, err.downcast().unwrap());
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(ADDR1),
+            addr,
+            &ExecuteMsg::Unbond {
+                amount: Uint128::new(100),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DirectUnbondingDisabled {},
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn test_update_config_unauthorized() {
+    let mut app = mock_app();
+    let pools = instantiate_mock_pools(&mut app);
+    let addr = setup_vault(&mut app, vec![pools.atom]);
+
+    // From ADDR2, so not owner
+    let err = update_config(
+        &mut app,
+        addr,
+        ADDR2,
+        Some(NEW_DESCRIPTION.to_string()),
+        Some(vec![pools.cl]),
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+}
+
+#[test]
+fn test_update_config_pools() {
+    let mut app = mock_app();
+    let pools = instantiate_mock_pools(&mut app);
+    let addr = setup_vault(&mut app, vec![pools.atom.clone(), pools.usdc.clone()]);
+
+    update_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        Some(NEW_DESCRIPTION.to_string()),
+        Some(vec![pools.cl.clone()]),
+        Some(vec![pools.atom.lp_source.clone()]),
+    )
+    .unwrap();
+    assert_eq!(
+        get_config(&mut app, addr.clone()),
+        Config {
+            name: NAME.to_string(),
+            description: NEW_DESCRIPTION.to_string(),
+            owner: Addr::unchecked(DAO_ADDR),
+            pools: vec![pools.usdc.clone(), pools.cl.clone()],
+            denom: NTRN_DENOM.to_string(),
+        }
+    );
+
+    let err = update_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        None,
+        Some(vec![pools.cl]),
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::DuplicatePool {}, err.downcast().unwrap());
+
+    let err = update_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        None,
+        None,
+        Some(vec![pools.atom.lp_source]),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::PoolNotFound {}, err.downcast().unwrap());
+
+    let err = update_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        None,
+        None,
+        Some(vec![pools.usdc.lp_source, pools.cl.lp_source]),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::NoPools {}, err.downcast().unwrap());
+
+    let err = update_config(&mut app, addr, DAO_ADDR, Some(String::new()), None, None).unwrap_err();
+    assert_eq!(
+        ContractError::DescriptionIsEmpty {},
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn test_voting_power_at_height() {
+    let mut app = mock_app();
+    let pools = instantiate_mock_pools(&mut app);
+    let addr = setup_vault(&mut app, vec![pools.atom, pools.usdc]);
+
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    // (USER_ATOM_LOCKUP_AT_HEIGHT / sqrt(NTRN_TWAP)) + (USER_USDC_LOCKUP_AT_HEIGHT / sqrt(NTRN_TWAP))
+    assert_eq!(resp.power, Uint128::from(500_000u128 + 1_000_000u128));
+
+    // A third pool is added without a new contract
+    let update_height = app.block_info().height;
+    update_config(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        None,
+        Some(vec![pools.cl]),
+        None,
+    )
+    .unwrap();
+    app.update_block(next_block);
+
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    // USER_UNCLAIMED_LP * NTRN_IN_CL_POOL / LP_TOTAL_SUPPLY is added
+    assert_eq!(
+        resp.power,
+        Uint128::from(500_000u128 + 1_000_000u128 + 5_000u128)
+    );
+
+    // the power at the heights before the update stays the same
+    let resp = get_voting_power_at_height(
+        &mut app,
+        addr.clone(),
+        ADDR1.to_string(),
+        Some(update_height),
+    );
+    assert_eq!(resp.power, Uint128::from(500_000u128 + 1_000_000u128));
+    let resp = get_total_power_at_height(&mut app, addr, Some(update_height));
+    assert_eq!(resp.power, Uint128::from(1_500_000u128 + 2_000_000u128));
+}
+
+#[test]
+fn test_total_power_at_height() {
+    let mut app = mock_app();
+    let pools = instantiate_mock_pools(&mut app);
+    let addr = setup_vault(&mut app, vec![pools.atom, pools.usdc, pools.cl]);
+
+    let resp = get_total_power_at_height(&mut app, addr, None);
+    // (TOTAL_ATOM_LOCKUP_AT_HEIGHT / sqrt(NTRN_TWAP)) + (TOTAL_USDC_LOCKUP_AT_HEIGHT / sqrt(NTRN_TWAP))
+    // + TOTAL_UNCLAIMED_LP * NTRN_IN_CL_POOL / LP_TOTAL_SUPPLY
+    assert_eq!(
+        resp.power,
+        Uint128::from(1_500_000u128 + 2_000_000u128 + 20_000u128)
+    );
+}
+
+#[test]
+fn test_cl_pool_valuation() {
+    let mut app = mock_app();
+    let pools = instantiate_mock_pools(&mut app);
+    let spot_addr = setup_vault(&mut app, vec![pools.cl.clone()]);
+
+    let PairKind::ClPool { pool_contract, .. } = pools.cl.pair_kind.clone() else {
+        unreachable!()
+    };
+    let median_addr = setup_vault(
+        &mut app,
+        vec![LpPool {
+            pair_kind: PairKind::ClPool {
+                pool_contract,
+                valuation: ClPoolValuation::Median {
+                    window: 10,
+                    samples: 5,
+                },
+            },
+            ..pools.cl
+        }],
+    );
+
+    // USER_UNCLAIMED_LP * NTRN_IN_MANIPULATED_CL_POOL / LP_TOTAL_SUPPLY
+    let resp = get_voting_power_at_height(
+        &mut app,
+        spot_addr.clone(),
+        ADDR1.to_string(),
+        Some(MANIPULATED_HEIGHT),
+    );
+    assert_eq!(resp.power, Uint128::from(50_000u128));
+    let resp = get_total_power_at_height(&mut app, spot_addr, Some(MANIPULATED_HEIGHT));
+    assert_eq!(resp.power, Uint128::from(200_000u128));
+
+    // the manipulated block is a single sample out of five, the median ignores it:
+    // USER_UNCLAIMED_LP * NTRN_IN_CL_POOL / LP_TOTAL_SUPPLY
+    let resp = get_voting_power_at_height(
+        &mut app,
+        median_addr.clone(),
+        ADDR1.to_string(),
+        Some(MANIPULATED_HEIGHT),
+    );
+    assert_eq!(resp.power, Uint128::from(5_000u128));
+    let resp = get_total_power_at_height(&mut app, median_addr, Some(MANIPULATED_HEIGHT));
+    assert_eq!(resp.power, Uint128::from(20_000u128));
+}
+
+#[test]
+fn test_power_at_height_non_default_denom() {
+    let mut app = mock_app();
+    let pools = instantiate_mock_pools(&mut app);
+    let vault_id = app.store_code(vault_contract());
+
+    let err = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            pools: vec![pools.atom.clone()],
+            denom: String::new(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::DenomIsEmpty {}, err.downcast().unwrap());
+
+    // the pairs must hold the denom
+    let err = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            pools: vec![pools.cl.clone()],
+            denom: String::from("uatom"),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::DenomNotInPair { .. }
+    ));
+
+    // oracle failures are not mistaken for a missing denom
+    let err = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            pools: vec![LpPool {
+                pair_kind: PairKind::XykTwap {
+                    oracle_contract: Addr::unchecked("not_an_oracle"),
+                },
+                ..pools.atom.clone()
+            }],
+            denom: SUBDAO_DENOM.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Std(_)
+    ));
+
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            pools: vec![pools.atom, pools.usdc, pools.cl],
+            denom: SUBDAO_DENOM.to_string(),
+        },
+    )
+    .unwrap();
+
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    // (USER_ATOM_LOCKUP_AT_HEIGHT / sqrt(SUBDAO_TWAP)) + (USER_USDC_LOCKUP_AT_HEIGHT / sqrt(SUBDAO_TWAP))
+    // + USER_UNCLAIMED_LP * SUBDAO_IN_CL_POOL / LP_TOTAL_SUPPLY
+    assert_eq!(
+        resp.power,
+        Uint128::from(250_000u128 + 500_000u128 + 2_000u128)
+    );
+    let resp = get_total_power_at_height(&mut app, addr.clone(), None);
+    assert_eq!(
+        resp.power,
+        Uint128::from(750_000u128 + 1_000_000u128 + 8_000u128)
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(DAO_ADDR),
+            addr.clone(),
+            &ExecuteMsg::UpdateConfig {
+                owner: None,
+                name: None,
+                description: None,
+                add_pools: None,
+                remove_pools: None,
+                denom: Some(String::from("uatom")),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::DenomNotInPair { .. }
+    ));
+
+    // switching back to NTRN gives the same power as a vault instantiated with the default denom
+    let update_height = app.block_info().height;
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        addr.clone(),
+        &ExecuteMsg::UpdateConfig {
+            owner: None,
+            name: None,
+            description: None,
+            add_pools: None,
+            remove_pools: None,
+            denom: Some(NTRN_DENOM.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    let resp = get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None);
+    assert_eq!(
+        resp.power,
+        Uint128::from(500_000u128 + 1_000_000u128 + 5_000u128)
+    );
+    // the past heights keep being valued in SUBDAO
+    let resp = get_voting_power_at_height(&mut app, addr, ADDR1.to_string(), Some(update_height));
+    assert_eq!(
+        resp.power,
+        Uint128::from(250_000u128 + 500_000u128 + 2_000u128)
+    );
+}
+
+#[test]
+fn test_query_info() {
+    let mut app = mock_app();
+    let pools = instantiate_mock_pools(&mut app);
+    let addr = setup_vault(&mut app, vec![pools.atom]);
+
+    let resp: InfoResponse = app
+        .wrap()
+        .query_wasm_smart(addr, &QueryMsg::Info {})
+        .unwrap();
+    assert_eq!(resp.info.contract, "crates.io:neutron-lp-vault");
+}
+
+#[test]
+pub fn test_migrate_update_version() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, "my-contract", "old-version").unwrap();
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    let version = cw2::get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, CONTRACT_VERSION);
+    assert_eq!(version.contract, CONTRACT_NAME);
+}
@@ -1,19 +1,29 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, to_json_binary, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
-    StdResult, Uint128,
+    coin, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Order, Response, StdResult, Storage, Uint128,
 };
 use cw2::set_contract_version;
-use cw_utils::must_pay;
+use cw_controllers::{Claim, ClaimsResponse};
+use cw_storage_plus::Bound;
+use cw_utils::{one_coin, Duration};
 use cwd_interface::voting::{
     BondingStatusResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
 use cwd_voting::vault::{BonderBalanceResponse, ListBondersResponse};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{Config, BONDED_BALANCES, BONDED_TOTAL, CONFIG, DAO};
+use crate::msg::{
+    DelegationResponse, DenomRate, ExecuteMsg, InstantiateMsg, LockResponse, MigrateMsg,
+    PendingBondTransfer, QueryMsg,
+};
+use crate::state::{
+    Config, TotalLock, BONDED_BALANCES, BONDED_TOTAL, CLAIMS, CONFIG, DAO, DELEGATED_BALANCES,
+    DELEGATED_LOCKS, DELEGATED_LOCK_EXPIRIES, DELEGATIONS, DENOM_BONDED_BALANCES,
+    DENOM_BONDED_TOTALS, DENOM_CLAIMS, DENOM_RATES, LOCK_ENDS, LOCK_EXPIRIES, MAX_CLAIMS,
+    PENDING_BOND_TRANSFERS, TOTAL_LOCK, UNBONDING_BALANCES,
+};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:neutron-voting-vault";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -34,6 +44,8 @@ pub fn instantiate(
         description: msg.description,
         owner,
         denom: msg.denom,
+        unbonding_period: msg.unbonding_period,
+        max_lock_duration: msg.max_lock_duration,
     };
     config.validate()?;
     CONFIG.save(deps.storage, &config)?;
@@ -43,7 +55,11 @@ pub fn instantiate(
         .add_attribute("action", "instantiate")
         .add_attribute("name", config.name)
         .add_attribute("description", config.description)
-        .add_attribute("owner", config.owner))
+        .add_attribute("owner", config.owner)
+        .add_attribute(
+            "unbonding_period",
+            format_unbonding_period(config.unbonding_period),
+        ))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -54,35 +70,116 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Bond {} => execute_bond(deps, env, info),
-        ExecuteMsg::Unbond { amount } => execute_unbond(deps, env, info, amount),
+        ExecuteMsg::Bond { lock } => execute_bond(deps, env, info, lock),
+        ExecuteMsg::Unbond { amount, denom } => execute_unbond(deps, env, info, amount, denom),
+        ExecuteMsg::ExtendLock { lock } => execute_extend_lock(deps, env, info, lock),
+        ExecuteMsg::Withdraw {} => execute_withdraw(deps, env, info),
         ExecuteMsg::UpdateConfig {
             owner,
             name,
             description,
-        } => execute_update_config(deps, info, owner, name, description),
+            unbonding_period,
+            clear_unbonding_period,
+        } => execute_update_config(
+            deps,
+            info,
+            owner,
+            name,
+            description,
+            unbonding_period,
+            clear_unbonding_period,
+        ),
+        ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::TransferBond {
+            recipient,
+            amount,
+            denom,
+            require_acceptance,
+        } => execute_transfer_bond(
+            deps,
+            env,
+            info,
+            recipient,
+            amount,
+            denom,
+            require_acceptance,
+        ),
+        ExecuteMsg::AcceptBondTransfer { sender } => {
+            execute_accept_bond_transfer(deps, env, info, sender)
+        }
+        ExecuteMsg::CancelBondTransfer { recipient } => {
+            execute_cancel_bond_transfer(deps, info, recipient)
+        }
+        ExecuteMsg::Delegate { to } => execute_delegate(deps, env, info, to),
+        ExecuteMsg::Undelegate {} => execute_undelegate(deps, env, info),
+        ExecuteMsg::SetDenomRate { denom, rate } => {
+            execute_set_denom_rate(deps, env, info, denom, rate)
+        }
     }
 }
 
-pub fn execute_bond(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+pub fn execute_bond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lock: Option<u64>,
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let amount = must_pay(&info, &config.denom)?;
+    let Coin { denom, amount } = one_coin(&info)?;
+    if denom != config.denom && DENOM_RATES.may_load(deps.storage, &denom)?.is_none() {
+        return Err(ContractError::UnsupportedDenom { denom });
+    }
 
-    BONDED_BALANCES.update(
-        deps.storage,
-        &info.sender,
-        env.block.height,
-        |balance| -> StdResult<Uint128> { Ok(balance.unwrap_or_default().checked_add(amount)?) },
-    )?;
-    BONDED_TOTAL.update(
+    let mut response = Response::new();
+    match config.max_lock_duration {
+        Some(max_lock_duration) => {
+            let height = env.block.height;
+            let balance = BONDED_BALANCES
+                .may_load(deps.storage, &info.sender)?
+                .unwrap_or_default();
+            let end = LOCK_ENDS
+                .may_load(deps.storage, &info.sender)?
+                .unwrap_or_default();
+            let new_end = match lock {
+                Some(lock) => {
+                    let new_end = lock_end(height, lock, max_lock_duration)?;
+                    if new_end < end {
+                        return Err(ContractError::LockCannotBeShortened { end });
+                    }
+                    new_end
+                }
+                None if end > height => end,
+                None => return Err(ContractError::LockRequired {}),
+            };
+            update_lock(
+                deps.storage,
+                height,
+                &info.sender,
+                (balance, end),
+                (balance.checked_add(amount)?, new_end),
+            )?;
+            response = response.add_attribute("lock_end", new_end.to_string());
+        }
+        None => {
+            if lock.is_some() {
+                return Err(ContractError::VoteEscrowDisabled {});
+            }
+        }
+    }
+
+    add_bonded(
         deps.storage,
+        &config,
         env.block.height,
-        |total| -> StdResult<Uint128> { Ok(total.unwrap_or_default().checked_add(amount)?) },
+        &info.sender,
+        &denom,
+        amount,
     )?;
 
-    Ok(Response::new()
+    Ok(response
         .add_attribute("action", "bond")
         .add_attribute("amount", amount.to_string())
+        .add_attribute("denom", denom)
         .add_attribute("from", info.sender))
 }
 
@@ -91,41 +188,693 @@ pub fn execute_unbond(
     env: Env,
     info: MessageInfo,
     amount: Uint128,
+    denom: Option<String>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    if config.max_lock_duration.is_some() {
+        return Err(ContractError::VoteEscrowEnabled {});
+    }
 
-    BONDED_BALANCES.update(
+    let denom = denom.unwrap_or_else(|| config.denom.clone());
+    sub_bonded(
         deps.storage,
-        &info.sender,
+        &config,
         env.block.height,
-        |balance| -> Result<Uint128, ContractError> {
-            balance
-                .unwrap_or_default()
-                .checked_sub(amount)
-                .map_err(|_e| ContractError::InvalidUnbondAmount {})
-        },
+        &info.sender,
+        &denom,
+        amount,
+    )?;
+
+    release_tokens(
+        deps,
+        &env,
+        config,
+        info.sender,
+        coin(amount.u128(), denom),
+        "unbond",
+    )
+}
+
+pub fn execute_extend_lock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lock: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let max_lock_duration = config
+        .max_lock_duration
+        .ok_or(ContractError::VoteEscrowDisabled {})?;
+
+    let height = env.block.height;
+    let balance = BONDED_BALANCES
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if balance.is_zero() {
+        return Err(ContractError::NoBondedTokens {});
+    }
+    let end = LOCK_ENDS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    let new_end = lock_end(height, lock, max_lock_duration)?;
+    if new_end <= end {
+        return Err(ContractError::LockCannotBeShortened { end });
+    }
+    update_lock(
+        deps.storage,
+        height,
+        &info.sender,
+        (balance, end),
+        (balance, new_end),
     )?;
-    BONDED_TOTAL.update(
+
+    Ok(Response::new()
+        .add_attribute("action", "extend_lock")
+        .add_attribute("from", info.sender)
+        .add_attribute("lock_end", new_end.to_string()))
+}
+
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.max_lock_duration.is_none() {
+        return Err(ContractError::VoteEscrowDisabled {});
+    }
+
+    let height = env.block.height;
+    let balance = BONDED_BALANCES
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if balance.is_zero() {
+        return Err(ContractError::NoBondedTokens {});
+    }
+    let end = LOCK_ENDS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if end > height {
+        return Err(ContractError::LockNotExpired { end });
+    }
+    update_lock(
         deps.storage,
-        env.block.height,
-        |total| -> Result<Uint128, ContractError> {
-            total
-                .unwrap_or_default()
-                .checked_sub(amount)
-                .map_err(|_e| ContractError::InvalidUnbondAmount {})
-        },
+        height,
+        &info.sender,
+        (balance, end),
+        (Uint128::zero(), 0),
+    )?;
+
+    sub_bonded(
+        deps.storage,
+        &config,
+        height,
+        &info.sender,
+        &config.denom,
+        balance,
     )?;
 
+    let denom = config.denom.clone();
+    release_tokens(
+        deps,
+        &env,
+        config,
+        info.sender,
+        coin(balance.u128(), denom),
+        "withdraw",
+    )
+}
+
+/// Sends the tokens back to the bonder, or keeps them in a claim if the unbonding period is set.
+fn release_tokens(
+    deps: DepsMut,
+    env: &Env,
+    config: Config,
+    sender: Addr,
+    tokens: Coin,
+    action: &str,
+) -> Result<Response, ContractError> {
+    match config.unbonding_period {
+        Some(unbonding_period) => {
+            let release_at = unbonding_period.after(&env.block);
+            if tokens.denom == config.denom {
+                if CLAIMS.query_claims(deps.as_ref(), &sender)?.claims.len() >= MAX_CLAIMS {
+                    return Err(ContractError::TooManyClaims {});
+                }
+                CLAIMS.create_claim(deps.storage, &sender, tokens.amount, release_at)?;
+            } else {
+                let mut claims = DENOM_CLAIMS
+                    .may_load(deps.storage, (&sender, &tokens.denom))?
+                    .unwrap_or_default();
+                if claims.len() >= MAX_CLAIMS {
+                    return Err(ContractError::TooManyClaims {});
+                }
+                claims.push(Claim::new(tokens.amount.u128(), release_at));
+                DENOM_CLAIMS.save(deps.storage, (&sender, &tokens.denom), &claims)?;
+            }
+            UNBONDING_BALANCES.update(
+                deps.storage,
+                (&sender, &tokens.denom),
+                env.block.height,
+                |balance| -> StdResult<Uint128> {
+                    Ok(balance.unwrap_or_default().checked_add(tokens.amount)?)
+                },
+            )?;
+
+            Ok(Response::new()
+                .add_attribute("action", action)
+                .add_attribute("from", sender)
+                .add_attribute("amount", tokens.amount)
+                .add_attribute("denom", tokens.denom)
+                .add_attribute("claim_duration", unbonding_period.to_string()))
+        }
+        None => {
+            let msg = CosmosMsg::Bank(BankMsg::Send {
+                to_address: sender.to_string(),
+                amount: vec![tokens.clone()],
+            });
+            Ok(Response::new()
+                .add_message(msg)
+                .add_attribute("action", action)
+                .add_attribute("from", sender)
+                .add_attribute("amount", tokens.amount)
+                .add_attribute("denom", tokens.denom)
+                .add_attribute("claim_duration", "None"))
+        }
+    }
+}
+
+/// Adds the amount to the bonded balance of the address in the denom.
+fn add_bonded(
+    storage: &mut dyn Storage,
+    config: &Config,
+    height: u64,
+    address: &Addr,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<()> {
+    if denom == config.denom {
+        BONDED_BALANCES.update(storage, address, height, |balance| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_add(amount)?)
+        })?;
+        BONDED_TOTAL.update(storage, height, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().checked_add(amount)?)
+        })?;
+    } else {
+        DENOM_BONDED_BALANCES.update(
+            storage,
+            (address, denom),
+            height,
+            |balance| -> StdResult<_> { Ok(balance.unwrap_or_default().checked_add(amount)?) },
+        )?;
+        DENOM_BONDED_TOTALS.update(storage, denom, height, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().checked_add(amount)?)
+        })?;
+        // `BONDED_BALANCES` serves as the list of bonders, so everyone must have an entry there
+        if BONDED_BALANCES.may_load(storage, address)?.is_none() {
+            BONDED_BALANCES.save(storage, address, &Uint128::zero(), height)?;
+        }
+    }
+    if let Some(delegate) = DELEGATIONS.may_load(storage, address)? {
+        DELEGATED_BALANCES.update(
+            storage,
+            (&delegate, denom),
+            height,
+            |balance| -> StdResult<_> { Ok(balance.unwrap_or_default().checked_add(amount)?) },
+        )?;
+    }
+    Ok(())
+}
+
+/// Subtracts the amount from the bonded balance of the address in the denom.
+fn sub_bonded(
+    storage: &mut dyn Storage,
+    config: &Config,
+    height: u64,
+    address: &Addr,
+    denom: &str,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let sub = |value: Option<Uint128>| -> Result<Uint128, ContractError> {
+        value
+            .unwrap_or_default()
+            .checked_sub(amount)
+            .map_err(|_e| ContractError::InvalidUnbondAmount {})
+    };
+    if denom == config.denom {
+        BONDED_BALANCES.update(storage, address, height, sub)?;
+        BONDED_TOTAL.update(storage, height, sub)?;
+    } else {
+        DENOM_BONDED_BALANCES.update(storage, (address, denom), height, sub)?;
+        DENOM_BONDED_TOTALS.update(storage, denom, height, sub)?;
+    }
+    if let Some(delegate) = DELEGATIONS.may_load(storage, address)? {
+        DELEGATED_BALANCES.update(storage, (&delegate, denom), height, sub)?;
+    }
+    Ok(())
+}
+
+fn lock_end(height: u64, lock: u64, max_lock_duration: u64) -> Result<u64, ContractError> {
+    if lock == 0 || lock > max_lock_duration {
+        return Err(ContractError::InvalidLockDuration {
+            max: max_lock_duration,
+        });
+    }
+    Ok(height + lock)
+}
+
+/// Returns the locks which expire after `after` and no later than `until`, either all of them
+/// or the ones delegated to the delegate.
+fn load_expiries(
+    storage: &dyn Storage,
+    delegate: Option<&Addr>,
+    after: u64,
+    until: u64,
+) -> StdResult<Vec<(u64, Uint128)>> {
+    if after >= until {
+        return Ok(vec![]);
+    }
+    let (min, max) = (Some(Bound::exclusive(after)), Some(Bound::inclusive(until)));
+    match delegate {
+        Some(delegate) => DELEGATED_LOCK_EXPIRIES
+            .prefix(delegate)
+            .range(storage, min, max, Order::Ascending)
+            .collect(),
+        None => LOCK_EXPIRIES
+            .range(storage, min, max, Order::Ascending)
+            .collect(),
+    }
+}
+
+/// Replaces the `(old_amount, old_end)` lock with the `(new_amount, new_end)` one in the sum
+/// of locks, either the total one or the one delegated to the delegate. Expired locks are not a
+/// part of the sum anymore, so only the active ones are subtracted from it.
+fn update_lock_sum(
+    storage: &mut dyn Storage,
+    height: u64,
+    delegate: Option<&Addr>,
+    (old_amount, old_end): (Uint128, u64),
+    (new_amount, new_end): (Uint128, u64),
+) -> StdResult<()> {
+    let mut sum = match delegate {
+        Some(delegate) => DELEGATED_LOCKS.may_load(storage, delegate)?,
+        None => TOTAL_LOCK.may_load(storage)?,
+    }
+    .unwrap_or_default();
+    let expiries = load_expiries(storage, delegate, sum.height, height)?;
+    sum.expire(expiries, height)?;
+
+    let mut update_expiry = |end: u64, amount: Uint128, add: bool| -> StdResult<()> {
+        let action = |expiring: Option<Uint128>| -> StdResult<Uint128> {
+            let expiring = expiring.unwrap_or_default();
+            Ok(if add {
+                expiring.checked_add(amount)?
+            } else {
+                expiring.checked_sub(amount)?
+            })
+        };
+        match delegate {
+            Some(delegate) => {
+                DELEGATED_LOCK_EXPIRIES.update(storage, (delegate, end), action)?;
+            }
+            None => {
+                LOCK_EXPIRIES.update(storage, end, action)?;
+            }
+        }
+        Ok(())
+    };
+    if old_end > height && !old_amount.is_zero() {
+        sum.sub(old_amount, old_end)?;
+        update_expiry(old_end, old_amount, false)?;
+    }
+    if new_end > height && !new_amount.is_zero() {
+        sum.add(new_amount, new_end)?;
+        update_expiry(new_end, new_amount, true)?;
+    }
+
+    match delegate {
+        Some(delegate) => DELEGATED_LOCKS.save(storage, delegate, &sum, height),
+        None => TOTAL_LOCK.save(storage, &sum, height),
+    }
+}
+
+/// Replaces the `(amount, end)` lock of the address and updates the total lock and the lock
+/// delegated to the address' delegate accordingly.
+fn update_lock(
+    storage: &mut dyn Storage,
+    height: u64,
+    address: &Addr,
+    old: (Uint128, u64),
+    new: (Uint128, u64),
+) -> StdResult<()> {
+    update_lock_sum(storage, height, None, old, new)?;
+    if let Some(delegate) = DELEGATIONS.may_load(storage, address)? {
+        update_lock_sum(storage, height, Some(&delegate), old, new)?;
+    }
+
+    let (new_amount, new_end) = new;
+    if new_amount.is_zero() {
+        LOCK_ENDS.remove(storage, address, height)?;
+    } else {
+        LOCK_ENDS.save(storage, address, &new_end, height)?;
+    }
+    Ok(())
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut release = vec![];
+    let amount = CLAIMS.claim_tokens(deps.storage, &info.sender, &env.block, None)?;
+    if !amount.is_zero() {
+        release.push(coin(amount.u128(), config.denom.clone()));
+    }
+    for denom in get_denoms(deps.storage, &config)? {
+        let claims = DENOM_CLAIMS
+            .may_load(deps.storage, (&info.sender, &denom))?
+            .unwrap_or_default();
+        let (mature, waiting): (Vec<Claim>, Vec<Claim>) = claims
+            .into_iter()
+            .partition(|claim| claim.release_at.is_expired(&env.block));
+        let amount: Uint128 = mature.iter().map(|claim| claim.amount).sum();
+        if !amount.is_zero() {
+            DENOM_CLAIMS.save(deps.storage, (&info.sender, &denom), &waiting)?;
+            release.push(coin(amount.u128(), denom));
+        }
+    }
+    if release.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    release.sort_by(|a, b| a.denom.cmp(&b.denom));
+    for released in &release {
+        UNBONDING_BALANCES.update(
+            deps.storage,
+            (&info.sender, &released.denom),
+            env.block.height,
+            |balance| -> StdResult<Uint128> {
+                Ok(balance.unwrap_or_default().checked_sub(released.amount)?)
+            },
+        )?;
+    }
+
+    let amount = release
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
     let msg = CosmosMsg::Bank(BankMsg::Send {
         to_address: info.sender.to_string(),
-        amount: coins(amount.u128(), config.denom),
+        amount: release,
     });
     Ok(Response::new()
         .add_message(msg)
-        .add_attribute("action", "unbond")
+        .add_attribute("action", "claim")
         .add_attribute("from", info.sender)
-        .add_attribute("amount", amount)
-        .add_attribute("claim_duration", "None"))
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_transfer_bond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+    denom: Option<String>,
+    require_acceptance: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    if recipient == info.sender {
+        return Err(ContractError::BondTransferToSelf {});
+    }
+    let tokens = coin(amount.u128(), denom.unwrap_or_else(|| config.denom.clone()));
+
+    let response = Response::new()
+        .add_attribute("action", "transfer_bond")
+        .add_attribute("from", info.sender.clone())
+        .add_attribute("to", recipient.clone())
+        .add_attribute("amount", tokens.to_string());
+
+    if require_acceptance {
+        if amount.is_zero() {
+            return Err(ContractError::InvalidTransferAmount {});
+        }
+        PENDING_BOND_TRANSFERS.save(deps.storage, (&recipient, &info.sender), &tokens)?;
+        return Ok(response.add_attribute("pending", "true"));
+    }
+
+    move_bond(deps, &env, &config, &info.sender, &recipient, tokens, false)?;
+    Ok(response.add_attribute("pending", "false"))
+}
+
+pub fn execute_accept_bond_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let sender = deps.api.addr_validate(&sender)?;
+    let tokens = PENDING_BOND_TRANSFERS
+        .may_load(deps.storage, (&info.sender, &sender))?
+        .ok_or_else(|| ContractError::NoPendingBondTransfer {
+            sender: sender.to_string(),
+        })?;
+    PENDING_BOND_TRANSFERS.remove(deps.storage, (&info.sender, &sender));
+
+    let amount = tokens.to_string();
+    move_bond(deps, &env, &config, &sender, &info.sender, tokens, true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_bond_transfer")
+        .add_attribute("from", sender)
+        .add_attribute("to", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_cancel_bond_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    if !PENDING_BOND_TRANSFERS.has(deps.storage, (&recipient, &info.sender)) {
+        return Err(ContractError::NoPendingBondTransfer {
+            sender: info.sender.to_string(),
+        });
+    }
+    PENDING_BOND_TRANSFERS.remove(deps.storage, (&recipient, &info.sender));
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_bond_transfer")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", recipient))
+}
+
+/// Moves bonded tokens between the addresses at the current height. In the vote-escrow mode
+/// the recipient's lock is extended to the end of the sender's one, so the moved tokens stay
+/// locked for at least as long as they were. Extending the lock of already bonded tokens needs
+/// the recipient's acceptance, so nobody can lock someone else's tokens by sending a few.
+fn move_bond(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    from: &Addr,
+    to: &Addr,
+    tokens: Coin,
+    accepted: bool,
+) -> Result<(), ContractError> {
+    let height = env.block.height;
+    let Coin { denom, amount } = tokens;
+    let from_balance = if denom == config.denom {
+        BONDED_BALANCES.may_load(deps.storage, from)?
+    } else {
+        DENOM_BONDED_BALANCES.may_load(deps.storage, (from, &denom))?
+    }
+    .unwrap_or_default();
+    if amount.is_zero() || amount > from_balance {
+        return Err(ContractError::InvalidTransferAmount {});
+    }
+
+    if config.max_lock_duration.is_some() {
+        let from_end = LOCK_ENDS.may_load(deps.storage, from)?.unwrap_or_default();
+        let to_balance = BONDED_BALANCES
+            .may_load(deps.storage, to)?
+            .unwrap_or_default();
+        let to_end = LOCK_ENDS.may_load(deps.storage, to)?.unwrap_or_default();
+        if !accepted && !to_balance.is_zero() && from_end > to_end {
+            return Err(ContractError::LockExtensionNotAccepted {});
+        }
+        update_lock(
+            deps.storage,
+            height,
+            from,
+            (from_balance, from_end),
+            (from_balance - amount, from_end),
+        )?;
+        update_lock(
+            deps.storage,
+            height,
+            to,
+            (to_balance, to_end),
+            (to_balance.checked_add(amount)?, to_end.max(from_end)),
+        )?;
+    }
+
+    sub_bonded(deps.storage, config, height, from, &denom, amount)?;
+    add_bonded(deps.storage, config, height, to, &denom, amount)?;
+    Ok(())
+}
+
+pub fn execute_delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    to: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let delegate = deps.api.addr_validate(&to)?;
+    if delegate == info.sender {
+        return Err(ContractError::DelegationToSelf {});
+    }
+    let height = env.block.height;
+
+    if let Some(current_delegate) = DELEGATIONS.may_load(deps.storage, &info.sender)? {
+        move_delegated_power(
+            deps.storage,
+            &config,
+            height,
+            &info.sender,
+            &current_delegate,
+            false,
+        )?;
+    }
+    DELEGATIONS.save(deps.storage, &info.sender, &delegate, height)?;
+    move_delegated_power(deps.storage, &config, height, &info.sender, &delegate, true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "delegate")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", delegate))
+}
+
+pub fn execute_undelegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let delegate = DELEGATIONS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NotDelegating {})?;
+    let height = env.block.height;
+
+    move_delegated_power(
+        deps.storage,
+        &config,
+        height,
+        &info.sender,
+        &delegate,
+        false,
+    )?;
+    DELEGATIONS.remove(deps.storage, &info.sender, height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "undelegate")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", delegate))
+}
+
+/// Adds the delegator's bonded balances and lock to the ones delegated to the delegate, or
+/// removes them from there.
+fn move_delegated_power(
+    storage: &mut dyn Storage,
+    config: &Config,
+    height: u64,
+    delegator: &Addr,
+    delegate: &Addr,
+    add: bool,
+) -> StdResult<()> {
+    let mut balances = vec![(
+        config.denom.clone(),
+        BONDED_BALANCES
+            .may_load(storage, delegator)?
+            .unwrap_or_default(),
+    )];
+    for denom in get_denoms(storage, config)? {
+        let balance = DENOM_BONDED_BALANCES
+            .may_load(storage, (delegator, &denom))?
+            .unwrap_or_default();
+        balances.push((denom, balance));
+    }
+    for (denom, balance) in balances {
+        if balance.is_zero() {
+            continue;
+        }
+        DELEGATED_BALANCES.update(
+            storage,
+            (delegate, &denom),
+            height,
+            |delegated| -> StdResult<_> {
+                let delegated = delegated.unwrap_or_default();
+                Ok(if add {
+                    delegated.checked_add(balance)?
+                } else {
+                    delegated.checked_sub(balance)?
+                })
+            },
+        )?;
+    }
+
+    if config.max_lock_duration.is_some() {
+        let lock = (
+            BONDED_BALANCES
+                .may_load(storage, delegator)?
+                .unwrap_or_default(),
+            LOCK_ENDS.may_load(storage, delegator)?.unwrap_or_default(),
+        );
+        let no_lock = (Uint128::zero(), 0);
+        let (old, new) = if add {
+            (no_lock, lock)
+        } else {
+            (lock, no_lock)
+        };
+        update_lock_sum(storage, height, Some(delegate), old, new)?;
+    }
+    Ok(())
+}
+
+pub fn execute_set_denom_rate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+    rate: Decimal,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.max_lock_duration.is_some() {
+        return Err(ContractError::MultiDenomInVoteEscrowMode {});
+    }
+    if denom.is_empty() {
+        return Err(ContractError::DenomIsEmpty {});
+    }
+    if rate.is_zero() {
+        return Err(ContractError::ZeroDenomRate {});
+    }
+
+    DENOM_RATES.save(deps.storage, &denom, &rate, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_denom_rate")
+        .add_attribute("denom", denom)
+        .add_attribute("rate", rate.to_string()))
 }
 
 pub fn execute_update_config(
@@ -134,6 +883,8 @@ pub fn execute_update_config(
     new_owner: String,
     new_name: String,
     new_description: String,
+    new_unbonding_period: Option<Duration>,
+    clear_unbonding_period: Option<bool>,
 ) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
     if info.sender != config.owner {
@@ -145,13 +896,34 @@ pub fn execute_update_config(
     config.owner = new_owner;
     config.name = new_name;
     config.description = new_description;
+
+    if clear_unbonding_period == Some(true) {
+        if new_unbonding_period.is_some() {
+            return Err(ContractError::ConflictingUnbondingPeriodUpdate {});
+        }
+        config.unbonding_period = None;
+    }
+
+    if let Some(unbonding_period) = new_unbonding_period {
+        config.unbonding_period = Some(unbonding_period);
+    }
     config.validate()?;
     CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new()
         .add_attribute("action", "update_config")
         .add_attribute("description", config.description)
-        .add_attribute("owner", config.owner))
+        .add_attribute("owner", config.owner)
+        .add_attribute(
+            "unbonding_period",
+            format_unbonding_period(config.unbonding_period),
+        ))
+}
+
+fn format_unbonding_period(unbonding_period: Option<Duration>) -> String {
+    unbonding_period
+        .map(|period| period.to_string())
+        .unwrap_or_else(|| String::from("None"))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -174,6 +946,13 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::BondingStatus { height, address } => {
             to_json_binary(&query_bonding_status(deps, env, height, address)?)
         }
+        QueryMsg::Claims { address, denom } => to_json_binary(&query_claims(deps, address, denom)?),
+        QueryMsg::DenomRates {} => to_json_binary(&query_denom_rates(deps)?),
+        QueryMsg::PendingBondTransfers { recipient } => {
+            to_json_binary(&query_pending_bond_transfers(deps, recipient)?)
+        }
+        QueryMsg::Lock { address } => to_json_binary(&query_lock(deps, address)?),
+        QueryMsg::Delegation { address } => to_json_binary(&query_delegation(deps, address)?),
     }
 }
 
@@ -185,9 +964,43 @@ pub fn query_voting_power_at_height(
 ) -> StdResult<VotingPowerAtHeightResponse> {
     let height = height.unwrap_or(env.block.height);
     let address = deps.api.addr_validate(&address)?;
-    let power = BONDED_BALANCES
+    let config = CONFIG.load(deps.storage)?;
+    // delegators' own power goes to their delegates
+    let delegating = DELEGATIONS
         .may_load_at_height(deps.storage, &address, height)?
-        .unwrap_or_default();
+        .is_some();
+    let power = match config.max_lock_duration {
+        Some(max_lock_duration) => {
+            let mut power =
+                lock_sum_at(deps.storage, Some(&address), height)?.power(max_lock_duration)?;
+            let balance = BONDED_BALANCES
+                .may_load_at_height(deps.storage, &address, height)?
+                .unwrap_or_default();
+            let end = LOCK_ENDS
+                .may_load_at_height(deps.storage, &address, height)?
+                .unwrap_or_default();
+            if !delegating && end > height {
+                power =
+                    power.checked_add(balance.multiply_ratio(end - height, max_lock_duration))?;
+            }
+            power
+        }
+        None => {
+            let mut balances = vec![];
+            for denom in
+                std::iter::once(config.denom.clone()).chain(get_denoms(deps.storage, &config)?)
+            {
+                let delegated = DELEGATED_BALANCES
+                    .may_load_at_height(deps.storage, (&address, &denom), height)?
+                    .unwrap_or_default();
+                balances.push(coin(delegated.u128(), denom));
+            }
+            if !delegating {
+                balances.extend(bonded_balances(deps, &config, &address, Some(height))?);
+            }
+            to_power(deps, &config, balances, height)?
+        }
+    };
     Ok(VotingPowerAtHeightResponse { power, height })
 }
 
@@ -197,12 +1010,117 @@ pub fn query_total_power_at_height(
     height: Option<u64>,
 ) -> StdResult<TotalPowerAtHeightResponse> {
     let height = height.unwrap_or(env.block.height);
-    let power = BONDED_TOTAL
-        .may_load_at_height(deps.storage, height)?
-        .unwrap_or_default();
+    let config = CONFIG.load(deps.storage)?;
+    let power = match config.max_lock_duration {
+        Some(max_lock_duration) => {
+            lock_sum_at(deps.storage, None, height)?.power(max_lock_duration)?
+        }
+        None => {
+            let mut totals = vec![coin(
+                BONDED_TOTAL
+                    .may_load_at_height(deps.storage, height)?
+                    .unwrap_or_default()
+                    .u128(),
+                config.denom.clone(),
+            )];
+            for denom in get_denoms(deps.storage, &config)? {
+                let total = DENOM_BONDED_TOTALS
+                    .may_load_at_height(deps.storage, &denom, height)?
+                    .unwrap_or_default();
+                totals.push(coin(total.u128(), denom));
+            }
+            to_power(deps, &config, totals, height)?
+        }
+    };
     Ok(TotalPowerAtHeightResponse { power, height })
 }
 
+/// Returns the sum of locks at the height, either the total one or the one delegated to the
+/// delegate.
+fn lock_sum_at(
+    storage: &dyn Storage,
+    delegate: Option<&Addr>,
+    height: u64,
+) -> StdResult<TotalLock> {
+    let mut sum = match delegate {
+        Some(delegate) => DELEGATED_LOCKS.may_load_at_height(storage, delegate, height)?,
+        None => TOTAL_LOCK.may_load_at_height(storage, height)?,
+    }
+    .unwrap_or_default();
+    let expiries = load_expiries(storage, delegate, sum.height, height)?;
+    sum.expire(expiries, height)?;
+    Ok(sum)
+}
+
+/// Returns the denoms other than the vault denom which have ever been accepted by the vault.
+fn get_denoms(storage: &dyn Storage, config: &Config) -> StdResult<Vec<String>> {
+    DENOM_RATES
+        .keys(storage, None, None, Order::Ascending)
+        .filter(|denom| !matches!(denom, Ok(denom) if *denom == config.denom))
+        .collect()
+}
+
+/// Returns the non-zero bonded balances of the address, at the height if it's set.
+fn bonded_balances(
+    deps: Deps,
+    config: &Config,
+    address: &Addr,
+    height: Option<u64>,
+) -> StdResult<Vec<Coin>> {
+    let balance = match height {
+        Some(height) => BONDED_BALANCES.may_load_at_height(deps.storage, address, height)?,
+        None => BONDED_BALANCES.may_load(deps.storage, address)?,
+    };
+    let mut balances = vec![coin(
+        balance.unwrap_or_default().u128(),
+        config.denom.clone(),
+    )];
+    for denom in get_denoms(deps.storage, config)? {
+        let balance = match height {
+            Some(height) => {
+                DENOM_BONDED_BALANCES.may_load_at_height(deps.storage, (address, &denom), height)?
+            }
+            None => DENOM_BONDED_BALANCES.may_load(deps.storage, (address, &denom))?,
+        };
+        balances.push(coin(balance.unwrap_or_default().u128(), denom));
+    }
+    balances.retain(|balance| !balance.amount.is_zero());
+    Ok(balances)
+}
+
+/// Returns the non-zero amounts of the address waiting in claims at the height.
+fn unbonding_balances(
+    deps: Deps,
+    config: &Config,
+    address: &Addr,
+    height: u64,
+) -> StdResult<Vec<Coin>> {
+    let mut balances = vec![];
+    for denom in std::iter::once(config.denom.clone()).chain(get_denoms(deps.storage, config)?) {
+        let balance = UNBONDING_BALANCES
+            .may_load_at_height(deps.storage, (address, &denom), height)?
+            .unwrap_or_default();
+        if !balance.is_zero() {
+            balances.push(coin(balance.u128(), denom));
+        }
+    }
+    Ok(balances)
+}
+
+/// Converts the tokens to voting power using the denom rates at the height.
+fn to_power(deps: Deps, config: &Config, tokens: Vec<Coin>, height: u64) -> StdResult<Uint128> {
+    let mut power = Uint128::zero();
+    for token in tokens {
+        let rate = match DENOM_RATES.may_load_at_height(deps.storage, &token.denom, height)? {
+            Some(rate) => rate,
+            None if token.denom == config.denom => Decimal::one(),
+            None => Decimal::zero(),
+        };
+        power = power.checked_add(token.amount.mul_floor(rate))?;
+    }
+    Ok(power)
+}
+
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
     let info = cw2::get_contract_version(deps.storage)?;
     to_json_binary(&cwd_interface::voting::InfoResponse { info })
@@ -240,13 +1158,17 @@ pub fn query_list_bonders(
         cosmwasm_std::Order::Ascending,
     )?;
 
+    let config = CONFIG.load(deps.storage)?;
     let bonders = bonders
         .into_iter()
-        .map(|(address, balance)| BonderBalanceResponse {
-            address: address.into_string(),
-            balance,
+        .map(|(address, balance)| {
+            Ok(BonderBalanceResponse {
+                balances: bonded_balances(deps, &config, &address, None)?,
+                address: address.into_string(),
+                balance,
+            })
         })
-        .collect();
+        .collect::<StdResult<Vec<_>>>()?;
 
     to_json_binary(&ListBondersResponse { bonders })
 }
@@ -259,16 +1181,97 @@ pub fn query_bonding_status(
 ) -> StdResult<BondingStatusResponse> {
     let address = deps.api.addr_validate(&address)?;
     let height = height.unwrap_or(env.block.height);
-    let power = BONDED_BALANCES
+    let config = CONFIG.load(deps.storage)?;
+    let mut power = BONDED_BALANCES
         .may_load_at_height(deps.storage, &address, height)?
         .unwrap_or_default();
+    // locked tokens can't be withdrawn until the lock expires
+    if config.max_lock_duration.is_some()
+        && LOCK_ENDS
+            .may_load_at_height(deps.storage, &address, height)?
+            .unwrap_or_default()
+            > height
+    {
+        power = Uint128::zero();
+    }
+    let unbonding_balances = unbonding_balances(deps, &config, &address, height)?;
+    let unbonding_amount = unbonding_balances
+        .iter()
+        .find(|balance| balance.denom == config.denom)
+        .map(|balance| balance.amount)
+        .unwrap_or_default();
     Ok(BondingStatusResponse {
         unbondable_abount: power,
         bonding_enabled: true,
+        unbonding_amount,
+        unbonding_balances,
+        bonded_balances: bonded_balances(deps, &config, &address, Some(height))?,
         height,
     })
 }
 
+pub fn query_claims(
+    deps: Deps,
+    address: String,
+    denom: Option<String>,
+) -> StdResult<ClaimsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let config = CONFIG.load(deps.storage)?;
+    match denom {
+        Some(denom) if denom != config.denom => Ok(ClaimsResponse {
+            claims: DENOM_CLAIMS
+                .may_load(deps.storage, (&address, &denom))?
+                .unwrap_or_default(),
+        }),
+        _ => CLAIMS.query_claims(deps, &address),
+    }
+}
+
+pub fn query_denom_rates(deps: Deps) -> StdResult<Vec<DenomRate>> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut rates = vec![DenomRate {
+        rate: DENOM_RATES
+            .may_load(deps.storage, &config.denom)?
+            .unwrap_or_else(Decimal::one),
+        denom: config.denom.clone(),
+    }];
+    for denom in get_denoms(deps.storage, &config)? {
+        let rate = DENOM_RATES.load(deps.storage, &denom)?;
+        rates.push(DenomRate { denom, rate });
+    }
+    Ok(rates)
+}
+
+pub fn query_pending_bond_transfers(
+    deps: Deps,
+    recipient: String,
+) -> StdResult<Vec<PendingBondTransfer>> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    PENDING_BOND_TRANSFERS
+        .prefix(&recipient)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(sender, amount)| PendingBondTransfer { sender, amount }))
+        .collect()
+}
+
+pub fn query_delegation(deps: Deps, address: String) -> StdResult<DelegationResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let delegate = DELEGATIONS.may_load(deps.storage, &address)?;
+    Ok(DelegationResponse { delegate })
+}
+
+pub fn query_lock(deps: Deps, address: String) -> StdResult<LockResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let end_height = LOCK_ENDS.may_load(deps.storage, &address)?;
+    let amount = match end_height {
+        Some(_) => BONDED_BALANCES
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default(),
+        None => Uint128::zero(),
+    };
+    Ok(LockResponse { amount, end_height })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     // Set contract to version to latest
@@ -19,6 +19,15 @@ pub enum ContractError {
     #[error("config denom cannot be empty.")]
     DenomIsEmpty {},
 
+    #[error("unbonding period cannot be zero.")]
+    InvalidUnbondingPeriod {},
+
+    #[error("Can't both set and clear the unbonding period")]
+    ConflictingUnbondingPeriodUpdate {},
+
+    #[error("max lock duration cannot be zero.")]
+    InvalidMaxLockDuration {},
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -27,4 +36,57 @@ pub enum ContractError {
 
     #[error("Can only unbond less than or equal to the amount you have bonded")]
     InvalidUnbondAmount {},
+
+    #[error("Lock duration must be between 1 and {max} blocks")]
+    InvalidLockDuration { max: u64 },
+
+    #[error("Lock can't end earlier than the current one, which ends at height {end}")]
+    LockCannotBeShortened { end: u64 },
+
+    #[error("Lock duration is required as there is no active lock")]
+    LockRequired {},
+
+    #[error("Lock hasn't expired yet, it ends at height {end}")]
+    LockNotExpired { end: u64 },
+
+    #[error("No bonded tokens")]
+    NoBondedTokens {},
+
+    #[error("Vote-escrow mode is disabled")]
+    VoteEscrowDisabled {},
+
+    #[error("Unbond is not available in vote-escrow mode, use Withdraw after the lock expires")]
+    VoteEscrowEnabled {},
+
+    #[error(
+        "Can only transfer a non-zero amount less than or equal to the amount you have bonded"
+    )]
+    InvalidTransferAmount {},
+
+    #[error("Can't transfer bonded tokens to yourself")]
+    BondTransferToSelf {},
+
+    #[error("The transfer extends the recipient's lock and must be sent with require_acceptance")]
+    LockExtensionNotAccepted {},
+
+    #[error("No pending bond transfer from {sender}")]
+    NoPendingBondTransfer { sender: String },
+
+    #[error("Can't delegate voting power to yourself")]
+    DelegationToSelf {},
+
+    #[error("Voting power is not delegated")]
+    NotDelegating {},
+
+    #[error("Denom {denom} is not accepted by the vault")]
+    UnsupportedDenom { denom: String },
+
+    #[error("Denom rate cannot be zero")]
+    ZeroDenomRate {},
+
+    #[error("Only the vault denom can be bonded in vote-escrow mode")]
+    MultiDenomInVoteEscrowMode {},
+
+    #[error("Too many outstanding claims. Claim some tokens before unbonding more.")]
+    TooManyClaims {},
 }
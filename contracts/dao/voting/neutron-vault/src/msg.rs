@@ -1,10 +1,11 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cw_utils::Duration;
 use cwd_interface::voting::InfoResponse;
 use cwd_interface::voting::{
     BondingStatusResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
-use cwd_macros::{info_query, voting_query, voting_vault, voting_vault_query};
+use cwd_macros::{info_query, voting_query, voting_vault_query};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
@@ -17,17 +18,81 @@ pub struct InstantiateMsg {
     pub owner: String,
     // Token denom e.g. untrn, or some ibc denom
     pub denom: String,
+    /// The period unbonded tokens are kept in claims before they can be claimed back. Unbonded
+    /// tokens are sent back at once if it is not set.
+    #[serde(default)]
+    pub unbonding_period: Option<Duration>,
+    /// Enables the vote-escrow mode if set. In this mode bonded tokens are locked for a chosen
+    /// number of blocks up to `max_lock_duration`, and voting power is proportional to the
+    /// remaining lock duration. Can't be changed after instantiation.
+    #[serde(default)]
+    pub max_lock_duration: Option<u64>,
 }
 
-#[voting_vault]
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
+    /// Bonds the sent tokens, which must be either the vault denom or a denom with a conversion
+    /// rate set. In the vote-escrow mode the tokens are added to the sender's lock
+    /// which ends `lock` blocks after the current height. `lock` can be omitted if the sender
+    /// already has an active lock, which is kept as is then.
+    Bond {
+        #[serde(default)]
+        lock: Option<u64>,
+    },
+    /// Unbonds the given amount of tokens of the denom, the vault denom by default. Not available
+    /// in the vote-escrow mode.
+    Unbond {
+        amount: Uint128,
+        #[serde(default)]
+        denom: Option<String>,
+    },
+    /// Moves the end of the sender's lock to `lock` blocks after the current height. Expired
+    /// locks are relocked this way. Only available in the vote-escrow mode.
+    ExtendLock { lock: u64 },
+    /// Withdraws all the sender's tokens once their lock has expired. Only available in the
+    /// vote-escrow mode.
+    Withdraw {},
     UpdateConfig {
         name: String,
         description: String,
         owner: String,
+        /// The new unbonding period. The current one is kept if it is not set. Claims created
+        /// before the update keep their release time.
+        #[serde(default)]
+        unbonding_period: Option<Duration>,
+        /// Disables the unbonding period, so that unbonded tokens are sent back at once. Can't
+        /// be combined with `unbonding_period`.
+        #[serde(default)]
+        clear_unbonding_period: Option<bool>,
+    },
+    /// Sends back the tokens of all the sender's matured claims.
+    Claim {},
+    /// Moves bonded tokens of the denom, the vault denom by default, to the recipient along with
+    /// their voting power. In the vote-escrow mode the tokens keep their lock, so the recipient's
+    /// lock is extended to the end of the sender's one if it ends earlier, which is only allowed
+    /// with `require_acceptance` if the recipient has bonded tokens. If `require_acceptance` is
+    /// set, the tokens are only moved once the recipient accepts the transfer.
+    TransferBond {
+        recipient: String,
+        amount: Uint128,
+        #[serde(default)]
+        denom: Option<String>,
+        #[serde(default)]
+        require_acceptance: bool,
     },
+    /// Accepts the pending bond transfer from the sender.
+    AcceptBondTransfer { sender: String },
+    /// Cancels the pending bond transfer to the recipient.
+    CancelBondTransfer { recipient: String },
+    /// Delegates the sender's voting power to another address, replacing the current delegation
+    /// if there is one. Delegated power is not delegated further by the delegate.
+    Delegate { to: String },
+    /// Takes the sender's voting power back from the delegate.
+    Undelegate {},
+    /// Sets the conversion rate of the denom to voting power, which allows bonding the denom.
+    /// Can only be called by the owner. Not available in the vote-escrow mode.
+    SetDenomRate { denom: String, rate: Decimal },
 }
 
 #[voting_query]
@@ -38,6 +103,48 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(crate::state::Config)]
     Config {},
+    /// Returns the claims of the address for the denom, the vault denom by default.
+    #[returns(cw_controllers::ClaimsResponse)]
+    Claims {
+        address: String,
+        denom: Option<String>,
+    },
+    #[returns(Vec<DenomRate>)]
+    DenomRates {},
+    /// Returns the bond transfers waiting for the recipient's acceptance.
+    #[returns(Vec<PendingBondTransfer>)]
+    PendingBondTransfers { recipient: String },
+    #[returns(LockResponse)]
+    Lock { address: String },
+    #[returns(DelegationResponse)]
+    Delegation { address: String },
+}
+
+#[cw_serde]
+pub struct DenomRate {
+    pub denom: String,
+    /// The amount of voting power one token of the denom gives.
+    pub rate: Decimal,
+}
+
+#[cw_serde]
+pub struct PendingBondTransfer {
+    pub sender: Addr,
+    pub amount: Coin,
+}
+
+#[cw_serde]
+pub struct DelegationResponse {
+    /// The address the voting power is delegated to, if any.
+    pub delegate: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct LockResponse {
+    /// The amount of locked tokens.
+    pub amount: Uint128,
+    /// The height at which the lock expires, if there is a lock.
+    pub end_height: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -1,6 +1,8 @@
 use crate::error::ContractError;
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
+use cosmwasm_std::{Addr, Coin, Decimal, StdResult, Uint128, Uint256};
+use cw_controllers::{Claim, Claims};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
+use cw_utils::Duration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -10,6 +12,13 @@ pub struct Config {
     pub description: String,
     pub owner: Addr,
     pub denom: String,
+    /// The period unbonded tokens are kept in claims before they can be claimed back. Unbonded
+    /// tokens are sent back at once if it is not set.
+    #[serde(default)]
+    pub unbonding_period: Option<Duration>,
+    /// The maximum lock duration in blocks. The vault works in the vote-escrow mode if it is set.
+    #[serde(default)]
+    pub max_lock_duration: Option<u64>,
 }
 
 impl Config {
@@ -24,6 +33,12 @@ impl Config {
         if self.denom.is_empty() {
             return Err(ContractError::DenomIsEmpty {});
         };
+        if let Some(Duration::Height(0) | Duration::Time(0)) = self.unbonding_period {
+            return Err(ContractError::InvalidUnbondingPeriod {});
+        };
+        if self.max_lock_duration == Some(0) {
+            return Err(ContractError::InvalidMaxLockDuration {});
+        };
         Ok(())
     }
 }
@@ -44,11 +59,150 @@ pub const BONDED_TOTAL: SnapshotItem<Uint128> = SnapshotItem::new(
     Strategy::EveryBlock,
 );
 
+/// Conversion rates of the bonded denoms to voting power. The vault denom has the rate of one
+/// unless it's set explicitly, other denoms can only be bonded once their rate is set.
+pub const DENOM_RATES: SnapshotMap<&str, Decimal> = SnapshotMap::new(
+    "denom_rates",
+    "denom_rates__checkpoints",
+    "denom_rates__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Bonded balances of the denoms other than the vault denom, which are kept in
+/// `BONDED_BALANCES`.
+pub const DENOM_BONDED_BALANCES: SnapshotMap<(&Addr, &str), Uint128> = SnapshotMap::new(
+    "denom_bonded_balances",
+    "denom_bonded_balances__checkpoints",
+    "denom_bonded_balances__changelog",
+    Strategy::EveryBlock,
+);
+
+pub const DENOM_BONDED_TOTALS: SnapshotMap<&str, Uint128> = SnapshotMap::new(
+    "denom_bonded_totals",
+    "denom_bonded_totals__checkpoints",
+    "denom_bonded_totals__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Bond transfers waiting for acceptance by recipient and sender.
+pub const PENDING_BOND_TRANSFERS: Map<(&Addr, &Addr), Coin> = Map::new("pending_bond_transfers");
+
+/// Lock end heights of the bonders in the vote-escrow mode.
+pub const LOCK_ENDS: SnapshotMap<&Addr, u64> = SnapshotMap::new(
+    "lock_ends",
+    "lock_ends__checkpoints",
+    "lock_ends__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Sum of the active locks in the vote-escrow mode as of `height`. The total voting power at
+/// a height `h` is `(weighted_end - amount * h) / max_lock_duration`, once the locks which
+/// expired between `height` and `h` are subtracted.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Default)]
+pub struct TotalLock {
+    /// Sum of the locked amounts.
+    pub amount: Uint128,
+    /// Sum of the locked amounts multiplied by their lock end heights.
+    pub weighted_end: Uint256,
+    pub height: u64,
+}
+
+impl TotalLock {
+    /// Adds the lock ending at `end` to the sum.
+    pub fn add(&mut self, amount: Uint128, end: u64) -> StdResult<()> {
+        self.amount = self.amount.checked_add(amount)?;
+        self.weighted_end = self
+            .weighted_end
+            .checked_add(Uint256::from(amount) * Uint256::from(end))?;
+        Ok(())
+    }
+
+    /// Subtracts the lock ending at `end` from the sum.
+    pub fn sub(&mut self, amount: Uint128, end: u64) -> StdResult<()> {
+        self.amount = self.amount.checked_sub(amount)?;
+        self.weighted_end = self
+            .weighted_end
+            .checked_sub(Uint256::from(amount) * Uint256::from(end))?;
+        Ok(())
+    }
+
+    /// Brings the sum up to the height, dropping the given locks which have expired since the
+    /// sum was saved last time.
+    pub fn expire(&mut self, expiries: Vec<(u64, Uint128)>, height: u64) -> StdResult<()> {
+        for (end, amount) in expiries {
+            self.sub(amount, end)?;
+        }
+        self.height = height;
+        Ok(())
+    }
+
+    /// Returns the voting power of the locks at the height the sum has been brought up to.
+    pub fn power(&self, max_lock_duration: u64) -> StdResult<Uint128> {
+        let power = (self.weighted_end - Uint256::from(self.amount) * Uint256::from(self.height))
+            / Uint256::from(max_lock_duration);
+        Ok(power.try_into()?)
+    }
+}
+
+pub const TOTAL_LOCK: SnapshotItem<TotalLock> = SnapshotItem::new(
+    "total_lock",
+    "total_lock__checkpoints",
+    "total_lock__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Locked amounts by their lock end heights. Only entries for heights in the future are ever
+/// changed, so the entries for past heights are final.
+pub const LOCK_EXPIRIES: Map<u64, Uint128> = Map::new("lock_expiries");
+
+/// Delegates of the bonders which delegate their voting power.
+pub const DELEGATIONS: SnapshotMap<&Addr, Addr> = SnapshotMap::new(
+    "delegations",
+    "delegations__checkpoints",
+    "delegations__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Sums of the bonded balances delegated to the delegates by denom.
+pub const DELEGATED_BALANCES: SnapshotMap<(&Addr, &str), Uint128> = SnapshotMap::new(
+    "delegated_balances",
+    "delegated_balances__checkpoints",
+    "delegated_balances__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Sums of the active locks delegated to the delegates in the vote-escrow mode, the same way
+/// `TOTAL_LOCK` sums up all the active locks.
+pub const DELEGATED_LOCKS: SnapshotMap<&Addr, TotalLock> = SnapshotMap::new(
+    "delegated_locks",
+    "delegated_locks__checkpoints",
+    "delegated_locks__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Delegated locked amounts by delegate and lock end height, the same way as `LOCK_EXPIRIES`.
+pub const DELEGATED_LOCK_EXPIRIES: Map<(&Addr, u64), Uint128> = Map::new("delegated_lock_expiries");
+
+/// The maximum number of pending claims an address can have at once.
+pub const MAX_CLAIMS: usize = 70;
+pub const CLAIMS: Claims = Claims::new("claims");
+/// Claims of the denoms other than the vault denom, which are kept in `CLAIMS`.
+pub const DENOM_CLAIMS: Map<(&Addr, &str), Vec<Claim>> = Map::new("denom_claims");
+/// Amounts of the unbonded tokens waiting in claims by bonder and denom, so that they can be
+/// queried at past heights.
+pub const UNBONDING_BALANCES: SnapshotMap<(&Addr, &str), Uint128> = SnapshotMap::new(
+    "unbonding_balances",
+    "unbonding_balances__checkpoints",
+    "unbonding_balances__changelog",
+    Strategy::EveryBlock,
+);
+
 #[cfg(test)]
 mod tests {
     use super::Config;
     use crate::error::ContractError;
     use cosmwasm_std::Addr;
+    use cw_utils::Duration;
 
     #[test]
     fn test_config_validate() {
@@ -57,6 +211,8 @@ mod tests {
             description: String::from("description"),
             owner: Addr::unchecked("owner"),
             denom: String::from("denom"),
+            unbonding_period: None,
+            max_lock_duration: None,
         };
         assert_eq!(cfg_ok.validate(), Ok(()));
 
@@ -65,6 +221,8 @@ mod tests {
             description: String::from("description"),
             owner: Addr::unchecked("owner"),
             denom: String::from("denom"),
+            unbonding_period: None,
+            max_lock_duration: None,
         };
         assert_eq!(
             cfg_empty_name.validate(),
@@ -76,6 +234,8 @@ mod tests {
             description: String::from(""),
             owner: Addr::unchecked("owner"),
             denom: String::from("denom"),
+            unbonding_period: None,
+            max_lock_duration: None,
         };
         assert_eq!(
             cfg_empty_description.validate(),
@@ -87,10 +247,48 @@ mod tests {
             description: String::from("description"),
             owner: Addr::unchecked("owner"),
             denom: String::from(""),
+            unbonding_period: None,
+            max_lock_duration: None,
         };
         assert_eq!(
             cfg_empty_denom.validate(),
             Err(ContractError::DenomIsEmpty {})
         );
+
+        let cfg_unbonding_period = Config {
+            name: String::from("name"),
+            description: String::from("description"),
+            owner: Addr::unchecked("owner"),
+            denom: String::from("denom"),
+            unbonding_period: Some(Duration::Height(10)),
+            max_lock_duration: Some(100),
+        };
+        assert_eq!(cfg_unbonding_period.validate(), Ok(()));
+
+        let cfg_zero_unbonding_period = Config {
+            name: String::from("name"),
+            description: String::from("description"),
+            owner: Addr::unchecked("owner"),
+            denom: String::from("denom"),
+            unbonding_period: Some(Duration::Time(0)),
+            max_lock_duration: None,
+        };
+        assert_eq!(
+            cfg_zero_unbonding_period.validate(),
+            Err(ContractError::InvalidUnbondingPeriod {})
+        );
+
+        let cfg_zero_max_lock_duration = Config {
+            name: String::from("name"),
+            description: String::from("description"),
+            owner: Addr::unchecked("owner"),
+            denom: String::from("denom"),
+            unbonding_period: None,
+            max_lock_duration: Some(0),
+        };
+        assert_eq!(
+            cfg_zero_max_lock_duration.validate(),
+            Err(ContractError::InvalidMaxLockDuration {})
+        );
     }
 }
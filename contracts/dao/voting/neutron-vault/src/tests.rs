@@ -1,11 +1,17 @@
 use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::msg::{
+    DelegationResponse, DenomRate, ExecuteMsg, InstantiateMsg, LockResponse, MigrateMsg,
+    PendingBondTransfer, QueryMsg,
+};
 use crate::state::Config;
+use crate::ContractError;
 use cosmwasm_std::testing::{mock_dependencies, mock_env};
-use cosmwasm_std::{coins, Addr, Coin, Empty, Uint128};
+use cosmwasm_std::{coin, coins, from_json, Addr, Coin, Decimal, Empty, Uint128};
+use cw_controllers::{Claim, ClaimsResponse};
 use cw_multi_test::{
     custom_app, next_block, App, AppResponse, Contract, ContractWrapper, Executor,
 };
+use cw_utils::{Duration, Expiration};
 use cwd_interface::voting::{
     BondingStatusResponse, InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
@@ -20,6 +26,7 @@ const ADDR1: &str = "addr1";
 const ADDR2: &str = "addr2";
 const DENOM: &str = "ujuno";
 const INVALID_DENOM: &str = "uinvalid";
+const LS_DENOM: &str = "ustjuno";
 const INIT_BALANCE: Uint128 = Uint128::new(10000);
 
 fn vault_contract() -> Box<dyn Contract<Empty>> {
@@ -46,6 +53,10 @@ fn mock_app() -> App {
                         denom: INVALID_DENOM.to_string(),
                         amount: INIT_BALANCE,
                     },
+                    Coin {
+                        denom: LS_DENOM.to_string(),
+                        amount: INIT_BALANCE,
+                    },
                 ],
             )
             .unwrap();
@@ -62,6 +73,10 @@ fn mock_app() -> App {
                         denom: INVALID_DENOM.to_string(),
                         amount: INIT_BALANCE,
                     },
+                    Coin {
+                        denom: LS_DENOM.to_string(),
+                        amount: INIT_BALANCE,
+                    },
                 ],
             )
             .unwrap();
@@ -78,6 +93,10 @@ fn mock_app() -> App {
                         denom: INVALID_DENOM.to_string(),
                         amount: INIT_BALANCE,
                     },
+                    Coin {
+                        denom: LS_DENOM.to_string(),
+                        amount: INIT_BALANCE,
+                    },
                 ],
             )
             .unwrap();
@@ -99,11 +118,49 @@ fn bond_tokens(
     app.execute_contract(
         Addr::unchecked(sender),
         contract_addr,
-        &ExecuteMsg::Bond {},
+        &ExecuteMsg::Bond { lock: None },
         &coins(amount, denom),
     )
 }
 
+fn bond_tokens_with_lock(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    amount: u128,
+    lock: u64,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::Bond { lock: Some(lock) },
+        &coins(amount, DENOM),
+    )
+}
+
+fn extend_lock(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    lock: u64,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::ExtendLock { lock },
+        &[],
+    )
+}
+
+fn withdraw(app: &mut App, contract_addr: Addr, sender: &str) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::Withdraw {},
+        &[],
+    )
+}
+
 fn unbond_tokens(
     app: &mut App,
     contract_addr: Addr,
@@ -115,6 +172,111 @@ fn unbond_tokens(
         contract_addr,
         &ExecuteMsg::Unbond {
             amount: Uint128::new(amount),
+            denom: None,
+        },
+        &[],
+    )
+}
+
+fn claim(app: &mut App, contract_addr: Addr, sender: &str) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::Claim {},
+        &[],
+    )
+}
+
+fn unbond_denom(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    amount: u128,
+    denom: &str,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::Unbond {
+            amount: Uint128::new(amount),
+            denom: Some(denom.to_string()),
+        },
+        &[],
+    )
+}
+
+fn transfer_bond(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    recipient: &str,
+    amount: u128,
+    require_acceptance: bool,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::TransferBond {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+            denom: None,
+            require_acceptance,
+        },
+        &[],
+    )
+}
+
+fn get_pending_bond_transfers(
+    app: &App,
+    contract_addr: &Addr,
+    recipient: &str,
+) -> Vec<PendingBondTransfer> {
+    app.wrap()
+        .query_wasm_smart(
+            contract_addr,
+            &QueryMsg::PendingBondTransfers {
+                recipient: recipient.to_string(),
+            },
+        )
+        .unwrap()
+}
+
+fn delegate(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    to: &str,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::Delegate { to: to.to_string() },
+        &[],
+    )
+}
+
+fn undelegate(app: &mut App, contract_addr: Addr, sender: &str) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::Undelegate {},
+        &[],
+    )
+}
+
+fn set_denom_rate(
+    app: &mut App,
+    contract_addr: Addr,
+    sender: &str,
+    denom: &str,
+    rate: Decimal,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(sender),
+        contract_addr,
+        &ExecuteMsg::SetDenomRate {
+            denom: denom.to_string(),
+            rate,
         },
         &[],
     )
@@ -135,6 +297,28 @@ fn update_config(
             owner,
             name,
             description,
+            unbonding_period: None,
+            clear_unbonding_period: None,
+        },
+        &[],
+    )
+}
+
+fn update_unbonding_period(
+    app: &mut App,
+    contract_addr: Addr,
+    unbonding_period: Option<Duration>,
+    clear_unbonding_period: Option<bool>,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked(DAO_ADDR),
+        contract_addr,
+        &ExecuteMsg::UpdateConfig {
+            owner: DAO_ADDR.to_string(),
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            unbonding_period,
+            clear_unbonding_period,
         },
         &[],
     )
@@ -192,6 +376,29 @@ fn get_bonding_status(app: &App, contract_addr: &Addr, address: &str) -> Bonding
         .unwrap()
 }
 
+fn get_claims(app: &App, contract_addr: &Addr, address: &str) -> ClaimsResponse {
+    app.wrap()
+        .query_wasm_smart(
+            contract_addr,
+            &QueryMsg::Claims {
+                address: address.to_string(),
+                denom: None,
+            },
+        )
+        .unwrap()
+}
+
+fn get_lock(app: &App, contract_addr: &Addr, address: &str) -> LockResponse {
+    app.wrap()
+        .query_wasm_smart(
+            contract_addr,
+            &QueryMsg::Lock {
+                address: address.to_string(),
+            },
+        )
+        .unwrap()
+}
+
 #[test]
 fn test_instantiate() {
     let mut app = mock_app();
@@ -204,13 +411,15 @@ fn test_instantiate() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
     assert_eq!(get_dao(&app, &addr), String::from(DAO_ADDR));
 }
 
 #[test]
-#[should_panic(expected = "Must send reserve token 'ujuno'")]
+#[should_panic(expected = "Denom uinvalid is not accepted by the vault")]
 fn test_bond_invalid_denom() {
     let mut app = mock_app();
     let vault_id = app.store_code(vault_contract());
@@ -222,6 +431,8 @@ fn test_bond_invalid_denom() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -241,6 +452,8 @@ fn test_bond_valid_denom() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -270,6 +483,8 @@ fn test_unbond_none_bonded() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -289,6 +504,8 @@ fn test_unbond_invalid_balance() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -312,6 +529,8 @@ fn test_unbond() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -344,6 +563,930 @@ fn test_unbond() {
     assert_eq!(bonding_status.unbondable_abount, Uint128::zero());
 }
 
+#[test]
+fn test_unbond_with_unbonding_period() {
+    let mut app = mock_app();
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            denom: DENOM.to_string(),
+            unbonding_period: Some(Duration::Height(10)),
+            max_lock_duration: None,
+        },
+    );
+
+    bond_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    app.update_block(next_block);
+
+    // Unbonded tokens are kept in a claim
+    unbond_tokens(&mut app, addr.clone(), ADDR1, 75).unwrap();
+    let release_height = app.block_info().height + 10;
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9900));
+    app.update_block(next_block);
+
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None).power,
+        Uint128::new(25)
+    );
+    let bonding_status = get_bonding_status(&app, &addr, ADDR1);
+    assert_eq!(bonding_status.unbondable_abount, Uint128::new(25));
+    assert_eq!(bonding_status.unbonding_amount, Uint128::new(75));
+    assert_eq!(
+        get_claims(&app, &addr, ADDR1).claims,
+        vec![Claim {
+            amount: Uint128::new(75),
+            release_at: Expiration::AtHeight(release_height),
+        }]
+    );
+
+    // The claim is not mature yet
+    let err = claim(&mut app, addr.clone(), ADDR1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NothingToClaim {}
+    );
+
+    app.update_block(|b| b.height = release_height);
+    claim(&mut app, addr.clone(), ADDR1).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9975));
+    assert_eq!(get_claims(&app, &addr, ADDR1).claims, vec![]);
+    app.update_block(next_block);
+    assert_eq!(
+        get_bonding_status(&app, &addr, ADDR1).unbonding_amount,
+        Uint128::zero()
+    );
+
+    // The tokens are sent back at once when the unbonding period is disabled
+    update_unbonding_period(&mut app, addr.clone(), None, Some(true)).unwrap();
+    unbond_tokens(&mut app, addr.clone(), ADDR1, 25).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), INIT_BALANCE);
+    assert_eq!(get_claims(&app, &addr, ADDR1).claims, vec![]);
+}
+
+#[test]
+fn test_update_config_unbonding_period() {
+    let mut app = mock_app();
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            denom: DENOM.to_string(),
+            unbonding_period: Some(Duration::Height(10)),
+            max_lock_duration: None,
+        },
+    );
+
+    // The unbonding period is kept if the update doesn't mention it
+    let msg: ExecuteMsg = from_json(format!(
+        r#"{{"update_config": {{"name": "new name", "description": "{DESCRIPTION}", "owner": "{DAO_ADDR}"}}}}"#
+    ))
+    .unwrap();
+    app.execute_contract(Addr::unchecked(DAO_ADDR), addr.clone(), &msg, &[])
+        .unwrap();
+    let config = get_config(&mut app, addr.clone());
+    assert_eq!(config.name, "new name");
+    assert_eq!(config.unbonding_period, Some(Duration::Height(10)));
+
+    update_unbonding_period(&mut app, addr.clone(), Some(Duration::Time(100)), None).unwrap();
+    assert_eq!(
+        get_config(&mut app, addr.clone()).unbonding_period,
+        Some(Duration::Time(100))
+    );
+
+    let err = update_unbonding_period(
+        &mut app,
+        addr.clone(),
+        Some(Duration::Height(20)),
+        Some(true),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ConflictingUnbondingPeriodUpdate {}
+    );
+
+    update_unbonding_period(&mut app, addr.clone(), None, Some(true)).unwrap();
+    assert_eq!(get_config(&mut app, addr).unbonding_period, None);
+}
+
+#[test]
+fn test_instantiate_zero_unbonding_period() {
+    let mut app = mock_app();
+    let vault_id = app.store_code(vault_contract());
+    let err = app
+        .instantiate_contract(
+            vault_id,
+            Addr::unchecked(DAO_ADDR),
+            &InstantiateMsg {
+                name: NAME.to_string(),
+                description: DESCRIPTION.to_string(),
+                owner: DAO_ADDR.to_string(),
+                denom: DENOM.to_string(),
+                unbonding_period: Some(Duration::Height(0)),
+                max_lock_duration: None,
+            },
+            &[],
+            "vault",
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidUnbondingPeriod {}
+    );
+}
+
+#[test]
+fn test_vote_escrow() {
+    let mut app = mock_app();
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: Some(100),
+        },
+    );
+    let start_height = app.block_info().height;
+
+    let err = bond_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::LockRequired {}
+    );
+    let err = bond_tokens_with_lock(&mut app, addr.clone(), ADDR1, 100, 101).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidLockDuration { max: 100 }
+    );
+
+    bond_tokens_with_lock(&mut app, addr.clone(), ADDR1, 100, 100).unwrap();
+    bond_tokens_with_lock(&mut app, addr.clone(), ADDR2, 200, 50).unwrap();
+    app.update_block(next_block);
+
+    // voting power decays linearly with the remaining lock duration
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None).power,
+        Uint128::new(99)
+    );
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::new(98)
+    );
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr.clone(), None).power,
+        Uint128::new(197)
+    );
+    assert_eq!(
+        get_lock(&app, &addr, ADDR1),
+        LockResponse {
+            amount: Uint128::new(100),
+            end_height: Some(start_height + 100),
+        }
+    );
+    assert_eq!(
+        get_bonding_status(&app, &addr, ADDR1).unbondable_abount,
+        Uint128::zero()
+    );
+
+    let err = unbond_tokens(&mut app, addr.clone(), ADDR2, 200).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::VoteEscrowEnabled {}
+    );
+    let err = withdraw(&mut app, addr.clone(), ADDR2).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::LockNotExpired {
+            end: start_height + 50
+        }
+    );
+
+    // the lock of ADDR2 has expired
+    app.update_block(|b| b.height = start_height + 50);
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::zero()
+    );
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr.clone(), None).power,
+        Uint128::new(50)
+    );
+    withdraw(&mut app, addr.clone(), ADDR2).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR2, DENOM), INIT_BALANCE);
+
+    let err = extend_lock(&mut app, addr.clone(), ADDR1, 40).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::LockCannotBeShortened {
+            end: start_height + 100
+        }
+    );
+    extend_lock(&mut app, addr.clone(), ADDR1, 100).unwrap();
+
+    app.update_block(|b| b.height = start_height + 60);
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None).power,
+        Uint128::new(90)
+    );
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr.clone(), None).power,
+        Uint128::new(90)
+    );
+
+    // historical queries are not affected by the later changes
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr.clone(), Some(start_height + 1)).power,
+        Uint128::new(197)
+    );
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr.clone(), Some(start_height + 50)).power,
+        Uint128::new(50)
+    );
+    assert_eq!(
+        get_voting_power_at_height(
+            &mut app,
+            addr.clone(),
+            ADDR1.to_string(),
+            Some(start_height + 50)
+        )
+        .power,
+        Uint128::new(50)
+    );
+    assert_eq!(
+        get_voting_power_at_height(
+            &mut app,
+            addr.clone(),
+            ADDR2.to_string(),
+            Some(start_height + 10)
+        )
+        .power,
+        Uint128::new(80)
+    );
+
+    // everything is withdrawable once the extended lock expires
+    app.update_block(|b| b.height = start_height + 150);
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr.clone(), None).power,
+        Uint128::zero()
+    );
+    withdraw(&mut app, addr.clone(), ADDR1).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), INIT_BALANCE);
+    assert_eq!(
+        get_lock(&app, &addr, ADDR1),
+        LockResponse {
+            amount: Uint128::zero(),
+            end_height: None,
+        }
+    );
+}
+
+#[test]
+fn test_vote_escrow_disabled() {
+    let mut app = mock_app();
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
+        },
+    );
+
+    let err = bond_tokens_with_lock(&mut app, addr.clone(), ADDR1, 100, 10).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::VoteEscrowDisabled {}
+    );
+    bond_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    let err = extend_lock(&mut app, addr.clone(), ADDR1, 10).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::VoteEscrowDisabled {}
+    );
+    let err = withdraw(&mut app, addr, ADDR1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::VoteEscrowDisabled {}
+    );
+}
+
+#[test]
+fn test_multi_denom() {
+    let mut app = mock_app();
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
+        },
+    );
+
+    let err = bond_tokens(&mut app, addr.clone(), ADDR1, 100, LS_DENOM).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::UnsupportedDenom {
+            denom: LS_DENOM.to_string()
+        }
+    );
+    let err = set_denom_rate(&mut app, addr.clone(), ADDR1, LS_DENOM, Decimal::one()).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::Unauthorized {}
+    );
+    let err =
+        set_denom_rate(&mut app, addr.clone(), DAO_ADDR, LS_DENOM, Decimal::zero()).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ZeroDenomRate {}
+    );
+
+    set_denom_rate(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        LS_DENOM,
+        Decimal::from_ratio(6u128, 5u128),
+    )
+    .unwrap();
+    bond_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    bond_tokens(&mut app, addr.clone(), ADDR1, 100, LS_DENOM).unwrap();
+    bond_tokens(&mut app, addr.clone(), ADDR2, 50, LS_DENOM).unwrap();
+    app.update_block(next_block);
+    let rate_change_height = app.block_info().height;
+
+    let rates: Vec<DenomRate> = app
+        .wrap()
+        .query_wasm_smart(addr.clone(), &QueryMsg::DenomRates {})
+        .unwrap();
+    assert_eq!(
+        rates,
+        vec![
+            DenomRate {
+                denom: DENOM.to_string(),
+                rate: Decimal::one(),
+            },
+            DenomRate {
+                denom: LS_DENOM.to_string(),
+                rate: Decimal::from_ratio(6u128, 5u128),
+            },
+        ]
+    );
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None).power,
+        Uint128::new(220)
+    );
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::new(60)
+    );
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr.clone(), None).power,
+        Uint128::new(280)
+    );
+    assert_eq!(
+        get_bonding_status(&app, &addr, ADDR1).bonded_balances,
+        vec![coin(100, DENOM), coin(100, LS_DENOM)]
+    );
+    let bonders: ListBondersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::ListBonders {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        bonders.bonders,
+        vec![
+            BonderBalanceResponse {
+                address: ADDR1.to_string(),
+                balance: Uint128::new(100),
+                balances: vec![coin(100, DENOM), coin(100, LS_DENOM)],
+            },
+            BonderBalanceResponse {
+                address: ADDR2.to_string(),
+                balance: Uint128::zero(),
+                balances: coins(50, LS_DENOM),
+            },
+        ]
+    );
+
+    // the new rate applies to the bonded tokens from the next block
+    set_denom_rate(
+        &mut app,
+        addr.clone(),
+        DAO_ADDR,
+        LS_DENOM,
+        Decimal::from_ratio(3u128, 2u128),
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None).power,
+        Uint128::new(250)
+    );
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr.clone(), None).power,
+        Uint128::new(325)
+    );
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr.clone(), Some(rate_change_height)).power,
+        Uint128::new(280)
+    );
+
+    let err = unbond_denom(&mut app, addr.clone(), ADDR1, 101, LS_DENOM).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidUnbondAmount {}
+    );
+    unbond_denom(&mut app, addr.clone(), ADDR1, 40, LS_DENOM).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR1, LS_DENOM), Uint128::new(9940));
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9900));
+    app.update_block(next_block);
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None).power,
+        Uint128::new(190)
+    );
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr, None).power,
+        Uint128::new(265)
+    );
+}
+
+#[test]
+fn test_multi_denom_claims() {
+    let mut app = mock_app();
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            denom: DENOM.to_string(),
+            unbonding_period: Some(Duration::Height(10)),
+            max_lock_duration: None,
+        },
+    );
+    set_denom_rate(&mut app, addr.clone(), DAO_ADDR, LS_DENOM, Decimal::one()).unwrap();
+    bond_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    bond_tokens(&mut app, addr.clone(), ADDR1, 50, LS_DENOM).unwrap();
+    app.update_block(next_block);
+
+    unbond_tokens(&mut app, addr.clone(), ADDR1, 30).unwrap();
+    unbond_denom(&mut app, addr.clone(), ADDR1, 20, LS_DENOM).unwrap();
+    let release_height = app.block_info().height + 10;
+    let claims: ClaimsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::Claims {
+                address: ADDR1.to_string(),
+                denom: Some(LS_DENOM.to_string()),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        claims.claims,
+        vec![Claim {
+            amount: Uint128::new(20),
+            release_at: Expiration::AtHeight(release_height),
+        }]
+    );
+
+    app.update_block(|b| b.height = release_height);
+    let bonding_status = get_bonding_status(&app, &addr, ADDR1);
+    assert_eq!(bonding_status.unbonding_amount, Uint128::new(30));
+    assert_eq!(
+        bonding_status.unbonding_balances,
+        vec![coin(30, DENOM), coin(20, LS_DENOM)]
+    );
+
+    claim(&mut app, addr.clone(), ADDR1).unwrap();
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9930));
+    assert_eq!(get_balance(&mut app, ADDR1, LS_DENOM), Uint128::new(9970));
+    app.update_block(next_block);
+    assert!(get_bonding_status(&app, &addr, ADDR1)
+        .unbonding_balances
+        .is_empty());
+    // the claims are still there at the heights before they were claimed
+    let bonding_status: BondingStatusResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::BondingStatus {
+                address: ADDR1.to_string(),
+                height: Some(release_height),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        bonding_status.unbonding_balances,
+        vec![coin(30, DENOM), coin(20, LS_DENOM)]
+    );
+    let err = claim(&mut app, addr, ADDR1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NothingToClaim {}
+    );
+}
+
+#[test]
+fn test_transfer_bond() {
+    let mut app = mock_app();
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
+        },
+    );
+    bond_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    app.update_block(next_block);
+
+    let err = transfer_bond(&mut app, addr.clone(), ADDR1, ADDR2, 101, false).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::InvalidTransferAmount {}
+    );
+    let err = transfer_bond(&mut app, addr.clone(), ADDR1, ADDR1, 10, false).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::BondTransferToSelf {}
+    );
+
+    let transfer_height = app.block_info().height;
+    transfer_bond(&mut app, addr.clone(), ADDR1, ADDR2, 40, false).unwrap();
+    // no tokens leave the vault
+    assert_eq!(get_balance(&mut app, ADDR1, DENOM), Uint128::new(9900));
+    assert_eq!(get_balance(&mut app, ADDR2, DENOM), INIT_BALANCE);
+    app.update_block(next_block);
+
+    assert_eq!(
+        get_voting_power_at_height(
+            &mut app,
+            addr.clone(),
+            ADDR1.to_string(),
+            Some(transfer_height)
+        )
+        .power,
+        Uint128::new(100)
+    );
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None).power,
+        Uint128::new(60)
+    );
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::new(40)
+    );
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr.clone(), None).power,
+        Uint128::new(100)
+    );
+
+    // transfers requiring acceptance are only done once accepted
+    transfer_bond(&mut app, addr.clone(), ADDR1, ADDR2, 10, true).unwrap();
+    assert_eq!(
+        get_pending_bond_transfers(&app, &addr, ADDR2),
+        vec![PendingBondTransfer {
+            sender: Addr::unchecked(ADDR1),
+            amount: coin(10, DENOM),
+        }]
+    );
+    app.update_block(next_block);
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::new(40)
+    );
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        addr.clone(),
+        &ExecuteMsg::AcceptBondTransfer {
+            sender: ADDR1.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    app.update_block(next_block);
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::new(50)
+    );
+    assert_eq!(get_pending_bond_transfers(&app, &addr, ADDR2), vec![]);
+    let err = app
+        .execute_contract(
+            Addr::unchecked(ADDR2),
+            addr.clone(),
+            &ExecuteMsg::AcceptBondTransfer {
+                sender: ADDR1.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NoPendingBondTransfer {
+            sender: ADDR1.to_string()
+        }
+    );
+
+    // pending transfers can be cancelled by the sender
+    transfer_bond(&mut app, addr.clone(), ADDR1, ADDR2, 10, true).unwrap();
+    app.execute_contract(
+        Addr::unchecked(ADDR1),
+        addr.clone(),
+        &ExecuteMsg::CancelBondTransfer {
+            recipient: ADDR2.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(get_pending_bond_transfers(&app, &addr, ADDR2), vec![]);
+}
+
+#[test]
+fn test_transfer_bond_vote_escrow() {
+    let mut app = mock_app();
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: Some(100),
+        },
+    );
+    let start_height = app.block_info().height;
+    bond_tokens_with_lock(&mut app, addr.clone(), ADDR1, 100, 100).unwrap();
+    bond_tokens_with_lock(&mut app, addr.clone(), ADDR2, 100, 50).unwrap();
+    app.update_block(next_block);
+
+    // the recipient's lock can't be extended without their acceptance
+    let err = transfer_bond(&mut app, addr.clone(), ADDR1, ADDR2, 50, false).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::LockExtensionNotAccepted {}
+    );
+    assert_eq!(
+        get_lock(&app, &addr, ADDR2),
+        LockResponse {
+            amount: Uint128::new(100),
+            end_height: Some(start_height + 50),
+        }
+    );
+
+    // the recipient's lock is extended to keep the transferred tokens locked
+    transfer_bond(&mut app, addr.clone(), ADDR1, ADDR2, 50, true).unwrap();
+    app.execute_contract(
+        Addr::unchecked(ADDR2),
+        addr.clone(),
+        &ExecuteMsg::AcceptBondTransfer {
+            sender: ADDR1.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        get_lock(&app, &addr, ADDR2),
+        LockResponse {
+            amount: Uint128::new(150),
+            end_height: Some(start_height + 100),
+        }
+    );
+    app.update_block(next_block);
+
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None).power,
+        Uint128::new(49)
+    );
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::new(147)
+    );
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr.clone(), None).power,
+        Uint128::new(196)
+    );
+
+    app.update_block(|b| b.height = start_height + 50);
+    let err = withdraw(&mut app, addr, ADDR2).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::LockNotExpired {
+            end: start_height + 100
+        }
+    );
+}
+
+#[test]
+fn test_delegation() {
+    let mut app = mock_app();
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
+        },
+    );
+    bond_tokens(&mut app, addr.clone(), ADDR1, 100, DENOM).unwrap();
+    bond_tokens(&mut app, addr.clone(), ADDR2, 50, DENOM).unwrap();
+    app.update_block(next_block);
+
+    let err = delegate(&mut app, addr.clone(), ADDR1, ADDR1).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::DelegationToSelf {}
+    );
+    let err = undelegate(&mut app, addr.clone(), ADDR2).unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NotDelegating {}
+    );
+
+    let delegation_height = app.block_info().height;
+    delegate(&mut app, addr.clone(), ADDR1, ADDR2).unwrap();
+    let delegation: DelegationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            addr.clone(),
+            &QueryMsg::Delegation {
+                address: ADDR1.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(delegation.delegate, Some(Addr::unchecked(ADDR2)));
+    app.update_block(next_block);
+
+    assert_eq!(
+        get_voting_power_at_height(
+            &mut app,
+            addr.clone(),
+            ADDR2.to_string(),
+            Some(delegation_height)
+        )
+        .power,
+        Uint128::new(50)
+    );
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None).power,
+        Uint128::zero()
+    );
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::new(150)
+    );
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr.clone(), None).power,
+        Uint128::new(150)
+    );
+
+    // changes of the delegator's balance are reflected in the delegate's power
+    bond_tokens(&mut app, addr.clone(), ADDR1, 20, DENOM).unwrap();
+    app.update_block(next_block);
+    let bond_height = app.block_info().height;
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::new(170)
+    );
+    unbond_tokens(&mut app, addr.clone(), ADDR1, 30).unwrap();
+    app.update_block(next_block);
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::new(140)
+    );
+
+    undelegate(&mut app, addr.clone(), ADDR1).unwrap();
+    app.update_block(next_block);
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None).power,
+        Uint128::new(90)
+    );
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::new(50)
+    );
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr, ADDR2.to_string(), Some(bond_height)).power,
+        Uint128::new(170)
+    );
+}
+
+#[test]
+fn test_delegation_vote_escrow() {
+    let mut app = mock_app();
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: Some(100),
+        },
+    );
+    let start_height = app.block_info().height;
+    bond_tokens_with_lock(&mut app, addr.clone(), ADDR1, 100, 100).unwrap();
+    bond_tokens_with_lock(&mut app, addr.clone(), ADDR2, 100, 50).unwrap();
+    delegate(&mut app, addr.clone(), ADDR1, ADDR2).unwrap();
+    app.update_block(next_block);
+
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None).power,
+        Uint128::zero()
+    );
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::new(148)
+    );
+
+    // the delegated power decays along with the delegator's lock
+    app.update_block(|b| b.height = start_height + 50);
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::new(50)
+    );
+    extend_lock(&mut app, addr.clone(), ADDR1, 100).unwrap();
+    app.update_block(|b| b.height = start_height + 60);
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::new(90)
+    );
+
+    undelegate(&mut app, addr.clone(), ADDR1).unwrap();
+    app.update_block(next_block);
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR1.to_string(), None).power,
+        Uint128::new(89)
+    );
+    assert_eq!(
+        get_voting_power_at_height(&mut app, addr.clone(), ADDR2.to_string(), None).power,
+        Uint128::zero()
+    );
+    assert_eq!(
+        get_voting_power_at_height(
+            &mut app,
+            addr.clone(),
+            ADDR2.to_string(),
+            Some(start_height + 55)
+        )
+        .power,
+        Uint128::new(95)
+    );
+    assert_eq!(
+        get_total_power_at_height(&mut app, addr, None).power,
+        Uint128::new(89)
+    );
+}
+
 #[test]
 #[should_panic(expected = "Unauthorized")]
 fn test_update_config_unauthorized() {
@@ -357,6 +1500,8 @@ fn test_update_config_unauthorized() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -384,6 +1529,8 @@ fn test_update_config_as_owner() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -405,6 +1552,8 @@ fn test_update_config_as_owner() {
             description: NEW_DESCRIPTION.to_string(),
             owner: Addr::unchecked(ADDR1),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
         config
     );
@@ -423,6 +1572,8 @@ fn test_update_config_invalid_description() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -451,6 +1602,8 @@ fn test_update_config_invalid_name() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -478,6 +1631,8 @@ fn test_query_dao() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -498,6 +1653,8 @@ fn test_query_info() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -518,6 +1675,8 @@ fn test_query_get_config() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -529,6 +1688,8 @@ fn test_query_get_config() {
             description: DESCRIPTION.to_string(),
             owner: Addr::unchecked(DAO_ADDR),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         }
     )
 }
@@ -545,6 +1706,8 @@ fn test_voting_power_queries() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -651,6 +1814,8 @@ fn test_query_list_bonders() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             denom: DENOM.to_string(),
+            unbonding_period: None,
+            max_lock_duration: None,
         },
     );
 
@@ -677,10 +1842,12 @@ fn test_query_list_bonders() {
             BonderBalanceResponse {
                 address: ADDR1.to_string(),
                 balance: Uint128::new(100),
+                balances: coins(100, DENOM),
             },
             BonderBalanceResponse {
                 address: ADDR2.to_string(),
                 balance: Uint128::new(50),
+                balances: coins(50, DENOM),
             },
         ],
     };
@@ -703,6 +1870,7 @@ fn test_query_list_bonders() {
         bonders: vec![BonderBalanceResponse {
             address: ADDR2.to_string(),
             balance: Uint128::new(50),
+            balances: coins(50, DENOM),
         }],
     };
 
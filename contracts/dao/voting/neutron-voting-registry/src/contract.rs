@@ -1,14 +1,21 @@
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, VotingVault};
-use crate::state::{Config, VotingVaultState, CONFIG, DAO, VAULT_STATES};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, PowerBreakdownResponse, QueryMsg, VaultPower,
+    VotingVault,
+};
+use crate::state::{
+    Config, VotingVaultState, CONFIG, DAO, REMOVED_VAULTS, VAULT_DEACTIVATION_HEIGHTS,
+    VAULT_STATES, VAULT_WEIGHTS,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::{
-    entry_point, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response,
-    StdError, StdResult,
+    entry_point, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult,
 };
 use cw2::set_contract_version;
 use cwd_interface::voting::{self, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
 use neutron_vault::msg::QueryMsg as VaultQueryMsg;
+use std::collections::BTreeSet;
 pub(crate) const CONTRACT_NAME: &str = "crates.io:neutron-voting-registry";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -32,14 +39,21 @@ pub fn instantiate(
         )?
     }
 
-    let config = Config { owner };
+    let config = Config {
+        owner,
+        vault_removal_delay: msg.vault_removal_delay,
+    };
 
     CONFIG.save(deps.storage, &config)?;
     DAO.save(deps.storage, &info.sender)?;
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
-        .add_attribute("owner", config.owner))
+        .add_attribute("owner", config.owner)
+        .add_attribute(
+            "vault_removal_delay",
+            config.vault_removal_delay.to_string(),
+        ))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -59,7 +73,27 @@ pub fn execute(
         ExecuteMsg::ActivateVotingVault {
             voting_vault_contract,
         } => execute_activate_voting_vault(deps, env, info, voting_vault_contract),
-        ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, info, owner),
+        ExecuteMsg::UpdateVotingVaultWeight {
+            voting_vault_contract,
+            weight,
+        } => execute_update_voting_vault_weight(deps, env, info, voting_vault_contract, weight),
+        ExecuteMsg::ReplaceVotingVault {
+            old_voting_vault_contract,
+            new_voting_vault_contract,
+        } => execute_replace_voting_vault(
+            deps,
+            env,
+            info,
+            old_voting_vault_contract,
+            new_voting_vault_contract,
+        ),
+        ExecuteMsg::RemoveVotingVault {
+            voting_vault_contract,
+        } => execute_remove_voting_vault(deps, env, info, voting_vault_contract),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            vault_removal_delay,
+        } => execute_update_config(deps, info, owner, vault_removal_delay),
     }
 }
 
@@ -83,10 +117,12 @@ pub fn execute_add_voting_vault(
     }
     VAULT_STATES.save(
         deps.storage,
-        voting_vault_contract_addr,
+        voting_vault_contract_addr.clone(),
         &VotingVaultState::Active,
         env.block.height,
     )?;
+    // A vault added again is a current one, its history is read from the snapshots.
+    REMOVED_VAULTS.remove(deps.storage, voting_vault_contract_addr);
 
     Ok(Response::new()
         .add_attribute("action", "add_voting_vault")
@@ -113,10 +149,11 @@ pub fn execute_deactivate_voting_vault(
 
     VAULT_STATES.save(
         deps.storage,
-        voting_vault_contract_addr,
+        voting_vault_contract_addr.clone(),
         &VotingVaultState::Inactive,
         env.block.height,
     )?;
+    VAULT_DEACTIVATION_HEIGHTS.save(deps.storage, voting_vault_contract_addr, &env.block.height)?;
 
     Ok(Response::new()
         .add_attribute("action", "deactivate_voting_vault")
@@ -143,20 +180,155 @@ pub fn execute_activate_voting_vault(
 
     VAULT_STATES.save(
         deps.storage,
-        voting_vault_contract_addr,
+        voting_vault_contract_addr.clone(),
         &VotingVaultState::Active,
         env.block.height,
     )?;
+    VAULT_DEACTIVATION_HEIGHTS.remove(deps.storage, voting_vault_contract_addr);
 
     Ok(Response::new()
         .add_attribute("action", "activate_voting_vault")
         .add_attribute("vault", voting_vault_contract))
 }
 
+pub fn execute_update_voting_vault_weight(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    voting_vault_contract: String,
+    weight: Decimal,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if weight.is_zero() {
+        return Err(ContractError::ZeroVotingVaultWeight {});
+    }
+
+    let voting_vault_contract_addr = deps.api.addr_validate(&voting_vault_contract)?;
+    // make sure the vault is known to the registry
+    VAULT_STATES.load(deps.storage, voting_vault_contract_addr.clone())?;
+
+    VAULT_WEIGHTS.save(
+        deps.storage,
+        voting_vault_contract_addr,
+        &weight,
+        env.block.height,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_voting_vault_weight")
+        .add_attribute("vault", voting_vault_contract)
+        .add_attribute("weight", weight.to_string()))
+}
+
+pub fn execute_replace_voting_vault(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    old_voting_vault_contract: String,
+    new_voting_vault_contract: String,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let old_vault = deps.api.addr_validate(&old_voting_vault_contract)?;
+    let new_vault = deps.api.addr_validate(&new_voting_vault_contract)?;
+
+    if VAULT_STATES.load(deps.storage, old_vault.clone())? == VotingVaultState::Inactive {
+        return Err(ContractError::VotingVaultAlreadyInactive {});
+    }
+    // the new vault is either a brand new one or an inactive one
+    if VAULT_STATES.may_load(deps.storage, new_vault.clone())? == Some(VotingVaultState::Active) {
+        return Err(ContractError::VotingVaultAlreadyActive {});
+    }
+
+    VAULT_STATES.save(
+        deps.storage,
+        old_vault.clone(),
+        &VotingVaultState::Inactive,
+        env.block.height,
+    )?;
+    VAULT_DEACTIVATION_HEIGHTS.save(deps.storage, old_vault.clone(), &env.block.height)?;
+
+    VAULT_STATES.save(
+        deps.storage,
+        new_vault.clone(),
+        &VotingVaultState::Active,
+        env.block.height,
+    )?;
+    VAULT_DEACTIVATION_HEIGHTS.remove(deps.storage, new_vault.clone());
+    REMOVED_VAULTS.remove(deps.storage, new_vault.clone());
+
+    if let Some(weight) = VAULT_WEIGHTS.may_load(deps.storage, old_vault)? {
+        VAULT_WEIGHTS.save(deps.storage, new_vault, &weight, env.block.height)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "replace_voting_vault")
+        .add_attribute("old_vault", old_voting_vault_contract)
+        .add_attribute("new_vault", new_voting_vault_contract))
+}
+
+pub fn execute_remove_voting_vault(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    voting_vault_contract: String,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let voting_vault_contract_addr = deps.api.addr_validate(&voting_vault_contract)?;
+
+    let vault_state = VAULT_STATES.load(deps.storage, voting_vault_contract_addr.clone())?;
+    if vault_state == VotingVaultState::Active {
+        return Err(ContractError::VotingVaultIsActive {});
+    }
+
+    let deactivated_at =
+        VAULT_DEACTIVATION_HEIGHTS.load(deps.storage, voting_vault_contract_addr.clone())?;
+    let removable_at = deactivated_at.saturating_add(config.vault_removal_delay);
+    if env.block.height < removable_at {
+        return Err(ContractError::VotingVaultRemovalTooEarly { removable_at });
+    }
+
+    // Snapshot maps keep the removed values in their changelogs, so the vault state and weight
+    // are still available at the heights before the removal.
+    VAULT_STATES.remove(
+        deps.storage,
+        voting_vault_contract_addr.clone(),
+        env.block.height,
+    )?;
+    if VAULT_WEIGHTS
+        .may_load(deps.storage, voting_vault_contract_addr.clone())?
+        .is_some()
+    {
+        VAULT_WEIGHTS.remove(
+            deps.storage,
+            voting_vault_contract_addr.clone(),
+            env.block.height,
+        )?;
+    }
+    VAULT_DEACTIVATION_HEIGHTS.remove(deps.storage, voting_vault_contract_addr.clone());
+    REMOVED_VAULTS.save(deps.storage, voting_vault_contract_addr, &env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_voting_vault")
+        .add_attribute("vault", voting_vault_contract))
+}
+
 pub fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
     new_owner: String,
+    vault_removal_delay: Option<u64>,
 ) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
     if info.sender != config.owner {
@@ -166,11 +338,18 @@ pub fn execute_update_config(
     let new_owner = deps.api.addr_validate(&new_owner)?;
 
     config.owner = new_owner;
+    if let Some(vault_removal_delay) = vault_removal_delay {
+        config.vault_removal_delay = vault_removal_delay;
+    }
 
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new()
         .add_attribute("action", "update_config")
-        .add_attribute("owner", config.owner))
+        .add_attribute("owner", config.owner)
+        .add_attribute(
+            "vault_removal_delay",
+            config.vault_removal_delay.to_string(),
+        ))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -188,6 +367,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::VotingVaults { height } => {
             to_json_binary(&query_voting_vaults(deps, env, height)?)
         }
+        QueryMsg::VotingPowerBreakdown { address, height } => {
+            to_json_binary(&query_voting_power_breakdown(deps, env, address, height)?)
+        }
+        QueryMsg::TotalPowerBreakdown { height } => {
+            to_json_binary(&query_total_power_breakdown(deps, env, height)?)
+        }
     }
 }
 
@@ -196,10 +381,8 @@ pub fn query_voting_vaults(
     env: Env,
     height: Option<u64>,
 ) -> StdResult<Vec<VotingVault>> {
-    let vaults = VAULT_STATES
-        .keys(deps.storage, None, None, Order::Ascending)
-        .collect::<Result<Vec<Addr>, StdError>>()?;
     let height = height.unwrap_or(env.block.height);
+    let vaults = get_vaults(deps, height)?;
 
     let mut voting_vaults: Vec<VotingVault> = vec![];
     for vault in vaults {
@@ -211,11 +394,14 @@ pub fn query_voting_vaults(
                 .querier
                 .query_wasm_smart(vault.clone(), &VaultQueryMsg::Name {})?;
 
+            let weight = get_vault_weight(deps, vault.clone(), height)?;
+
             voting_vaults.push(VotingVault {
                 address: vault.to_string(),
                 name,
                 description,
                 state,
+                weight,
             })
         }
     }
@@ -229,68 +415,119 @@ pub fn query_voting_power_at_height(
     address: String,
     height: Option<u64>,
 ) -> StdResult<VotingPowerAtHeightResponse> {
-    let vaults = VAULT_STATES
-        .keys(deps.storage, None, None, Order::Ascending)
-        .collect::<Result<Vec<Addr>, StdError>>()?;
+    let breakdown = query_voting_power_breakdown(deps, env, address, height)?;
+    Ok(VotingPowerAtHeightResponse {
+        power: breakdown.power,
+        height: breakdown.height,
+    })
+}
+
+pub fn query_total_power_at_height(
+    deps: Deps,
+    env: Env,
+    height: Option<u64>,
+) -> StdResult<TotalPowerAtHeightResponse> {
+    let breakdown = query_total_power_breakdown(deps, env, height)?;
+    Ok(TotalPowerAtHeightResponse {
+        power: breakdown.power,
+        height: breakdown.height,
+    })
+}
+
+pub fn query_voting_power_breakdown(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<PowerBreakdownResponse> {
     let height = height.unwrap_or(env.block.height);
 
-    let mut resp = VotingPowerAtHeightResponse {
+    let mut resp = PowerBreakdownResponse {
         power: Default::default(),
         height,
+        vaults: vec![],
     };
-    for vault in vaults {
-        if let Some(vault_state) =
-            VAULT_STATES.may_load_at_height(deps.storage, vault.clone(), height)?
-        {
-            if vault_state == VotingVaultState::Active {
-                let vp_in_vault: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
-                    vault,
-                    &voting::Query::VotingPowerAtHeight {
-                        height: Some(height),
-                        address: address.clone(),
-                    },
-                )?;
-
-                resp.power = resp.power.checked_add(vp_in_vault.power)?;
-            }
-        }
+    for (vault, weight) in get_active_vaults(deps, height)? {
+        let vp_in_vault: VotingPowerAtHeightResponse = deps.querier.query_wasm_smart(
+            vault.clone(),
+            &voting::Query::VotingPowerAtHeight {
+                height: Some(height),
+                address: address.clone(),
+            },
+        )?;
+
+        let vault_power = VaultPower::new(vault, vp_in_vault.power, weight);
+        resp.power = resp.power.checked_add(vault_power.weighted_power)?;
+        resp.vaults.push(vault_power);
     }
 
     Ok(resp)
 }
 
-pub fn query_total_power_at_height(
+pub fn query_total_power_breakdown(
     deps: Deps,
     env: Env,
     height: Option<u64>,
-) -> StdResult<TotalPowerAtHeightResponse> {
-    let vaults = VAULT_STATES
-        .keys(deps.storage, None, None, Order::Ascending)
-        .collect::<Result<Vec<Addr>, StdError>>()?;
+) -> StdResult<PowerBreakdownResponse> {
     let height = height.unwrap_or(env.block.height);
 
-    let mut resp = TotalPowerAtHeightResponse {
+    let mut resp = PowerBreakdownResponse {
         power: Default::default(),
         height,
+        vaults: vec![],
     };
+    for (vault, weight) in get_active_vaults(deps, height)? {
+        let vp_in_vault: TotalPowerAtHeightResponse = deps.querier.query_wasm_smart(
+            vault.clone(),
+            &voting::Query::TotalPowerAtHeight {
+                height: Some(height),
+            },
+        )?;
+
+        let vault_power = VaultPower::new(vault, vp_in_vault.power, weight);
+        resp.power = resp.power.checked_add(vault_power.weighted_power)?;
+        resp.vaults.push(vault_power);
+    }
+
+    Ok(resp)
+}
+
+/// Returns the vaults that might have been in the registry at the height: the current ones
+/// and the ones removed at or after the height.
+fn get_vaults(deps: Deps, height: u64) -> StdResult<BTreeSet<Addr>> {
+    let removed_vaults = REMOVED_VAULTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((vault, removed_at)) => (removed_at >= height).then_some(Ok(vault)),
+            Err(err) => Some(Err(err)),
+        });
+    VAULT_STATES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .chain(removed_vaults)
+        .collect::<Result<BTreeSet<Addr>, StdError>>()
+}
+
+/// Returns the vaults which are active at the height along with their weights.
+fn get_active_vaults(deps: Deps, height: u64) -> StdResult<Vec<(Addr, Decimal)>> {
+    let vaults = get_vaults(deps, height)?;
+
+    let mut active_vaults = vec![];
     for vault in vaults {
-        if let Some(vault_state) =
-            VAULT_STATES.may_load_at_height(deps.storage, vault.clone(), height)?
+        if VAULT_STATES.may_load_at_height(deps.storage, vault.clone(), height)?
+            == Some(VotingVaultState::Active)
         {
-            if vault_state == VotingVaultState::Active {
-                let vp_in_vault: TotalPowerAtHeightResponse = deps.querier.query_wasm_smart(
-                    vault,
-                    &voting::Query::TotalPowerAtHeight {
-                        height: Some(height),
-                    },
-                )?;
-
-                resp.power = resp.power.checked_add(vp_in_vault.power)?;
-            }
+            let weight = get_vault_weight(deps, vault.clone(), height)?;
+            active_vaults.push((vault, weight));
         }
     }
 
-    Ok(resp)
+    Ok(active_vaults)
+}
+
+fn get_vault_weight(deps: Deps, vault: Addr, height: u64) -> StdResult<Decimal> {
+    Ok(VAULT_WEIGHTS
+        .may_load_at_height(deps.storage, vault, height)?
+        .unwrap_or(Decimal::one()))
 }
 
 pub fn query_info(deps: Deps) -> StdResult<Binary> {
@@ -304,8 +541,22 @@ pub fn query_dao(deps: Deps) -> StdResult<Binary> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     // Set contract to version to latest
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // The deactivation heights of the vaults deactivated before the migration are unknown,
+    // so the removal delay is counted from the migration.
+    let inactive_vaults = VAULT_STATES
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok((_, VotingVaultState::Active))))
+        .map(|item| item.map(|(vault, _)| vault))
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for vault in inactive_vaults {
+        if !VAULT_DEACTIVATION_HEIGHTS.has(deps.storage, vault.clone()) {
+            VAULT_DEACTIVATION_HEIGHTS.save(deps.storage, vault, &env.block.height)?;
+        }
+    }
+
     Ok(Response::default())
 }
@@ -21,4 +21,13 @@ pub enum ContractError {
 
     #[error("Voting vault is already in the inactive state")]
     VotingVaultAlreadyInactive {},
+
+    #[error("Voting vault weight must be positive, deactivate the vault instead")]
+    ZeroVotingVaultWeight {},
+
+    #[error("Only inactive voting vaults can be removed")]
+    VotingVaultIsActive {},
+
+    #[error("Voting vault can't be removed until height {removable_at}")]
+    VotingVaultRemovalTooEarly { removable_at: u64 },
 }
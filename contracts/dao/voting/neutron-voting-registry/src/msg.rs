@@ -1,6 +1,6 @@
 use crate::state::VotingVaultState;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Decimal, Uint128};
 use cwd_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
@@ -14,15 +14,42 @@ pub struct InstantiateMsg {
     pub owner: String,
     // A list of addresses of relative voting vault contracts.
     pub voting_vaults: Vec<String>,
+    // The number of blocks a vault has to stay inactive before it can be removed.
+    #[serde(default)]
+    pub vault_removal_delay: u64,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    AddVotingVault { new_voting_vault_contract: String },
-    DeactivateVotingVault { voting_vault_contract: String },
-    ActivateVotingVault { voting_vault_contract: String },
-    UpdateConfig { owner: String },
+    AddVotingVault {
+        new_voting_vault_contract: String,
+    },
+    DeactivateVotingVault {
+        voting_vault_contract: String,
+    },
+    ActivateVotingVault {
+        voting_vault_contract: String,
+    },
+    UpdateVotingVaultWeight {
+        voting_vault_contract: String,
+        weight: Decimal,
+    },
+    /// Deactivates the old vault and activates the new one at the same height. The new vault
+    /// inherits the weight of the old one.
+    ReplaceVotingVault {
+        old_voting_vault_contract: String,
+        new_voting_vault_contract: String,
+    },
+    /// Removes the vault which has been inactive for at least `vault_removal_delay` blocks.
+    RemoveVotingVault {
+        voting_vault_contract: String,
+    },
+    UpdateConfig {
+        owner: String,
+        #[serde(default)]
+        vault_removal_delay: Option<u64>,
+    },
 }
 
 #[voting_query]
@@ -36,6 +63,15 @@ pub enum QueryMsg {
     Config {},
     #[returns(Vec<VotingVault>)]
     VotingVaults { height: Option<u64> },
+    /// Returns the voting power of the address in each of the active vaults.
+    #[returns(PowerBreakdownResponse)]
+    VotingPowerBreakdown {
+        address: String,
+        height: Option<u64>,
+    },
+    /// Returns the total power of each of the active vaults.
+    #[returns(PowerBreakdownResponse)]
+    TotalPowerBreakdown { height: Option<u64> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -47,4 +83,35 @@ pub struct VotingVault {
     pub name: String,
     pub description: String,
     pub state: VotingVaultState,
+    pub weight: Decimal,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct VaultPower {
+    pub address: String,
+    /// The power reported by the vault.
+    pub power: Uint128,
+    pub weight: Decimal,
+    /// The power multiplied by the vault weight, i.e. the vault contribution to the
+    /// registry power.
+    pub weighted_power: Uint128,
+}
+
+impl VaultPower {
+    pub fn new(address: Addr, power: Uint128, weight: Decimal) -> Self {
+        VaultPower {
+            address: address.to_string(),
+            power,
+            weight,
+            weighted_power: power.mul_floor(weight),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct PowerBreakdownResponse {
+    /// The sum of the weighted powers of the vaults.
+    pub power: Uint128,
+    pub height: u64,
+    pub vaults: Vec<VaultPower>,
 }
@@ -1,11 +1,14 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, SnapshotMap, Strategy};
+use cosmwasm_std::{Addr, Decimal};
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct Config {
     pub owner: Addr,
+    /// The number of blocks a vault has to stay inactive before it can be removed.
+    #[serde(default)]
+    pub vault_removal_delay: u64,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
@@ -19,6 +22,22 @@ pub const VAULT_STATES: SnapshotMap<Addr, VotingVaultState> = SnapshotMap::new(
     Strategy::EveryBlock,
 );
 
+/// Snapshots of vault weights throughout the chain life. Voting power of a vault is multiplied
+/// by its weight, vaults with no weight set have the weight of one.
+pub const VAULT_WEIGHTS: SnapshotMap<Addr, Decimal> = SnapshotMap::new(
+    "voting_vault_weight",
+    "voting_vault_weight__checkpoints",
+    "voting_vault_weight__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Heights at which the currently inactive vaults were deactivated.
+pub const VAULT_DEACTIVATION_HEIGHTS: Map<Addr, u64> = Map::new("voting_vault_deactivation_height");
+
+/// Heights at which the vaults were removed. Removed vaults are kept here to serve historical
+/// queries at heights when the vaults were still in the registry.
+pub const REMOVED_VAULTS: Map<Addr, u64> = Map::new("removed_voting_vaults");
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, Copy)]
 pub enum VotingVaultState {
     /// Voting vault is active means that it's considered in voting power queries to the
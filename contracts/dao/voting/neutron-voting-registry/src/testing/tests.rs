@@ -1,19 +1,22 @@
 use crate::contract::{execute, instantiate, query};
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, VotingVault};
-use crate::state::{Config, VotingVaultState};
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, PowerBreakdownResponse, QueryMsg, VaultPower, VotingVault,
+};
+use crate::state::{Config, VotingVaultState, REMOVED_VAULTS};
 use crate::testing::mock_querier::{
     mock_dependencies, MOCK_VAULT_1, MOCK_VAULT_1_DESC, MOCK_VAULT_1_NAME, MOCK_VAULT_1_VP,
     MOCK_VAULT_2, MOCK_VAULT_2_DESC, MOCK_VAULT_2_NAME, MOCK_VAULT_2_VP, MOCK_VAULT_3,
-    MOCK_VAULT_3_DESC, MOCK_VAULT_3_NAME, MOCK_VAULT_MEMBER,
+    MOCK_VAULT_3_DESC, MOCK_VAULT_3_NAME, MOCK_VAULT_3_VP, MOCK_VAULT_MEMBER,
 };
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{from_json, Addr, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{from_json, Addr, Decimal, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
 use cwd_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
 const DAO_ADDR: &str = "dao";
 const ADDR1: &str = "addr1";
+const VAULT_REMOVAL_DELAY: u64 = 100;
 
 #[test]
 fn test_instantiate() {
@@ -27,6 +30,7 @@ fn test_instantiate() {
         InstantiateMsg {
             owner: String::from(DAO_ADDR),
             voting_vaults: vec![MOCK_VAULT_1.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -34,7 +38,8 @@ fn test_instantiate() {
     assert_eq!(
         get_config(deps.as_ref(), env.clone()),
         Config {
-            owner: Addr::unchecked(String::from(DAO_ADDR))
+            owner: Addr::unchecked(String::from(DAO_ADDR)),
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         }
     );
 
@@ -45,6 +50,7 @@ fn test_instantiate() {
             name: String::from(MOCK_VAULT_1_NAME),
             description: String::from(MOCK_VAULT_1_DESC),
             state: VotingVaultState::Active,
+            weight: Decimal::one(),
         }]
     );
 }
@@ -65,6 +71,7 @@ fn test_instantiate_multiple_vaults() {
                 MOCK_VAULT_2.to_string(),
                 MOCK_VAULT_3.to_string(),
             ],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -72,7 +79,8 @@ fn test_instantiate_multiple_vaults() {
     assert_eq!(
         get_config(deps.as_ref(), env.clone()),
         Config {
-            owner: Addr::unchecked(String::from(DAO_ADDR))
+            owner: Addr::unchecked(String::from(DAO_ADDR)),
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         }
     );
 
@@ -84,18 +92,21 @@ fn test_instantiate_multiple_vaults() {
                 name: String::from(MOCK_VAULT_1_NAME),
                 description: String::from(MOCK_VAULT_1_DESC),
                 state: VotingVaultState::Active,
+                weight: Decimal::one(),
             },
             VotingVault {
                 address: String::from(MOCK_VAULT_2),
                 name: String::from(MOCK_VAULT_2_NAME),
                 description: String::from(MOCK_VAULT_2_DESC),
                 state: VotingVaultState::Active,
+                weight: Decimal::one(),
             },
             VotingVault {
                 address: String::from(MOCK_VAULT_3),
                 name: String::from(MOCK_VAULT_3_NAME),
                 description: String::from(MOCK_VAULT_3_DESC),
                 state: VotingVaultState::Active,
+                weight: Decimal::one(),
             }
         ]
     );
@@ -113,6 +124,7 @@ fn test_update_config_unauthorized() {
         InstantiateMsg {
             owner: String::from(DAO_ADDR),
             voting_vaults: vec![MOCK_VAULT_1.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -133,6 +145,7 @@ fn test_update_config_as_owner() {
         InstantiateMsg {
             owner: String::from(DAO_ADDR),
             voting_vaults: vec![MOCK_VAULT_1.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -142,6 +155,7 @@ fn test_update_config_as_owner() {
         get_config(deps.as_ref(), env),
         Config {
             owner: Addr::unchecked(ADDR1),
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         }
     );
 }
@@ -158,6 +172,7 @@ fn test_query_dao() {
         InstantiateMsg {
             owner: String::from(DAO_ADDR),
             voting_vaults: vec![MOCK_VAULT_1.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -177,6 +192,7 @@ fn test_query_info() {
         InstantiateMsg {
             owner: String::from(DAO_ADDR),
             voting_vaults: vec![MOCK_VAULT_1.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -199,6 +215,7 @@ fn test_query_get_config() {
         InstantiateMsg {
             owner: String::from(DAO_ADDR),
             voting_vaults: vec![MOCK_VAULT_1.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -207,6 +224,7 @@ fn test_query_get_config() {
         get_config(deps.as_ref(), env),
         Config {
             owner: Addr::unchecked(DAO_ADDR),
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         }
     )
 }
@@ -224,6 +242,7 @@ fn test_initial_voting_power() {
         InstantiateMsg {
             owner: String::from(DAO_ADDR),
             voting_vaults: vec![MOCK_VAULT_1.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -255,6 +274,7 @@ fn test_initial_voting_power() {
             name: String::from(MOCK_VAULT_1_NAME),
             description: String::from(MOCK_VAULT_1_DESC),
             state: VotingVaultState::Active,
+            weight: Decimal::one(),
         }],
     );
     assert_eq!(
@@ -285,6 +305,7 @@ fn test_add_vault() {
         InstantiateMsg {
             owner: String::from(DAO_ADDR),
             voting_vaults: vec![MOCK_VAULT_1.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -301,6 +322,7 @@ fn test_add_vault() {
             name: String::from(MOCK_VAULT_1_NAME),
             description: String::from(MOCK_VAULT_1_DESC),
             state: VotingVaultState::Active,
+            weight: Decimal::one(),
         }]
     );
     assert_eq!(
@@ -326,12 +348,14 @@ fn test_add_vault() {
                 name: String::from(MOCK_VAULT_1_NAME),
                 description: String::from(MOCK_VAULT_1_DESC),
                 state: VotingVaultState::Active,
+                weight: Decimal::one(),
             },
             VotingVault {
                 address: String::from(MOCK_VAULT_2),
                 name: String::from(MOCK_VAULT_2_NAME),
                 description: String::from(MOCK_VAULT_2_DESC),
                 state: VotingVaultState::Active,
+                weight: Decimal::one(),
             }
         ]
     );
@@ -363,6 +387,7 @@ fn test_add_vault_unauthorized() {
         InstantiateMsg {
             owner: String::from(DAO_ADDR),
             voting_vaults: vec![MOCK_VAULT_1.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -380,6 +405,7 @@ fn test_add_vault_unauthorized() {
             name: String::from(MOCK_VAULT_1_NAME),
             description: String::from(MOCK_VAULT_1_DESC),
             state: VotingVaultState::Active,
+            weight: Decimal::one(),
         }]
     );
 }
@@ -396,6 +422,7 @@ fn test_add_already_existing_vault() {
         InstantiateMsg {
             owner: String::from(DAO_ADDR),
             voting_vaults: vec![MOCK_VAULT_1.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -416,6 +443,7 @@ fn test_add_already_existing_vault() {
             name: String::from(MOCK_VAULT_1_NAME),
             description: String::from(MOCK_VAULT_1_DESC),
             state: VotingVaultState::Active,
+            weight: Decimal::one(),
         }]
     );
 }
@@ -433,6 +461,7 @@ fn test_vaults_activation_deactivation() {
         InstantiateMsg {
             owner: String::from(DAO_ADDR),
             voting_vaults: vec![MOCK_VAULT_1.to_string(), MOCK_VAULT_2.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -473,12 +502,14 @@ fn test_vaults_activation_deactivation() {
                 name: String::from(MOCK_VAULT_1_NAME),
                 description: String::from(MOCK_VAULT_1_DESC),
                 state: VotingVaultState::Active,
+                weight: Decimal::one(),
             },
             VotingVault {
                 address: String::from(MOCK_VAULT_2),
                 name: String::from(MOCK_VAULT_2_NAME),
                 description: String::from(MOCK_VAULT_2_DESC),
                 state: VotingVaultState::Active,
+                weight: Decimal::one(),
             }
         ]
     );
@@ -505,12 +536,14 @@ fn test_vaults_activation_deactivation() {
                 name: String::from(MOCK_VAULT_1_NAME),
                 description: String::from(MOCK_VAULT_1_DESC),
                 state: VotingVaultState::Inactive,
+                weight: Decimal::one(),
             },
             VotingVault {
                 address: String::from(MOCK_VAULT_2),
                 name: String::from(MOCK_VAULT_2_NAME),
                 description: String::from(MOCK_VAULT_2_DESC),
                 state: VotingVaultState::Active,
+                weight: Decimal::one(),
             }
         ]
     );
@@ -543,12 +576,14 @@ fn test_vaults_activation_deactivation() {
                 name: String::from(MOCK_VAULT_1_NAME),
                 description: String::from(MOCK_VAULT_1_DESC),
                 state: VotingVaultState::Inactive,
+                weight: Decimal::one(),
             },
             VotingVault {
                 address: String::from(MOCK_VAULT_2),
                 name: String::from(MOCK_VAULT_2_NAME),
                 description: String::from(MOCK_VAULT_2_DESC),
                 state: VotingVaultState::Active,
+                weight: Decimal::one(),
             }
         ]
     );
@@ -575,12 +610,14 @@ fn test_vaults_activation_deactivation() {
                 name: String::from(MOCK_VAULT_1_NAME),
                 description: String::from(MOCK_VAULT_1_DESC),
                 state: VotingVaultState::Active,
+                weight: Decimal::one(),
             },
             VotingVault {
                 address: String::from(MOCK_VAULT_2),
                 name: String::from(MOCK_VAULT_2_NAME),
                 description: String::from(MOCK_VAULT_2_DESC),
                 state: VotingVaultState::Active,
+                weight: Decimal::one(),
             }
         ]
     );
@@ -609,12 +646,14 @@ fn test_vaults_activation_deactivation() {
                 name: String::from(MOCK_VAULT_1_NAME),
                 description: String::from(MOCK_VAULT_1_DESC),
                 state: VotingVaultState::Inactive,
+                weight: Decimal::one(),
             },
             VotingVault {
                 address: String::from(MOCK_VAULT_2),
                 name: String::from(MOCK_VAULT_2_NAME),
                 description: String::from(MOCK_VAULT_2_DESC),
                 state: VotingVaultState::Active,
+                weight: Decimal::one(),
             }
         ]
     );
@@ -648,6 +687,7 @@ fn test_vaults_activation_deactivation_unauthorized() {
         InstantiateMsg {
             owner: String::from(DAO_ADDR),
             voting_vaults: vec![MOCK_VAULT_1.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -658,6 +698,7 @@ fn test_vaults_activation_deactivation_unauthorized() {
             name: String::from(MOCK_VAULT_1_NAME),
             description: String::from(MOCK_VAULT_1_DESC),
             state: VotingVaultState::Active,
+            weight: Decimal::one(),
         }],
     );
 
@@ -679,6 +720,7 @@ fn test_vaults_activation_deactivation_unauthorized() {
             name: String::from(MOCK_VAULT_1_NAME),
             description: String::from(MOCK_VAULT_1_DESC),
             state: VotingVaultState::Active,
+            weight: Decimal::one(),
         }],
     );
 
@@ -697,6 +739,7 @@ fn test_vaults_activation_deactivation_unauthorized() {
             name: String::from(MOCK_VAULT_1_NAME),
             description: String::from(MOCK_VAULT_1_DESC),
             state: VotingVaultState::Inactive,
+            weight: Decimal::one(),
         }],
     );
 
@@ -718,6 +761,7 @@ fn test_vaults_activation_deactivation_unauthorized() {
             name: String::from(MOCK_VAULT_1_NAME),
             description: String::from(MOCK_VAULT_1_DESC),
             state: VotingVaultState::Inactive,
+            weight: Decimal::one(),
         }],
     );
 }
@@ -735,6 +779,7 @@ fn test_vaults_activation_deactivation_wrong_switch() {
         InstantiateMsg {
             owner: String::from(DAO_ADDR),
             voting_vaults: vec![MOCK_VAULT_1.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
         },
     )
     .unwrap();
@@ -745,6 +790,7 @@ fn test_vaults_activation_deactivation_wrong_switch() {
             name: String::from(MOCK_VAULT_1_NAME),
             description: String::from(MOCK_VAULT_1_DESC),
             state: VotingVaultState::Active,
+            weight: Decimal::one(),
         }],
     );
 
@@ -778,6 +824,7 @@ fn test_vaults_activation_deactivation_wrong_switch() {
             name: String::from(MOCK_VAULT_1_NAME),
             description: String::from(MOCK_VAULT_1_DESC),
             state: VotingVaultState::Inactive,
+            weight: Decimal::one(),
         }],
     );
 
@@ -790,6 +837,478 @@ fn test_vaults_activation_deactivation_wrong_switch() {
     );
 }
 
+#[test]
+fn test_vault_weights() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let init_height = env.block.height;
+    let info = mock_info(DAO_ADDR, &[]);
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            owner: String::from(DAO_ADDR),
+            voting_vaults: vec![MOCK_VAULT_1.to_string(), MOCK_VAULT_2.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
+        },
+    )
+    .unwrap();
+
+    let weight = Decimal::from_ratio(3u128, 2u128);
+    let err = update_voting_vault_weight(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADDR1, &[]),
+        MOCK_VAULT_1.to_string(),
+        weight,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+
+    let err = update_voting_vault_weight(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        MOCK_VAULT_1.to_string(),
+        Decimal::zero(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ContractError::ZeroVotingVaultWeight {}.to_string()
+    );
+
+    // the vault must be known to the registry
+    update_voting_vault_weight(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        MOCK_VAULT_3.to_string(),
+        weight,
+    )
+    .unwrap_err();
+
+    let weight_height = init_height + 10;
+    env.block.height = weight_height;
+    update_voting_vault_weight(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        MOCK_VAULT_1.to_string(),
+        weight,
+    )
+    .unwrap();
+
+    // no voting power change at the height of the update
+    assert_eq!(
+        get_voting_power(
+            deps.as_ref(),
+            env.clone(),
+            String::from(MOCK_VAULT_MEMBER),
+            Some(weight_height)
+        )
+        .power,
+        Uint128::from(MOCK_VAULT_1_VP + MOCK_VAULT_2_VP),
+    );
+    assert_eq!(
+        get_total_voting_power(deps.as_ref(), env.clone(), Some(weight_height)).power,
+        Uint128::from(MOCK_VAULT_1_VP + MOCK_VAULT_2_VP),
+    );
+
+    // the vault 1 power is multiplied by its weight from the next height
+    assert_eq!(
+        get_voting_vaults(deps.as_ref(), env.clone(), Some(weight_height + 1)),
+        vec![
+            VotingVault {
+                address: String::from(MOCK_VAULT_1),
+                name: String::from(MOCK_VAULT_1_NAME),
+                description: String::from(MOCK_VAULT_1_DESC),
+                state: VotingVaultState::Active,
+                weight,
+            },
+            VotingVault {
+                address: String::from(MOCK_VAULT_2),
+                name: String::from(MOCK_VAULT_2_NAME),
+                description: String::from(MOCK_VAULT_2_DESC),
+                state: VotingVaultState::Active,
+                weight: Decimal::one(),
+            }
+        ]
+    );
+    assert_eq!(
+        get_voting_power(
+            deps.as_ref(),
+            env.clone(),
+            String::from(MOCK_VAULT_MEMBER),
+            Some(weight_height + 1)
+        )
+        .power,
+        Uint128::from(MOCK_VAULT_1_VP * 3 / 2 + MOCK_VAULT_2_VP),
+    );
+    assert_eq!(
+        get_total_voting_power(deps.as_ref(), env, Some(weight_height + 1)).power,
+        Uint128::from(MOCK_VAULT_1_VP * 3 / 2 + MOCK_VAULT_2_VP),
+    );
+}
+
+#[test]
+fn test_power_breakdown() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let info = mock_info(DAO_ADDR, &[]);
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            owner: String::from(DAO_ADDR),
+            voting_vaults: vec![
+                MOCK_VAULT_1.to_string(),
+                MOCK_VAULT_2.to_string(),
+                MOCK_VAULT_3.to_string(),
+            ],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
+        },
+    )
+    .unwrap();
+    let weight = Decimal::from_ratio(1u128, 2u128);
+    update_voting_vault_weight(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        MOCK_VAULT_2.to_string(),
+        weight,
+    )
+    .unwrap();
+    deactivate_voting_vault(deps.as_mut(), env.clone(), info, MOCK_VAULT_3.to_string()).unwrap();
+    env.block.height += 1;
+
+    // inactive vaults aren't listed
+    let expected_vaults = vec![
+        VaultPower {
+            address: String::from(MOCK_VAULT_1),
+            power: Uint128::from(MOCK_VAULT_1_VP),
+            weight: Decimal::one(),
+            weighted_power: Uint128::from(MOCK_VAULT_1_VP),
+        },
+        VaultPower {
+            address: String::from(MOCK_VAULT_2),
+            power: Uint128::from(MOCK_VAULT_2_VP),
+            weight,
+            weighted_power: Uint128::from(MOCK_VAULT_2_VP / 2),
+        },
+    ];
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::VotingPowerBreakdown {
+            address: String::from(MOCK_VAULT_MEMBER),
+            height: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        from_json::<PowerBreakdownResponse>(res).unwrap(),
+        PowerBreakdownResponse {
+            power: Uint128::from(MOCK_VAULT_1_VP + MOCK_VAULT_2_VP / 2),
+            height: env.block.height,
+            vaults: expected_vaults.clone(),
+        }
+    );
+    assert_eq!(
+        get_voting_power(
+            deps.as_ref(),
+            env.clone(),
+            String::from(MOCK_VAULT_MEMBER),
+            None
+        )
+        .power,
+        Uint128::from(MOCK_VAULT_1_VP + MOCK_VAULT_2_VP / 2),
+    );
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::TotalPowerBreakdown { height: None },
+    )
+    .unwrap();
+    assert_eq!(
+        from_json::<PowerBreakdownResponse>(res).unwrap(),
+        PowerBreakdownResponse {
+            power: Uint128::from(MOCK_VAULT_1_VP + MOCK_VAULT_2_VP / 2),
+            height: env.block.height,
+            vaults: expected_vaults,
+        }
+    );
+
+    // the address has no power in the vaults
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::VotingPowerBreakdown {
+            address: String::from(ADDR1),
+            height: None,
+        },
+    )
+    .unwrap();
+    let breakdown: PowerBreakdownResponse = from_json(res).unwrap();
+    assert_eq!(breakdown.power, Uint128::zero());
+    assert!(breakdown
+        .vaults
+        .iter()
+        .all(|vault| vault.weighted_power.is_zero()));
+}
+
+#[test]
+fn test_replace_voting_vault() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let init_height = env.block.height;
+    let info = mock_info(DAO_ADDR, &[]);
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            owner: String::from(DAO_ADDR),
+            voting_vaults: vec![MOCK_VAULT_1.to_string(), MOCK_VAULT_2.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
+        },
+    )
+    .unwrap();
+    let weight = Decimal::from_ratio(3u128, 2u128);
+    update_voting_vault_weight(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        MOCK_VAULT_1.to_string(),
+        weight,
+    )
+    .unwrap();
+
+    let err = replace_voting_vault(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADDR1, &[]),
+        MOCK_VAULT_1.to_string(),
+        MOCK_VAULT_3.to_string(),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+
+    // the successor must not be active
+    let err = replace_voting_vault(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        MOCK_VAULT_1.to_string(),
+        MOCK_VAULT_2.to_string(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ContractError::VotingVaultAlreadyActive {}.to_string()
+    );
+
+    let replace_height = init_height + 10;
+    env.block.height = replace_height;
+    replace_voting_vault(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        MOCK_VAULT_1.to_string(),
+        MOCK_VAULT_3.to_string(),
+    )
+    .unwrap();
+
+    // the old vault can't be replaced twice
+    let err = replace_voting_vault(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        MOCK_VAULT_1.to_string(),
+        MOCK_VAULT_3.to_string(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ContractError::VotingVaultAlreadyInactive {}.to_string()
+    );
+
+    assert_eq!(
+        get_voting_power(
+            deps.as_ref(),
+            env.clone(),
+            String::from(MOCK_VAULT_MEMBER),
+            Some(replace_height)
+        )
+        .power,
+        Uint128::from(MOCK_VAULT_1_VP * 3 / 2 + MOCK_VAULT_2_VP),
+    );
+    // the successor inherits the weight of the old vault
+    assert_eq!(
+        get_voting_vaults(deps.as_ref(), env.clone(), Some(replace_height + 1)),
+        vec![
+            VotingVault {
+                address: String::from(MOCK_VAULT_1),
+                name: String::from(MOCK_VAULT_1_NAME),
+                description: String::from(MOCK_VAULT_1_DESC),
+                state: VotingVaultState::Inactive,
+                weight,
+            },
+            VotingVault {
+                address: String::from(MOCK_VAULT_2),
+                name: String::from(MOCK_VAULT_2_NAME),
+                description: String::from(MOCK_VAULT_2_DESC),
+                state: VotingVaultState::Active,
+                weight: Decimal::one(),
+            },
+            VotingVault {
+                address: String::from(MOCK_VAULT_3),
+                name: String::from(MOCK_VAULT_3_NAME),
+                description: String::from(MOCK_VAULT_3_DESC),
+                state: VotingVaultState::Active,
+                weight,
+            }
+        ]
+    );
+    assert_eq!(
+        get_total_voting_power(deps.as_ref(), env, Some(replace_height + 1)).power,
+        Uint128::from(MOCK_VAULT_2_VP + MOCK_VAULT_3_VP * 3 / 2),
+    );
+}
+
+#[test]
+fn test_remove_voting_vault() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let init_height = env.block.height;
+    let info = mock_info(DAO_ADDR, &[]);
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        InstantiateMsg {
+            owner: String::from(DAO_ADDR),
+            voting_vaults: vec![MOCK_VAULT_1.to_string(), MOCK_VAULT_2.to_string()],
+            vault_removal_delay: VAULT_REMOVAL_DELAY,
+        },
+    )
+    .unwrap();
+
+    let err = remove_voting_vault(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        MOCK_VAULT_1.to_string(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ContractError::VotingVaultIsActive {}.to_string()
+    );
+
+    let deactivate_height = init_height + 10;
+    env.block.height = deactivate_height;
+    deactivate_voting_vault(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        MOCK_VAULT_1.to_string(),
+    )
+    .unwrap();
+
+    env.block.height = deactivate_height + VAULT_REMOVAL_DELAY - 1;
+    let err = remove_voting_vault(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        MOCK_VAULT_1.to_string(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ContractError::VotingVaultRemovalTooEarly {
+            removable_at: deactivate_height + VAULT_REMOVAL_DELAY
+        }
+        .to_string()
+    );
+
+    let err = remove_voting_vault(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADDR1, &[]),
+        MOCK_VAULT_1.to_string(),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+
+    let remove_height = deactivate_height + VAULT_REMOVAL_DELAY;
+    env.block.height = remove_height;
+    remove_voting_vault(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        MOCK_VAULT_1.to_string(),
+    )
+    .unwrap();
+
+    // the removed vault isn't listed anymore
+    assert_eq!(
+        get_voting_vaults(deps.as_ref(), env.clone(), Some(remove_height + 1)),
+        vec![VotingVault {
+            address: String::from(MOCK_VAULT_2),
+            name: String::from(MOCK_VAULT_2_NAME),
+            description: String::from(MOCK_VAULT_2_DESC),
+            state: VotingVaultState::Active,
+            weight: Decimal::one(),
+        }]
+    );
+    // but the history is kept
+    assert_eq!(
+        get_voting_vaults(deps.as_ref(), env.clone(), Some(deactivate_height)).len(),
+        2
+    );
+    assert_eq!(
+        get_voting_power(
+            deps.as_ref(),
+            env.clone(),
+            String::from(MOCK_VAULT_MEMBER),
+            Some(deactivate_height)
+        )
+        .power,
+        Uint128::from(MOCK_VAULT_1_VP + MOCK_VAULT_2_VP),
+    );
+    assert_eq!(
+        get_total_voting_power(deps.as_ref(), env.clone(), Some(deactivate_height)).power,
+        Uint128::from(MOCK_VAULT_1_VP + MOCK_VAULT_2_VP),
+    );
+    assert_eq!(
+        get_total_voting_power(deps.as_ref(), env.clone(), Some(remove_height + 1)).power,
+        Uint128::from(MOCK_VAULT_2_VP),
+    );
+
+    // the removed vault can't be activated, but can be added again
+    activate_voting_vault(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        MOCK_VAULT_1.to_string(),
+    )
+    .unwrap_err();
+    add_voting_vault(deps.as_mut(), env.clone(), info, MOCK_VAULT_1.to_string()).unwrap();
+    assert!(!REMOVED_VAULTS.has(deps.as_ref().storage, Addr::unchecked(MOCK_VAULT_1)));
+    assert_eq!(
+        get_total_voting_power(deps.as_ref(), env.clone(), Some(remove_height + 1)).power,
+        Uint128::from(MOCK_VAULT_1_VP + MOCK_VAULT_2_VP),
+    );
+    assert_eq!(
+        get_total_voting_power(deps.as_ref(), env, Some(deactivate_height)).power,
+        Uint128::from(MOCK_VAULT_1_VP + MOCK_VAULT_2_VP),
+    );
+}
+
 fn get_voting_vaults(deps: Deps, env: Env, height: Option<u64>) -> Vec<VotingVault> {
     let res = query(deps, env, QueryMsg::VotingVaults { height }).unwrap();
     from_json(res).unwrap()
@@ -879,5 +1398,65 @@ fn update_config(
     info: MessageInfo,
     owner: String,
 ) -> Result<Response, ContractError> {
-    execute(deps, env, info, ExecuteMsg::UpdateConfig { owner })
+    execute(
+        deps,
+        env,
+        info,
+        ExecuteMsg::UpdateConfig {
+            owner,
+            vault_removal_delay: None,
+        },
+    )
+}
+
+fn update_voting_vault_weight(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vault: String,
+    weight: Decimal,
+) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        env,
+        info,
+        ExecuteMsg::UpdateVotingVaultWeight {
+            voting_vault_contract: vault,
+            weight,
+        },
+    )
+}
+
+fn replace_voting_vault(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    old_vault: String,
+    new_vault: String,
+) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        env,
+        info,
+        ExecuteMsg::ReplaceVotingVault {
+            old_voting_vault_contract: old_vault,
+            new_voting_vault_contract: new_vault,
+        },
+    )
+}
+
+fn remove_voting_vault(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vault: String,
+) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        env,
+        info,
+        ExecuteMsg::RemoveVotingVault {
+            voting_vault_contract: vault,
+        },
+    )
 }
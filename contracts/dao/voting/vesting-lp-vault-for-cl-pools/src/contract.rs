@@ -2,7 +2,7 @@ use astroport::asset::AssetInfo;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, Uint128,
+    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, Uint128,
 };
 use cw2::set_contract_version;
 use cwd_interface::voting::{
@@ -16,7 +16,10 @@ use neutron_vesting_lp_vault_for_cl_pools::{
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     types::Config,
 };
-use neutron_voting_power::voting_power::voting_power_from_lp_tokens;
+use neutron_voting_power::snapshot::load_at_height;
+use neutron_voting_power::voting_power::{
+    cl_pool_has_denom, voting_power_from_lp_tokens_with_valuation, ClPoolValuation,
+};
 use vesting_base::msg::{QueryMsg as VestingLpQueryMsg, QueryMsgHistorical};
 use vesting_base::types::Config as VestingBaseConfig;
 
@@ -42,9 +45,14 @@ pub fn instantiate(
         usdc_vesting_lp_contract: deps.api.addr_validate(&msg.usdc_vesting_lp_contract)?,
         usdc_cl_pool_contract: deps.api.addr_validate(&msg.usdc_cl_pool_contract)?,
         owner,
+        valuation: msg.valuation,
+        denom: msg.denom,
     };
     config.validate()?;
-    CONFIG.save(deps.storage, &config)?;
+    validate_denom(deps.as_ref(), &config)?;
+    // the vault only reads the vesting LP and pools' history, so the initial config counts at all
+    // the heights before the first update
+    CONFIG.save(deps.storage, &config, 0)?;
     DAO.save(deps.storage, &info.sender)?;
 
     Ok(Response::new()
@@ -55,7 +63,8 @@ pub fn instantiate(
         .add_attribute("atom_vesting_lp_contract", config.atom_vesting_lp_contract)
         .add_attribute("atom_cl_pool_contract", config.atom_cl_pool_contract)
         .add_attribute("usdc_vesting_lp_contract", config.usdc_vesting_lp_contract)
-        .add_attribute("usdc_cl_pool_contract", config.usdc_cl_pool_contract))
+        .add_attribute("usdc_cl_pool_contract", config.usdc_cl_pool_contract)
+        .add_attribute("denom", config.denom))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -76,8 +85,11 @@ pub fn execute(
             usdc_cl_pool_contract: usdc_oracle_contract,
             name,
             description,
+            valuation,
+            denom,
         } => execute_update_config(
             deps,
+            env,
             info,
             owner,
             atom_vesting_lp_contract,
@@ -86,6 +98,8 @@ pub fn execute(
             usdc_oracle_contract,
             name,
             description,
+            valuation,
+            denom,
         ),
     }
 }
@@ -106,6 +120,7 @@ pub fn execute_unbond(
 #[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     new_owner: String,
     new_atom_vesting_lp_contract: String,
@@ -114,6 +129,8 @@ pub fn execute_update_config(
     new_usdc_oracle_contract: String,
     new_name: String,
     new_description: String,
+    new_valuation: Option<ClPoolValuation>,
+    new_denom: Option<String>,
 ) -> ContractResult<Response> {
     let mut config: Config = CONFIG.load(deps.storage)?;
     if info.sender != config.owner {
@@ -133,8 +150,15 @@ pub fn execute_update_config(
     config.usdc_cl_pool_contract = new_usdc_oracle_contract;
     config.name = new_name;
     config.description = new_description;
+    if let Some(valuation) = new_valuation {
+        config.valuation = valuation;
+    }
+    if let Some(denom) = new_denom {
+        config.denom = denom;
+    }
     config.validate()?;
-    CONFIG.save(deps.storage, &config)?;
+    validate_denom(deps.as_ref(), &config)?;
+    CONFIG.save(deps.storage, &config, env.block.height)?;
 
     Ok(Response::new()
         .add_attribute("action", "update_config")
@@ -143,7 +167,21 @@ pub fn execute_update_config(
         .add_attribute("atom_vesting_lp_contract", config.atom_vesting_lp_contract)
         .add_attribute("atom_oracle_contract", config.atom_cl_pool_contract)
         .add_attribute("usdc_vesting_lp_contract", config.usdc_vesting_lp_contract)
-        .add_attribute("usdc_oracle_contract", config.usdc_cl_pool_contract))
+        .add_attribute("usdc_oracle_contract", config.usdc_cl_pool_contract)
+        .add_attribute("denom", config.denom))
+}
+
+/// checks whether both CL pools hold the denom LP tokens are valued in.
+fn validate_denom(deps: Deps, config: &Config) -> ContractResult<()> {
+    for pool_contract in [&config.atom_cl_pool_contract, &config.usdc_cl_pool_contract] {
+        if !cl_pool_has_denom(deps, pool_contract, &config.denom)? {
+            return Err(ContractError::DenomNotInPair {
+                denom: config.denom.clone(),
+                contract: pool_contract.to_string(),
+            });
+        }
+    }
+    Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -198,19 +236,16 @@ fn get_voting_power(
             ))));
         };
 
-        let lp_total_supply: Uint128 = deps.querier.query_wasm_smart(
-            lp_token_address.to_string(),
-            &astroport::xastro_token::QueryMsg::TotalSupplyAt { block: height },
-        )?;
-
-        voting_power = voting_power.checked_add(voting_power_from_lp_tokens(
+        voting_power = voting_power.checked_add(voting_power_from_lp_tokens_with_valuation(
             deps,
             deps.querier
                 .query_wasm_smart::<Option<Uint128>>(vesting_lp, &query_msg)?
                 .unwrap_or_default(),
-            lp_total_supply,
+            &Addr::unchecked(lp_token_address.to_string()),
             cl_pool,
+            &config.denom,
             height,
+            &config.valuation,
         )?)?;
     }
     Ok(voting_power)
@@ -222,8 +257,8 @@ pub fn query_voting_power_at_height(
     address: String,
     height: Option<u64>,
 ) -> ContractResult<VotingPowerAtHeightResponse> {
-    let config = CONFIG.load(deps.storage)?;
     let height = height.unwrap_or(env.block.height);
+    let config = load_at_height(deps.storage, &CONFIG, height)?;
     let query_msg = VestingLpQueryMsg::HistoricalExtension {
         msg: QueryMsgHistorical::UnclaimedAmountAtHeight { address, height },
     };
@@ -239,8 +274,8 @@ pub fn query_total_power_at_height(
     env: Env,
     height: Option<u64>,
 ) -> ContractResult<TotalPowerAtHeightResponse> {
-    let config = CONFIG.load(deps.storage)?;
     let height = height.unwrap_or(env.block.height);
+    let config = load_at_height(deps.storage, &CONFIG, height)?;
     let query_msg = VestingLpQueryMsg::HistoricalExtension {
         msg: QueryMsgHistorical::UnclaimedTotalAmountAtHeight { height },
     };
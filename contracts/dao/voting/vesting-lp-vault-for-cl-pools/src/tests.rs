@@ -1,15 +1,24 @@
 use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
-use astroport::asset::AssetInfo;
+use astroport::asset::{AssetInfo, PairInfo};
+use astroport::factory::PairType;
+use astroport::pair_concentrated::QueryMsg as ClPoolQueryMsg;
 use cosmwasm_std::testing::{mock_dependencies, mock_env};
-use cosmwasm_std::{coins, Addr, Coin, Empty, Uint128};
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, Binary, Coin, Deps, Empty, Env, Response, StdError, StdResult,
+    Uint128,
+};
 use cw_multi_test::{custom_app, App, AppResponse, Contract, ContractWrapper, Executor};
 use cwd_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
 use neutron_vesting_lp_vault_for_cl_pools::{
+    error::ContractError,
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     types::Config,
 };
+use neutron_voting_power::voting_power::ClPoolValuation;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 const DAO_ADDR: &str = "dao";
 const NAME: &str = "name";
@@ -18,8 +27,6 @@ const DESCRIPTION: &str = "description";
 const NEW_DESCRIPTION: &str = "new description";
 const ATOM_VESTING_LP_ADDR: &str = "atom_vesting_lp";
 const USDC_VESTING_LP_ADDR: &str = "usdc_vesting_lp";
-const ATOM_CL_POOL_ADDR: &str = "atom_cl_pool";
-const USDC_CL_POOL_ADDR: &str = "usdc_cl_pool";
 const NEW_ATOM_VESTING_LP_ADDR: &str = "new_atom_vesting_lp";
 const NEW_USDC_VESTING_LP_ADDR: &str = "new_usdc_vesting_lp";
 const NEW_ATOM_CL_POOL_ADDR: &str = "new_atom_cl_pool";
@@ -27,6 +34,8 @@ const NEW_USDC_CL_POOL_ADDR: &str = "new_usdc_cl_pool";
 const ADDR1: &str = "addr1";
 const ADDR2: &str = "addr2";
 const DENOM: &str = "ujuno";
+const NTRN_DENOM: &str = "untrn";
+const SUBDAO_DENOM: &str = "usubdao";
 const INIT_BALANCE: Uint128 = Uint128::new(10000);
 
 fn vault_contract() -> Box<dyn Contract<Empty>> {
@@ -91,6 +100,58 @@ fn mock_app() -> App {
     })
 }
 
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct EmptyMsg {}
+
+fn cl_pool_query(_deps: Deps, env: Env, msg: ClPoolQueryMsg) -> StdResult<Binary> {
+    match msg {
+        ClPoolQueryMsg::Pair {} => to_json_binary(&PairInfo {
+            asset_infos: vec![
+                AssetInfo::NativeToken {
+                    denom: NTRN_DENOM.to_string(),
+                },
+                AssetInfo::NativeToken {
+                    denom: SUBDAO_DENOM.to_string(),
+                },
+            ],
+            contract_addr: env.contract.address,
+            liquidity_token: Addr::unchecked("lp_token"),
+            pair_type: PairType::Custom("concentrated".to_string()),
+        }),
+        _ => unreachable!(),
+    }
+}
+
+fn cl_pool_contract() -> Box<dyn Contract<Empty>> {
+    let contract: ContractWrapper<
+        EmptyMsg,
+        EmptyMsg,
+        ClPoolQueryMsg,
+        ContractError,
+        ContractError,
+        StdError,
+    > = ContractWrapper::new(
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        cl_pool_query,
+    );
+    Box::new(contract)
+}
+
+fn instantiate_cl_pool_contract(app: &mut App) -> Addr {
+    let contract_id = app.store_code(cl_pool_contract());
+    app.instantiate_contract(
+        contract_id,
+        Addr::unchecked(DAO_ADDR),
+        &EmptyMsg {},
+        &[],
+        "cl pool contract",
+        None,
+    )
+    .unwrap()
+}
+
 fn instantiate_vault(app: &mut App, id: u64, msg: InstantiateMsg) -> Addr {
     app.instantiate_contract(id, Addr::unchecked(DAO_ADDR), &msg, &[], "vault", None)
         .unwrap()
@@ -168,6 +229,8 @@ fn update_config(
     usdc_cl_pool_contract: String,
     name: String,
     description: String,
+    valuation: Option<ClPoolValuation>,
+    denom: Option<String>,
 ) -> anyhow::Result<AppResponse> {
     app.execute_contract(
         Addr::unchecked(sender),
@@ -180,6 +243,8 @@ fn update_config(
             usdc_cl_pool_contract,
             name,
             description,
+            valuation,
+            denom,
         },
         &[],
     )
@@ -224,6 +289,8 @@ fn get_dao(app: &App, contract_addr: &Addr) -> String {
 #[test]
 fn test_instantiate() {
     let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     // Populated fields
     let addr = instantiate_vault(
@@ -234,9 +301,11 @@ fn test_instantiate() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
     assert_eq!(get_dao(&app, &addr), String::from(DAO_ADDR));
@@ -250,9 +319,11 @@ fn test_instantiate() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
     assert_eq!(get_dao(&app, &addr), String::from(DAO_ADDR));
@@ -262,6 +333,8 @@ fn test_instantiate() {
 #[should_panic(expected = "Bonding is not available for this contract")]
 fn test_bond() {
     let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -271,9 +344,11 @@ fn test_bond() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -285,6 +360,8 @@ fn test_bond() {
 #[should_panic(expected = "Direct unbonding is not available for this contract")]
 fn test_unbond() {
     let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -294,9 +371,11 @@ fn test_unbond() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -307,6 +386,8 @@ fn test_unbond() {
 #[should_panic(expected = "Unauthorized")]
 fn test_update_config_unauthorized() {
     let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -316,9 +397,11 @@ fn test_update_config_unauthorized() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -334,6 +417,8 @@ fn test_update_config_unauthorized() {
         NEW_USDC_CL_POOL_ADDR.to_string(),
         NEW_NAME.to_string(),
         NEW_DESCRIPTION.to_string(),
+        None,
+        None,
     )
     .unwrap();
 }
@@ -341,6 +426,10 @@ fn test_update_config_unauthorized() {
 #[test]
 fn test_update_config() {
     let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let new_atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let new_usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -350,24 +439,31 @@ fn test_update_config() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
-    // Change owner, description, name, lp-vesting and cl pool contracts
+    // Change owner, description, name, lp-vesting and cl pool contracts, valuation and denom
     update_config(
         &mut app,
         addr.clone(),
         DAO_ADDR,
         ADDR1.to_string(),
         NEW_ATOM_VESTING_LP_ADDR.to_string(),
-        NEW_ATOM_CL_POOL_ADDR.to_string(),
+        new_atom_cl_pool_contract.to_string(),
         NEW_USDC_VESTING_LP_ADDR.to_string(),
-        NEW_USDC_CL_POOL_ADDR.to_string(),
+        new_usdc_cl_pool_contract.to_string(),
         NEW_NAME.to_string(),
         NEW_DESCRIPTION.to_string(),
+        Some(ClPoolValuation::Median {
+            window: 10,
+            samples: 5,
+        }),
+        Some(SUBDAO_DENOM.to_string()),
     )
     .unwrap();
 
@@ -378,9 +474,14 @@ fn test_update_config() {
             description: NEW_DESCRIPTION.to_string(),
             owner: Addr::unchecked(ADDR1),
             atom_vesting_lp_contract: Addr::unchecked(NEW_ATOM_VESTING_LP_ADDR),
-            atom_cl_pool_contract: Addr::unchecked(NEW_ATOM_CL_POOL_ADDR),
+            atom_cl_pool_contract: new_atom_cl_pool_contract,
             usdc_vesting_lp_contract: Addr::unchecked(NEW_USDC_VESTING_LP_ADDR),
-            usdc_cl_pool_contract: Addr::unchecked(NEW_USDC_CL_POOL_ADDR),
+            usdc_cl_pool_contract: new_usdc_cl_pool_contract,
+            valuation: ClPoolValuation::Median {
+                window: 10,
+                samples: 5,
+            },
+            denom: SUBDAO_DENOM.to_string(),
         },
         config
     );
@@ -390,6 +491,8 @@ fn test_update_config() {
 #[should_panic(expected = "config description cannot be empty.")]
 fn test_update_config_invalid_description() {
     let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -399,9 +502,11 @@ fn test_update_config_invalid_description() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -412,11 +517,13 @@ fn test_update_config_invalid_description() {
         DAO_ADDR,
         DAO_ADDR.to_string(),
         ATOM_VESTING_LP_ADDR.to_string(),
-        ATOM_CL_POOL_ADDR.to_string(),
+        atom_cl_pool_contract.to_string(),
         USDC_VESTING_LP_ADDR.to_string(),
-        USDC_CL_POOL_ADDR.to_string(),
+        usdc_cl_pool_contract.to_string(),
         NEW_NAME.to_string(),
         String::from(""),
+        None,
+        None,
     )
     .unwrap();
 }
@@ -425,6 +532,8 @@ fn test_update_config_invalid_description() {
 #[should_panic(expected = "config name cannot be empty.")]
 fn test_update_config_invalid_name() {
     let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -434,9 +543,11 @@ fn test_update_config_invalid_name() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -447,11 +558,13 @@ fn test_update_config_invalid_name() {
         DAO_ADDR,
         DAO_ADDR.to_string(),
         ATOM_VESTING_LP_ADDR.to_string(),
-        ATOM_CL_POOL_ADDR.to_string(),
+        atom_cl_pool_contract.to_string(),
         USDC_VESTING_LP_ADDR.to_string(),
-        USDC_CL_POOL_ADDR.to_string(),
+        usdc_cl_pool_contract.to_string(),
         String::from(""),
         NEW_DESCRIPTION.to_string(),
+        None,
+        None,
     )
     .unwrap();
 }
@@ -459,6 +572,8 @@ fn test_update_config_invalid_name() {
 #[test]
 fn test_query_dao() {
     let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -468,9 +583,11 @@ fn test_query_dao() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -482,6 +599,8 @@ fn test_query_dao() {
 #[test]
 fn test_query_info() {
     let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -491,9 +610,11 @@ fn test_query_info() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -508,6 +629,8 @@ fn test_query_info() {
 #[test]
 fn test_query_get_config() {
     let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -517,9 +640,11 @@ fn test_query_get_config() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -531,9 +656,11 @@ fn test_query_get_config() {
             description: DESCRIPTION.to_string(),
             owner: Addr::unchecked(DAO_ADDR),
             atom_vesting_lp_contract: Addr::unchecked(ATOM_VESTING_LP_ADDR),
-            atom_cl_pool_contract: Addr::unchecked(ATOM_CL_POOL_ADDR),
+            atom_cl_pool_contract: atom_cl_pool_contract,
             usdc_vesting_lp_contract: Addr::unchecked(USDC_VESTING_LP_ADDR),
-            usdc_cl_pool_contract: Addr::unchecked(USDC_CL_POOL_ADDR),
+            usdc_cl_pool_contract: usdc_cl_pool_contract,
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         }
     )
 }
@@ -541,6 +668,8 @@ fn test_query_get_config() {
 #[test]
 fn test_voting_power_at_height() {
     let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
 
     let vesting_lp_id = app.store_code(vesting_lp_contract());
     let lp_token_id = app.store_code(lp_token_contract());
@@ -615,9 +744,11 @@ fn test_voting_power_at_height() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: atom_vesting_lp_addr.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
             usdc_vesting_lp_contract: usdc_vesting_lp_addr.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -629,6 +760,8 @@ fn test_voting_power_at_height() {
 #[test]
 fn test_total_power_at_height() {
     let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
 
     let vesting_lp_id = app.store_code(vesting_lp_contract());
     let lp_token_id = app.store_code(lp_token_contract());
@@ -703,9 +836,11 @@ fn test_total_power_at_height() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: atom_vesting_lp_addr.to_string(),
-            atom_cl_pool_contract: ATOM_CL_POOL_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
             usdc_vesting_lp_contract: usdc_vesting_lp_addr.to_string(),
-            usdc_cl_pool_contract: USDC_CL_POOL_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -714,6 +849,70 @@ fn test_total_power_at_height() {
     assert!(resp.power.is_zero());
 }
 
+#[test]
+#[should_panic(expected = "config denom uatom is not an asset of the contract0 pair.")]
+fn test_instantiate_denom_not_in_pair() {
+    let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let vault_id = app.store_code(vault_contract());
+    instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: String::from("uatom"),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "config denom uatom is not an asset of the contract0 pair.")]
+fn test_update_config_denom_not_in_pair() {
+    let mut app = mock_app();
+    let atom_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let usdc_cl_pool_contract = instantiate_cl_pool_contract(&mut app);
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
+            atom_cl_pool_contract: atom_cl_pool_contract.to_string(),
+            usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
+            usdc_cl_pool_contract: usdc_cl_pool_contract.to_string(),
+            valuation: ClPoolValuation::default(),
+            denom: NTRN_DENOM.to_string(),
+        },
+    );
+
+    update_config(
+        &mut app,
+        addr,
+        DAO_ADDR,
+        DAO_ADDR.to_string(),
+        ATOM_VESTING_LP_ADDR.to_string(),
+        atom_cl_pool_contract.to_string(),
+        USDC_VESTING_LP_ADDR.to_string(),
+        usdc_cl_pool_contract.to_string(),
+        NAME.to_string(),
+        DESCRIPTION.to_string(),
+        None,
+        Some(String::from("uatom")),
+    )
+    .unwrap();
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
@@ -11,12 +11,13 @@ use cwd_interface::voting::{
 use serde::Serialize;
 
 use crate::state::{CONFIG, DAO};
-use neutron_oracle::voting_power::voting_power_from_lp_tokens;
+use neutron_oracle::voting_power::{pair_has_denom, voting_power_from_lp_tokens};
 use neutron_vesting_lp_vault::{
     error::{ContractError, ContractResult},
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     types::Config,
 };
+use neutron_voting_power::snapshot::load_at_height;
 use vesting_base::msg::{QueryMsg as VestingLpQueryMsg, QueryMsgHistorical};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:neutron-vesting-lp-vault";
@@ -25,7 +26,7 @@ pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> ContractResult<Response> {
@@ -41,9 +42,13 @@ pub fn instantiate(
         usdc_vesting_lp_contract: deps.api.addr_validate(&msg.usdc_vesting_lp_contract)?,
         usdc_oracle_contract: deps.api.addr_validate(&msg.usdc_oracle_contract)?,
         owner,
+        denom: msg.denom,
     };
     config.validate()?;
-    CONFIG.save(deps.storage, &config)?;
+    validate_denom(deps.as_ref(), &config, env.block.height)?;
+    // the vault only reads the vesting LP and oracles' history, so the initial config counts at all
+    // the heights before the first update
+    CONFIG.save(deps.storage, &config, 0)?;
     DAO.save(deps.storage, &info.sender)?;
 
     Ok(Response::new()
@@ -54,7 +59,8 @@ pub fn instantiate(
         .add_attribute("atom_vesting_lp_contract", config.atom_vesting_lp_contract)
         .add_attribute("atom_oracle_contract", config.atom_oracle_contract)
         .add_attribute("usdc_vesting_lp_contract", config.usdc_vesting_lp_contract)
-        .add_attribute("usdc_oracle_contract", config.usdc_oracle_contract))
+        .add_attribute("usdc_oracle_contract", config.usdc_oracle_contract)
+        .add_attribute("denom", config.denom))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -75,8 +81,10 @@ pub fn execute(
             usdc_oracle_contract,
             name,
             description,
+            denom,
         } => execute_update_config(
             deps,
+            env,
             info,
             owner,
             atom_vesting_lp_contract,
@@ -85,6 +93,7 @@ pub fn execute(
             usdc_oracle_contract,
             name,
             description,
+            denom,
         ),
     }
 }
@@ -105,6 +114,7 @@ pub fn execute_unbond(
 #[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     new_owner: String,
     new_atom_vesting_lp_contract: String,
@@ -113,6 +123,7 @@ pub fn execute_update_config(
     new_usdc_oracle_contract: String,
     new_name: String,
     new_description: String,
+    new_denom: Option<String>,
 ) -> ContractResult<Response> {
     let mut config: Config = CONFIG.load(deps.storage)?;
     if info.sender != config.owner {
@@ -132,8 +143,12 @@ pub fn execute_update_config(
     config.usdc_oracle_contract = new_usdc_oracle_contract;
     config.name = new_name;
     config.description = new_description;
+    if let Some(denom) = new_denom {
+        config.denom = denom;
+    }
     config.validate()?;
-    CONFIG.save(deps.storage, &config)?;
+    validate_denom(deps.as_ref(), &config, env.block.height)?;
+    CONFIG.save(deps.storage, &config, env.block.height)?;
 
     Ok(Response::new()
         .add_attribute("action", "update_config")
@@ -142,7 +157,21 @@ pub fn execute_update_config(
         .add_attribute("atom_vesting_lp_contract", config.atom_vesting_lp_contract)
         .add_attribute("atom_oracle_contract", config.atom_oracle_contract)
         .add_attribute("usdc_vesting_lp_contract", config.usdc_vesting_lp_contract)
-        .add_attribute("usdc_oracle_contract", config.usdc_oracle_contract))
+        .add_attribute("usdc_oracle_contract", config.usdc_oracle_contract)
+        .add_attribute("denom", config.denom))
+}
+
+/// checks whether both oracles' pairs hold the denom LP tokens are valued in.
+fn validate_denom(deps: Deps, config: &Config, height: u64) -> ContractResult<()> {
+    for oracle_contract in [&config.atom_oracle_contract, &config.usdc_oracle_contract] {
+        if !pair_has_denom(deps, oracle_contract, &config.denom, height)? {
+            return Err(ContractError::DenomNotInPair {
+                denom: config.denom.clone(),
+                contract: oracle_contract.to_string(),
+            });
+        }
+    }
+    Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -191,6 +220,7 @@ fn get_voting_power(
                 .query_wasm_smart::<Option<Uint128>>(vesting_lp, &query_msg)?
                 .unwrap_or_default(),
             oracle,
+            &config.denom,
             height,
         )?;
     }
@@ -203,8 +233,8 @@ pub fn query_voting_power_at_height(
     address: String,
     height: Option<u64>,
 ) -> ContractResult<VotingPowerAtHeightResponse> {
-    let config = CONFIG.load(deps.storage)?;
     let height = height.unwrap_or(env.block.height);
+    let config = load_at_height(deps.storage, &CONFIG, height)?;
     let query_msg = VestingLpQueryMsg::HistoricalExtension {
         msg: QueryMsgHistorical::UnclaimedAmountAtHeight { address, height },
     };
@@ -223,8 +253,8 @@ pub fn query_total_power_at_height(
     env: Env,
     height: Option<u64>,
 ) -> ContractResult<TotalPowerAtHeightResponse> {
-    let config = CONFIG.load(deps.storage)?;
     let height = height.unwrap_or(env.block.height);
+    let config = load_at_height(deps.storage, &CONFIG, height)?;
     let query_msg = VestingLpQueryMsg::HistoricalExtension {
         msg: QueryMsgHistorical::UnclaimedTotalAmountAtHeight { height },
     };
@@ -1,14 +1,22 @@
 use crate::contract::{migrate, CONTRACT_NAME, CONTRACT_VERSION};
+use astroport::asset::AssetInfo;
+use astroport::oracle::QueryMsg as OracleQueryMsg;
 use cosmwasm_std::testing::{mock_dependencies, mock_env};
-use cosmwasm_std::{coins, Addr, Coin, Empty, Uint128};
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, Binary, Coin, Decimal256, Deps, Empty, Env, Response, StdError,
+    StdResult, Uint128,
+};
 use cw_multi_test::{custom_app, App, AppResponse, Contract, ContractWrapper, Executor};
 use cwd_interface::voting::{
     InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
 use neutron_vesting_lp_vault::{
+    error::ContractError,
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
     types::Config,
 };
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 const DAO_ADDR: &str = "dao";
 const NAME: &str = "name";
@@ -17,8 +25,6 @@ const DESCRIPTION: &str = "description";
 const NEW_DESCRIPTION: &str = "new description";
 const ATOM_VESTING_LP_ADDR: &str = "atom_vesting_lp";
 const USDC_VESTING_LP_ADDR: &str = "usdc_vesting_lp";
-const ATOM_ORACLE_ADDR: &str = "atom_oracle";
-const USDC_ORACLE_ADDR: &str = "usdc_oracle";
 const NEW_ATOM_VESTING_LP_ADDR: &str = "new_atom_vesting_lp";
 const NEW_USDC_VESTING_LP_ADDR: &str = "new_usdc_vesting_lp";
 const NEW_ATOM_ORACLE_ADDR: &str = "new_atom_oracle";
@@ -26,6 +32,8 @@ const NEW_USDC_ORACLE_ADDR: &str = "new_usdc_oracle";
 const ADDR1: &str = "addr1";
 const ADDR2: &str = "addr2";
 const DENOM: &str = "ujuno";
+const NTRN_DENOM: &str = "untrn";
+const SUBDAO_DENOM: &str = "usubdao";
 const INIT_BALANCE: Uint128 = Uint128::new(10000);
 
 fn vault_contract() -> Box<dyn Contract<Empty>> {
@@ -81,6 +89,60 @@ fn mock_app() -> App {
     })
 }
 
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct EmptyMsg {}
+
+fn oracle_query(_deps: Deps, _env: Env, msg: OracleQueryMsg) -> StdResult<Binary> {
+    match msg {
+        OracleQueryMsg::TWAPAtHeight { token, height: _ } => {
+            let twap = match token.clone() {
+                AssetInfo::NativeToken { denom } => match denom.as_str() {
+                    NTRN_DENOM => Decimal256::from_ratio(4u64, 1u64),
+                    SUBDAO_DENOM => Decimal256::from_ratio(16u64, 1u64),
+                    // the oracle doesn't price tokens outside of its pair
+                    _ => return Err(StdError::generic_err("Invalid Token")),
+                },
+                AssetInfo::Token { contract_addr: _ } => {
+                    return Err(StdError::generic_err("Invalid Token"))
+                }
+            };
+
+            to_json_binary(&vec![(token, twap)])
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn oracle_contract() -> Box<dyn Contract<Empty>> {
+    let contract: ContractWrapper<
+        EmptyMsg,
+        EmptyMsg,
+        OracleQueryMsg,
+        ContractError,
+        ContractError,
+        StdError,
+    > = ContractWrapper::new(
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        |_, _, _, _: EmptyMsg| Ok(Response::new()),
+        oracle_query,
+    );
+    Box::new(contract)
+}
+
+fn instantiate_oracle_contract(app: &mut App) -> Addr {
+    let contract_id = app.store_code(oracle_contract());
+    app.instantiate_contract(
+        contract_id,
+        Addr::unchecked(DAO_ADDR),
+        &EmptyMsg {},
+        &[],
+        "oracle contract",
+        None,
+    )
+    .unwrap()
+}
+
 fn instantiate_vault(app: &mut App, id: u64, msg: InstantiateMsg) -> Addr {
     app.instantiate_contract(id, Addr::unchecked(DAO_ADDR), &msg, &[], "vault", None)
         .unwrap()
@@ -134,6 +196,7 @@ fn update_config(
     usdc_oracle_contract: String,
     name: String,
     description: String,
+    denom: Option<String>,
 ) -> anyhow::Result<AppResponse> {
     app.execute_contract(
         Addr::unchecked(sender),
@@ -146,6 +209,7 @@ fn update_config(
             usdc_oracle_contract,
             name,
             description,
+            denom,
         },
         &[],
     )
@@ -190,6 +254,8 @@ fn get_dao(app: &App, contract_addr: &Addr) -> String {
 #[test]
 fn test_instantiate() {
     let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     // Populated fields
     let addr = instantiate_vault(
@@ -200,9 +266,10 @@ fn test_instantiate() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_oracle_contract: ATOM_ORACLE_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_oracle_contract: USDC_ORACLE_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
     assert_eq!(get_dao(&app, &addr), String::from(DAO_ADDR));
@@ -216,9 +283,10 @@ fn test_instantiate() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_oracle_contract: ATOM_ORACLE_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_oracle_contract: USDC_ORACLE_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
     assert_eq!(get_dao(&app, &addr), String::from(DAO_ADDR));
@@ -228,6 +296,8 @@ fn test_instantiate() {
 #[should_panic(expected = "Bonding is not available for this contract")]
 fn test_bond() {
     let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -237,9 +307,10 @@ fn test_bond() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_oracle_contract: ATOM_ORACLE_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_oracle_contract: USDC_ORACLE_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -251,6 +322,8 @@ fn test_bond() {
 #[should_panic(expected = "Direct unbonding is not available for this contract")]
 fn test_unbond() {
     let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -260,9 +333,10 @@ fn test_unbond() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_oracle_contract: ATOM_ORACLE_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_oracle_contract: USDC_ORACLE_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -273,6 +347,8 @@ fn test_unbond() {
 #[should_panic(expected = "Unauthorized")]
 fn test_update_config_unauthorized() {
     let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -282,9 +358,10 @@ fn test_update_config_unauthorized() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_oracle_contract: ATOM_ORACLE_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_oracle_contract: USDC_ORACLE_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -300,6 +377,7 @@ fn test_update_config_unauthorized() {
         NEW_USDC_ORACLE_ADDR.to_string(),
         NEW_NAME.to_string(),
         NEW_DESCRIPTION.to_string(),
+        None,
     )
     .unwrap();
 }
@@ -307,6 +385,10 @@ fn test_update_config_unauthorized() {
 #[test]
 fn test_update_config() {
     let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
+    let new_atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let new_usdc_oracle_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -316,24 +398,26 @@ fn test_update_config() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_oracle_contract: ATOM_ORACLE_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_oracle_contract: USDC_ORACLE_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
-    // Change owner, description, name, lp-vesting and oracle contracts
+    // Change owner, description, name, denom, lp-vesting and oracle contracts
     update_config(
         &mut app,
         addr.clone(),
         DAO_ADDR,
         ADDR1.to_string(),
         NEW_ATOM_VESTING_LP_ADDR.to_string(),
-        NEW_ATOM_ORACLE_ADDR.to_string(),
+        new_atom_oracle_contract.to_string(),
         NEW_USDC_VESTING_LP_ADDR.to_string(),
-        NEW_USDC_ORACLE_ADDR.to_string(),
+        new_usdc_oracle_contract.to_string(),
         NEW_NAME.to_string(),
         NEW_DESCRIPTION.to_string(),
+        Some(SUBDAO_DENOM.to_string()),
     )
     .unwrap();
 
@@ -344,9 +428,10 @@ fn test_update_config() {
             description: NEW_DESCRIPTION.to_string(),
             owner: Addr::unchecked(ADDR1),
             atom_vesting_lp_contract: Addr::unchecked(NEW_ATOM_VESTING_LP_ADDR),
-            atom_oracle_contract: Addr::unchecked(NEW_ATOM_ORACLE_ADDR),
+            atom_oracle_contract: new_atom_oracle_contract,
             usdc_vesting_lp_contract: Addr::unchecked(NEW_USDC_VESTING_LP_ADDR),
-            usdc_oracle_contract: Addr::unchecked(NEW_USDC_ORACLE_ADDR),
+            usdc_oracle_contract: new_usdc_oracle_contract,
+            denom: SUBDAO_DENOM.to_string(),
         },
         config
     );
@@ -356,6 +441,8 @@ fn test_update_config() {
 #[should_panic(expected = "config description cannot be empty.")]
 fn test_update_config_invalid_description() {
     let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -365,9 +452,10 @@ fn test_update_config_invalid_description() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_oracle_contract: ATOM_ORACLE_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_oracle_contract: USDC_ORACLE_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -378,11 +466,12 @@ fn test_update_config_invalid_description() {
         DAO_ADDR,
         DAO_ADDR.to_string(),
         ATOM_VESTING_LP_ADDR.to_string(),
-        ATOM_ORACLE_ADDR.to_string(),
+        atom_oracle_contract.to_string(),
         USDC_VESTING_LP_ADDR.to_string(),
-        USDC_ORACLE_ADDR.to_string(),
+        usdc_oracle_contract.to_string(),
         NEW_NAME.to_string(),
         String::from(""),
+        None,
     )
     .unwrap();
 }
@@ -391,6 +480,8 @@ fn test_update_config_invalid_description() {
 #[should_panic(expected = "config name cannot be empty.")]
 fn test_update_config_invalid_name() {
     let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -400,9 +491,10 @@ fn test_update_config_invalid_name() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_oracle_contract: ATOM_ORACLE_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_oracle_contract: USDC_ORACLE_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -413,18 +505,44 @@ fn test_update_config_invalid_name() {
         DAO_ADDR,
         DAO_ADDR.to_string(),
         ATOM_VESTING_LP_ADDR.to_string(),
-        ATOM_ORACLE_ADDR.to_string(),
+        atom_oracle_contract.to_string(),
         USDC_VESTING_LP_ADDR.to_string(),
-        USDC_ORACLE_ADDR.to_string(),
+        usdc_oracle_contract.to_string(),
         String::from(""),
         NEW_DESCRIPTION.to_string(),
+        None,
     )
     .unwrap();
 }
 
+#[test]
+#[should_panic(expected = "config denom cannot be empty.")]
+fn test_instantiate_invalid_denom() {
+    let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
+    let vault_id = app.store_code(vault_contract());
+    instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
+            usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: String::from(""),
+        },
+    );
+}
+
 #[test]
 fn test_query_dao() {
     let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -434,9 +552,10 @@ fn test_query_dao() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_oracle_contract: ATOM_ORACLE_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_oracle_contract: USDC_ORACLE_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -448,6 +567,8 @@ fn test_query_dao() {
 #[test]
 fn test_query_info() {
     let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -457,9 +578,10 @@ fn test_query_info() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_oracle_contract: ATOM_ORACLE_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_oracle_contract: USDC_ORACLE_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -471,6 +593,8 @@ fn test_query_info() {
 #[test]
 fn test_query_get_config() {
     let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
     let vault_id = app.store_code(vault_contract());
     let addr = instantiate_vault(
         &mut app,
@@ -480,9 +604,10 @@ fn test_query_get_config() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
-            atom_oracle_contract: ATOM_ORACLE_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
             usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
-            usdc_oracle_contract: USDC_ORACLE_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -494,9 +619,10 @@ fn test_query_get_config() {
             description: DESCRIPTION.to_string(),
             owner: Addr::unchecked(DAO_ADDR),
             atom_vesting_lp_contract: Addr::unchecked(ATOM_VESTING_LP_ADDR),
-            atom_oracle_contract: Addr::unchecked(ATOM_ORACLE_ADDR),
+            atom_oracle_contract,
             usdc_vesting_lp_contract: Addr::unchecked(USDC_VESTING_LP_ADDR),
-            usdc_oracle_contract: Addr::unchecked(USDC_ORACLE_ADDR),
+            usdc_oracle_contract,
+            denom: NTRN_DENOM.to_string(),
         }
     )
 }
@@ -504,6 +630,8 @@ fn test_query_get_config() {
 #[test]
 fn test_voting_power_at_height() {
     let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
 
     let vesting_lp_id = app.store_code(vesting_lp_contract());
     let atom_vesting_lp_addr = instantiate_vesting_lp(
@@ -534,9 +662,10 @@ fn test_voting_power_at_height() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: atom_vesting_lp_addr.to_string(),
-            atom_oracle_contract: ATOM_ORACLE_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
             usdc_vesting_lp_contract: usdc_vesting_lp_addr.to_string(),
-            usdc_oracle_contract: USDC_ORACLE_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -548,6 +677,8 @@ fn test_voting_power_at_height() {
 #[test]
 fn test_total_power_at_height() {
     let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
 
     let vesting_lp_id = app.store_code(vesting_lp_contract());
     let atom_vesting_lp_addr = instantiate_vesting_lp(
@@ -578,9 +709,10 @@ fn test_total_power_at_height() {
             description: DESCRIPTION.to_string(),
             owner: DAO_ADDR.to_string(),
             atom_vesting_lp_contract: atom_vesting_lp_addr.to_string(),
-            atom_oracle_contract: ATOM_ORACLE_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
             usdc_vesting_lp_contract: usdc_vesting_lp_addr.to_string(),
-            usdc_oracle_contract: USDC_ORACLE_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
         },
     );
 
@@ -589,6 +721,67 @@ fn test_total_power_at_height() {
     assert!(resp.power.is_zero());
 }
 
+#[test]
+#[should_panic(expected = "config denom uatom is not an asset of the contract0 pair.")]
+fn test_instantiate_denom_not_in_pair() {
+    let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
+    let vault_id = app.store_code(vault_contract());
+    instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
+            usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: String::from("uatom"),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "config denom uatom is not an asset of the contract0 pair.")]
+fn test_update_config_denom_not_in_pair() {
+    let mut app = mock_app();
+    let atom_oracle_contract = instantiate_oracle_contract(&mut app);
+    let usdc_oracle_contract = instantiate_oracle_contract(&mut app);
+    let vault_id = app.store_code(vault_contract());
+    let addr = instantiate_vault(
+        &mut app,
+        vault_id,
+        InstantiateMsg {
+            name: NAME.to_string(),
+            description: DESCRIPTION.to_string(),
+            owner: DAO_ADDR.to_string(),
+            atom_vesting_lp_contract: ATOM_VESTING_LP_ADDR.to_string(),
+            atom_oracle_contract: atom_oracle_contract.to_string(),
+            usdc_vesting_lp_contract: USDC_VESTING_LP_ADDR.to_string(),
+            usdc_oracle_contract: usdc_oracle_contract.to_string(),
+            denom: NTRN_DENOM.to_string(),
+        },
+    );
+
+    update_config(
+        &mut app,
+        addr,
+        DAO_ADDR,
+        DAO_ADDR.to_string(),
+        ATOM_VESTING_LP_ADDR.to_string(),
+        atom_oracle_contract.to_string(),
+        USDC_VESTING_LP_ADDR.to_string(),
+        usdc_oracle_contract.to_string(),
+        NAME.to_string(),
+        DESCRIPTION.to_string(),
+        Some(String::from("uatom")),
+    )
+    .unwrap();
+}
+
 #[test]
 pub fn test_migrate_update_version() {
     let mut deps = mock_dependencies();
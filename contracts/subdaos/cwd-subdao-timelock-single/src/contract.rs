@@ -1,19 +1,21 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Reply,
-    Response, StdError, StdResult, SubMsg, WasmMsg,
+    from_json, to_json_binary, Addr, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
+    Reply, Response, StdError, StdResult, SubMsg, WasmMsg,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
 use cw_storage_plus::Bound;
+use cw_utils::Duration;
 use cwd_proposal_single::{
     msg::QueryMsg as MainDaoProposalModuleQueryMsg,
-    query::ProposalResponse as MainDaoProposalResponse,
+    query::ProposalResponse as MainDaoProposalResponse, state::Config as MainDaoProposalConfig,
 };
 use cwd_voting::status::Status;
 use neutron_dao_pre_propose_overrule::msg::{
-    ExecuteMsg as OverruleExecuteMsg, ProposeMessage as OverruleProposeMessage,
-    QueryExt as OverruleQueryExt, QueryMsg as OverruleQueryMsg,
+    ExecuteExt as OverruleExecuteExt, ExecuteMsg as OverruleExecuteMsg, OverruleRule,
+    ProposeMessage as OverruleProposeMessage, QueryExt as OverruleQueryExt,
+    QueryMsg as OverruleQueryMsg,
 };
 use neutron_sdk::bindings::msg::NeutronMsg;
 use neutron_subdao_core::msg::ExecuteMsg as CoreExecuteMsg;
@@ -23,14 +25,23 @@ use neutron_subdao_proposal_single::msg::QueryMsg as ProposalQueryMsg;
 use neutron_subdao_proposal_single::types::Config as ProposalConfig;
 use neutron_subdao_timelock_single::{
     msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
-    types::{Config, ProposalListResponse, ProposalStatus, SingleChoiceProposal},
+    types::{
+        Config, ExecutableProposalsResponse, ExecutionFailure, IndexedMsg, ProposalListResponse,
+        ProposalStatus, RetryPolicy, SingleChoiceProposal, SurvivingMsgsResponse,
+    },
 };
 
 use crate::error::ContractError;
-use crate::state::{CONFIG, DEFAULT_LIMIT, PROPOSALS, PROPOSAL_EXECUTION_ERRORS};
+use crate::state::{
+    proposals, CONFIG, CONFIG_V1, DEFAULT_LIMIT, MAX_EXECUTABLE_SCAN_LIMIT,
+    PROPOSAL_EXECUTION_ERRORS, PROPOSAL_EXECUTION_FAILURES,
+};
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-subdao-timelock-single";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// The version that introduced timelock durations. Contracts migrating
+/// from older versions have the legacy config and proposals.
+const TIMELOCK_DURATION_VERSION: &str = "0.3.0";
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -56,10 +67,16 @@ pub fn instantiate(
     // about the main DAO than it should IMO. It also makes testing harder.
     let overrule_pre_propose = deps.api.addr_validate(&msg.overrule_pre_propose)?;
 
+    validate_timelock_duration(&msg.timelock_duration)?;
+    let fast_track_approvers = validate_addresses(&deps, &msg.fast_track_approvers)?;
+
     let config = Config {
         owner: main_dao,
         overrule_pre_propose,
         subdao: subdao_core,
+        timelock_duration: msg.timelock_duration,
+        retry_policy: msg.retry_policy,
+        fast_track_approvers,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -70,7 +87,8 @@ pub fn instantiate(
         .add_attribute(
             "overrule_pre_propose",
             config.overrule_pre_propose.to_string(),
-        ))
+        )
+        .add_attribute("timelock_duration", config.timelock_duration.to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -87,13 +105,37 @@ pub fn execute(
         ExecuteMsg::ExecuteProposal { proposal_id } => {
             execute_execute_proposal(deps, env, info, proposal_id)
         }
+        ExecuteMsg::RetryProposal { proposal_id } => {
+            execute_retry_proposal(deps, env, info, proposal_id)
+        }
+        ExecuteMsg::AbandonProposal { proposal_id } => {
+            execute_abandon_proposal(deps, info, proposal_id)
+        }
         ExecuteMsg::OverruleProposal { proposal_id } => {
-            execute_overrule_proposal(deps, info, proposal_id)
+            execute_overrule_proposal(deps, env, info, proposal_id)
         }
+        ExecuteMsg::FastTrack { proposal_id } => execute_fast_track(deps, info, proposal_id),
+        ExecuteMsg::OverruleProposalMsgs {
+            proposal_id,
+            msg_indices,
+        } => execute_overrule_proposal_msgs(deps, info, proposal_id, msg_indices),
         ExecuteMsg::UpdateConfig {
             owner,
             overrule_pre_propose,
-        } => execute_update_config(deps, info, owner, overrule_pre_propose),
+            timelock_duration,
+            retry_policy,
+            clear_retry_policy,
+            fast_track_approvers,
+        } => execute_update_config(
+            deps,
+            info,
+            owner,
+            overrule_pre_propose,
+            timelock_duration,
+            retry_policy,
+            clear_retry_policy,
+            fast_track_approvers,
+        ),
     }
 }
 
@@ -110,16 +152,20 @@ pub fn execute_timelock_proposal(
         return Err(ContractError::Unauthorized {});
     }
 
-    // We expect only one specific message `ExecuteMsg::ExecuteTimelockedMsgs` inside
-    let msg = verify_msg(msgs)?;
+    // We expect only `ExecuteMsg::ExecuteTimelockedMsgs` messages inside
+    unwrap_timelocked_msgs(&msgs)?;
 
     let proposal = SingleChoiceProposal {
         id: proposal_id,
-        msgs: vec![msg],
+        msgs,
         status: ProposalStatus::Timelocked,
+        timelocked_at: Some(env.block.height),
+        executable_at: Some(config.timelock_duration.after(&env.block)),
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
     };
 
-    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+    proposals().save(deps.storage, proposal_id, &proposal)?;
 
     let create_overrule_proposal = WasmMsg::Execute {
         contract_addr: config.overrule_pre_propose.to_string(),
@@ -139,7 +185,11 @@ pub fn execute_timelock_proposal(
         .add_attribute("action", "timelock_proposal")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string())
-        .add_attribute("status", proposal.status.to_string()))
+        .add_attribute("status", proposal.status.to_string())
+        .add_attribute(
+            "executable_at",
+            config.timelock_duration.after(&env.block).to_string(),
+        ))
 }
 
 pub fn execute_execute_proposal(
@@ -150,98 +200,277 @@ pub fn execute_execute_proposal(
 ) -> Result<Response<NeutronMsg>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
-    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    let mut proposal = proposals().load(deps.storage, proposal_id)?;
 
     // Check if proposal is timelocked
-    if proposal.status != ProposalStatus::Timelocked {
+    if !is_timelocked(&proposal) {
         return Err(ContractError::WrongStatus {
             status: proposal.status.to_string(),
         });
     }
 
-    if !is_overrule_proposal_declined(&deps, &env, &config.overrule_pre_propose, proposal.id)? {
+    if !is_timelock_over(deps.as_ref(), &env, &config.overrule_pre_propose, &proposal)? {
         return Err(ContractError::TimeLocked {});
     }
 
-    // Update proposal status
-    proposal.status = ProposalStatus::Executed;
-    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
-
-    let response: Response<NeutronMsg> = {
-        // In order to get config.close_proposal_on_execution_failure on proposal module,
-        // we have to query subdao to get proposal module address and then it's config
-        let proposal_module: Addr = deps.querier.query_wasm_smart(
-            config.subdao,
-            &SubdaoQuery::TimelockProposalModuleAddress {
-                timelock: env.contract.address.to_string(),
-            },
-        )?;
-        let proposal_config: ProposalConfig = deps
-            .querier
-            .query_wasm_smart(proposal_module, &ProposalQueryMsg::Config {})?;
+    let response = execute_proposal_msgs(deps, &env, &config, &mut proposal)?;
 
-        // We expect only one specific message `ExecuteMsg::ExecuteTimelockedMsgs` inside
-        let msg = verify_msg(proposal.msgs)?;
+    Ok(response
+        .add_attribute("action", "execute_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
 
-        match proposal_config.close_proposal_on_execution_failure {
-            true => Response::default().add_submessage(SubMsg::reply_on_error(msg, proposal_id)),
-            false => Response::default().add_message(msg),
+pub fn execute_retry_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut proposal = proposals().load(deps.storage, proposal_id)?;
+
+    if proposal.status != ProposalStatus::ExecutionFailed {
+        return Err(ContractError::WrongStatus {
+            status: proposal.status.to_string(),
+        });
+    }
+
+    let retry_policy = config
+        .retry_policy
+        .clone()
+        .ok_or(ContractError::RetriesDisabled {})?;
+
+    // Every failed attempt is recorded once, the first execution attempt
+    // included
+    let failures = PROPOSAL_EXECUTION_FAILURES
+        .may_load(deps.storage, proposal_id)?
+        .unwrap_or_default();
+    let attempts = failures.len() as u64;
+    if attempts >= retry_policy.max_attempts {
+        return Err(ContractError::MaxAttemptsReached { attempts });
+    }
+    if let Some(first_failure) = failures.first() {
+        if retry_policy
+            .retry_deadline(first_failure)
+            .is_expired(&env.block)
+        {
+            return Err(ContractError::RetryWindowExpired {});
         }
-    };
+    }
+
+    let response = execute_proposal_msgs(deps, &env, &config, &mut proposal)?;
 
-    // Note: we add the proposal messages as submessages to change the status to ExecutionFailed
-    // in the reply handler if any of the submessages fail.
     Ok(response
-        .add_attribute("action", "execute_proposal")
+        .add_attribute("action", "retry_proposal")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("attempt", (attempts + 1).to_string()))
+}
+
+pub fn execute_abandon_proposal(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if config.subdao != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut proposal = proposals().load(deps.storage, proposal_id)?;
+
+    if proposal.status != ProposalStatus::ExecutionFailed {
+        return Err(ContractError::WrongStatus {
+            status: proposal.status.to_string(),
+        });
+    }
+
+    proposal.status = ProposalStatus::Abandoned;
+    proposals().save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "abandon_proposal")
         .add_attribute("sender", info.sender)
         .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
-/// `verify_msg` checks that there is only one message inside `msgs`
-/// and verifies type inside of `CoreExecuteMsg::ExecuteTimelockedMsgs`
-fn verify_msg(msgs: Vec<CosmosMsg<NeutronMsg>>) -> Result<CosmosMsg<NeutronMsg>, ContractError> {
-    let msgs_len = msgs.len();
-    // Expect exactly one message
-    if msgs_len != 1 {
-        return Err(ContractError::CanOnlyExecuteOneMsg { len: msgs_len });
+/// Marks the proposal as executed and returns the response executing its
+/// surviving messages in a single submessage.
+fn execute_proposal_msgs(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    proposal: &mut SingleChoiceProposal,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    // Update proposal status
+    proposal.status = ProposalStatus::Executed;
+    proposals().save(deps.storage, proposal.id, proposal)?;
+
+    // In order to get config.close_proposal_on_execution_failure on proposal module,
+    // we have to query subdao to get proposal module address and then it's config
+    let proposal_module: Addr = deps.querier.query_wasm_smart(
+        config.subdao.clone(),
+        &SubdaoQuery::TimelockProposalModuleAddress {
+            timelock: env.contract.address.to_string(),
+        },
+    )?;
+    let proposal_config: ProposalConfig = deps
+        .querier
+        .query_wasm_smart(proposal_module, &ProposalQueryMsg::Config {})?;
+
+    // We expect only `ExecuteMsg::ExecuteTimelockedMsgs` messages inside
+    let msg = proposal_execution_msg(config, proposal)?;
+
+    // Note: we add the proposal message as a submessage to change the status to ExecutionFailed
+    // in the reply handler if it fails.
+    Ok(match proposal_config.close_proposal_on_execution_failure {
+        true => Response::default().add_submessage(SubMsg::reply_on_error(msg, proposal.id)),
+        false => Response::default().add_message(msg),
+    })
+}
+
+/// Rejects zero timelock durations, which would leave the main DAO no
+/// time to overrule proposals.
+fn validate_timelock_duration(duration: &Duration) -> Result<(), ContractError> {
+    match duration {
+        Duration::Height(0) | Duration::Time(0) => Err(ContractError::ZeroTimelockDuration {}),
+        _ => Ok(()),
     }
-    let msg: CosmosMsg<NeutronMsg> = msgs
+}
+
+fn validate_addresses(deps: &DepsMut, addresses: &[String]) -> StdResult<Vec<Addr>> {
+    addresses
+        .iter()
+        .map(|address| deps.api.addr_validate(address))
+        .collect()
+}
+
+/// Returns whether the proposal is still waiting to be executed.
+fn is_timelocked(proposal: &SingleChoiceProposal) -> bool {
+    proposal.status == ProposalStatus::Timelocked
+        || proposal.status == ProposalStatus::PartiallyOverruled
+}
+
+/// Verifies that `msgs` is not empty and contains only
+/// `CoreExecuteMsg::ExecuteTimelockedMsgs` messages, and returns the
+/// messages wrapped into each of them.
+fn unwrap_timelocked_msgs(
+    msgs: &[CosmosMsg<NeutronMsg>],
+) -> Result<Vec<Vec<CosmosMsg<NeutronMsg>>>, ContractError> {
+    if msgs.is_empty() {
+        return Err(ContractError::NoMsgs {});
+    }
+
+    msgs.iter()
+        .map(|msg| match msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                msg: core_execute_msg,
+                ..
+            }) => match from_json::<CoreExecuteMsg>(core_execute_msg) {
+                Ok(CoreExecuteMsg::ExecuteTimelockedMsgs { msgs }) => Ok(msgs),
+                _ => Err(ContractError::CanOnlyExecuteExecuteTimelockedMsgs {}),
+            },
+            _ => Err(ContractError::CanOnlyExecuteExecuteTimelockedMsgs {}),
+        })
+        .collect()
+}
+
+/// Returns the messages wrapped into the proposal messages along with
+/// their indices, leaving out the overruled ones.
+fn indexed_surviving_msgs(
+    proposal: &SingleChoiceProposal,
+) -> Result<Vec<IndexedMsg>, ContractError> {
+    Ok(unwrap_timelocked_msgs(&proposal.msgs)?
         .into_iter()
-        .next()
-        .ok_or(ContractError::CanOnlyExecuteOneMsg { len: msgs_len })?;
-
-    // Expect only `ExecuteMsg::ExecuteTimelockedMsgs`
-    match &msg {
-        &CosmosMsg::Wasm(WasmMsg::Execute {
-            msg: ref core_execute_msg,
-            contract_addr: _,
-            funds: _,
-        }) => match from_json::<CoreExecuteMsg>(core_execute_msg) {
-            Ok(CoreExecuteMsg::ExecuteTimelockedMsgs { msgs: _ }) => {}
-            _ => return Err(ContractError::CanOnlyExecuteExecuteTimelockedMsgs {}),
-        },
-        _ => return Err(ContractError::CanOnlyExecuteExecuteTimelockedMsgs {}),
+        .flatten()
+        .enumerate()
+        .map(|(index, msg)| IndexedMsg {
+            index: index as u64,
+            msg,
+        })
+        .filter(|msg| !proposal.overruled_msgs.contains(&msg.index))
+        .collect())
+}
+
+/// Returns the message executing the proposal. The messages that haven't
+/// been overruled are wrapped into a single
+/// `CoreExecuteMsg::ExecuteTimelockedMsgs` message to the subDAO, so that
+/// they either all succeed or all fail together.
+fn proposal_execution_msg(
+    config: &Config,
+    proposal: &SingleChoiceProposal,
+) -> Result<CosmosMsg<NeutronMsg>, ContractError> {
+    let wrapped_msgs = unwrap_timelocked_msgs(&proposal.msgs)?;
+    if proposal.msgs.len() == 1 && proposal.overruled_msgs.is_empty() {
+        return Ok(proposal.msgs[0].clone());
     }
 
-    Ok(msg)
+    let mut index: u64 = 0;
+    let mut msgs = vec![];
+    let mut funds: Vec<Coin> = vec![];
+    for (msg, wrapped) in proposal.msgs.iter().zip(wrapped_msgs) {
+        let mut surviving = vec![];
+        for wrapped_msg in wrapped {
+            if !proposal.overruled_msgs.contains(&index) {
+                surviving.push(wrapped_msg);
+            }
+            index += 1;
+        }
+        if surviving.is_empty() {
+            continue;
+        }
+
+        if let CosmosMsg::Wasm(WasmMsg::Execute {
+            funds: msg_funds, ..
+        }) = msg
+        {
+            for coin in msg_funds {
+                match funds.iter_mut().find(|fund| fund.denom == coin.denom) {
+                    Some(fund) => fund.amount += coin.amount,
+                    None => funds.push(coin.clone()),
+                }
+            }
+        }
+        msgs.extend(surviving);
+    }
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.subdao.to_string(),
+        msg: to_json_binary(&CoreExecuteMsg::ExecuteTimelockedMsgs { msgs })?,
+        funds,
+    }))
 }
 
 pub fn execute_overrule_proposal(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     proposal_id: u64,
 ) -> Result<Response<NeutronMsg>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
     // Check if sender is owner; the owner is supposed to be the main Neutron DAO.
-    if config.owner != info.sender {
+    // Anyone can overrule the proposal once the main DAO has declined to approve
+    // a proposal that matched one of its overrule rules.
+    if config.owner != info.sender
+        && !is_approval_declined(
+            deps.as_ref(),
+            &env,
+            &config.overrule_pre_propose,
+            proposal_id,
+        )?
+    {
         return Err(ContractError::Unauthorized {});
     }
 
-    let mut proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+    let mut proposal = proposals().load(deps.storage, proposal_id)?;
 
     // Check if proposal is timelocked
-    if proposal.status != ProposalStatus::Timelocked {
+    if !is_timelocked(&proposal) {
         return Err(ContractError::WrongStatus {
             status: proposal.status.to_string(),
         });
@@ -249,7 +478,7 @@ pub fn execute_overrule_proposal(
 
     // Update proposal status
     proposal.status = ProposalStatus::Overruled;
-    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+    proposals().save(deps.storage, proposal_id, &proposal)?;
 
     Ok(Response::default()
         .add_attribute("action", "overrule_proposal")
@@ -257,11 +486,126 @@ pub fn execute_overrule_proposal(
         .add_attribute("proposal_id", proposal_id.to_string()))
 }
 
+pub fn execute_fast_track(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if config.owner != info.sender && !config.fast_track_approvers.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut proposal = proposals().load(deps.storage, proposal_id)?;
+
+    // Check if proposal is timelocked
+    if !is_timelocked(&proposal) {
+        return Err(ContractError::WrongStatus {
+            status: proposal.status.to_string(),
+        });
+    }
+
+    if proposal.fast_tracked_by.is_some() {
+        return Err(ContractError::AlreadyFastTracked {});
+    }
+
+    proposal.fast_tracked_by = Some(info.sender.clone());
+    proposals().save(deps.storage, proposal_id, &proposal)?;
+
+    // The proposal is going to be executed anyway, so there is no point
+    // in voting on its overrule proposals.
+    let close_overrule_proposals = WasmMsg::Execute {
+        contract_addr: config.overrule_pre_propose.to_string(),
+        msg: to_json_binary(&OverruleExecuteMsg::Extension {
+            msg: OverruleExecuteExt::CloseOverruleProposals { proposal_id },
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::default()
+        .add_message(close_overrule_proposals)
+        .add_attribute("action", "fast_track")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+pub fn execute_overrule_proposal_msgs(
+    deps: DepsMut,
+    info: MessageInfo,
+    proposal_id: u64,
+    msg_indices: Vec<u64>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Check if sender is owner; the owner is supposed to be the main Neutron DAO.
+    if config.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut proposal = proposals().load(deps.storage, proposal_id)?;
+
+    // Check if proposal is timelocked
+    if !is_timelocked(&proposal) {
+        return Err(ContractError::WrongStatus {
+            status: proposal.status.to_string(),
+        });
+    }
+
+    if msg_indices.is_empty() {
+        return Err(ContractError::NoMsgIndices {});
+    }
+
+    let msgs_count = unwrap_timelocked_msgs(&proposal.msgs)?
+        .iter()
+        .map(|msgs| msgs.len() as u64)
+        .sum::<u64>();
+    for index in &msg_indices {
+        if *index >= msgs_count {
+            return Err(ContractError::MsgIndexOutOfRange {
+                index: *index,
+                count: msgs_count,
+            });
+        }
+        if !proposal.overruled_msgs.contains(index) {
+            proposal.overruled_msgs.push(*index);
+        }
+    }
+    proposal.overruled_msgs.sort_unstable();
+
+    // Update proposal status
+    proposal.status = if proposal.overruled_msgs.len() as u64 == msgs_count {
+        ProposalStatus::Overruled
+    } else {
+        ProposalStatus::PartiallyOverruled
+    };
+    proposals().save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::default()
+        .add_attribute("action", "overrule_proposal_msgs")
+        .add_attribute("sender", info.sender)
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute(
+            "msg_indices",
+            msg_indices
+                .iter()
+                .map(|index| index.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        )
+        .add_attribute("status", proposal.status.to_string()))
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
     new_owner: Option<String>,
     new_overrule_pre_propose: Option<String>,
+    new_timelock_duration: Option<Duration>,
+    new_retry_policy: Option<RetryPolicy>,
+    clear_retry_policy: Option<bool>,
+    new_fast_track_approvers: Option<Vec<String>>,
 ) -> Result<Response<NeutronMsg>, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
     if info.sender != config.owner {
@@ -280,6 +624,26 @@ pub fn execute_update_config(
         config.overrule_pre_propose = deps.api.addr_validate(&overrule_pre_propose)?;
     }
 
+    if let Some(timelock_duration) = new_timelock_duration {
+        validate_timelock_duration(&timelock_duration)?;
+        config.timelock_duration = timelock_duration;
+    }
+
+    if clear_retry_policy == Some(true) {
+        if new_retry_policy.is_some() {
+            return Err(ContractError::ConflictingRetryPolicyUpdate {});
+        }
+        config.retry_policy = None;
+    }
+
+    if let Some(retry_policy) = new_retry_policy {
+        config.retry_policy = Some(retry_policy);
+    }
+
+    if let Some(fast_track_approvers) = new_fast_track_approvers {
+        config.fast_track_approvers = validate_addresses(&deps, &fast_track_approvers)?;
+    }
+
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new()
         .add_attribute("action", "update_config")
@@ -287,25 +651,39 @@ pub fn execute_update_config(
         .add_attribute(
             "overrule_pre_propose",
             config.overrule_pre_propose.to_string(),
-        ))
+        )
+        .add_attribute("timelock_duration", config.timelock_duration.to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::Proposal { proposal_id } => query_proposal(deps, proposal_id),
         QueryMsg::ListProposals { start_after, limit } => {
             query_list_proposals(deps, start_after, limit)
         }
+        QueryMsg::ListProposalsByStatus {
+            status,
+            start_after,
+            limit,
+        } => query_list_proposals_by_status(deps, status, start_after, limit),
+        QueryMsg::ListExecutable { start_after, limit } => {
+            query_list_executable(deps, env, start_after, limit)
+        }
         QueryMsg::ProposalExecutionError { proposal_id } => {
             query_proposal_execution_error(deps, proposal_id)
         }
+        QueryMsg::ProposalExecutionFailures { proposal_id } => {
+            query_proposal_execution_failures(deps, proposal_id)
+        }
+        QueryMsg::ExecutableAt { proposal_id } => query_executable_at(deps, proposal_id),
+        QueryMsg::SurvivingMsgs { proposal_id } => query_surviving_msgs(deps, proposal_id),
     }
 }
 
 pub fn query_proposal(deps: Deps, id: u64) -> StdResult<Binary> {
-    let proposal = PROPOSALS.load(deps.storage, id)?;
+    let proposal = proposals().load(deps.storage, id)?;
     to_json_binary(&proposal)
 }
 
@@ -316,7 +694,7 @@ pub fn query_list_proposals(
 ) -> StdResult<Binary> {
     let min = start_after.map(Bound::exclusive);
     let limit = limit.unwrap_or(DEFAULT_LIMIT);
-    let props: Vec<SingleChoiceProposal> = PROPOSALS
+    let props: Vec<SingleChoiceProposal> = proposals()
         .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
         .take(limit as usize)
         .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
@@ -327,23 +705,202 @@ pub fn query_list_proposals(
     to_json_binary(&ProposalListResponse { proposals: props })
 }
 
+pub fn query_list_proposals_by_status(
+    deps: Deps,
+    status: ProposalStatus,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let min = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    let props: Vec<SingleChoiceProposal> = proposals()
+        .idx
+        .status
+        .prefix(status.to_string())
+        .range(deps.storage, min, None, cosmwasm_std::Order::Ascending)
+        .take(limit as usize)
+        .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
+        .into_iter()
+        .map(|(_, proposal)| proposal)
+        .collect();
+
+    to_json_binary(&ProposalListResponse { proposals: props })
+}
+
+pub fn query_list_executable(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    // The limit counts the checked proposals rather than the returned
+    // ones, so that the query cost is bounded.
+    let limit = limit
+        .unwrap_or(DEFAULT_LIMIT)
+        .min(MAX_EXECUTABLE_SCAN_LIMIT) as usize;
+
+    // Timelocked and partially overruled proposals are merged in the
+    // order of their IDs.
+    let status_index = proposals().idx.status;
+    let mut timelocked = status_index
+        .prefix(ProposalStatus::Timelocked.to_string())
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .peekable();
+    let mut partially_overruled = status_index
+        .prefix(ProposalStatus::PartiallyOverruled.to_string())
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            cosmwasm_std::Order::Ascending,
+        )
+        .peekable();
+
+    let mut props: Vec<SingleChoiceProposal> = vec![];
+    let mut checked = 0;
+    let mut last_checked_id = None;
+    while checked < limit {
+        let item = match (timelocked.peek(), partially_overruled.peek()) {
+            (Some(Ok((a, _))), Some(Ok((b, _)))) if b < a => partially_overruled.next(),
+            (Some(_), _) => timelocked.next(),
+            (None, _) => partially_overruled.next(),
+        };
+        let Some(item) = item else {
+            break;
+        };
+        let (id, proposal) = item?;
+        checked += 1;
+        last_checked_id = Some(id);
+        // A proposal which overrule state can't be queried doesn't fail
+        // the whole query.
+        if is_timelock_over(deps, &env, &config.overrule_pre_propose, &proposal).unwrap_or(false) {
+            props.push(proposal);
+        }
+    }
+
+    // The listing continues after the last checked proposal, unless
+    // there are no proposals left to check.
+    let next_start_after = match timelocked.peek().or(partially_overruled.peek()) {
+        Some(_) => last_checked_id,
+        None => None,
+    };
+
+    to_json_binary(&ExecutableProposalsResponse {
+        proposals: props,
+        next_start_after,
+    })
+}
+
 pub fn query_proposal_execution_error(deps: Deps, proposal_id: u64) -> StdResult<Binary> {
     let error = PROPOSAL_EXECUTION_ERRORS.may_load(deps.storage, proposal_id)?;
     to_json_binary(&error)
 }
 
+pub fn query_proposal_execution_failures(deps: Deps, proposal_id: u64) -> StdResult<Binary> {
+    let failures = PROPOSAL_EXECUTION_FAILURES
+        .may_load(deps.storage, proposal_id)?
+        .unwrap_or_default();
+    to_json_binary(&failures)
+}
+
+pub fn query_executable_at(deps: Deps, proposal_id: u64) -> StdResult<Binary> {
+    let proposal = proposals().load(deps.storage, proposal_id)?;
+    to_json_binary(&proposal.executable_at)
+}
+
+pub fn query_surviving_msgs(deps: Deps, proposal_id: u64) -> StdResult<Binary> {
+    let proposal = proposals().load(deps.storage, proposal_id)?;
+    let msgs =
+        indexed_surviving_msgs(&proposal).map_err(|err| StdError::generic_err(err.to_string()))?;
+    to_json_binary(&SurvivingMsgsResponse { msgs })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let version = get_contract_version(deps.storage)?;
+    let response = if is_older_version(&version.version, TIMELOCK_DURATION_VERSION) {
+        migrate_from_v1(deps.branch(), env, msg)?
+    } else {
+        migrate_config(deps.branch(), msg)?
+    };
+
     // Set contract to version to latest
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(response)
+}
 
-    let config = CONFIG.load(deps.storage)?;
-    let props: Vec<SingleChoiceProposal> = PROPOSALS
+/// Converts the config of a contract without timelock durations and
+/// overrules its proposals stuck with closed overrule proposals.
+fn migrate_from_v1(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let old_config = CONFIG_V1.load(deps.storage)?;
+    let timelock_duration = match msg.timelock_duration {
+        Some(timelock_duration) => timelock_duration,
+        // Proposals used to be timelocked for as long as the main DAO
+        // voted on overruling them.
+        None => {
+            let propose: Addr = deps.querier.query_wasm_smart(
+                &old_config.overrule_pre_propose,
+                &OverruleQueryMsg::ProposalModule {},
+            )?;
+            let overrule_config: MainDaoProposalConfig = deps
+                .querier
+                .query_wasm_smart(propose, &MainDaoProposalModuleQueryMsg::Config {})?;
+            overrule_config.max_voting_period
+        }
+    };
+    validate_timelock_duration(&timelock_duration)?;
+    let config = Config {
+        owner: old_config.owner,
+        overrule_pre_propose: old_config.overrule_pre_propose,
+        subdao: old_config.subdao,
+        timelock_duration,
+        retry_policy: msg.retry_policy,
+        fast_track_approvers: validate_addresses(
+            &deps,
+            &msg.fast_track_approvers.unwrap_or_default(),
+        )?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    let props: Vec<SingleChoiceProposal> = proposals()
         .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
         .collect::<Result<Vec<(u64, SingleChoiceProposal)>, _>>()?
         .into_iter()
         .map(|(_, proposal)| proposal)
         .collect();
+    // Proposals are saved once again to build the status index
+    for item in &props {
+        proposals().save(deps.storage, item.id, item)?;
+    }
+
+    // Proposals that failed before retries were introduced have no failures
+    // recorded, so their failure is recorded at the migration block: it counts
+    // as the first attempt and starts the retry window.
+    for item in &props {
+        if item.status == ProposalStatus::ExecutionFailed
+            && !PROPOSAL_EXECUTION_FAILURES.has(deps.storage, item.id)
+        {
+            let error = PROPOSAL_EXECUTION_ERRORS
+                .may_load(deps.storage, item.id)?
+                .unwrap_or_default();
+            PROPOSAL_EXECUTION_FAILURES.save(
+                deps.storage,
+                item.id,
+                &vec![ExecutionFailure {
+                    height: env.block.height,
+                    time: env.block.time,
+                    error,
+                }],
+            )?;
+        }
+    }
+
     let mut migrated_ids: Vec<String> = vec![];
     for mut item in props {
         let overrule_proposal_id: u64 = deps.querier.query_wasm_smart(
@@ -373,21 +930,130 @@ pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, Co
         if overrule_proposal.proposal.status == Status::Closed {
             item.status = ProposalStatus::Overruled;
             migrated_ids.push(item.id.to_string());
-            PROPOSALS.save(deps.storage, item.id, &item)?;
+            proposals().save(deps.storage, item.id, &item)?;
         }
     }
 
     Ok(Response::default().add_attribute("migrated_proposal_ids", migrated_ids.join(",")))
 }
 
-// Returns true if overrule proposal for this subdao proposal
-// was declined. (voting is over, and overrule is not accepted).
-fn is_overrule_proposal_declined(
-    deps: &DepsMut,
+/// Updates the config fields set in the migrate message of a contract
+/// that already has timelock durations, keeping the rest.
+fn migrate_config(deps: DepsMut, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(timelock_duration) = msg.timelock_duration {
+        validate_timelock_duration(&timelock_duration)?;
+        config.timelock_duration = timelock_duration;
+    }
+    if let Some(retry_policy) = msg.retry_policy {
+        config.retry_policy = Some(retry_policy);
+    }
+    if let Some(fast_track_approvers) = msg.fast_track_approvers {
+        config.fast_track_approvers = validate_addresses(&deps, &fast_track_approvers)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+/// Returns whether the `major.minor.patch` version is older than the
+/// other one. Pre-release and build suffixes are ignored.
+fn is_older_version(version: &str, other: &str) -> bool {
+    let parse = |version: &str| -> Vec<u64> {
+        version
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse().unwrap_or_default())
+            .collect()
+    };
+    parse(version) < parse(other)
+}
+
+// Returns true if the timelocked proposal can be executed. Proposals are
+// never executable while a partial overrule proposal for them is open or
+// passed but not executed yet. Proposals that matched one
+// of the main DAO's overrule rules are executable once the main DAO
+// passes the proposal to approve them. Fast-tracked proposals are
+// executable right away unless the main DAO has passed the overrule
+// proposal before the proposal was fast-tracked. Proposals with an
+// `executable_at` are executable once it has expired, regardless of the
+// overrule voting period, unless the main DAO has passed the overrule
+// proposal. Older proposals are executable once the overrule proposal
+// was declined.
+fn is_timelock_over(
+    deps: Deps,
+    env: &Env,
+    overrule_pre_propose: &Addr,
+    proposal: &SingleChoiceProposal,
+) -> StdResult<bool> {
+    let overrule_status =
+        query_overrule_proposal_status(deps, env, overrule_pre_propose, proposal.id)?;
+    if has_pending_partial_overrule(deps, env, overrule_pre_propose, proposal.id)? {
+        return Ok(false);
+    }
+    if query_fired_rule(deps, env, overrule_pre_propose, proposal.id).is_some() {
+        return Ok(overrule_status == Status::Passed || overrule_status == Status::Executed);
+    }
+    if proposal.fast_tracked_by.is_some() {
+        return Ok(overrule_status != Status::Passed);
+    }
+    match proposal.executable_at {
+        Some(executable_at) => {
+            Ok(executable_at.is_expired(&env.block) && overrule_status != Status::Passed)
+        }
+        // we check for both Rejected and Closed status
+        // since anybody can close rejected overrule proposals
+        None => Ok(overrule_status == Status::Rejected || overrule_status == Status::Closed),
+    }
+}
+
+// Returns true if the proposal matched one of the main DAO's overrule rules
+// and the main DAO is done voting on its approval proposal without passing it.
+fn is_approval_declined(
+    deps: Deps,
+    env: &Env,
+    overrule_pre_propose: &Addr,
+    subdao_proposal_id: u64,
+) -> StdResult<bool> {
+    if query_fired_rule(deps, env, overrule_pre_propose, subdao_proposal_id).is_none() {
+        return Ok(false);
+    }
+    let approval_status =
+        query_overrule_proposal_status(deps, env, overrule_pre_propose, subdao_proposal_id)?;
+    Ok(approval_status == Status::Rejected || approval_status == Status::Closed)
+}
+
+// Returns the overrule rule this subdao proposal matched, if any.
+fn query_fired_rule(
+    deps: Deps,
+    env: &Env,
+    overrule_pre_propose: &Addr,
+    subdao_proposal_id: u64,
+) -> Option<OverruleRule> {
+    // Overrule pre-propose modules that predate overrule rules don't
+    // support the query, no rules can fire there.
+    deps.querier
+        .query_wasm_smart(
+            overrule_pre_propose,
+            &OverruleQueryMsg::QueryExtension {
+                msg: OverruleQueryExt::FiredRule {
+                    timelock_address: env.contract.address.to_string(),
+                    subdao_proposal_id,
+                },
+            },
+        )
+        .unwrap_or(None)
+}
+
+// Returns the status of the overrule proposal for this subdao proposal.
+fn query_overrule_proposal_status(
+    deps: Deps,
     env: &Env,
     overrule_pre_propose: &Addr,
     subdao_proposal_id: u64,
-) -> Result<bool, ContractError> {
+) -> StdResult<Status> {
     let overrule_proposal_id: u64 = deps.querier.query_wasm_smart(
         overrule_pre_propose,
         &OverruleQueryMsg::QueryExtension {
@@ -406,24 +1072,74 @@ fn is_overrule_proposal_declined(
             proposal_id: overrule_proposal_id,
         },
     )?;
-    // we check for both Rejected and Closed status
-    // since anybody can close rejected overrule proposals
-    Ok(overrule_proposal.proposal.status == Status::Rejected
-        || overrule_proposal.proposal.status == Status::Closed)
+    Ok(overrule_proposal.proposal.status)
+}
+
+// Returns true if one of the partial overrule proposals for this subdao
+// proposal is still open or has passed but hasn't been executed yet. The
+// proposal must not be executed until the messages are overruled.
+fn has_pending_partial_overrule(
+    deps: Deps,
+    env: &Env,
+    overrule_pre_propose: &Addr,
+    subdao_proposal_id: u64,
+) -> StdResult<bool> {
+    // Overrule pre-propose modules that predate partial overrules don't
+    // support the query, no partial overrule proposals exist there.
+    let partial_overrule_proposal_ids: Vec<u64> = match deps.querier.query_wasm_smart(
+        overrule_pre_propose,
+        &OverruleQueryMsg::QueryExtension {
+            msg: OverruleQueryExt::PartialOverruleProposalIds {
+                timelock_address: env.contract.address.to_string(),
+                subdao_proposal_id,
+            },
+        },
+    ) {
+        Ok(ids) => ids,
+        Err(err) if is_query_unsupported(&err) => vec![],
+        Err(err) => return Err(err),
+    };
+    if partial_overrule_proposal_ids.is_empty() {
+        return Ok(false);
+    }
+
+    let propose: Addr = deps
+        .querier
+        .query_wasm_smart(overrule_pre_propose, &OverruleQueryMsg::ProposalModule {})?;
+    for proposal_id in partial_overrule_proposal_ids {
+        let partial_overrule_proposal: MainDaoProposalResponse = deps.querier.query_wasm_smart(
+            &propose,
+            &MainDaoProposalModuleQueryMsg::Proposal { proposal_id },
+        )?;
+        if matches!(
+            partial_overrule_proposal.proposal.status,
+            Status::Open | Status::Passed
+        ) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// Tells whether the query failed because the queried contract doesn't know
+// the query message, e.g. since the contract predates it.
+fn is_query_unsupported(err: &StdError) -> bool {
+    err.to_string().contains("unknown variant")
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     let proposal_id = msg.id;
 
-    PROPOSALS.update(deps.storage, proposal_id, |prop| match prop {
-        Some(mut prop) => {
-            prop.status = ProposalStatus::ExecutionFailed;
-
-            Ok(prop)
-        }
-        None => Err(ContractError::NoSuchProposal { id: proposal_id }),
-    })?;
+    let mut proposal = proposals()
+        .may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::NoSuchProposal { id: proposal_id })?;
+    // The proposal messages are executed in a single submessage, so an
+    // attempt can't fail more than once. Still, only the first failure
+    // of an attempt is recorded for the retry policy to count attempts.
+    let new_attempt = proposal.status != ProposalStatus::ExecutionFailed;
+    proposal.status = ProposalStatus::ExecutionFailed;
+    proposals().save(deps.storage, proposal_id, &proposal)?;
 
     // Error is reduced before cosmwasm reply and is expected in form of "codespace=? code=?"
     let error = msg.result.into_result().err().ok_or_else(|| {
@@ -433,6 +1149,21 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
         ))
     })?;
     PROPOSAL_EXECUTION_ERRORS.save(deps.storage, proposal_id, &error)?;
+    if new_attempt {
+        PROPOSAL_EXECUTION_FAILURES.update(
+            deps.storage,
+            proposal_id,
+            |failures| -> StdResult<Vec<ExecutionFailure>> {
+                let mut failures = failures.unwrap_or_default();
+                failures.push(ExecutionFailure {
+                    height: env.block.height,
+                    time: env.block.time,
+                    error,
+                });
+                Ok(failures)
+            },
+        )?;
+    }
 
     Ok(Response::new().add_attribute(
         "timelocked_proposal_execution_failed",
@@ -24,8 +24,32 @@ pub enum ContractError {
     #[error("Can not create overrule proposal for main DAO")]
     CantCreateOverrule {},
 
-    #[error("Can only execute proposals with exactly one message that of ExecuteTimelockedMsgs type. Got {len} messages.")]
-    CanOnlyExecuteOneMsg { len: usize },
+    #[error("Timelock duration must be greater than zero")]
+    ZeroTimelockDuration {},
+
+    #[error("Proposal must contain at least one message")]
+    NoMsgs {},
+
+    #[error("No message indices to overrule given")]
+    NoMsgIndices {},
+
+    #[error("Message index {index} is out of range, the proposal contains {count} messages")]
+    MsgIndexOutOfRange { index: u64, count: u64 },
+
+    #[error("Proposal is already fast-tracked")]
+    AlreadyFastTracked {},
+
+    #[error("Retrying failed proposals is disabled")]
+    RetriesDisabled {},
+
+    #[error("Proposal has reached the maximum number of execution attempts ({attempts})")]
+    MaxAttemptsReached { attempts: u64 },
+
+    #[error("Retry window of the proposal has expired")]
+    RetryWindowExpired {},
+
+    #[error("Can't both set and clear the retry policy")]
+    ConflictingRetryPolicyUpdate {},
 
     #[error("Can only execute msg of ExecuteTimelockedMsgs type")]
     CanOnlyExecuteExecuteTimelockedMsgs {},
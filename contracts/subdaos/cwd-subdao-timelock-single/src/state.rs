@@ -1,10 +1,53 @@
-use cw_storage_plus::{Item, Map};
-use neutron_subdao_timelock_single::types::{Config, SingleChoiceProposal};
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use neutron_subdao_timelock_single::types::{Config, ExecutionFailure, SingleChoiceProposal};
+use serde::{Deserialize, Serialize};
+
+/// Config of the contract versions before timelock durations were
+/// introduced. Used for migration only.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ConfigV1 {
+    pub owner: Addr,
+    pub overrule_pre_propose: Addr,
+    pub subdao: Addr,
+}
 
 /// Default limit for proposal pagination.
 pub const DEFAULT_LIMIT: u64 = 30;
+/// Maximum number of proposals checked by a single `ListExecutable` query,
+/// since checking a proposal takes several queries to the main DAO.
+pub const MAX_EXECUTABLE_SCAN_LIMIT: u64 = 30;
 
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const PROPOSALS: Map<u64, SingleChoiceProposal> = Map::new("proposals");
+pub const CONFIG_V1: Item<ConfigV1> = Item::new("config");
+
+pub struct ProposalIndexes<'a> {
+    pub status: MultiIndex<'a, String, SingleChoiceProposal, u64>,
+}
+
+impl<'a> IndexList<SingleChoiceProposal> for ProposalIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SingleChoiceProposal>> + '_> {
+        let v: Vec<&dyn Index<SingleChoiceProposal>> = vec![&self.status];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Proposals indexed by their status. The index is keyed by the status
+/// name, so that it doesn't depend on the order of the status variants.
+pub fn proposals<'a>() -> IndexedMap<'a, u64, SingleChoiceProposal, ProposalIndexes<'a>> {
+    let indexes = ProposalIndexes {
+        status: MultiIndex::new(
+            |_pk, proposal| proposal.status.to_string(),
+            "proposals",
+            "proposals__status",
+        ),
+    };
+    IndexedMap::new("proposals", indexes)
+}
+
 /// Execution errors for proposals that do not close on failure (Config.close_proposal_on_execution_failure set to false)
 pub const PROPOSAL_EXECUTION_ERRORS: Map<u64, String> = Map::new("proposal_execution_errors");
+/// All the failed execution attempts of proposals, the last one of them is
+/// also stored in PROPOSAL_EXECUTION_ERRORS.
+pub const PROPOSAL_EXECUTION_FAILURES: Map<u64, Vec<ExecutionFailure>> =
+    Map::new("proposal_execution_failures");
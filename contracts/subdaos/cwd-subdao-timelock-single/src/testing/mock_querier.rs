@@ -18,7 +18,7 @@ use cwd_voting::threshold::PercentageThreshold::Majority;
 use cwd_voting::threshold::Threshold;
 use cwd_voting::voting::Votes;
 use neutron_dao_pre_propose_overrule::msg::{
-    QueryExt as PreProposeOverruleQueryExt, QueryMsg as PreProposeOverruleQuery,
+    OverruleRule, QueryExt as PreProposeOverruleQueryExt, QueryMsg as PreProposeOverruleQuery,
 };
 use neutron_subdao_core::msg::QueryMsg as CoreSubdaoQuery;
 use neutron_subdao_pre_propose_single::msg::{
@@ -51,6 +51,10 @@ pub struct WasmMockQuerier {
     base: MockQuerier,
     overrule_proposal_status: Rc<RefCell<Status>>,
     close_proposal_on_execution_failure: bool,
+    fired_rule: Option<OverruleRule>,
+    fired_rule_unsupported: bool,
+    partial_overrule_proposals: Vec<(u64, Status)>,
+    partial_overrule_query_error: Option<String>,
 }
 
 impl Querier for WasmMockQuerier {
@@ -112,6 +116,33 @@ impl WasmMockQuerier {
                         PreProposeOverruleQuery::QueryExtension {
                             msg: PreProposeOverruleQueryExt::OverruleProposalId { .. },
                         } => to_json_binary(&1),
+                        PreProposeOverruleQuery::QueryExtension {
+                            msg: PreProposeOverruleQueryExt::PartialOverruleProposalIds { .. },
+                        } => {
+                            if let Some(err) = &self.partial_overrule_query_error {
+                                return SystemResult::Ok(ContractResult::Err(err.clone()));
+                            }
+                            to_json_binary(
+                                &self
+                                    .partial_overrule_proposals
+                                    .iter()
+                                    .map(|(id, _)| *id)
+                                    .collect::<Vec<u64>>(),
+                            )
+                        }
+                        PreProposeOverruleQuery::QueryExtension {
+                            msg: PreProposeOverruleQueryExt::OverruleRules { .. },
+                        } => todo!(),
+                        PreProposeOverruleQuery::QueryExtension {
+                            msg: PreProposeOverruleQueryExt::FiredRule { .. },
+                        } => {
+                            if self.fired_rule_unsupported {
+                                return SystemResult::Ok(ContractResult::Err(
+                                    "unknown variant `fired_rule`".to_string(),
+                                ));
+                            }
+                            to_json_binary(&self.fired_rule)
+                        }
                     };
                     return SystemResult::Ok(ContractResult::from(reply));
                 }
@@ -128,30 +159,36 @@ impl WasmMockQuerier {
                             dao: Addr::unchecked(MOCK_MAIN_DAO_ADDR),
                             close_proposal_on_execution_failure: false,
                         }),
-                        ProposeQuery::Proposal { .. } => to_json_binary(&MainDaoProposalResponse {
-                            id: 1,
-                            proposal: MainDaoSingleChoiceProposal {
-                                title: "".to_string(),
-                                description: "".to_string(),
-                                proposer: Addr::unchecked(""),
-                                start_height: 0,
-                                min_voting_period: None,
-                                expiration: Default::default(),
-                                threshold: Threshold::AbsoluteCount {
-                                    threshold: Uint128::new(1),
-                                },
-                                total_power: Default::default(),
-                                msgs: vec![],
-                                // status: Status::Rejected,
-                                status: *(*self.overrule_proposal_status).borrow(),
-                                votes: Votes {
-                                    yes: Default::default(),
-                                    no: Default::default(),
-                                    abstain: Default::default(),
+                        ProposeQuery::Proposal { proposal_id } => {
+                            to_json_binary(&MainDaoProposalResponse {
+                                id: proposal_id,
+                                proposal: MainDaoSingleChoiceProposal {
+                                    title: "".to_string(),
+                                    description: "".to_string(),
+                                    proposer: Addr::unchecked(""),
+                                    start_height: 0,
+                                    min_voting_period: None,
+                                    expiration: Default::default(),
+                                    threshold: Threshold::AbsoluteCount {
+                                        threshold: Uint128::new(1),
+                                    },
+                                    total_power: Default::default(),
+                                    msgs: vec![],
+                                    status: self
+                                        .partial_overrule_proposals
+                                        .iter()
+                                        .find(|(id, _)| *id == proposal_id)
+                                        .map(|(_, status)| *status)
+                                        .unwrap_or(*(*self.overrule_proposal_status).borrow()),
+                                    votes: Votes {
+                                        yes: Default::default(),
+                                        no: Default::default(),
+                                        abstain: Default::default(),
+                                    },
+                                    allow_revoting: false,
                                 },
-                                allow_revoting: false,
-                            },
-                        }),
+                            })
+                        }
                         ProposeQuery::ListProposals { .. } => todo!(),
                         ProposeQuery::ReverseProposals { .. } => todo!(),
                         ProposeQuery::ProposalCount { .. } => todo!(),
@@ -193,6 +230,22 @@ impl WasmMockQuerier {
     pub fn set_close_proposal_on_execution_failure(&mut self, v: bool) {
         self.close_proposal_on_execution_failure = v
     }
+
+    pub fn set_fired_rule(&mut self, rule: Option<OverruleRule>) {
+        self.fired_rule = rule
+    }
+
+    pub fn set_fired_rule_unsupported(&mut self, v: bool) {
+        self.fired_rule_unsupported = v
+    }
+
+    pub fn set_partial_overrule_proposals(&mut self, proposals: Vec<(u64, Status)>) {
+        self.partial_overrule_proposals = proposals
+    }
+
+    pub fn set_partial_overrule_query_error(&mut self, err: Option<String>) {
+        self.partial_overrule_query_error = err
+    }
 }
 
 impl WasmMockQuerier {
@@ -201,6 +254,10 @@ impl WasmMockQuerier {
             base,
             overrule_proposal_status: x,
             close_proposal_on_execution_failure: true,
+            fired_rule: None,
+            fired_rule_unsupported: false,
+            partial_overrule_proposals: vec![],
+            partial_overrule_query_error: None,
         }
     }
 }
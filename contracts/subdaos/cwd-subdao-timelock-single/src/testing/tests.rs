@@ -1,16 +1,21 @@
 use crate::contract::query_proposal_execution_error;
 use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
 use cosmwasm_std::{
-    from_json,
+    coins, from_json,
     testing::{mock_env, mock_info},
-    to_json_binary, Addr, Attribute, CosmosMsg, Reply, SubMsg, SubMsgResult, WasmMsg,
+    to_json_binary, Addr, Attribute, BankMsg, CosmosMsg, Deps, Reply, SubMsg, SubMsgResult,
+    WasmMsg,
 };
+use cw_utils::{Duration, Expiration};
 use cwd_voting::status::Status;
 use neutron_sdk::bindings::msg::NeutronMsg;
 use neutron_subdao_core::msg::ExecuteMsg as CoreExecuteMsg;
 use neutron_subdao_timelock_single::{
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    types::{Config, ProposalListResponse, ProposalStatus, SingleChoiceProposal},
+    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg},
+    types::{
+        Config, ExecutableProposalsResponse, ExecutionFailure, IndexedMsg, ProposalListResponse,
+        ProposalStatus, RetryPolicy, SingleChoiceProposal, SurvivingMsgsResponse,
+    },
 };
 
 use std::cell::RefCell;
@@ -18,12 +23,13 @@ use std::rc::Rc;
 
 use crate::testing::mock_querier::{MOCK_MAIN_DAO_ADDR, MOCK_OVERRULE_PREPROPOSAL};
 use crate::{
-    contract::{execute, instantiate, query, reply},
-    state::{CONFIG, DEFAULT_LIMIT, PROPOSALS},
+    contract::{execute, instantiate, migrate, query, reply, CONTRACT_NAME},
+    state::{proposals, ConfigV1, CONFIG, CONFIG_V1, DEFAULT_LIMIT, PROPOSAL_EXECUTION_ERRORS},
     testing::mock_querier::MOCK_TIMELOCK_INITIALIZER,
 };
 use neutron_dao_pre_propose_overrule::msg::{
-    ExecuteMsg as OverruleExecuteMsg, ProposeMessage as OverruleProposeMessage,
+    ExecuteExt as OverruleExecuteExt, ExecuteMsg as OverruleExecuteMsg, OverruleRule,
+    ProposeMessage as OverruleProposeMessage,
 };
 
 use super::mock_querier::{mock_dependencies, MOCK_SUBDAO_CORE_ADDR};
@@ -36,6 +42,9 @@ fn test_instantiate_test() {
     let info = mock_info("neutron1unknownsender", &[]);
     let msg = InstantiateMsg {
         overrule_pre_propose: MOCK_OVERRULE_PREPROPOSAL.to_string(),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
     };
     let res = instantiate(deps.as_mut(), env.clone(), info, msg);
     assert_eq!(
@@ -45,8 +54,25 @@ fn test_instantiate_test() {
 
     let info = mock_info(MOCK_TIMELOCK_INITIALIZER, &[]);
 
+    for timelock_duration in [Duration::Height(0), Duration::Time(0)] {
+        let msg = InstantiateMsg {
+            overrule_pre_propose: MOCK_OVERRULE_PREPROPOSAL.to_string(),
+            timelock_duration,
+            retry_policy: None,
+            fast_track_approvers: vec![],
+        };
+        let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg);
+        assert_eq!(
+            "Timelock duration must be greater than zero",
+            res.unwrap_err().to_string()
+        );
+    }
+
     let msg = InstantiateMsg {
         overrule_pre_propose: MOCK_OVERRULE_PREPROPOSAL.to_string(),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
     };
     let res = instantiate(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     let res_ok = res.unwrap();
@@ -54,6 +80,7 @@ fn test_instantiate_test() {
         Attribute::new("action", "instantiate"),
         Attribute::new("owner", MOCK_MAIN_DAO_ADDR),
         Attribute::new("overrule_pre_propose", MOCK_OVERRULE_PREPROPOSAL),
+        Attribute::new("timelock_duration", "height: 10"),
     ];
     assert_eq!(expected_attributes, res_ok.attributes);
     let config = CONFIG.load(&deps.storage).unwrap();
@@ -61,11 +88,17 @@ fn test_instantiate_test() {
         owner: Addr::unchecked(MOCK_MAIN_DAO_ADDR),
         overrule_pre_propose: Addr::unchecked(msg.overrule_pre_propose),
         subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
     };
     assert_eq!(expected_config, config);
 
     let msg = InstantiateMsg {
         overrule_pre_propose: MOCK_OVERRULE_PREPROPOSAL.to_string(),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
     };
     let res = instantiate(deps.as_mut(), env, info, msg.clone());
     let res_ok = res.unwrap();
@@ -73,6 +106,7 @@ fn test_instantiate_test() {
         Attribute::new("action", "instantiate"),
         Attribute::new("owner", MOCK_MAIN_DAO_ADDR),
         Attribute::new("overrule_pre_propose", MOCK_OVERRULE_PREPROPOSAL),
+        Attribute::new("timelock_duration", "height: 10"),
     ];
     assert_eq!(expected_attributes, res_ok.attributes);
     let config = CONFIG.load(&deps.storage).unwrap();
@@ -80,6 +114,9 @@ fn test_instantiate_test() {
         owner: Addr::unchecked(MOCK_MAIN_DAO_ADDR),
         overrule_pre_propose: Addr::unchecked(msg.overrule_pre_propose),
         subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
     };
     assert_eq!(expected_config, config);
 }
@@ -111,6 +148,9 @@ fn test_execute_timelock_proposal() {
         owner: Addr::unchecked("owner"),
         overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
         subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
     };
     CONFIG.save(deps.as_mut().storage, &config).unwrap();
 
@@ -138,28 +178,21 @@ fn test_execute_timelock_proposal() {
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), empty_msgs_msg);
     assert_eq!(
-        "Can only execute proposals with exactly one message that of ExecuteTimelockedMsgs type. Got 0 messages.",
-        res.unwrap_err().to_string()
-    );
-
-    // check that execution fails when there are 2 messages inside
-    let too_many_msgs_msg = ExecuteMsg::TimelockProposal {
-        proposal_id: 10,
-        msgs: vec![correct_proposal_msg(), correct_proposal_msg()],
-    };
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), too_many_msgs_msg);
-    assert_eq!(
-        "Can only execute proposals with exactly one message that of ExecuteTimelockedMsgs type. Got 2 messages.",
+        "Proposal must contain at least one message",
         res.unwrap_err().to_string()
     );
 
     // successful case
-    let res_ok = execute(deps.as_mut(), env, info, correct_msg).unwrap();
+    let res_ok = execute(deps.as_mut(), env.clone(), info, correct_msg).unwrap();
     let expected_attributes = vec![
         Attribute::new("action", "timelock_proposal"),
         Attribute::new("sender", MOCK_SUBDAO_CORE_ADDR),
         Attribute::new("proposal_id", "10"),
         Attribute::new("status", "timelocked"),
+        Attribute::new(
+            "executable_at",
+            format!("expiration height: {}", env.block.height + 10),
+        ),
     ];
     assert_eq!(expected_attributes, res_ok.attributes);
     assert_eq!(1, res_ok.messages.len());
@@ -183,8 +216,12 @@ fn test_execute_timelock_proposal() {
         id: 10,
         msgs: vec![correct_proposal_msg()],
         status: ProposalStatus::Timelocked,
+        timelocked_at: Some(env.block.height),
+        executable_at: Some(Expiration::AtHeight(env.block.height + 10)),
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
     };
-    let prop = PROPOSALS.load(deps.as_mut().storage, 10u64).unwrap();
+    let prop = proposals().load(deps.as_mut().storage, 10u64).unwrap();
     assert_eq!(expected_proposal, prop);
 }
 
@@ -207,6 +244,9 @@ fn test_execute_proposal() {
         owner: Addr::unchecked("owner"),
         overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
         subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
     };
     CONFIG.save(deps.as_mut().storage, &config).unwrap();
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
@@ -225,8 +265,12 @@ fn test_execute_proposal() {
             id: 10,
             msgs: vec![correct_proposal_msg()],
             status: s,
+            timelocked_at: None,
+            executable_at: None,
+            overruled_msgs: vec![],
+            fast_tracked_by: None,
         };
-        PROPOSALS
+        proposals()
             .save(deps.as_mut().storage, proposal.id, &proposal)
             .unwrap();
         let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
@@ -242,8 +286,12 @@ fn test_execute_proposal() {
         id: 10,
         msgs: vec![correct_proposal_msg()],
         status: ProposalStatus::Timelocked,
+        timelocked_at: None,
+        executable_at: None,
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
     };
-    PROPOSALS
+    proposals()
         .save(deps.as_mut().storage, proposal.id, &proposal)
         .unwrap();
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
@@ -267,7 +315,7 @@ fn test_execute_proposal() {
             .collect::<Vec<SubMsg<NeutronMsg>>>(),
         res.messages
     );
-    let updated_prop = PROPOSALS.load(deps.as_mut().storage, 10).unwrap();
+    let updated_prop = proposals().load(deps.as_mut().storage, 10).unwrap();
     assert_eq!(ProposalStatus::Executed, updated_prop.status);
 
     // check execution with close_proposal_on_execution_failure = true and overrule proposal Status == Closed
@@ -276,8 +324,12 @@ fn test_execute_proposal() {
         id: 11,
         msgs: vec![correct_proposal_msg()],
         status: ProposalStatus::Timelocked,
+        timelocked_at: None,
+        executable_at: None,
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
     };
-    PROPOSALS
+    proposals()
         .save(deps.as_mut().storage, proposal.id, &proposal)
         .unwrap();
     // if overrule has Status::Closed that means it was rejected
@@ -301,22 +353,26 @@ fn test_execute_proposal() {
             .collect::<Vec<SubMsg<NeutronMsg>>>(),
         res.messages
     );
-    let updated_prop = PROPOSALS.load(deps.as_mut().storage, 11).unwrap();
+    let updated_prop = proposals().load(deps.as_mut().storage, 11).unwrap();
     assert_eq!(ProposalStatus::Executed, updated_prop.status);
 
-    // check that execution fails when there not exactly one message in proposal
+    // check that proposals with several messages are executed in a single submessage
     let proposal = SingleChoiceProposal {
         id: 10,
         msgs: vec![correct_proposal_msg(), correct_proposal_msg()],
         status: ProposalStatus::Timelocked,
+        timelocked_at: None,
+        executable_at: None,
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
     };
-    PROPOSALS
+    proposals()
         .save(deps.as_mut().storage, proposal.id, &proposal)
         .unwrap();
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
     assert_eq!(
-        "Can only execute proposals with exactly one message that of ExecuteTimelockedMsgs type. Got 2 messages.",
-        res.unwrap_err().to_string()
+        vec![SubMsg::reply_on_error(timelocked_msgs(vec![]), proposal.id)],
+        res.messages
     );
 
     // check that execution fails when there is a wrong type of message inside
@@ -324,8 +380,12 @@ fn test_execute_proposal() {
         id: 10,
         msgs: vec![NeutronMsg::remove_interchain_query(1).into()],
         status: ProposalStatus::Timelocked,
+        timelocked_at: None,
+        executable_at: None,
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
     };
-    PROPOSALS
+    proposals()
         .save(deps.as_mut().storage, proposal.id, &proposal)
         .unwrap();
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
@@ -339,13 +399,17 @@ fn test_execute_proposal() {
         id: 10,
         msgs: vec![],
         status: ProposalStatus::Timelocked,
+        timelocked_at: None,
+        executable_at: None,
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
     };
-    PROPOSALS
+    proposals()
         .save(deps.as_mut().storage, proposal.id, &proposal)
         .unwrap();
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
     assert_eq!(
-        "Can only execute proposals with exactly one message that of ExecuteTimelockedMsgs type. Got 0 messages.",
+        "Proposal must contain at least one message",
         res.unwrap_err().to_string()
     );
 
@@ -355,8 +419,12 @@ fn test_execute_proposal() {
         id: 10,
         msgs: vec![correct_proposal_msg()],
         status: ProposalStatus::Timelocked,
+        timelocked_at: None,
+        executable_at: None,
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
     };
-    PROPOSALS
+    proposals()
         .save(deps.as_mut().storage, proposal2.id, &proposal2)
         .unwrap();
     let res = execute(deps.as_mut(), env, info, msg).unwrap();
@@ -373,10 +441,319 @@ fn test_execute_proposal() {
         .map(|msg| SubMsg::new(msg.clone()))
         .collect::<Vec<SubMsg<NeutronMsg>>>();
     assert_eq!(expected_msgs, res.messages);
-    let updated_prop_2 = PROPOSALS.load(deps.as_mut().storage, 10).unwrap();
+    let updated_prop_2 = proposals().load(deps.as_mut().storage, 10).unwrap();
     assert_eq!(ProposalStatus::Executed, updated_prop_2.status);
 }
 
+#[test]
+fn test_execute_proposal_after_timelock_duration() {
+    let overrule_proposal_status = Rc::new(RefCell::new(Status::Open));
+    let mut deps = mock_dependencies(Rc::clone(&overrule_proposal_status));
+    let mut env = mock_env();
+    let info = mock_info("neutron1unknownsender", &[]);
+
+    let config = Config {
+        owner: Addr::unchecked("owner"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
+    };
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    let proposal = SingleChoiceProposal {
+        id: 10,
+        msgs: vec![correct_proposal_msg()],
+        status: ProposalStatus::Timelocked,
+        timelocked_at: Some(env.block.height),
+        executable_at: Some(Expiration::AtHeight(env.block.height + 10)),
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
+    };
+    proposals()
+        .save(deps.as_mut().storage, proposal.id, &proposal)
+        .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::ExecutableAt { proposal_id: 10 },
+    )
+    .unwrap();
+    let executable_at: Option<Expiration> = from_json(res).unwrap();
+    assert_eq!(executable_at, proposal.executable_at);
+
+    // the timelock duration hasn't passed yet
+    let msg = ExecuteMsg::ExecuteProposal { proposal_id: 10 };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert_eq!("Proposal is timelocked", res.unwrap_err().to_string());
+
+    // the main DAO has passed the overrule proposal, but hasn't executed it yet
+    env.block.height += 10;
+    {
+        let mut data_mut_ref = overrule_proposal_status.borrow_mut();
+        *data_mut_ref = Status::Passed;
+    }
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert_eq!("Proposal is timelocked", res.unwrap_err().to_string());
+
+    // the timelock duration doesn't depend on the overrule voting period,
+    // so the proposal is executable while the overrule proposal is open
+    {
+        let mut data_mut_ref = overrule_proposal_status.borrow_mut();
+        *data_mut_ref = Status::Open;
+    }
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        vec![SubMsg::reply_on_error(correct_proposal_msg(), proposal.id)],
+        res.messages
+    );
+    let updated_prop = proposals().load(deps.as_mut().storage, 10).unwrap();
+    assert_eq!(ProposalStatus::Executed, updated_prop.status);
+}
+
+#[test]
+fn test_fast_track() {
+    let overrule_proposal_status = Rc::new(RefCell::new(Status::Open));
+    let mut deps = mock_dependencies(Rc::clone(&overrule_proposal_status));
+    deps.querier.set_close_proposal_on_execution_failure(true);
+    let env = mock_env();
+
+    let config = Config {
+        owner: Addr::unchecked("owner"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![Addr::unchecked("neutron1securitysubdao")],
+    };
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    let proposal = SingleChoiceProposal {
+        id: 10,
+        msgs: vec![correct_proposal_msg()],
+        status: ProposalStatus::Timelocked,
+        timelocked_at: Some(env.block.height),
+        executable_at: Some(Expiration::AtHeight(env.block.height + 10)),
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
+    };
+    proposals()
+        .save(deps.as_mut().storage, proposal.id, &proposal)
+        .unwrap();
+
+    let msg = ExecuteMsg::FastTrack { proposal_id: 10 };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("neutron1unknownsender", &[]),
+        msg.clone(),
+    );
+    assert_eq!("Unauthorized", res.unwrap_err().to_string());
+
+    let info = mock_info("neutron1securitysubdao", &[]);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    let expected_attributes = vec![
+        Attribute::new("action", "fast_track"),
+        Attribute::new("sender", "neutron1securitysubdao"),
+        Attribute::new("proposal_id", "10"),
+    ];
+    assert_eq!(expected_attributes, res.attributes);
+    assert_eq!(
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: MOCK_OVERRULE_PREPROPOSAL.to_string(),
+            msg: to_json_binary(&OverruleExecuteMsg::Extension {
+                msg: OverruleExecuteExt::CloseOverruleProposals { proposal_id: 10 },
+            })
+            .unwrap(),
+            funds: vec![],
+        })],
+        res.messages
+    );
+    let updated_prop = proposals().load(deps.as_mut().storage, 10).unwrap();
+    assert_eq!(
+        Some(Addr::unchecked("neutron1securitysubdao")),
+        updated_prop.fast_tracked_by
+    );
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg);
+    assert_eq!(
+        "Proposal is already fast-tracked",
+        res.unwrap_err().to_string()
+    );
+
+    // the proposal is executable even though the timelock isn't over
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::ExecuteProposal { proposal_id: 10 },
+    )
+    .unwrap();
+    assert_eq!(
+        vec![SubMsg::reply_on_error(correct_proposal_msg(), 10)],
+        res.messages
+    );
+
+    // executed proposals can't be fast-tracked
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("owner", &[]),
+        ExecuteMsg::FastTrack { proposal_id: 10 },
+    );
+    assert_eq!(
+        "Wrong proposal status (executed)",
+        res.unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_execute_proposal_with_fired_rule() {
+    let overrule_proposal_status = Rc::new(RefCell::new(Status::Open));
+    let mut deps = mock_dependencies(Rc::clone(&overrule_proposal_status));
+    deps.querier
+        .set_fired_rule(Some(OverruleRule::WasmMigrate {}));
+    let env = mock_env();
+    let info = mock_info("neutron1unknownsender", &[]);
+
+    let config = Config {
+        owner: Addr::unchecked("owner"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
+    };
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    let proposal = SingleChoiceProposal {
+        id: 10,
+        msgs: vec![correct_proposal_msg()],
+        status: ProposalStatus::Timelocked,
+        timelocked_at: Some(env.block.height - 10),
+        executable_at: Some(Expiration::AtHeight(env.block.height)),
+        overruled_msgs: vec![],
+        fast_tracked_by: Some(Addr::unchecked("owner")),
+    };
+    proposals()
+        .save(deps.as_mut().storage, proposal.id, &proposal)
+        .unwrap();
+
+    // the proposal isn't executable until the main DAO approves it, even
+    // though the timelock is over and the proposal is fast-tracked
+    let msg = ExecuteMsg::ExecuteProposal { proposal_id: 10 };
+    for status in [Status::Open, Status::Rejected, Status::Closed] {
+        {
+            let mut data_mut_ref = overrule_proposal_status.borrow_mut();
+            *data_mut_ref = status;
+        }
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+        assert_eq!("Proposal is timelocked", res.unwrap_err().to_string());
+    }
+
+    {
+        let mut data_mut_ref = overrule_proposal_status.borrow_mut();
+        *data_mut_ref = Status::Passed;
+    }
+    let res = execute(deps.as_mut(), env, info, msg).unwrap();
+    assert_eq!(
+        vec![SubMsg::reply_on_error(correct_proposal_msg(), 10)],
+        res.messages
+    );
+}
+
+#[test]
+fn test_overrule_proposal_with_declined_approval() {
+    let overrule_proposal_status = Rc::new(RefCell::new(Status::Open));
+    let mut deps = mock_dependencies(Rc::clone(&overrule_proposal_status));
+    deps.querier
+        .set_fired_rule(Some(OverruleRule::WasmMigrate {}));
+    let env = mock_env();
+    let info = mock_info("neutron1unknownsender", &[]);
+
+    let config = Config {
+        owner: Addr::unchecked("owner"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
+    };
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    let proposal = SingleChoiceProposal {
+        id: 10,
+        msgs: vec![correct_proposal_msg()],
+        status: ProposalStatus::Timelocked,
+        timelocked_at: Some(env.block.height),
+        executable_at: Some(Expiration::AtHeight(env.block.height + 10)),
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
+    };
+    proposals()
+        .save(deps.as_mut().storage, proposal.id, &proposal)
+        .unwrap();
+
+    // the main DAO may still approve the proposal
+    let msg = ExecuteMsg::OverruleProposal { proposal_id: 10 };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert_eq!("Unauthorized", res.unwrap_err().to_string());
+
+    // the main DAO has declined to approve the proposal, so anyone can overrule it
+    {
+        let mut data_mut_ref = overrule_proposal_status.borrow_mut();
+        *data_mut_ref = Status::Rejected;
+    }
+    execute(deps.as_mut(), env, info, msg).unwrap();
+    let updated_prop = proposals().load(deps.as_mut().storage, 10).unwrap();
+    assert_eq!(ProposalStatus::Overruled, updated_prop.status);
+}
+
+#[test]
+fn test_execute_proposal_without_fired_rule_query() {
+    let overrule_proposal_status = Rc::new(RefCell::new(Status::Rejected));
+    let mut deps = mock_dependencies(Rc::clone(&overrule_proposal_status));
+    // overrule pre-propose modules that predate overrule rules fail the queries
+    deps.querier.set_fired_rule_unsupported(true);
+    deps.querier.set_partial_overrule_query_error(Some(
+        "unknown variant `partial_overrule_proposal_ids`".to_string(),
+    ));
+    let env = mock_env();
+    let info = mock_info("neutron1unknownsender", &[]);
+
+    let config = Config {
+        owner: Addr::unchecked("owner"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
+    };
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    let proposal = SingleChoiceProposal {
+        id: 10,
+        msgs: vec![correct_proposal_msg()],
+        status: ProposalStatus::Timelocked,
+        timelocked_at: Some(env.block.height - 10),
+        executable_at: Some(Expiration::AtHeight(env.block.height)),
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
+    };
+    proposals()
+        .save(deps.as_mut().storage, proposal.id, &proposal)
+        .unwrap();
+
+    let msg = ExecuteMsg::ExecuteProposal { proposal_id: 10 };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(
+        vec![SubMsg::reply_on_error(correct_proposal_msg(), 10)],
+        res.messages
+    );
+
+    // nobody but the owner can overrule the proposal there
+    let msg = ExecuteMsg::OverruleProposal { proposal_id: 10 };
+    let res = execute(deps.as_mut(), env, info, msg);
+    assert_eq!("Unauthorized", res.unwrap_err().to_string());
+}
+
 #[test]
 fn test_overrule_proposal() {
     let overrule_proposal_status = Rc::new(RefCell::new(Status::Open));
@@ -396,6 +773,9 @@ fn test_overrule_proposal() {
         owner: Addr::unchecked("owner"),
         overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
         subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
     };
     CONFIG.save(deps.as_mut().storage, &config).unwrap();
     let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
@@ -413,8 +793,12 @@ fn test_overrule_proposal() {
             id: 10,
             msgs: vec![correct_proposal_msg()],
             status: s,
+            timelocked_at: None,
+            executable_at: None,
+            overruled_msgs: vec![],
+            fast_tracked_by: None,
         };
-        PROPOSALS
+        proposals()
             .save(deps.as_mut().storage, proposal.id, &proposal)
             .unwrap();
         let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
@@ -428,8 +812,12 @@ fn test_overrule_proposal() {
         id: 10,
         msgs: vec![correct_proposal_msg()],
         status: ProposalStatus::Timelocked,
+        timelocked_at: None,
+        executable_at: None,
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
     };
-    PROPOSALS
+    proposals()
         .save(deps.as_mut().storage, proposal.id, &proposal)
         .unwrap();
     let res_ok = execute(deps.as_mut(), env, info.clone(), msg).unwrap();
@@ -440,59 +828,256 @@ fn test_overrule_proposal() {
         Attribute::new("proposal_id", proposal.id.to_string()),
     ];
     assert_eq!(expected_attributes, res_ok.attributes);
-    let updated_prop = PROPOSALS.load(deps.as_mut().storage, 10).unwrap();
+    let updated_prop = proposals().load(deps.as_mut().storage, 10).unwrap();
     assert_eq!(ProposalStatus::Overruled, updated_prop.status);
 }
 
 #[test]
-fn execute_update_config() {
+fn test_overrule_proposal_msgs() {
     let overrule_proposal_status = Rc::new(RefCell::new(Status::Open));
     let mut deps = mock_dependencies(Rc::clone(&overrule_proposal_status));
     let env = mock_env();
-    let info = mock_info("neutron1unknownsender", &[]);
-
-    let msg = ExecuteMsg::UpdateConfig {
-        owner: None,
-        overrule_pre_propose: Some("neutron1someotheroverrule".to_string()),
-    };
-
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
-    assert_eq!(
-        "type: neutron_subdao_timelock_single::types::Config; key: [63, 6F, 6E, 66, 69, 67] not found",
-        res.unwrap_err().to_string()
-    );
 
     let config = Config {
         owner: Addr::unchecked("owner"),
         overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
         subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
     };
     CONFIG.save(deps.as_mut().storage, &config).unwrap();
-    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
-    assert_eq!("Unauthorized", res.unwrap_err().to_string());
+    for id in [10, 11] {
+        let proposal = SingleChoiceProposal {
+            id,
+            msgs: vec![
+                timelocked_msgs(vec![burn_msg(1), burn_msg(2)]),
+                timelocked_msgs(vec![burn_msg(3)]),
+            ],
+            status: ProposalStatus::Timelocked,
+            timelocked_at: None,
+            executable_at: None,
+            overruled_msgs: vec![],
+            fast_tracked_by: None,
+        };
+        proposals()
+            .save(deps.as_mut().storage, proposal.id, &proposal)
+            .unwrap();
+    }
 
-    let info = mock_info("owner", &[]);
-    let config = Config {
-        owner: Addr::unchecked("none"),
-        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
-        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+    let msg = ExecuteMsg::OverruleProposalMsgs {
+        proposal_id: 10,
+        msg_indices: vec![1],
     };
-    CONFIG.save(deps.as_mut().storage, &config).unwrap();
-    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("neutron1unknownsender", &[]),
+        msg.clone(),
+    );
     assert_eq!("Unauthorized", res.unwrap_err().to_string());
 
-    let config = Config {
-        owner: Addr::unchecked("owner"),
-        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
-        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
-    };
-    CONFIG.save(deps.as_mut().storage, &config).unwrap();
-    let res_ok = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-    assert_eq!(0, res_ok.messages.len());
-    let expected_attributes = vec![
-        Attribute::new("action", "update_config"),
-        Attribute::new("owner", "owner"),
-        Attribute::new("overrule_pre_propose", "neutron1someotheroverrule"),
+    let info = mock_info("owner", &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::OverruleProposalMsgs {
+            proposal_id: 10,
+            msg_indices: vec![],
+        },
+    );
+    assert_eq!(
+        "No message indices to overrule given",
+        res.unwrap_err().to_string()
+    );
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::OverruleProposalMsgs {
+            proposal_id: 10,
+            msg_indices: vec![3],
+        },
+    );
+    assert_eq!(
+        "Message index 3 is out of range, the proposal contains 3 messages",
+        res.unwrap_err().to_string()
+    );
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let expected_attributes = vec![
+        Attribute::new("action", "overrule_proposal_msgs"),
+        Attribute::new("sender", "owner"),
+        Attribute::new("proposal_id", "10"),
+        Attribute::new("msg_indices", "1"),
+        Attribute::new("status", "partially_overruled"),
+    ];
+    assert_eq!(expected_attributes, res.attributes);
+    let updated_prop = proposals().load(deps.as_mut().storage, 10).unwrap();
+    assert_eq!(ProposalStatus::PartiallyOverruled, updated_prop.status);
+    assert_eq!(vec![1], updated_prop.overruled_msgs);
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::SurvivingMsgs { proposal_id: 10 },
+    )
+    .unwrap();
+    let surviving: SurvivingMsgsResponse = from_json(res).unwrap();
+    assert_eq!(
+        surviving,
+        SurvivingMsgsResponse {
+            msgs: vec![
+                IndexedMsg {
+                    index: 0,
+                    msg: burn_msg(1)
+                },
+                IndexedMsg {
+                    index: 2,
+                    msg: burn_msg(3)
+                },
+            ]
+        }
+    );
+
+    // the proposal isn't executed while the main DAO is yet to execute a
+    // passed partial overrule proposal
+    {
+        let mut data_mut_ref = overrule_proposal_status.borrow_mut();
+        *data_mut_ref = Status::Rejected;
+    }
+    deps.querier
+        .set_partial_overrule_proposals(vec![(2, Status::Rejected), (3, Status::Passed)]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::ExecuteProposal { proposal_id: 10 },
+    );
+    assert_eq!("Proposal is timelocked", res.unwrap_err().to_string());
+
+    // nor while a partial overrule proposal is still open
+    deps.querier
+        .set_partial_overrule_proposals(vec![(2, Status::Rejected), (3, Status::Open)]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::ExecuteProposal { proposal_id: 10 },
+    );
+    assert_eq!("Proposal is timelocked", res.unwrap_err().to_string());
+
+    // the errors of the partial overrule proposals query are not ignored
+    deps.querier
+        .set_partial_overrule_query_error(Some("out of gas".to_string()));
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::ExecuteProposal { proposal_id: 10 },
+    );
+    assert!(res.unwrap_err().to_string().contains("out of gas"));
+    deps.querier.set_partial_overrule_query_error(None);
+
+    // the rest of the messages are executed together
+    deps.querier
+        .set_partial_overrule_proposals(vec![(2, Status::Rejected), (3, Status::Executed)]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::ExecuteProposal { proposal_id: 10 },
+    )
+    .unwrap();
+    assert_eq!(
+        vec![SubMsg::reply_on_error(
+            timelocked_msgs(vec![burn_msg(1), burn_msg(3)]),
+            10
+        )],
+        res.messages
+    );
+
+    // overruling every message overrules the whole proposal
+    let res = execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::OverruleProposalMsgs {
+            proposal_id: 11,
+            msg_indices: vec![2, 0, 1],
+        },
+    )
+    .unwrap();
+    assert_eq!(Attribute::new("status", "overruled"), res.attributes[4]);
+    let updated_prop = proposals().load(deps.as_mut().storage, 11).unwrap();
+    assert_eq!(ProposalStatus::Overruled, updated_prop.status);
+    assert_eq!(vec![0, 1, 2], updated_prop.overruled_msgs);
+}
+
+#[test]
+fn execute_update_config() {
+    let overrule_proposal_status = Rc::new(RefCell::new(Status::Open));
+    let mut deps = mock_dependencies(Rc::clone(&overrule_proposal_status));
+    let env = mock_env();
+    let info = mock_info("neutron1unknownsender", &[]);
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        overrule_pre_propose: Some("neutron1someotheroverrule".to_string()),
+        timelock_duration: None,
+        retry_policy: None,
+        clear_retry_policy: None,
+        fast_track_approvers: None,
+    };
+
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert_eq!(
+        "type: neutron_subdao_timelock_single::types::Config; key: [63, 6F, 6E, 66, 69, 67] not found",
+        res.unwrap_err().to_string()
+    );
+
+    let config = Config {
+        owner: Addr::unchecked("owner"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
+    };
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), info, msg.clone());
+    assert_eq!("Unauthorized", res.unwrap_err().to_string());
+
+    let info = mock_info("owner", &[]);
+    let config = Config {
+        owner: Addr::unchecked("none"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
+    };
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert_eq!("Unauthorized", res.unwrap_err().to_string());
+
+    let config = Config {
+        owner: Addr::unchecked("owner"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
+    };
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    let res_ok = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    assert_eq!(0, res_ok.messages.len());
+    let expected_attributes = vec![
+        Attribute::new("action", "update_config"),
+        Attribute::new("owner", "owner"),
+        Attribute::new("overrule_pre_propose", "neutron1someotheroverrule"),
+        Attribute::new("timelock_duration", "height: 10"),
     ];
     assert_eq!(expected_attributes, res_ok.attributes);
     let updated_config = CONFIG.load(deps.as_mut().storage).unwrap();
@@ -502,13 +1087,39 @@ fn execute_update_config() {
         Config {
             owner: Addr::unchecked("owner"),
             overrule_pre_propose: Addr::unchecked(some_other_prepropose),
-            subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR)
+            subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+            timelock_duration: Duration::Height(10),
+            retry_policy: None,
+            fast_track_approvers: vec![],
         }
     );
 
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::UpdateConfig {
+            owner: None,
+            overrule_pre_propose: None,
+            timelock_duration: Some(Duration::Height(0)),
+            retry_policy: None,
+            clear_retry_policy: None,
+            fast_track_approvers: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        "Timelock duration must be greater than zero",
+        err.to_string()
+    );
+
     let msg = ExecuteMsg::UpdateConfig {
         owner: Some("neutron1newowner".to_string()),
         overrule_pre_propose: None,
+        timelock_duration: Some(Duration::Time(100)),
+        retry_policy: None,
+        clear_retry_policy: None,
+        fast_track_approvers: None,
     };
 
     let res_ok = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
@@ -517,6 +1128,7 @@ fn execute_update_config() {
         Attribute::new("action", "update_config"),
         Attribute::new("owner", "neutron1newowner"),
         Attribute::new("overrule_pre_propose", some_other_prepropose),
+        Attribute::new("timelock_duration", "time: 100"),
     ];
     assert_eq!(expected_attributes, res_ok.attributes);
     let updated_config = CONFIG.load(deps.as_mut().storage).unwrap();
@@ -525,13 +1137,146 @@ fn execute_update_config() {
         Config {
             owner: Addr::unchecked("neutron1newowner"),
             overrule_pre_propose: Addr::unchecked(some_other_prepropose),
-            subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR)
+            subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+            timelock_duration: Duration::Time(100),
+            retry_policy: None,
+            fast_track_approvers: vec![],
         }
     );
 
     // old owner
-    let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
     assert_eq!("Unauthorized", err.to_string());
+
+    // the retry policy can be set and cleared
+    let info = mock_info("neutron1newowner", &[]);
+    let retry_policy = RetryPolicy {
+        max_attempts: 3,
+        retry_window: Duration::Height(100),
+    };
+    let update_retry_policy = |retry_policy: Option<RetryPolicy>,
+                               clear_retry_policy: Option<bool>| {
+        ExecuteMsg::UpdateConfig {
+            owner: None,
+            overrule_pre_propose: None,
+            timelock_duration: None,
+            retry_policy,
+            clear_retry_policy,
+            fast_track_approvers: None,
+        }
+    };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        update_retry_policy(Some(retry_policy.clone()), None),
+    )
+    .unwrap();
+    assert_eq!(
+        CONFIG.load(deps.as_ref().storage).unwrap().retry_policy,
+        Some(retry_policy.clone())
+    );
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        update_retry_policy(Some(retry_policy), Some(true)),
+    )
+    .unwrap_err();
+    assert_eq!("Can't both set and clear the retry policy", err.to_string());
+    execute(
+        deps.as_mut(),
+        env,
+        info,
+        update_retry_policy(None, Some(true)),
+    )
+    .unwrap();
+    assert_eq!(
+        CONFIG.load(deps.as_ref().storage).unwrap().retry_policy,
+        None
+    );
+}
+
+#[test]
+fn test_migrate() {
+    let overrule_proposal_status = Rc::new(RefCell::new(Status::Open));
+    let mut deps = mock_dependencies(Rc::clone(&overrule_proposal_status));
+    let env = mock_env();
+    let old_config = ConfigV1 {
+        owner: Addr::unchecked("owner"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+    };
+    CONFIG_V1.save(deps.as_mut().storage, &old_config).unwrap();
+    cw2::set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.2.3").unwrap();
+
+    // a zero timelock duration is rejected
+    let err = migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            timelock_duration: Some(Duration::Time(0)),
+            retry_policy: None,
+            fast_track_approvers: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        "Timelock duration must be greater than zero",
+        err.to_string()
+    );
+
+    // the timelock duration defaults to the voting period of the
+    // overrule proposals
+    let msg: MigrateMsg =
+        from_json(br#"{"retry_policy": {"max_attempts": 2, "retry_window": {"height": 5}}}"#)
+            .unwrap();
+    migrate(deps.as_mut(), env.clone(), msg).unwrap();
+    let config = Config {
+        owner: Addr::unchecked("owner"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Time(10),
+        retry_policy: Some(RetryPolicy {
+            max_attempts: 2,
+            retry_window: Duration::Height(5),
+        }),
+        fast_track_approvers: vec![],
+    };
+    assert_eq!(CONFIG.load(deps.as_ref().storage).unwrap(), config);
+
+    // later migrations keep the config fields the message omits, and
+    // don't overrule proposals with closed overrule proposals, which are
+    // fast-tracked ones now
+    {
+        let mut data_mut_ref = overrule_proposal_status.borrow_mut();
+        *data_mut_ref = Status::Closed;
+    }
+    let proposal = SingleChoiceProposal {
+        id: 1,
+        msgs: vec![correct_proposal_msg()],
+        status: ProposalStatus::Timelocked,
+        timelocked_at: Some(env.block.height),
+        executable_at: Some(Expiration::AtHeight(env.block.height + 10)),
+        overruled_msgs: vec![],
+        fast_tracked_by: Some(Addr::unchecked("owner")),
+    };
+    proposals()
+        .save(deps.as_mut().storage, proposal.id, &proposal)
+        .unwrap();
+    let msg: MigrateMsg = from_json(br#"{"fast_track_approvers": ["security_subdao"]}"#).unwrap();
+    migrate(deps.as_mut(), env, msg).unwrap();
+    assert_eq!(
+        CONFIG.load(deps.as_ref().storage).unwrap(),
+        Config {
+            fast_track_approvers: vec![Addr::unchecked("security_subdao")],
+            ..config
+        }
+    );
+    assert_eq!(
+        proposals().load(deps.as_ref().storage, 1).unwrap(),
+        proposal
+    );
 }
 
 #[test]
@@ -542,6 +1287,9 @@ fn test_query_config() {
         owner: Addr::unchecked("owner"),
         overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
         subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
     };
     CONFIG.save(deps.as_mut().storage, &config).unwrap();
     let query_msg = QueryMsg::Config {};
@@ -559,8 +1307,12 @@ fn test_query_proposals() {
             id: i,
             msgs: vec![correct_proposal_msg()],
             status: ProposalStatus::Timelocked,
+            timelocked_at: None,
+            executable_at: None,
+            overruled_msgs: vec![],
+            fast_tracked_by: None,
         };
-        PROPOSALS.save(deps.as_mut().storage, i, &prop).unwrap();
+        proposals().save(deps.as_mut().storage, i, &prop).unwrap();
     }
     for i in 1..=100 {
         let query_msg = QueryMsg::Proposal { proposal_id: i };
@@ -570,6 +1322,10 @@ fn test_query_proposals() {
             id: i,
             msgs: vec![correct_proposal_msg()],
             status: ProposalStatus::Timelocked,
+            timelocked_at: None,
+            executable_at: None,
+            overruled_msgs: vec![],
+            fast_tracked_by: None,
         };
         assert_eq!(expected_prop, queried_prop)
     }
@@ -585,6 +1341,10 @@ fn test_query_proposals() {
             id: i,
             msgs: vec![correct_proposal_msg()],
             status: ProposalStatus::Timelocked,
+            timelocked_at: None,
+            executable_at: None,
+            overruled_msgs: vec![],
+            fast_tracked_by: None,
         };
         assert_eq!(expected_prop, *p);
     }
@@ -601,6 +1361,10 @@ fn test_query_proposals() {
             id: i,
             msgs: vec![correct_proposal_msg()],
             status: ProposalStatus::Timelocked,
+            timelocked_at: None,
+            executable_at: None,
+            overruled_msgs: vec![],
+            fast_tracked_by: None,
         };
         assert_eq!(expected_prop, *p);
     }
@@ -617,6 +1381,10 @@ fn test_query_proposals() {
             id: i,
             msgs: vec![correct_proposal_msg()],
             status: ProposalStatus::Timelocked,
+            timelocked_at: None,
+            executable_at: None,
+            overruled_msgs: vec![],
+            fast_tracked_by: None,
         };
         assert_eq!(expected_prop, *p);
     }
@@ -633,6 +1401,10 @@ fn test_query_proposals() {
             id: i,
             msgs: vec![correct_proposal_msg()],
             status: ProposalStatus::Timelocked,
+            timelocked_at: None,
+            executable_at: None,
+            overruled_msgs: vec![],
+            fast_tracked_by: None,
         };
         assert_eq!(expected_prop, *p);
     }
@@ -649,12 +1421,213 @@ fn test_query_proposals() {
             id: i,
             msgs: vec![correct_proposal_msg()],
             status: ProposalStatus::Timelocked,
+            timelocked_at: None,
+            executable_at: None,
+            overruled_msgs: vec![],
+            fast_tracked_by: None,
         };
         assert_eq!(expected_prop, *p);
     }
     assert_eq!(queried_props.proposals.len(), 10);
 }
 
+#[test]
+fn test_query_proposals_by_status() {
+    let overrule_proposal_status = Rc::new(RefCell::new(Status::Open));
+    let mut deps = mock_dependencies(Rc::clone(&overrule_proposal_status));
+    let env = mock_env();
+    let config = Config {
+        owner: Addr::unchecked("owner"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
+    };
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+    let expired = Some(Expiration::AtHeight(env.block.height));
+    let not_expired = Some(Expiration::AtHeight(env.block.height + 1));
+    let props = vec![
+        (ProposalStatus::Timelocked, expired, None),
+        (ProposalStatus::Timelocked, not_expired, None),
+        (ProposalStatus::PartiallyOverruled, expired, None),
+        (ProposalStatus::Executed, expired, None),
+        (
+            ProposalStatus::Timelocked,
+            not_expired,
+            Some(Addr::unchecked("owner")),
+        ),
+    ];
+    for (id, (status, executable_at, fast_tracked_by)) in (1..).zip(props) {
+        let prop = SingleChoiceProposal {
+            id,
+            msgs: vec![correct_proposal_msg()],
+            status,
+            timelocked_at: Some(env.block.height - 10),
+            executable_at,
+            overruled_msgs: vec![],
+            fast_tracked_by,
+        };
+        proposals().save(deps.as_mut().storage, id, &prop).unwrap();
+    }
+
+    assert_eq!(
+        list_proposal_ids(
+            deps.as_ref(),
+            QueryMsg::ListProposalsByStatus {
+                status: ProposalStatus::Timelocked,
+                start_after: None,
+                limit: None,
+            }
+        ),
+        vec![1, 2, 5]
+    );
+    assert_eq!(
+        list_proposal_ids(
+            deps.as_ref(),
+            QueryMsg::ListProposalsByStatus {
+                status: ProposalStatus::Timelocked,
+                start_after: Some(1),
+                limit: Some(1),
+            }
+        ),
+        vec![2]
+    );
+    assert_eq!(
+        list_proposal_ids(
+            deps.as_ref(),
+            QueryMsg::ListProposalsByStatus {
+                status: ProposalStatus::Executed,
+                start_after: None,
+                limit: None,
+            }
+        ),
+        vec![4]
+    );
+
+    // the status index follows status updates
+    let mut prop = proposals().load(deps.as_ref().storage, 1).unwrap();
+    prop.status = ProposalStatus::Overruled;
+    proposals().save(deps.as_mut().storage, 1, &prop).unwrap();
+    assert_eq!(
+        list_proposal_ids(
+            deps.as_ref(),
+            QueryMsg::ListProposalsByStatus {
+                status: ProposalStatus::Timelocked,
+                start_after: None,
+                limit: None,
+            }
+        ),
+        vec![2, 5]
+    );
+    assert_eq!(
+        list_proposal_ids(
+            deps.as_ref(),
+            QueryMsg::ListProposalsByStatus {
+                status: ProposalStatus::Overruled,
+                start_after: None,
+                limit: None,
+            }
+        ),
+        vec![1]
+    );
+
+    // the proposals which timelock is over and the fast-tracked ones are
+    // executable even while the main DAO is voting on the overrule proposals
+    assert_eq!(
+        list_proposal_ids(
+            deps.as_ref(),
+            QueryMsg::ListExecutable {
+                start_after: None,
+                limit: None
+            }
+        ),
+        vec![3, 5]
+    );
+
+    // the limit counts the checked proposals, and the listing can be
+    // continued even if none of them is executable
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::ListExecutable {
+            start_after: None,
+            limit: Some(1),
+        },
+    )
+    .unwrap();
+    let page: ExecutableProposalsResponse = from_json(res).unwrap();
+    assert!(page.proposals.is_empty());
+    assert_eq!(page.next_start_after, Some(2));
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::ListExecutable {
+            start_after: page.next_start_after,
+            limit: Some(2),
+        },
+    )
+    .unwrap();
+    let page: ExecutableProposalsResponse = from_json(res).unwrap();
+    assert_eq!(
+        page.proposals.iter().map(|p| p.id).collect::<Vec<u64>>(),
+        vec![3, 5]
+    );
+    assert_eq!(page.next_start_after, None);
+
+    {
+        let mut data_mut_ref = overrule_proposal_status.borrow_mut();
+        *data_mut_ref = Status::Rejected;
+    }
+    assert_eq!(
+        list_proposal_ids(
+            deps.as_ref(),
+            QueryMsg::ListExecutable {
+                start_after: None,
+                limit: None
+            }
+        ),
+        vec![3, 5]
+    );
+    assert_eq!(
+        list_proposal_ids(
+            deps.as_ref(),
+            QueryMsg::ListExecutable {
+                start_after: Some(3),
+                limit: None
+            }
+        ),
+        vec![5]
+    );
+    assert_eq!(
+        list_proposal_ids(
+            deps.as_ref(),
+            QueryMsg::ListExecutable {
+                start_after: None,
+                limit: Some(2)
+            }
+        ),
+        vec![3]
+    );
+
+    // nothing is executable once the main DAO passes the overrule proposals
+    {
+        let mut data_mut_ref = overrule_proposal_status.borrow_mut();
+        *data_mut_ref = Status::Passed;
+    }
+    assert_eq!(
+        list_proposal_ids(
+            deps.as_ref(),
+            QueryMsg::ListExecutable {
+                start_after: None,
+                limit: None
+            }
+        ),
+        Vec::<u64>::new()
+    );
+}
+
 #[test]
 fn test_reply() {
     let overrule_proposal_status = Rc::new(RefCell::new(Status::Open));
@@ -670,9 +1643,13 @@ fn test_reply() {
         id: 10,
         msgs: vec![correct_proposal_msg()],
         status: ProposalStatus::Timelocked,
+        timelocked_at: None,
+        executable_at: None,
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
     };
     let env = mock_env();
-    PROPOSALS.save(deps.as_mut().storage, 10, &prop).unwrap();
+    proposals().save(deps.as_mut().storage, 10, &prop).unwrap();
     let res_ok = reply(deps.as_mut(), env, msg).unwrap();
     assert_eq!(0, res_ok.messages.len());
     let expected_attributes = vec![Attribute::new("timelocked_proposal_execution_failed", "10")];
@@ -683,6 +1660,303 @@ fn test_reply() {
     assert_eq!(error, Some("error".to_string()));
 }
 
+#[test]
+fn test_retry_proposal() {
+    let overrule_proposal_status = Rc::new(RefCell::new(Status::Open));
+    let mut deps = mock_dependencies(Rc::clone(&overrule_proposal_status));
+    deps.querier.set_close_proposal_on_execution_failure(true);
+    let mut env = mock_env();
+    let info = mock_info("neutron1unknownsender", &[]);
+
+    let mut config = Config {
+        owner: Addr::unchecked("owner"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: None,
+        fast_track_approvers: vec![],
+    };
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+    let proposal = SingleChoiceProposal {
+        id: 10,
+        msgs: vec![
+            timelocked_msgs(vec![burn_msg(1)]),
+            timelocked_msgs(vec![burn_msg(2)]),
+        ],
+        status: ProposalStatus::Timelocked,
+        timelocked_at: None,
+        executable_at: None,
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
+    };
+    proposals()
+        .save(deps.as_mut().storage, proposal.id, &proposal)
+        .unwrap();
+    let msg = ExecuteMsg::RetryProposal { proposal_id: 10 };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert_eq!(
+        "Wrong proposal status (timelocked)",
+        res.unwrap_err().to_string()
+    );
+
+    let failure = Reply {
+        id: 10,
+        result: SubMsgResult::Err("error".to_string()),
+    };
+    reply(deps.as_mut(), env.clone(), failure.clone()).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert_eq!(
+        "Retrying failed proposals is disabled",
+        res.unwrap_err().to_string()
+    );
+
+    config.retry_policy = Some(RetryPolicy {
+        max_attempts: 3,
+        retry_window: Duration::Height(100),
+    });
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+    // two more attempts are allowed
+    for attempt in 2..=3 {
+        env.block.height += 1;
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+        let expected_attributes = vec![
+            Attribute::new("action", "retry_proposal"),
+            Attribute::new("sender", "neutron1unknownsender"),
+            Attribute::new("proposal_id", "10"),
+            Attribute::new("attempt", attempt.to_string()),
+        ];
+        assert_eq!(expected_attributes, res.attributes);
+        // every attempt executes all the messages in a single submessage,
+        // so nothing executed by a failed attempt stays committed
+        assert_eq!(
+            vec![SubMsg::reply_on_error(
+                timelocked_msgs(vec![burn_msg(1), burn_msg(2)]),
+                10
+            )],
+            res.messages
+        );
+        let updated_prop = proposals().load(deps.as_mut().storage, 10).unwrap();
+        assert_eq!(ProposalStatus::Executed, updated_prop.status);
+
+        reply(deps.as_mut(), env.clone(), failure.clone()).unwrap();
+        // another failure within the same attempt isn't recorded
+        reply(deps.as_mut(), env.clone(), failure.clone()).unwrap();
+    }
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert_eq!(
+        "Proposal has reached the maximum number of execution attempts (3)",
+        res.unwrap_err().to_string()
+    );
+
+    let query_msg = QueryMsg::ProposalExecutionFailures { proposal_id: 10 };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let failures: Vec<ExecutionFailure> = from_json(res).unwrap();
+    let first_height = mock_env().block.height;
+    assert_eq!(
+        failures,
+        vec![
+            ExecutionFailure {
+                height: first_height,
+                time: env.block.time,
+                error: "error".to_string(),
+            },
+            ExecutionFailure {
+                height: first_height + 1,
+                time: env.block.time,
+                error: "error".to_string(),
+            },
+            ExecutionFailure {
+                height: first_height + 2,
+                time: env.block.time,
+                error: "error".to_string(),
+            },
+        ]
+    );
+
+    // the proposal can't be retried after the retry window
+    config.retry_policy = Some(RetryPolicy {
+        max_attempts: 10,
+        retry_window: Duration::Height(100),
+    });
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+    env.block.height = first_height + 100;
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert_eq!(
+        "Retry window of the proposal has expired",
+        res.unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_retry_legacy_failed_proposal() {
+    let overrule_proposal_status = Rc::new(RefCell::new(Status::Rejected));
+    let mut deps = mock_dependencies(Rc::clone(&overrule_proposal_status));
+    let mut env = mock_env();
+    let info = mock_info("neutron1unknownsender", &[]);
+
+    // the proposal failed before retries were introduced, so it has
+    // no failures recorded
+    let old_config = ConfigV1 {
+        owner: Addr::unchecked("owner"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+    };
+    CONFIG_V1.save(deps.as_mut().storage, &old_config).unwrap();
+    cw2::set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.2.3").unwrap();
+    let proposal = SingleChoiceProposal {
+        id: 10,
+        msgs: vec![timelocked_msgs(vec![burn_msg(1)])],
+        status: ProposalStatus::ExecutionFailed,
+        timelocked_at: None,
+        executable_at: None,
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
+    };
+    proposals()
+        .save(deps.as_mut().storage, proposal.id, &proposal)
+        .unwrap();
+    PROPOSAL_EXECUTION_ERRORS
+        .save(deps.as_mut().storage, proposal.id, &"error".to_string())
+        .unwrap();
+
+    migrate(
+        deps.as_mut(),
+        env.clone(),
+        MigrateMsg {
+            timelock_duration: Some(Duration::Height(10)),
+            retry_policy: Some(RetryPolicy {
+                max_attempts: 2,
+                retry_window: Duration::Height(100),
+            }),
+            fast_track_approvers: None,
+        },
+    )
+    .unwrap();
+
+    // the failure is recorded at the migration block
+    let query_msg = QueryMsg::ProposalExecutionFailures { proposal_id: 10 };
+    let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+    let failures: Vec<ExecutionFailure> = from_json(res).unwrap();
+    assert_eq!(
+        failures,
+        vec![ExecutionFailure {
+            height: env.block.height,
+            time: env.block.time,
+            error: "error".to_string(),
+        }]
+    );
+
+    // the retry window starts at the migration
+    let msg = ExecuteMsg::RetryProposal { proposal_id: 10 };
+    let mut expired_env = env.clone();
+    expired_env.block.height += 100;
+    let res = execute(deps.as_mut(), expired_env, info.clone(), msg.clone());
+    assert_eq!(
+        "Retry window of the proposal has expired",
+        res.unwrap_err().to_string()
+    );
+
+    // and the legacy failure counts as the first attempt
+    env.block.height += 1;
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(
+        Attribute::new("attempt", "2"),
+        *res.attributes.last().unwrap()
+    );
+    reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: 10,
+            result: SubMsgResult::Err("error".to_string()),
+        },
+    )
+    .unwrap();
+    let res = execute(deps.as_mut(), env, info, msg);
+    assert_eq!(
+        "Proposal has reached the maximum number of execution attempts (2)",
+        res.unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_abandon_proposal() {
+    let overrule_proposal_status = Rc::new(RefCell::new(Status::Open));
+    let mut deps = mock_dependencies(Rc::clone(&overrule_proposal_status));
+    let env = mock_env();
+
+    let config = Config {
+        owner: Addr::unchecked("owner"),
+        overrule_pre_propose: Addr::unchecked(MOCK_OVERRULE_PREPROPOSAL),
+        subdao: Addr::unchecked(MOCK_SUBDAO_CORE_ADDR),
+        timelock_duration: Duration::Height(10),
+        retry_policy: Some(RetryPolicy {
+            max_attempts: 3,
+            retry_window: Duration::Height(100),
+        }),
+        fast_track_approvers: vec![],
+    };
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+    let proposal = SingleChoiceProposal {
+        id: 10,
+        msgs: vec![correct_proposal_msg()],
+        status: ProposalStatus::Timelocked,
+        timelocked_at: None,
+        executable_at: None,
+        overruled_msgs: vec![],
+        fast_tracked_by: None,
+    };
+    proposals()
+        .save(deps.as_mut().storage, proposal.id, &proposal)
+        .unwrap();
+
+    let msg = ExecuteMsg::AbandonProposal { proposal_id: 10 };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        msg.clone(),
+    );
+    assert_eq!("Unauthorized", res.unwrap_err().to_string());
+
+    let info = mock_info(MOCK_SUBDAO_CORE_ADDR, &[]);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone());
+    assert_eq!(
+        "Wrong proposal status (timelocked)",
+        res.unwrap_err().to_string()
+    );
+
+    let failure = Reply {
+        id: 10,
+        result: SubMsgResult::Err("error".to_string()),
+    };
+    reply(deps.as_mut(), env.clone(), failure).unwrap();
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+    let expected_attributes = vec![
+        Attribute::new("action", "abandon_proposal"),
+        Attribute::new("sender", MOCK_SUBDAO_CORE_ADDR),
+        Attribute::new("proposal_id", "10"),
+    ];
+    assert_eq!(expected_attributes, res.attributes);
+    let updated_prop = proposals().load(deps.as_mut().storage, 10).unwrap();
+    assert_eq!(ProposalStatus::Abandoned, updated_prop.status);
+
+    // abandoned proposals can't be retried
+    let res = execute(
+        deps.as_mut(),
+        env,
+        info,
+        ExecuteMsg::RetryProposal { proposal_id: 10 },
+    );
+    assert_eq!(
+        "Wrong proposal status (abandoned)",
+        res.unwrap_err().to_string()
+    );
+}
+
 fn correct_proposal_msg() -> CosmosMsg<NeutronMsg> {
     CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: "".to_string(),
@@ -690,3 +1964,23 @@ fn correct_proposal_msg() -> CosmosMsg<NeutronMsg> {
         funds: vec![],
     })
 }
+
+fn timelocked_msgs(msgs: Vec<CosmosMsg<NeutronMsg>>) -> CosmosMsg<NeutronMsg> {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: MOCK_SUBDAO_CORE_ADDR.to_string(),
+        msg: to_json_binary(&CoreExecuteMsg::ExecuteTimelockedMsgs { msgs }).unwrap(),
+        funds: vec![],
+    })
+}
+
+fn burn_msg(amount: u128) -> CosmosMsg<NeutronMsg> {
+    CosmosMsg::Bank(BankMsg::Burn {
+        amount: coins(amount, "untrn"),
+    })
+}
+
+fn list_proposal_ids(deps: Deps, query_msg: QueryMsg) -> Vec<u64> {
+    let res = query(deps, mock_env(), query_msg).unwrap();
+    let queried_props: ProposalListResponse = from_json(res).unwrap();
+    queried_props.proposals.iter().map(|p| p.id).collect()
+}
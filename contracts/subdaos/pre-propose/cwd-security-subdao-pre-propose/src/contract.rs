@@ -1,30 +1,47 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_json, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
-    WasmMsg,
+    from_json, to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult, WasmMsg,
 };
 use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use cw_utils::{Duration, Expiration};
+use cwd_core::msg::QueryMsg as MainDaoQueryMsg;
+use exec_control::pause::MAX_PAUSE_DURATION;
 use neutron_sdk::bindings::msg::NeutronMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::state::EMERGENCY_ACTIONS;
 use cwd_pre_propose_base::{
     error::PreProposeError,
     msg::{ExecuteMsg as ExecuteBase, InstantiateMsg as InstantiateBase, QueryMsg as QueryBase},
     state::PreProposeContract,
 };
 use neutron_security_subdao_pre_propose::msg::{
-    ExecuteMsgPauseTypedDuration, ExecuteMsgPauseUntypedDuration, MigrateMsg,
+    ExecuteExt, ExecuteMsgPauseTypedDuration, ExecuteMsgPauseUntypedDuration,
+    ExecuteMsgRemoveStrategy, MigrateMsg, QueryExt,
 };
-use neutron_security_subdao_pre_propose::types::ProposeMessage;
+use neutron_security_subdao_pre_propose::types::{
+    ContractPauseStatus, EmergencyAction, EmergencyCatalogEntry, ProposeMessage,
+    PAUSABLE_ITEM_PREFIX, PAUSE_TYPED_DURATION, PAUSE_UNTYPED_DURATION,
+};
+use neutron_subdao_core::msg::QueryMsg as SubdaoQueryMsg;
 pub type InstantiateMsg = InstantiateBase;
-pub type ExecuteMsg = ExecuteBase<ProposeMessage>;
-pub type QueryMsg = QueryBase<Empty>;
+pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
+pub type QueryMsg = QueryBase<QueryExt>;
 
 pub(crate) const CONTRACT_NAME: &str = "crates.io:cwd-security-subdao-pre-propose";
 pub(crate) const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// The pause info query every pausable contract supports.
+#[derive(Serialize, JsonSchema, Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+enum PauseInfoQuery {
+    PauseInfo {},
+}
+
 /// Internal version of the propose message that includes the
 /// `proposer` field. The module will fill this in based on the sender
 /// of the external message.
@@ -39,7 +56,10 @@ enum ProposeMessageInternal {
     },
 }
 
-type PrePropose = PreProposeContract<ProposeMessageInternal, Empty>;
+type PrePropose = PreProposeContract<ProposeMessageInternal, QueryExt>;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -77,13 +97,25 @@ pub fn execute(
             for msg in &msgs {
                 match msg {
                     CosmosMsg::Wasm(WasmMsg::Execute {
-                        contract_addr: _contract_addr,
+                        contract_addr,
                         msg,
                         funds,
                     }) => {
-                        if (from_json::<ExecuteMsgPauseTypedDuration>(msg).is_err()
-                            && from_json::<ExecuteMsgPauseUntypedDuration>(msg).is_err())
-                            || !funds.is_empty()
+                        if !funds.is_empty() {
+                            return Err(PreProposeError::MalformedProposal {});
+                        }
+                        if from_json::<ExecuteMsgPauseTypedDuration>(msg).is_ok()
+                            || from_json::<ExecuteMsgPauseUntypedDuration>(msg).is_ok()
+                        {
+                            continue;
+                        }
+                        // Anything but pausing has to be in the emergency catalog
+                        let actions = EMERGENCY_ACTIONS
+                            .may_load(deps.storage, deps.api.addr_validate(contract_addr)?)?
+                            .unwrap_or_default();
+                        if !actions
+                            .iter()
+                            .any(|action| is_emergency_action_msg(action, msg))
                         {
                             return Err(PreProposeError::MalformedProposal {});
                         }
@@ -105,6 +137,36 @@ pub fn execute(
                 },
             }
         }
+        ExecuteMsg::Propose {
+            msg:
+                ProposeMessage::PauseAll {
+                    title,
+                    description,
+                    duration,
+                },
+        } => {
+            if duration > MAX_PAUSE_DURATION {
+                return Err(PreProposeError::MalformedProposal {});
+            }
+
+            let msgs = get_pausable_contracts(deps.as_ref())?
+                .into_iter()
+                .map(|(contract, duration_type)| pause_msg(contract, &duration_type, duration))
+                .collect::<StdResult<Vec<CosmosMsg<NeutronMsg>>>>()?;
+            if msgs.is_empty() {
+                return Err(PreProposeError::MalformedProposal {});
+            }
+
+            ExecuteInternal::Propose {
+                msg: ProposeMessageInternal::Propose {
+                    // Fill in proposer based on message sender.
+                    proposer: Some(info.sender.to_string()),
+                    title,
+                    description,
+                    msgs,
+                },
+            }
+        }
         ExecuteMsg::Withdraw { denom } => ExecuteInternal::Withdraw { denom },
         ExecuteMsg::UpdateConfig {
             deposit_info,
@@ -127,14 +189,184 @@ pub fn execute(
             proposal_id,
             new_status,
         },
+        ExecuteMsg::Extension {
+            msg: ExecuteExt::SetEmergencyActions { contract, actions },
+        } => return execute_set_emergency_actions(deps, info, contract, actions),
     };
 
     PrePropose::default().execute(deps, env, info, internalized)
 }
 
+fn execute_set_emergency_actions(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: String,
+    actions: Vec<EmergencyAction>,
+) -> Result<Response, PreProposeError> {
+    // The security subDAO must not be able to extend its own powers, so the
+    // catalog is managed by the main DAO.
+    if info.sender != get_main_dao(deps.as_ref())? {
+        return Err(PreProposeError::NotDao {});
+    }
+
+    let contract = deps.api.addr_validate(&contract)?;
+    if actions.is_empty() {
+        EMERGENCY_ACTIONS.remove(deps.storage, contract.clone());
+    } else {
+        EMERGENCY_ACTIONS.save(deps.storage, contract.clone(), &actions)?;
+    }
+
+    Ok(Response::default()
+        .add_attribute("action", "set_emergency_actions")
+        .add_attribute("contract", contract)
+        .add_attribute("actions_count", actions.len().to_string()))
+}
+
+fn get_main_dao(deps: Deps) -> StdResult<Addr> {
+    deps.querier.query_wasm_smart(
+        PrePropose::default().dao.load(deps.storage)?,
+        &SubdaoQueryMsg::MainDao {},
+    )
+}
+
+// Returns the contracts of the pause registry along with the duration types of their
+// `Pause` messages.
+fn get_pausable_contracts(deps: Deps) -> StdResult<Vec<(Addr, String)>> {
+    let items: Vec<(String, String)> = deps.querier.query_wasm_smart(
+        get_main_dao(deps)?,
+        &MainDaoQueryMsg::ListItems {
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    items
+        .into_iter()
+        .filter_map(|(key, duration_type)| {
+            key.strip_prefix(PAUSABLE_ITEM_PREFIX)
+                .map(|contract| Ok((deps.api.addr_validate(contract)?, duration_type)))
+        })
+        .collect()
+}
+
+fn pause_msg(
+    contract: Addr,
+    duration_type: &str,
+    duration: u64,
+) -> StdResult<CosmosMsg<NeutronMsg>> {
+    let msg = match duration_type {
+        PAUSE_TYPED_DURATION => to_json_binary(&ExecuteMsgPauseTypedDuration::Pause {
+            duration: Duration::Height(duration),
+        })?,
+        PAUSE_UNTYPED_DURATION => {
+            to_json_binary(&ExecuteMsgPauseUntypedDuration::Pause { duration })?
+        }
+        _ => {
+            return Err(StdError::generic_err(format!(
+                "unknown pause duration type '{}' of {}",
+                duration_type, contract
+            )))
+        }
+    };
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract.to_string(),
+        msg,
+        funds: vec![],
+    }))
+}
+
+pub(crate) fn is_emergency_action_msg(action: &EmergencyAction, msg: &Binary) -> bool {
+    match action {
+        EmergencyAction::RemoveStrategy {} => from_json::<ExecuteMsgRemoveStrategy>(msg).is_ok(),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    PrePropose::default().query(deps, env, msg)
+    match msg {
+        QueryMsg::QueryExtension {
+            msg: QueryExt::EmergencyCatalog { start_after, limit },
+        } => to_json_binary(&query_emergency_catalog(deps, start_after, limit)?),
+        QueryMsg::QueryExtension {
+            msg: QueryExt::PauseStatus {},
+        } => to_json_binary(&query_pause_status(deps)?),
+        _ => PrePropose::default().query(deps, env, msg),
+    }
+}
+
+fn query_emergency_catalog(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<EmergencyCatalogEntry>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    EMERGENCY_ACTIONS
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(contract, actions)| EmergencyCatalogEntry { contract, actions }))
+        .collect()
+}
+
+/// The pause info of both the contracts using the `exec-control` package and the DAO
+/// core, which reports the pause expiration instead of the height.
+#[derive(Deserialize)]
+enum PauseInfo {
+    #[serde(rename = "paused", alias = "Paused")]
+    Paused {
+        until_height: Option<u64>,
+        expiration: Option<Expiration>,
+    },
+    #[serde(rename = "unpaused", alias = "Unpaused")]
+    Unpaused {},
+}
+
+// A contract of the registry which pause info can't be queried is reported
+// with the error instead of failing the whole query.
+fn query_pause_status(deps: Deps) -> StdResult<Vec<ContractPauseStatus>> {
+    let items: Vec<(String, String)> = deps.querier.query_wasm_smart(
+        get_main_dao(deps)?,
+        &MainDaoQueryMsg::ListItems {
+            start_after: None,
+            limit: None,
+        },
+    )?;
+    Ok(items
+        .into_iter()
+        .filter_map(|(key, _)| key.strip_prefix(PAUSABLE_ITEM_PREFIX).map(str::to_string))
+        .map(|contract| match query_paused_until(deps, &contract) {
+            Ok(paused_until) => ContractPauseStatus {
+                contract: Addr::unchecked(contract),
+                paused_until,
+                error: None,
+            },
+            Err(err) => ContractPauseStatus {
+                contract: Addr::unchecked(contract),
+                paused_until: None,
+                error: Some(err.to_string()),
+            },
+        })
+        .collect())
+}
+
+fn query_paused_until(deps: Deps, contract: &str) -> StdResult<Option<Expiration>> {
+    let contract = deps.api.addr_validate(contract)?;
+    let pause_info: PauseInfo = deps
+        .querier
+        .query_wasm_smart(contract, &PauseInfoQuery::PauseInfo {})?;
+    Ok(match pause_info {
+        PauseInfo::Paused {
+            until_height,
+            expiration,
+        } => until_height.map(Expiration::AtHeight).or(expiration),
+        PauseInfo::Unpaused {} => None,
+    })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -1 +1,5 @@
 pub mod contract;
+mod state;
+
+#[cfg(test)]
+pub mod testing;
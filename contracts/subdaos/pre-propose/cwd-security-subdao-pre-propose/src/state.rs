@@ -0,0 +1,6 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::Map;
+use neutron_security_subdao_pre_propose::types::EmergencyAction;
+
+/// Emergency actions the security subDAO may take on the contracts, besides pausing them.
+pub const EMERGENCY_ACTIONS: Map<Addr, Vec<EmergencyAction>> = Map::new("emergency_actions");
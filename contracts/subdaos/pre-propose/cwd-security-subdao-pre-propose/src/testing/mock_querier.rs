@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use cosmwasm_std::{
+    from_json,
+    testing::{MockApi, MockQuerier, MockStorage},
+    to_json_binary, Addr, Binary, ContractResult, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, WasmQuery,
+};
+use cwd_core::msg::QueryMsg as MainDaoQueryMsg;
+use neutron_subdao_core::msg::QueryMsg as SubdaoQueryMsg;
+use serde::Deserialize;
+
+pub const MOCK_MAIN_DAO_CORE: &str = "neutron1main_dao_core";
+pub const MOCK_SUBDAO_CORE: &str = "neutron1subdao_core";
+pub const MOCK_PROPOSE_MODULE: &str = "neutron1propose_module";
+
+pub fn mock_dependencies(
+    contracts: HashMap<String, Box<dyn ContractQuerier>>,
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier = WasmMockQuerier::new(MockQuerier::new(&[]), contracts);
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+        custom_query_type: PhantomData,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier,
+    contracts: HashMap<String, Box<dyn ContractQuerier>>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_json(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return QuerierResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {:?}", e),
+                    request: bin_request.into(),
+                });
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier, contracts: HashMap<String, Box<dyn ContractQuerier>>) -> Self {
+        WasmMockQuerier { base, contracts }
+    }
+
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match self.contracts.get(contract_addr) {
+                    None => SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr.to_string(),
+                    }),
+                    Some(m) => m.query(msg),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+pub trait ContractQuerier {
+    fn query(&self, msg: &Binary) -> QuerierResult;
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ProposalModuleQueryMsg {
+    Dao {},
+}
+
+pub struct MockProposalModuleQueries {}
+
+impl ContractQuerier for MockProposalModuleQueries {
+    fn query(&self, msg: &Binary) -> QuerierResult {
+        match from_json(msg) {
+            Ok(ProposalModuleQueryMsg::Dao {}) => SystemResult::Ok(ContractResult::from(
+                to_json_binary(&Addr::unchecked(MOCK_SUBDAO_CORE)),
+            )),
+            Err(_) => SystemResult::Err(SystemError::Unknown {}),
+        }
+    }
+}
+
+pub struct MockSubdaoCoreQueries {}
+
+impl ContractQuerier for MockSubdaoCoreQueries {
+    fn query(&self, msg: &Binary) -> QuerierResult {
+        match from_json(msg) {
+            Ok(SubdaoQueryMsg::MainDao {}) => SystemResult::Ok(ContractResult::from(
+                to_json_binary(&Addr::unchecked(MOCK_MAIN_DAO_CORE)),
+            )),
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        }
+    }
+}
+
+pub struct MockMainDaoQueries {
+    /// The items of the main DAO core, the pause registry included.
+    pub items: Vec<(String, String)>,
+}
+
+impl ContractQuerier for MockMainDaoQueries {
+    fn query(&self, msg: &Binary) -> QuerierResult {
+        match from_json(msg) {
+            Ok(MainDaoQueryMsg::ListItems { .. }) => {
+                SystemResult::Ok(ContractResult::from(to_json_binary(&self.items)))
+            }
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum PausableQueryMsg {
+    PauseInfo {},
+}
+
+/// A pausable contract replying to `PauseInfo` with the given response.
+pub struct MockPausableQueries {
+    pub pause_info: Binary,
+}
+
+impl ContractQuerier for MockPausableQueries {
+    fn query(&self, msg: &Binary) -> QuerierResult {
+        match from_json(msg) {
+            Ok(PausableQueryMsg::PauseInfo {}) => {
+                SystemResult::Ok(ContractResult::Ok(self.pause_info.clone()))
+            }
+            Err(_) => SystemResult::Err(SystemError::Unknown {}),
+        }
+    }
+}
+
+pub fn get_dao_with_items(
+    items: Vec<(String, String)>,
+) -> HashMap<String, Box<dyn ContractQuerier>> {
+    let mut contracts: HashMap<String, Box<dyn ContractQuerier>> = HashMap::new();
+    contracts.insert(
+        MOCK_PROPOSE_MODULE.to_string(),
+        Box::new(MockProposalModuleQueries {}),
+    );
+    contracts.insert(
+        MOCK_SUBDAO_CORE.to_string(),
+        Box::new(MockSubdaoCoreQueries {}),
+    );
+    contracts.insert(
+        MOCK_MAIN_DAO_CORE.to_string(),
+        Box::new(MockMainDaoQueries { items }),
+    );
+    contracts
+}
+
+pub fn get_properly_initialized_dao() -> HashMap<String, Box<dyn ContractQuerier>> {
+    get_dao_with_items(vec![])
+}
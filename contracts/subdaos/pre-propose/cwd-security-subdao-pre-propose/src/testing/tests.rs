@@ -0,0 +1,571 @@
+use cosmwasm_std::{
+    coins, from_json,
+    testing::{mock_env, mock_info},
+    to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Empty, Response, WasmMsg,
+};
+use cw_utils::{Duration, Expiration};
+use cwd_core::query::PauseInfoResponse as MainDaoPauseInfoResponse;
+use cwd_pre_propose_base::error::PreProposeError;
+use exec_control::pause::{PauseInfoResponse, MAX_PAUSE_DURATION};
+use neutron_sdk::bindings::msg::NeutronMsg;
+use serde::Serialize;
+
+use neutron_security_subdao_pre_propose::{
+    msg::{
+        ExecuteExt, ExecuteMsgPauseTypedDuration, ExecuteMsgPauseUntypedDuration,
+        ExecuteMsgRemoveStrategy, QueryExt,
+    },
+    types::{ContractPauseStatus, EmergencyAction, EmergencyCatalogEntry, ProposeMessage},
+};
+use neutron_subdao_proposal_single::msg::ExecuteMsg as ProposalExecuteMsg;
+
+use crate::contract::{
+    execute, instantiate, is_emergency_action_msg, query, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use crate::testing::mock_querier::{
+    get_dao_with_items, get_properly_initialized_dao, mock_dependencies, MockPausableQueries,
+    MOCK_MAIN_DAO_CORE, MOCK_PROPOSE_MODULE, MOCK_SUBDAO_CORE,
+};
+
+const PROPOSER_ADDR: &str = "neutron1proposer";
+const MOCK_CHAIN_MANAGER: &str = "neutron1chain_manager";
+const MOCK_UNLISTED_CONTRACT: &str = "neutron1unlisted_contract";
+const MOCK_TYPED_PAUSABLE: &str = "neutron1typed_pausable";
+const MOCK_UNTYPED_PAUSABLE: &str = "neutron1untyped_pausable";
+
+/// A hook message that is not an emergency action.
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum AddHookMsg {
+    AddProposalHook { address: String },
+}
+
+fn init_base_contract(deps: DepsMut) {
+    let msg = InstantiateMsg {
+        deposit_info: None,
+        open_proposal_submission: true,
+    };
+    let info = mock_info(MOCK_PROPOSE_MODULE, &[]);
+    instantiate(deps, mock_env(), info, msg).unwrap();
+}
+
+fn set_emergency_actions(
+    deps: DepsMut,
+    sender: &str,
+    contract: &str,
+    actions: Vec<EmergencyAction>,
+) -> Result<(), PreProposeError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::Extension {
+            msg: ExecuteExt::SetEmergencyActions {
+                contract: contract.to_string(),
+                actions,
+            },
+        },
+    )
+    .map(|_| ())
+}
+
+fn propose(deps: DepsMut, msgs: Vec<CosmosMsg<NeutronMsg>>) -> Result<(), PreProposeError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info(PROPOSER_ADDR, &[]),
+        ExecuteMsg::Propose {
+            msg: ProposeMessage::Propose {
+                title: "Emergency".to_string(),
+                description: "Emergency actions".to_string(),
+                msgs,
+            },
+        },
+    )
+    .map(|_| ())
+}
+
+fn query_catalog(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Vec<EmergencyCatalogEntry> {
+    let res = query(
+        deps,
+        mock_env(),
+        QueryMsg::QueryExtension {
+            msg: QueryExt::EmergencyCatalog { start_after, limit },
+        },
+    )
+    .unwrap();
+    from_json(res).unwrap()
+}
+
+fn wasm_execute(contract: &str, msg: Binary) -> CosmosMsg<NeutronMsg> {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract.to_string(),
+        msg,
+        funds: vec![],
+    })
+}
+
+fn remove_strategy_msg() -> Binary {
+    to_json_binary(&ExecuteMsgRemoveStrategy::RemoveStrategy {
+        address: Addr::unchecked("neutron1strategy"),
+    })
+    .unwrap()
+}
+
+fn add_hook_msg() -> Binary {
+    to_json_binary(&AddHookMsg::AddProposalHook {
+        address: "neutron1hook".to_string(),
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_set_emergency_actions() {
+    let mut deps = mock_dependencies(get_properly_initialized_dao());
+    init_base_contract(deps.as_mut());
+
+    let actions = vec![EmergencyAction::RemoveStrategy {}];
+
+    // only the main DAO manages the catalog, neither the subdao nor anybody else
+    for sender in [PROPOSER_ADDR, MOCK_SUBDAO_CORE, MOCK_PROPOSE_MODULE] {
+        let res = set_emergency_actions(deps.as_mut(), sender, MOCK_CHAIN_MANAGER, actions.clone());
+        assert_eq!(res, Err(PreProposeError::NotDao {}));
+    }
+    assert!(query_catalog(deps.as_ref(), None, None).is_empty());
+
+    set_emergency_actions(
+        deps.as_mut(),
+        MOCK_MAIN_DAO_CORE,
+        MOCK_CHAIN_MANAGER,
+        actions.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        query_catalog(deps.as_ref(), None, None),
+        vec![EmergencyCatalogEntry {
+            contract: Addr::unchecked(MOCK_CHAIN_MANAGER),
+            actions,
+        }]
+    );
+
+    // the contract address is validated
+    let res = set_emergency_actions(
+        deps.as_mut(),
+        MOCK_MAIN_DAO_CORE,
+        "Neutron1InvalidContract",
+        vec![EmergencyAction::RemoveStrategy {}],
+    );
+    assert!(matches!(res, Err(PreProposeError::Std(_))));
+
+    // an empty list removes the contract from the catalog
+    set_emergency_actions(
+        deps.as_mut(),
+        MOCK_MAIN_DAO_CORE,
+        MOCK_CHAIN_MANAGER,
+        vec![],
+    )
+    .unwrap();
+    assert!(query_catalog(deps.as_ref(), None, None).is_empty());
+}
+
+#[test]
+fn test_query_emergency_catalog() {
+    let mut deps = mock_dependencies(get_properly_initialized_dao());
+    init_base_contract(deps.as_mut());
+
+    let contracts = [
+        "neutron1contract_a",
+        "neutron1contract_b",
+        "neutron1contract_c",
+    ];
+    for contract in contracts {
+        set_emergency_actions(
+            deps.as_mut(),
+            MOCK_MAIN_DAO_CORE,
+            contract,
+            vec![EmergencyAction::RemoveStrategy {}],
+        )
+        .unwrap();
+    }
+
+    let catalog = query_catalog(deps.as_ref(), None, None);
+    assert_eq!(
+        catalog
+            .iter()
+            .map(|entry| entry.contract.to_string())
+            .collect::<Vec<String>>(),
+        contracts.to_vec()
+    );
+
+    let catalog = query_catalog(deps.as_ref(), None, Some(2));
+    assert_eq!(catalog.len(), 2);
+    assert_eq!(catalog[1].contract, Addr::unchecked(contracts[1]));
+
+    let catalog = query_catalog(deps.as_ref(), Some(contracts[1].to_string()), None);
+    assert_eq!(
+        catalog,
+        vec![EmergencyCatalogEntry {
+            contract: Addr::unchecked(contracts[2]),
+            actions: vec![EmergencyAction::RemoveStrategy {}],
+        }]
+    );
+}
+
+#[test]
+fn test_propose_scoped_to_catalog() {
+    let mut deps = mock_dependencies(get_properly_initialized_dao());
+    init_base_contract(deps.as_mut());
+
+    set_emergency_actions(
+        deps.as_mut(),
+        MOCK_MAIN_DAO_CORE,
+        MOCK_CHAIN_MANAGER,
+        vec![EmergencyAction::RemoveStrategy {}],
+    )
+    .unwrap();
+
+    // the actions from the catalog are allowed on their contracts
+    propose(
+        deps.as_mut(),
+        vec![wasm_execute(MOCK_CHAIN_MANAGER, remove_strategy_msg())],
+    )
+    .unwrap();
+
+    // but not on the contracts outside of the catalog
+    let res = propose(
+        deps.as_mut(),
+        vec![wasm_execute(MOCK_UNLISTED_CONTRACT, remove_strategy_msg())],
+    );
+    assert_eq!(res, Err(PreProposeError::MalformedProposal {}));
+
+    // a single disallowed message fails the whole proposal
+    let res = propose(
+        deps.as_mut(),
+        vec![
+            wasm_execute(MOCK_CHAIN_MANAGER, remove_strategy_msg()),
+            wasm_execute(MOCK_CHAIN_MANAGER, add_hook_msg()),
+        ],
+    );
+    assert_eq!(res, Err(PreProposeError::MalformedProposal {}));
+
+    // pausing is allowed on any contract
+    propose(
+        deps.as_mut(),
+        vec![
+            wasm_execute(
+                MOCK_UNLISTED_CONTRACT,
+                to_json_binary(&ExecuteMsgPauseTypedDuration::Pause {
+                    duration: Duration::Height(10),
+                })
+                .unwrap(),
+            ),
+            wasm_execute(
+                MOCK_UNLISTED_CONTRACT,
+                to_json_binary(&ExecuteMsgPauseUntypedDuration::Pause { duration: 10 }).unwrap(),
+            ),
+            CosmosMsg::Custom(NeutronMsg::RemoveSchedule {
+                name: "schedule".to_string(),
+            }),
+        ],
+    )
+    .unwrap();
+
+    // the messages must not carry funds
+    let res = propose(
+        deps.as_mut(),
+        vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CHAIN_MANAGER.to_string(),
+            msg: remove_strategy_msg(),
+            funds: coins(1, "untrn"),
+        })],
+    );
+    assert_eq!(res, Err(PreProposeError::MalformedProposal {}));
+
+    // and can't be anything but wasm executions and schedule removals
+    let res = propose(
+        deps.as_mut(),
+        vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: PROPOSER_ADDR.to_string(),
+            amount: coins(1, "untrn"),
+        })],
+    );
+    assert_eq!(res, Err(PreProposeError::MalformedProposal {}));
+
+    // the actions are gone once the main DAO removes the contract from the catalog
+    set_emergency_actions(
+        deps.as_mut(),
+        MOCK_MAIN_DAO_CORE,
+        MOCK_CHAIN_MANAGER,
+        vec![],
+    )
+    .unwrap();
+    let res = propose(
+        deps.as_mut(),
+        vec![wasm_execute(MOCK_CHAIN_MANAGER, remove_strategy_msg())],
+    );
+    assert_eq!(res, Err(PreProposeError::MalformedProposal {}));
+}
+
+#[test]
+fn test_is_emergency_action_msg() {
+    let cases: Vec<(EmergencyAction, Binary, bool)> = vec![
+        (
+            EmergencyAction::RemoveStrategy {},
+            remove_strategy_msg(),
+            true,
+        ),
+        (EmergencyAction::RemoveStrategy {}, add_hook_msg(), false),
+        (
+            EmergencyAction::RemoveStrategy {},
+            to_json_binary(&Empty {}).unwrap(),
+            false,
+        ),
+    ];
+    for (action, msg, expected) in cases {
+        assert_eq!(
+            is_emergency_action_msg(&action, &msg),
+            expected,
+            "{:?} on {}",
+            action,
+            String::from_utf8_lossy(msg.as_slice())
+        );
+    }
+}
+
+fn pause_all(deps: DepsMut, duration: u64) -> Result<Response, PreProposeError> {
+    execute(
+        deps,
+        mock_env(),
+        mock_info(PROPOSER_ADDR, &[]),
+        ExecuteMsg::Propose {
+            msg: ProposeMessage::PauseAll {
+                title: "Pause".to_string(),
+                description: "Pause everything".to_string(),
+                duration,
+            },
+        },
+    )
+}
+
+fn pause_registry() -> Vec<(String, String)> {
+    vec![
+        ("config".to_string(), "not a pausable contract".to_string()),
+        (
+            format!("pausable:{}", MOCK_TYPED_PAUSABLE),
+            "typed".to_string(),
+        ),
+        (
+            format!("pausable:{}", MOCK_UNTYPED_PAUSABLE),
+            "untyped".to_string(),
+        ),
+    ]
+}
+
+#[test]
+fn test_pause_all() {
+    let mut deps = mock_dependencies(get_dao_with_items(pause_registry()));
+    init_base_contract(deps.as_mut());
+
+    let res = pause_all(deps.as_mut(), 100).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    let (contract_addr, msg) = match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) => (contract_addr, msg),
+        _ => panic!("unexpected message"),
+    };
+    assert_eq!(contract_addr, MOCK_PROPOSE_MODULE);
+    let ProposalExecuteMsg::Propose { msgs, proposer, .. } = from_json(msg).unwrap() else {
+        panic!("unexpected proposal module message")
+    };
+    assert_eq!(proposer, Some(PROPOSER_ADDR.to_string()));
+    // every contract of the registry is paused with the message of its duration type,
+    // the rest of the items are skipped
+    assert_eq!(
+        msgs,
+        vec![
+            wasm_execute(
+                MOCK_TYPED_PAUSABLE,
+                to_json_binary(&ExecuteMsgPauseTypedDuration::Pause {
+                    duration: Duration::Height(100),
+                })
+                .unwrap(),
+            ),
+            wasm_execute(
+                MOCK_UNTYPED_PAUSABLE,
+                to_json_binary(&ExecuteMsgPauseUntypedDuration::Pause { duration: 100 }).unwrap(),
+            ),
+        ]
+    );
+
+    // the pause duration is capped
+    let res = pause_all(deps.as_mut(), MAX_PAUSE_DURATION + 1);
+    assert_eq!(res, Err(PreProposeError::MalformedProposal {}));
+    pause_all(deps.as_mut(), MAX_PAUSE_DURATION).unwrap();
+}
+
+#[test]
+fn test_pause_all_empty_registry() {
+    let mut deps = mock_dependencies(get_properly_initialized_dao());
+    init_base_contract(deps.as_mut());
+    let res = pause_all(deps.as_mut(), 100);
+    assert_eq!(res, Err(PreProposeError::MalformedProposal {}));
+
+    // items that aren't pausable contracts don't count
+    let mut deps = mock_dependencies(get_dao_with_items(vec![(
+        "config".to_string(),
+        "typed".to_string(),
+    )]));
+    init_base_contract(deps.as_mut());
+    let res = pause_all(deps.as_mut(), 100);
+    assert_eq!(res, Err(PreProposeError::MalformedProposal {}));
+}
+
+#[test]
+fn test_pause_all_malformed_registry() {
+    // unknown duration type
+    let mut items = pause_registry();
+    items.push((
+        "pausable:neutron1weird_pausable".to_string(),
+        "blocks".to_string(),
+    ));
+    let mut deps = mock_dependencies(get_dao_with_items(items));
+    init_base_contract(deps.as_mut());
+    let err = pause_all(deps.as_mut(), 100).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("unknown pause duration type 'blocks' of neutron1weird_pausable"));
+
+    // invalid contract address
+    let mut items = pause_registry();
+    items.push((
+        "pausable:Neutron1InvalidContract".to_string(),
+        "typed".to_string(),
+    ));
+    let mut deps = mock_dependencies(get_dao_with_items(items));
+    init_base_contract(deps.as_mut());
+    let res = pause_all(deps.as_mut(), 100);
+    assert!(matches!(res, Err(PreProposeError::Std(_))));
+}
+
+#[test]
+fn test_query_pause_status() {
+    let paused_until = Expiration::AtHeight(12345);
+    let mut contracts = get_dao_with_items(pause_registry());
+    // the DAO core reports the pause expiration
+    contracts.insert(
+        MOCK_TYPED_PAUSABLE.to_string(),
+        Box::new(MockPausableQueries {
+            pause_info: to_json_binary(&MainDaoPauseInfoResponse::Paused {
+                expiration: paused_until,
+            })
+            .unwrap(),
+        }),
+    );
+    // the contracts using the exec-control package report the pause height
+    contracts.insert(
+        MOCK_UNTYPED_PAUSABLE.to_string(),
+        Box::new(MockPausableQueries {
+            pause_info: to_json_binary(&PauseInfoResponse::Paused {
+                until_height: 12345,
+            })
+            .unwrap(),
+        }),
+    );
+    let mut deps = mock_dependencies(contracts);
+    init_base_contract(deps.as_mut());
+
+    let query_pause_status = |deps: Deps| -> Vec<ContractPauseStatus> {
+        let res = query(
+            deps,
+            mock_env(),
+            QueryMsg::QueryExtension {
+                msg: QueryExt::PauseStatus {},
+            },
+        )
+        .unwrap();
+        from_json(res).unwrap()
+    };
+    assert_eq!(
+        query_pause_status(deps.as_ref()),
+        vec![
+            ContractPauseStatus {
+                contract: Addr::unchecked(MOCK_TYPED_PAUSABLE),
+                paused_until: Some(paused_until),
+                error: None,
+            },
+            ContractPauseStatus {
+                contract: Addr::unchecked(MOCK_UNTYPED_PAUSABLE),
+                paused_until: Some(paused_until),
+                error: None,
+            },
+        ]
+    );
+
+    // the contracts which pause info can't be queried are reported with
+    // the error, without failing the query
+    let mut registry = pause_registry();
+    registry.push((
+        format!("pausable:{}", MOCK_UNLISTED_CONTRACT),
+        "untyped".to_string(),
+    ));
+    registry.push(("pausable:Neutron1Invalid".to_string(), "typed".to_string()));
+    let mut contracts = get_dao_with_items(registry);
+    contracts.insert(
+        MOCK_TYPED_PAUSABLE.to_string(),
+        Box::new(MockPausableQueries {
+            pause_info: to_json_binary(&Empty {}).unwrap(),
+        }),
+    );
+    contracts.insert(
+        MOCK_UNTYPED_PAUSABLE.to_string(),
+        Box::new(MockPausableQueries {
+            pause_info: to_json_binary(&PauseInfoResponse::Unpaused {}).unwrap(),
+        }),
+    );
+    let mut deps = mock_dependencies(contracts);
+    init_base_contract(deps.as_mut());
+    let statuses = query_pause_status(deps.as_ref());
+    assert_eq!(
+        statuses
+            .iter()
+            .map(|status| (status.contract.to_string(), status.error.is_some()))
+            .collect::<Vec<(String, bool)>>(),
+        vec![
+            (MOCK_TYPED_PAUSABLE.to_string(), true),
+            (MOCK_UNTYPED_PAUSABLE.to_string(), false),
+            (MOCK_UNLISTED_CONTRACT.to_string(), true),
+            ("Neutron1Invalid".to_string(), true),
+        ]
+    );
+    assert!(statuses.iter().all(|status| status.paused_until.is_none()));
+
+    // unpaused contracts have no pause expiration
+    let mut contracts = get_dao_with_items(pause_registry());
+    contracts.insert(
+        MOCK_TYPED_PAUSABLE.to_string(),
+        Box::new(MockPausableQueries {
+            pause_info: to_json_binary(&MainDaoPauseInfoResponse::Unpaused {}).unwrap(),
+        }),
+    );
+    contracts.insert(
+        MOCK_UNTYPED_PAUSABLE.to_string(),
+        Box::new(MockPausableQueries {
+            pause_info: to_json_binary(&PauseInfoResponse::Unpaused {}).unwrap(),
+        }),
+    );
+    let mut deps = mock_dependencies(contracts);
+    init_base_contract(deps.as_mut());
+    assert!(query_pause_status(deps.as_ref())
+        .iter()
+        .all(|status| status.paused_until.is_none()));
+
+    // the registry is empty
+    let mut deps = mock_dependencies(get_properly_initialized_dao());
+    init_base_contract(deps.as_mut());
+    assert!(query_pause_status(deps.as_ref()).is_empty());
+}
@@ -153,6 +153,7 @@ pub fn execute(
             proposal_id,
             new_status,
         },
+        ExecuteMsg::Extension { msg } => ExecuteInternal::Extension { msg },
     };
 
     PrePropose::default().execute(deps, env, info, internalized)
@@ -2,14 +2,124 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use cosmwasm_std::{Addr, CustomQuery, Deps, StdError, StdResult, Storage, SubMsg};
-use cw_storage_plus::Item;
+use cosmwasm_std::{
+    Addr, Api, CosmosMsg, CustomQuery, Deps, ReplyOn, StdError, StdResult, Storage, SubMsg,
+    Uint128, WasmMsg,
+};
+use cw_storage_plus::{Item, Map};
+use cwd_voting::status::Status;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
 pub struct HooksResponse {
     pub hooks: Vec<String>,
 }
 
+/// Restricts the events a hook is called for. Every restriction is
+/// optional, a hook without any restrictions is called for every event.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Default)]
+pub struct HookFilter {
+    /// Only call the hook for proposal status changes to one of these
+    /// statuses. A new proposal is considered a change to `open`.
+    /// Ignored for vote hooks.
+    pub statuses: Option<Vec<Status>>,
+    /// Only call the hook for votes cast with at least this voting
+    /// power. Ignored for proposal hooks.
+    pub min_vote_power: Option<Uint128>,
+    /// Only call the hook for proposals that contain a message
+    /// targeting this contract.
+    pub contract: Option<String>,
+}
+
+impl HookFilter {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        if let Some(contract) = &self.contract {
+            api.addr_validate(contract)?;
+        }
+        Ok(())
+    }
+
+    /// Returns whether a hook with this filter should be called for the
+    /// event.
+    pub fn matches(&self, event: &HookEvent) -> bool {
+        if let (Some(statuses), Some(status)) = (&self.statuses, &event.status) {
+            if !statuses.iter().any(|s| s.to_string() == *status) {
+                return false;
+            }
+        }
+        if let (Some(min_vote_power), Some(vote_power)) = (self.min_vote_power, event.vote_power) {
+            if vote_power < min_vote_power {
+                return false;
+            }
+        }
+        if let Some(contract) = &self.contract {
+            if !event.contracts.contains(contract) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Properties of an event hooks can be filtered on.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct HookEvent {
+    /// The status the proposal has changed to, as displayed by
+    /// [`Status`].
+    pub status: Option<String>,
+    /// The voting power of the vote.
+    pub vote_power: Option<Uint128>,
+    /// The contracts targeted by the messages of the proposal.
+    pub contracts: Vec<String>,
+}
+
+/// Returns the addresses of the contracts targeted by the messages.
+pub fn msg_contracts<T>(msgs: &[CosmosMsg<T>]) -> Vec<String> {
+    let mut contracts: Vec<String> = vec![];
+    for msg in msgs {
+        if let CosmosMsg::Wasm(
+            WasmMsg::Execute { contract_addr, .. }
+            | WasmMsg::Migrate { contract_addr, .. }
+            | WasmMsg::UpdateAdmin { contract_addr, .. }
+            | WasmMsg::ClearAdmin { contract_addr },
+        ) = msg
+        {
+            if !contracts.contains(contract_addr) {
+                contracts.push(contract_addr.clone());
+            }
+        }
+    }
+    contracts
+}
+
+/// Subscription settings and failure tracking information of a
+/// registered hook.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Default)]
+pub struct HookStatus {
+    /// The number of times the hook has failed in a row. Reset once
+    /// the hook succeeds.
+    pub failures: u64,
+    /// The number of consecutive failures tolerated before the hook
+    /// is removed. With zero, the hook is removed the first time it
+    /// fails.
+    pub failures_tolerated: u64,
+    /// The error returned by the last failed execution of the hook.
+    pub last_error: Option<String>,
+    /// The height of the block the hook has failed last time at.
+    pub last_failure_height: Option<u64>,
+    /// The events the hook is called for. `None` means every event.
+    pub filter: Option<HookFilter>,
+}
+
+/// The outcome of a recorded hook failure.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum HookFailure {
+    /// The hook has failed more times than it is tolerated and was
+    /// removed.
+    Removed { addr: Addr },
+    /// The hook is still registered.
+    Tolerated { addr: Addr, failures: u64 },
+}
+
 #[derive(Error, Debug, PartialEq)]
 pub enum HookError {
     #[error("{0}")]
@@ -23,31 +133,65 @@ pub enum HookError {
 }
 
 // store all hook addresses in one item. We cannot have many of them before the contract becomes unusable anyway.
-pub struct Hooks<'a>(Item<'a, Vec<Addr>>);
+pub struct Hooks<'a> {
+    hooks: Item<'a, Vec<Addr>>,
+    statuses: Map<'a, Addr, HookStatus>,
+    /// The hooks as they were when the last batch of messages was
+    /// prepared. Reply IDs refer to positions in this list, so that
+    /// removing a hook while the batch executes doesn't make the replies
+    /// of the later hooks point to other hooks.
+    batch: Item<'a, Vec<Addr>>,
+}
 
 impl<'a> Hooks<'a> {
-    pub const fn new(storage_key: &'a str) -> Self {
-        Hooks(Item::new(storage_key))
+    pub const fn new(storage_key: &'a str, statuses_key: &'a str, batch_key: &'a str) -> Self {
+        Hooks {
+            hooks: Item::new(storage_key),
+            statuses: Map::new(statuses_key),
+            batch: Item::new(batch_key),
+        }
     }
 
     pub fn add_hook(&self, storage: &mut dyn Storage, addr: Addr) -> Result<(), HookError> {
-        let mut hooks = self.0.may_load(storage)?.unwrap_or_default();
+        self.add_hook_with_config(storage, addr, 0, None)
+    }
+
+    /// Registers a hook which tolerates the given number of failures
+    /// and is only called for events matching the filter.
+    pub fn add_hook_with_config(
+        &self,
+        storage: &mut dyn Storage,
+        addr: Addr,
+        failures_tolerated: u64,
+        filter: Option<HookFilter>,
+    ) -> Result<(), HookError> {
+        let mut hooks = self.hooks.may_load(storage)?.unwrap_or_default();
         if !hooks.iter().any(|h| h == addr) {
-            hooks.push(addr);
+            hooks.push(addr.clone());
         } else {
             return Err(HookError::HookAlreadyRegistered {});
         }
-        Ok(self.0.save(storage, &hooks)?)
+        self.statuses.save(
+            storage,
+            addr,
+            &HookStatus {
+                failures_tolerated,
+                filter,
+                ..Default::default()
+            },
+        )?;
+        Ok(self.hooks.save(storage, &hooks)?)
     }
 
     pub fn remove_hook(&self, storage: &mut dyn Storage, addr: Addr) -> Result<(), HookError> {
-        let mut hooks = self.0.load(storage)?;
+        let mut hooks = self.hooks.load(storage)?;
         if let Some(p) = hooks.iter().position(|x| x == addr) {
             hooks.remove(p);
         } else {
             return Err(HookError::HookNotRegistered {});
         }
-        Ok(self.0.save(storage, &hooks)?)
+        self.statuses.remove(storage, addr);
+        Ok(self.hooks.save(storage, &hooks)?)
     }
 
     pub fn remove_hook_by_index(
@@ -55,18 +199,91 @@ impl<'a> Hooks<'a> {
         storage: &mut dyn Storage,
         index: u64,
     ) -> Result<Addr, HookError> {
-        let mut hooks = self.0.load(storage)?;
+        let mut hooks = self.hooks.load(storage)?;
         let hook = hooks.remove(index as usize);
-        self.0.save(storage, &hooks)?;
+        self.statuses.remove(storage, hook.clone());
+        self.hooks.save(storage, &hooks)?;
         Ok(hook)
     }
 
+    /// Returns the hook the message with the given index of the last
+    /// batch was prepared for, if it is still registered.
+    fn batch_hook(&self, storage: &dyn Storage, index: u64) -> StdResult<Option<Addr>> {
+        let hooks = self.hooks.may_load(storage)?.unwrap_or_default();
+        // Hooks registered before batches were recorded have replies
+        // referring to their current position.
+        let batch = self
+            .batch
+            .may_load(storage)?
+            .unwrap_or_else(|| hooks.clone());
+        Ok(batch
+            .get(index as usize)
+            .filter(|addr| hooks.contains(addr))
+            .cloned())
+    }
+
+    /// Records a failed execution of the hook with the given index in
+    /// the last batch. The hook is removed once it has failed more
+    /// times in a row than it is tolerated. Returns `None` if the hook
+    /// has been removed since the batch was prepared.
+    pub fn record_hook_failure(
+        &self,
+        storage: &mut dyn Storage,
+        index: u64,
+        error: String,
+        height: u64,
+    ) -> Result<Option<HookFailure>, HookError> {
+        let Some(addr) = self.batch_hook(storage, index)? else {
+            return Ok(None);
+        };
+
+        // Hooks registered before failure tracking was introduced have
+        // no status and are removed on the first failure.
+        let mut status = self
+            .statuses
+            .may_load(storage, addr.clone())?
+            .unwrap_or_default();
+        status.failures += 1;
+        status.last_error = Some(error);
+        status.last_failure_height = Some(height);
+
+        if status.failures > status.failures_tolerated {
+            self.remove_hook(storage, addr.clone())?;
+            return Ok(Some(HookFailure::Removed { addr }));
+        }
+
+        self.statuses.save(storage, addr.clone(), &status)?;
+        Ok(Some(HookFailure::Tolerated {
+            addr,
+            failures: status.failures,
+        }))
+    }
+
+    /// Records a successful execution of the hook with the given index
+    /// in the last batch, resetting its failure count. Returns `None`
+    /// if the hook has been removed since the batch was prepared.
+    pub fn record_hook_success(
+        &self,
+        storage: &mut dyn Storage,
+        index: u64,
+    ) -> Result<Option<Addr>, HookError> {
+        let Some(addr) = self.batch_hook(storage, index)? else {
+            return Ok(None);
+        };
+
+        if let Some(mut status) = self.statuses.may_load(storage, addr.clone())? {
+            status.failures = 0;
+            self.statuses.save(storage, addr.clone(), &status)?;
+        }
+        Ok(Some(addr))
+    }
+
     pub fn prepare_hooks<F: FnMut(Addr) -> StdResult<SubMsg>>(
         &self,
         storage: &dyn Storage,
         prep: F,
     ) -> StdResult<Vec<SubMsg>> {
-        self.0
+        self.hooks
             .may_load(storage)?
             .unwrap_or_default()
             .into_iter()
@@ -74,20 +291,76 @@ impl<'a> Hooks<'a> {
             .collect()
     }
 
+    /// Prepares messages for the hooks whose filter matches the event.
+    /// `prep` receives the index of the hook in the list of registered
+    /// hooks, which is recorded as the batch, so that replies can be
+    /// traced back to the hook. Messages of hooks that have failed last
+    /// time reply on success as well, so that their failure count can
+    /// be reset with [`Hooks::record_hook_success`].
+    pub fn prepare_filtered_hooks<F: FnMut(u64, Addr) -> StdResult<SubMsg>>(
+        &self,
+        storage: &mut dyn Storage,
+        event: &HookEvent,
+        mut prep: F,
+    ) -> StdResult<Vec<SubMsg>> {
+        let hooks = self.hooks.may_load(storage)?.unwrap_or_default();
+        if self.batch.may_load(storage)?.as_ref() != Some(&hooks) {
+            self.batch.save(storage, &hooks)?;
+        }
+        let mut msgs = vec![];
+        for (index, addr) in hooks.into_iter().enumerate() {
+            let status = self
+                .statuses
+                .may_load(storage, addr.clone())?
+                .unwrap_or_default();
+            if status
+                .filter
+                .as_ref()
+                .map_or(true, |filter| filter.matches(event))
+            {
+                let mut msg = prep(index as u64, addr)?;
+                if status.failures > 0 && msg.reply_on == ReplyOn::Error {
+                    msg.reply_on = ReplyOn::Always;
+                }
+                msgs.push(msg);
+            }
+        }
+        Ok(msgs)
+    }
+
     pub fn hook_count(&self, storage: &dyn Storage) -> StdResult<u32> {
         // The WASM VM (as of version 1) is 32 bit and sets limits for
         // memory accordingly:
         // <https://webassembly.github.io/spec/core/syntax/types.html#syntax-limits>. We
         // can safely return a u32 here as that's the biggest size in
         // the WASM VM.
-        Ok(self.0.may_load(storage)?.unwrap_or_default().len() as u32)
+        Ok(self.hooks.may_load(storage)?.unwrap_or_default().len() as u32)
     }
 
     pub fn query_hooks<Q: CustomQuery>(&self, deps: Deps<Q>) -> StdResult<HooksResponse> {
-        let hooks = self.0.may_load(deps.storage)?.unwrap_or_default();
+        let hooks = self.hooks.may_load(deps.storage)?.unwrap_or_default();
         let hooks = hooks.into_iter().map(String::from).collect();
         Ok(HooksResponse { hooks })
     }
+
+    /// Returns the subscription settings and failure tracking
+    /// information of the hook, or `None`
+    /// if the address is not registered as a hook.
+    pub fn query_hook_status<Q: CustomQuery>(
+        &self,
+        deps: Deps<Q>,
+        addr: Addr,
+    ) -> StdResult<Option<HookStatus>> {
+        let hooks = self.hooks.may_load(deps.storage)?.unwrap_or_default();
+        if !hooks.contains(&addr) {
+            return Ok(None);
+        }
+        Ok(Some(
+            self.statuses
+                .may_load(deps.storage, addr)?
+                .unwrap_or_default(),
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -106,7 +379,7 @@ mod tests {
     fn test_hooks() {
         let mut deps = mock_dependencies();
         let storage = &mut deps.storage;
-        let hooks = Hooks::new("hooks");
+        let hooks = Hooks::new("hooks", "hook_statuses", "hook_batch");
         hooks.add_hook(storage, addr!("ekez")).unwrap();
         hooks.add_hook(storage, addr!("meow")).unwrap();
 
@@ -141,4 +414,283 @@ mod tests {
 
         assert_eq!(the_hooks, vec![addr!("meow")]);
     }
+
+    #[test]
+    fn test_hook_failures() {
+        let mut deps = mock_dependencies();
+        let hooks = Hooks::new("hooks", "hook_statuses", "hook_batch");
+        hooks.add_hook(&mut deps.storage, addr!("ekez")).unwrap();
+        hooks
+            .add_hook_with_config(&mut deps.storage, addr!("meow"), 1, None)
+            .unwrap();
+
+        // The second hook tolerates a single failure.
+        let failure = hooks
+            .record_hook_failure(&mut deps.storage, 1, "error".to_string(), 10)
+            .unwrap();
+        assert_eq!(
+            failure,
+            Some(HookFailure::Tolerated {
+                addr: addr!("meow"),
+                failures: 1
+            })
+        );
+        assert_eq!(
+            hooks
+                .query_hook_status(deps.as_ref(), addr!("meow"))
+                .unwrap(),
+            Some(HookStatus {
+                failures: 1,
+                failures_tolerated: 1,
+                last_error: Some("error".to_string()),
+                last_failure_height: Some(10),
+                filter: None,
+            })
+        );
+
+        // The first hook doesn't tolerate failures at all.
+        let failure = hooks
+            .record_hook_failure(&mut deps.storage, 0, "error".to_string(), 11)
+            .unwrap();
+        assert_eq!(
+            failure,
+            Some(HookFailure::Removed {
+                addr: addr!("ekez")
+            })
+        );
+        assert_eq!(
+            hooks
+                .query_hook_status(deps.as_ref(), addr!("ekez"))
+                .unwrap(),
+            None
+        );
+
+        let failure = hooks
+            .record_hook_failure(&mut deps.storage, 0, "error".to_string(), 12)
+            .unwrap();
+        assert_eq!(
+            failure,
+            Some(HookFailure::Removed {
+                addr: addr!("meow")
+            })
+        );
+        assert_eq!(hooks.hook_count(&deps.storage).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_hook_failures_reset_on_success() {
+        let mut deps = mock_dependencies();
+        let hooks = Hooks::new("hooks", "hook_statuses", "hook_batch");
+        hooks
+            .add_hook_with_config(&mut deps.storage, addr!("meow"), 1, None)
+            .unwrap();
+
+        let reply_on = |storage: &mut dyn Storage| {
+            hooks
+                .prepare_filtered_hooks(storage, &HookEvent::default(), |index, _| {
+                    Ok(SubMsg::reply_on_error(
+                        BankMsg::Burn { amount: vec![] },
+                        index,
+                    ))
+                })
+                .unwrap()[0]
+                .reply_on
+                .clone()
+        };
+        assert_eq!(reply_on(&mut deps.storage), ReplyOn::Error);
+
+        hooks
+            .record_hook_failure(&mut deps.storage, 0, "error".to_string(), 10)
+            .unwrap();
+        // The hook has failed, so it is called with a reply on success
+        // as well.
+        assert_eq!(reply_on(&mut deps.storage), ReplyOn::Always);
+
+        assert_eq!(
+            hooks.record_hook_success(&mut deps.storage, 0).unwrap(),
+            Some(addr!("meow"))
+        );
+        assert_eq!(reply_on(&mut deps.storage), ReplyOn::Error);
+        assert_eq!(
+            hooks
+                .query_hook_status(deps.as_ref(), addr!("meow"))
+                .unwrap()
+                .unwrap()
+                .failures,
+            0
+        );
+
+        // Failures are counted from zero again.
+        let failure = hooks
+            .record_hook_failure(&mut deps.storage, 0, "error".to_string(), 12)
+            .unwrap();
+        assert_eq!(
+            failure,
+            Some(HookFailure::Tolerated {
+                addr: addr!("meow"),
+                failures: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_hook_removed_during_batch() {
+        let mut deps = mock_dependencies();
+        let hooks = Hooks::new("hooks", "hook_statuses", "hook_batch");
+        hooks
+            .add_hook_with_config(&mut deps.storage, addr!("ekez"), 1, None)
+            .unwrap();
+        hooks
+            .add_hook_with_config(&mut deps.storage, addr!("meow"), 1, None)
+            .unwrap();
+        // Both hooks have failed before, so both reply on success too.
+        for index in 0..2 {
+            hooks
+                .record_hook_failure(&mut deps.storage, index, "error".to_string(), 10)
+                .unwrap();
+        }
+
+        let msgs = hooks
+            .prepare_filtered_hooks(&mut deps.storage, &HookEvent::default(), |index, _| {
+                Ok(SubMsg::reply_on_error(
+                    BankMsg::Burn { amount: vec![] },
+                    index,
+                ))
+            })
+            .unwrap();
+        assert_eq!(msgs.len(), 2);
+
+        // The first hook is removed while the batch executes, e.g. by a
+        // message of the executed proposal.
+        hooks.remove_hook(&mut deps.storage, addr!("ekez")).unwrap();
+
+        // The reply of the second hook still refers to it.
+        assert_eq!(
+            hooks.record_hook_success(&mut deps.storage, 1).unwrap(),
+            Some(addr!("meow"))
+        );
+        assert_eq!(
+            hooks
+                .query_hook_status(deps.as_ref(), addr!("meow"))
+                .unwrap()
+                .unwrap()
+                .failures,
+            0
+        );
+
+        // The replies of the removed hook are ignored.
+        assert_eq!(
+            hooks
+                .record_hook_failure(&mut deps.storage, 0, "error".to_string(), 11)
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            hooks.record_hook_success(&mut deps.storage, 0).unwrap(),
+            None
+        );
+        let HooksResponse { hooks: the_hooks } = hooks.query_hooks(deps.as_ref()).unwrap();
+        assert_eq!(the_hooks, vec![addr!("meow")]);
+    }
+
+    #[test]
+    fn test_filtered_hooks() {
+        let mut deps = mock_dependencies();
+        let hooks = Hooks::new("hooks", "hook_statuses", "hook_batch");
+        hooks.add_hook(&mut deps.storage, addr!("ekez")).unwrap();
+        hooks
+            .add_hook_with_config(
+                &mut deps.storage,
+                addr!("meow"),
+                0,
+                Some(HookFilter {
+                    statuses: Some(vec![Status::Executed]),
+                    min_vote_power: Some(Uint128::new(10)),
+                    contract: None,
+                }),
+            )
+            .unwrap();
+        hooks
+            .add_hook_with_config(
+                &mut deps.storage,
+                addr!("woof"),
+                0,
+                Some(HookFilter {
+                    contract: Some("target".to_string()),
+                    ..Default::default()
+                }),
+            )
+            .unwrap();
+
+        let mut prepare = |event: &HookEvent| {
+            hooks
+                .prepare_filtered_hooks(&mut deps.storage, event, |index, a| {
+                    Ok(SubMsg::reply_on_error(
+                        BankMsg::Burn {
+                            amount: coins(a.as_str().len() as u128, "uekez"),
+                        },
+                        index,
+                    ))
+                })
+                .unwrap()
+                .into_iter()
+                .map(|msg| msg.id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            prepare(&HookEvent {
+                status: Some("passed".to_string()),
+                ..Default::default()
+            }),
+            vec![0]
+        );
+        assert_eq!(
+            prepare(&HookEvent {
+                status: Some("executed".to_string()),
+                contracts: vec!["target".to_string()],
+                ..Default::default()
+            }),
+            vec![0, 1, 2]
+        );
+        assert_eq!(
+            prepare(&HookEvent {
+                vote_power: Some(Uint128::new(9)),
+                contracts: vec!["target".to_string()],
+                ..Default::default()
+            }),
+            vec![0, 2]
+        );
+        assert_eq!(
+            prepare(&HookEvent {
+                vote_power: Some(Uint128::new(10)),
+                ..Default::default()
+            }),
+            vec![0, 1]
+        );
+    }
+
+    #[test]
+    fn test_msg_contracts() {
+        let msgs: Vec<CosmosMsg> = vec![
+            WasmMsg::Execute {
+                contract_addr: "a".to_string(),
+                msg: Default::default(),
+                funds: vec![],
+            }
+            .into(),
+            BankMsg::Burn { amount: vec![] }.into(),
+            WasmMsg::ClearAdmin {
+                contract_addr: "b".to_string(),
+            }
+            .into(),
+            WasmMsg::Execute {
+                contract_addr: "a".to_string(),
+                msg: Default::default(),
+                funds: vec![],
+            }
+            .into(),
+        ];
+        assert_eq!(msg_contracts(&msgs), vec!["a".to_string(), "b".to_string()]);
+    }
 }
@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Coin, Uint128};
 use cw2::ContractVersion;
 use cwd_macros::{active_query, info_query, proposal_module_query, token_query, voting_query};
 use schemars::JsonSchema;
@@ -46,6 +46,15 @@ pub struct IsActiveResponse {
 pub struct BondingStatusResponse {
     pub bonding_enabled: bool,
     pub unbondable_abount: Uint128,
+    /// The amount of unbonded tokens which are waiting in claims to be claimed back.
+    #[serde(default)]
+    pub unbonding_amount: Uint128,
+    /// Unbonded amounts waiting in claims by denom, for the vaults which accept multiple denoms.
+    #[serde(default)]
+    pub unbonding_balances: Vec<Coin>,
+    /// Bonded balances by denom, for the vaults which accept multiple denoms.
+    #[serde(default)]
+    pub bonded_balances: Vec<Coin>,
     pub height: u64,
 }
 
@@ -98,6 +98,7 @@ where
                 proposal_id,
                 new_status,
             } => self.execute_proposal_completed_hook(deps.as_ref(), info, proposal_id, new_status),
+            ExecuteMsg::Extension { .. } => Ok(Response::default()),
         }
     }
 
@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary};
+use cosmwasm_std::{Addr, Binary, Empty};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -23,7 +23,7 @@ pub struct InstantiateMsg {
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-pub enum ExecuteMsg<ProposalMessage> {
+pub enum ExecuteMsg<ProposalMessage, ExecuteExt = Empty> {
     /// Creates a new proposal in the pre-propose module. MSG will be
     /// serialized and used as the proposal creation message.
     Propose { msg: ProposalMessage },
@@ -83,6 +83,10 @@ pub enum ExecuteMsg<ProposalMessage> {
         proposal_id: u64,
         new_status: Status,
     },
+
+    /// Extension for execute messages. The default implementation
+    /// does nothing if called.
+    Extension { msg: ExecuteExt },
 }
 
 #[cw_serde]
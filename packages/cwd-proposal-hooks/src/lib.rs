@@ -1,5 +1,5 @@
-use cosmwasm_std::{to_json_binary, StdResult, Storage, SubMsg, WasmMsg};
-use cwd_hooks::Hooks;
+use cosmwasm_std::{to_json_binary, Binary, StdResult, Storage, SubMsg, WasmMsg};
+use cwd_hooks::{HookEvent, Hooks};
 use cwd_voting::reply::mask_proposal_hook_index;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -28,11 +28,14 @@ pub enum ProposalHookExecuteMsg {
 /// Prepares new proposal hook messages. These messages reply on error
 /// and have even reply IDs.
 /// IDs are set to even numbers to then be interleaved with the vote hooks.
+/// `contracts` are the contracts targeted by the proposal messages, see
+/// [`cwd_hooks::msg_contracts`].
 pub fn new_proposal_hooks(
     hooks: Hooks,
-    storage: &dyn Storage,
+    storage: &mut dyn Storage,
     id: u64,
     proposer: &str,
+    contracts: Vec<String>,
 ) -> StdResult<Vec<SubMsg>> {
     let msg = to_json_binary(&ProposalHookExecuteMsg::ProposalHook(
         ProposalHookMsg::NewProposal {
@@ -41,20 +44,12 @@ pub fn new_proposal_hooks(
         },
     ))?;
 
-    let mut index: u64 = 0;
-    let messages = hooks.prepare_hooks(storage, |a| {
-        let execute = WasmMsg::Execute {
-            contract_addr: a.to_string(),
-            msg: msg.clone(),
-            funds: vec![],
-        };
-        let masked_index = mask_proposal_hook_index(index);
-        let tmp = SubMsg::reply_on_error(execute, masked_index);
-        index += 1;
-        Ok(tmp)
-    })?;
-
-    Ok(messages)
+    let event = HookEvent {
+        status: Some("open".to_string()),
+        vote_power: None,
+        contracts,
+    };
+    prepare_proposal_hooks(hooks, storage, &event, msg)
 }
 
 /// Prepares proposal status hook messages. These messages reply on error
@@ -62,15 +57,21 @@ pub fn new_proposal_hooks(
 /// IDs are set to even numbers to then be interleaved with the vote hooks.
 pub fn proposal_status_changed_hooks(
     hooks: Hooks,
-    storage: &dyn Storage,
+    storage: &mut dyn Storage,
     id: u64,
     old_status: String,
     new_status: String,
+    contracts: Vec<String>,
 ) -> StdResult<Vec<SubMsg>> {
     if old_status == new_status {
         return Ok(vec![]);
     }
 
+    let event = HookEvent {
+        status: Some(new_status.clone()),
+        vote_power: None,
+        contracts,
+    };
     let msg = to_json_binary(&ProposalHookExecuteMsg::ProposalHook(
         ProposalHookMsg::ProposalStatusChanged {
             id,
@@ -78,18 +79,27 @@ pub fn proposal_status_changed_hooks(
             new_status,
         },
     ))?;
-    let mut index: u64 = 0;
-    let messages = hooks.prepare_hooks(storage, |a| {
+    prepare_proposal_hooks(hooks, storage, &event, msg)
+}
+
+/// Prepares the messages for the hooks subscribed to the event. Reply
+/// IDs are derived from the index of the hook, so that skipped hooks
+/// don't shift the IDs of the others.
+fn prepare_proposal_hooks(
+    hooks: Hooks,
+    storage: &mut dyn Storage,
+    event: &HookEvent,
+    msg: Binary,
+) -> StdResult<Vec<SubMsg>> {
+    hooks.prepare_filtered_hooks(storage, event, |index, a| {
         let execute = WasmMsg::Execute {
             contract_addr: a.to_string(),
             msg: msg.clone(),
             funds: vec![],
         };
-        let masked_index = mask_proposal_hook_index(index);
-        let tmp = SubMsg::reply_on_error(execute, masked_index);
-        index += 1;
-        Ok(tmp)
-    })?;
-
-    Ok(messages)
+        Ok(SubMsg::reply_on_error(
+            execute,
+            mask_proposal_hook_index(index),
+        ))
+    })
 }
@@ -1,5 +1,5 @@
-use cosmwasm_std::{to_json_binary, StdResult, Storage, SubMsg, WasmMsg};
-use cwd_hooks::Hooks;
+use cosmwasm_std::{to_json_binary, StdResult, Storage, SubMsg, Uint128, WasmMsg};
+use cwd_hooks::{HookEvent, Hooks};
 use cwd_voting::reply::mask_vote_hook_index;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -24,28 +24,35 @@ pub enum VoteHookExecuteMsg {
 /// Prepares new vote hook messages. These messages reply on error
 /// and have even reply IDs.
 /// IDs are set to odd numbers to then be interleaved with the proposal hooks.
+/// `contracts` are the contracts targeted by the proposal messages, see
+/// [`cwd_hooks::msg_contracts`].
 pub fn new_vote_hooks(
     hooks: Hooks,
-    storage: &dyn Storage,
+    storage: &mut dyn Storage,
     proposal_id: u64,
     voter: String,
     vote: String,
+    vote_power: Uint128,
+    contracts: Vec<String>,
 ) -> StdResult<Vec<SubMsg>> {
     let msg = to_json_binary(&VoteHookExecuteMsg::VoteHook(VoteHookMsg::NewVote {
         proposal_id,
         voter,
         vote,
     }))?;
-    let mut index: u64 = 0;
-    hooks.prepare_hooks(storage, |a| {
+    let event = HookEvent {
+        status: None,
+        vote_power: Some(vote_power),
+        contracts,
+    };
+    // Reply IDs are derived from the index of the hook, so that skipped
+    // hooks don't shift the IDs of the others.
+    hooks.prepare_filtered_hooks(storage, &event, |index, a| {
         let execute = WasmMsg::Execute {
             contract_addr: a.to_string(),
             msg: msg.clone(),
             funds: vec![],
         };
-        let masked_index = mask_vote_hook_index(index);
-        let tmp = SubMsg::reply_on_error(execute, masked_index);
-        index += 1;
-        Ok(tmp)
+        Ok(SubMsg::reply_on_error(execute, mask_vote_hook_index(index)))
     })
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Coin, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -15,4 +15,7 @@ pub struct ListBondersResponse {
 pub struct BonderBalanceResponse {
     pub address: String,
     pub balance: Uint128,
+    /// Bonded balances by denom, for the vaults which accept multiple denoms.
+    #[serde(default)]
+    pub balances: Vec<Coin>,
 }
@@ -1,3 +1,4 @@
+use cosmwasm_std::Uint128;
 use cwd_pre_propose_base::msg::{ExecuteMsg as ExecuteBase, QueryMsg as QueryBase};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -9,9 +10,60 @@ pub enum ProposeMessage {
         timelock_contract: String,
         proposal_id: u64,
     },
+    /// Proposes to overrule only the messages with the given indices,
+    /// as returned by the `SurvivingMsgs` query of the timelock
+    /// contract.
+    ProposePartialOverrule {
+        timelock_contract: String,
+        proposal_id: u64,
+        msg_indices: Vec<u64>,
+    },
+}
+
+#[derive(Serialize, JsonSchema, Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteExt {
+    /// Sets the overrule rules of the subDAO, replacing the existing
+    /// ones. Can only be called by the main DAO.
+    SetOverruleRules {
+        subdao: String,
+        rules: Vec<OverruleRule>,
+    },
+    /// Closes the open overrule proposals created for the subDAO
+    /// proposal. Can only be called by the subDAO timelock contract
+    /// the proposal belongs to, e.g. when the proposal is fast-tracked.
+    CloseOverruleProposals { proposal_id: u64 },
+}
+
+pub type ExecuteMsg = ExecuteBase<ProposeMessage, ExecuteExt>;
+
+/// A rule the main DAO sets for a subDAO. SubDAO proposals with a message
+/// matching one of the rules are overruled by default: instead of an
+/// overrule proposal, a proposal to approve them is created, and they are
+/// only executed if the main DAO passes it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OverruleRule {
+    /// Matches proposals whose `BankMsg::Send` messages send more than
+    /// `amount` of `denom` in total.
+    BankSendAbove { denom: String, amount: Uint128 },
+    /// Matches `WasmMsg::Migrate` messages.
+    WasmMigrate {},
+    /// Matches `WasmMsg::UpdateAdmin` and `WasmMsg::ClearAdmin` messages.
+    WasmAdminChange {},
 }
 
-pub type ExecuteMsg = ExecuteBase<ProposeMessage>;
+impl std::fmt::Display for OverruleRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OverruleRule::BankSendAbove { denom, amount } => {
+                write!(f, "bank send above {}{}", amount, denom)
+            }
+            OverruleRule::WasmMigrate {} => write!(f, "wasm migrate"),
+            OverruleRule::WasmAdminChange {} => write!(f, "wasm admin change"),
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -24,6 +76,22 @@ pub enum QueryExt {
         timelock_address: String,
         subdao_proposal_id: u64,
     },
+    /// Returns the IDs of the partial overrule proposals created for
+    /// the subDAO proposal. Returns `Vec<u64>`.
+    PartialOverruleProposalIds {
+        timelock_address: String,
+        subdao_proposal_id: u64,
+    },
+    /// Returns the overrule rules of the subDAO. Returns
+    /// `Vec<OverruleRule>`.
+    OverruleRules { subdao: String },
+    /// Returns the overrule rule the subDAO proposal matched, if any.
+    /// Such proposals are only executed if the main DAO passes the
+    /// proposal created for them. Returns `Option<OverruleRule>`.
+    FiredRule {
+        timelock_address: String,
+        subdao_proposal_id: u64,
+    },
 }
 
 pub type QueryMsg = QueryBase<QueryExt>;
@@ -1,5 +1,6 @@
 use crate::error::ContractError;
 use cosmwasm_std::Addr;
+use neutron_voting_power::voting_power::ClPoolValuation;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +12,10 @@ pub struct Config {
     pub usdc_cl_pool_contract: Addr,
     pub atom_cl_pool_contract: Addr,
     pub owner: Addr,
+    #[serde(default)]
+    pub valuation: ClPoolValuation,
+    #[serde(default = "neutron_voting_power::voting_power::default_denom")]
+    pub denom: String,
 }
 
 impl Config {
@@ -22,6 +27,10 @@ impl Config {
         if self.description.is_empty() {
             return Err(ContractError::DescriptionIsEmpty {});
         };
+        if self.denom.is_empty() {
+            return Err(ContractError::DenomIsEmpty {});
+        };
+        self.valuation.validate()?;
         Ok(())
     }
 }
@@ -30,7 +39,8 @@ impl Config {
 mod tests {
     use super::Config;
     use crate::error::ContractError;
-    use cosmwasm_std::Addr;
+    use cosmwasm_std::{from_json, Addr, StdError};
+    use neutron_voting_power::voting_power::ClPoolValuation;
 
     #[test]
     fn test_config_validate() {
@@ -41,6 +51,8 @@ mod tests {
             usdc_cl_pool_contract: Addr::unchecked("cl_pool_usdc_contract"),
             atom_cl_pool_contract: Addr::unchecked("cl_pool_atom_contract"),
             owner: Addr::unchecked("owner"),
+            valuation: ClPoolValuation::default(),
+            denom: String::from("untrn"),
         };
         assert_eq!(cfg_ok.validate(), Ok(()));
 
@@ -51,6 +63,8 @@ mod tests {
             usdc_cl_pool_contract: Addr::unchecked("cl_pool_usdc_contract"),
             atom_cl_pool_contract: Addr::unchecked("cl_pool_atom_contract"),
             owner: Addr::unchecked("owner"),
+            valuation: ClPoolValuation::default(),
+            denom: String::from("untrn"),
         };
         assert_eq!(
             cfg_empty_name.validate(),
@@ -64,10 +78,64 @@ mod tests {
             usdc_cl_pool_contract: Addr::unchecked("cl_pool_usdc_contract"),
             atom_cl_pool_contract: Addr::unchecked("cl_pool_atom_contract"),
             owner: Addr::unchecked("owner"),
+            valuation: ClPoolValuation::default(),
+            denom: String::from("untrn"),
         };
         assert_eq!(
             cfg_empty_description.validate(),
             Err(ContractError::DescriptionIsEmpty {})
         );
+
+        let cfg_empty_denom = Config {
+            name: String::from("name"),
+            description: String::from("description"),
+            lockdrop_contract: Addr::unchecked("lockdrop_contract"),
+            usdc_cl_pool_contract: Addr::unchecked("cl_pool_usdc_contract"),
+            atom_cl_pool_contract: Addr::unchecked("cl_pool_atom_contract"),
+            owner: Addr::unchecked("owner"),
+            valuation: ClPoolValuation::default(),
+            denom: String::from(""),
+        };
+        assert_eq!(
+            cfg_empty_denom.validate(),
+            Err(ContractError::DenomIsEmpty {})
+        );
+
+        let cfg_invalid_valuation = Config {
+            name: String::from("name"),
+            description: String::from("description"),
+            lockdrop_contract: Addr::unchecked("lockdrop_contract"),
+            usdc_cl_pool_contract: Addr::unchecked("cl_pool_usdc_contract"),
+            atom_cl_pool_contract: Addr::unchecked("cl_pool_atom_contract"),
+            owner: Addr::unchecked("owner"),
+            valuation: ClPoolValuation::Median {
+                window: 0,
+                samples: 5,
+            },
+            denom: String::from("untrn"),
+        };
+        assert_eq!(
+            cfg_invalid_valuation.validate(),
+            Err(ContractError::Std(StdError::generic_err(
+                "valuation window must be between 1 and 100000"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_config_default_denom() {
+        // configs stored before the denom became configurable don't have it
+        let cfg: Config = from_json(
+            br#"{
+                "name": "name",
+                "description": "description",
+                "lockdrop_contract": "lockdrop_contract",
+                "usdc_cl_pool_contract": "cl_pool_usdc_contract",
+                "atom_cl_pool_contract": "cl_pool_atom_contract",
+                "owner": "owner"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.denom, "untrn");
     }
 }
@@ -1,15 +1,20 @@
 use astroport_periphery::lockdrop::{PoolType, QueryMsg as LockdropQueryMsg};
 use cosmwasm_std::{Addr, Deps, StdResult, Uint128};
-use neutron_voting_power::voting_power::voting_power_from_lp_tokens;
+use neutron_voting_power::voting_power::{
+    voting_power_from_lp_tokens_with_valuation, ClPoolValuation,
+};
 use serde::Serialize;
 
+#[allow(clippy::too_many_arguments)]
 pub fn get_voting_power_for_address(
     deps: Deps,
     lockdrop_contract: &Addr,
     pool_contract: &Addr,
     pool_type: PoolType,
     address: String,
+    denom: &str,
     height: u64,
+    valuation: &ClPoolValuation,
 ) -> StdResult<Uint128> {
     get_voting_power(
         deps,
@@ -20,7 +25,9 @@ pub fn get_voting_power_for_address(
             user_address: address,
             height,
         },
+        denom,
         height,
+        valuation,
     )
 }
 
@@ -29,14 +36,18 @@ pub fn get_voting_power_total(
     lp_contract: &Addr,
     pool_contract: &Addr,
     pool_type: PoolType,
+    denom: &str,
     height: u64,
+    valuation: &ClPoolValuation,
 ) -> StdResult<Uint128> {
     get_voting_power(
         deps,
         lp_contract,
         pool_contract,
         &LockdropQueryMsg::QueryLockupTotalAtHeight { pool_type, height },
+        denom,
         height,
+        valuation,
     )
 }
 
@@ -45,7 +56,9 @@ pub fn get_voting_power(
     lockdrop_contract: &Addr,
     pool_contract: &Addr,
     msg: &impl Serialize,
+    denom: &str,
     height: u64,
+    valuation: &ClPoolValuation,
 ) -> StdResult<Uint128> {
     let lp_tokens: Option<Uint128> = deps.querier.query_wasm_smart(lockdrop_contract, msg)?;
 
@@ -54,16 +67,13 @@ pub fn get_voting_power(
         &astroport::pair_concentrated::QueryMsg::Pair {},
     )?;
 
-    let lp_total_supply: Uint128 = deps.querier.query_wasm_smart(
-        pair_info.liquidity_token,
-        &astroport::xastro_token::QueryMsg::TotalSupplyAt { block: height },
-    )?;
-
-    voting_power_from_lp_tokens(
+    voting_power_from_lp_tokens_with_valuation(
         deps,
         lp_tokens.unwrap_or_default(),
-        lp_total_supply,
+        &pair_info.liquidity_token,
         pool_contract,
+        denom,
         height,
+        valuation,
     )
 }
@@ -21,6 +21,9 @@ pub struct InstantiateMsg {
     pub oracle_atom_contract: String,
     /// Owner can update all configs including changing the owner. This will generally be a DAO.
     pub owner: String,
+    /// The denom LP tokens are valued in. NTRN by default.
+    #[serde(default = "neutron_voting_power::voting_power::default_denom")]
+    pub denom: String,
 }
 
 #[voting_vault]
@@ -34,6 +37,7 @@ pub enum ExecuteMsg {
         oracle_atom_contract: Option<String>,
         name: Option<String>,
         description: Option<String>,
+        denom: Option<String>,
     },
 }
 
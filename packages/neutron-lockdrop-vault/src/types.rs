@@ -11,6 +11,8 @@ pub struct Config {
     pub oracle_usdc_contract: Addr,
     pub oracle_atom_contract: Addr,
     pub owner: Addr,
+    #[serde(default = "neutron_voting_power::voting_power::default_denom")]
+    pub denom: String,
 }
 
 impl Config {
@@ -22,6 +24,9 @@ impl Config {
         if self.description.is_empty() {
             return Err(ContractError::DescriptionIsEmpty {});
         };
+        if self.denom.is_empty() {
+            return Err(ContractError::DenomIsEmpty {});
+        };
         Ok(())
     }
 }
@@ -30,7 +35,7 @@ impl Config {
 mod tests {
     use super::Config;
     use crate::error::ContractError;
-    use cosmwasm_std::Addr;
+    use cosmwasm_std::{from_json, Addr};
 
     #[test]
     fn test_config_validate() {
@@ -41,6 +46,7 @@ mod tests {
             oracle_usdc_contract: Addr::unchecked("oracle_usdc_contract"),
             oracle_atom_contract: Addr::unchecked("oracle_atom_contract"),
             owner: Addr::unchecked("owner"),
+            denom: String::from("untrn"),
         };
         assert_eq!(cfg_ok.validate(), Ok(()));
 
@@ -51,6 +57,7 @@ mod tests {
             oracle_usdc_contract: Addr::unchecked("oracle_usdc_contract"),
             oracle_atom_contract: Addr::unchecked("oracle_atom_contract"),
             owner: Addr::unchecked("owner"),
+            denom: String::from("untrn"),
         };
         assert_eq!(
             cfg_empty_name.validate(),
@@ -64,10 +71,42 @@ mod tests {
             oracle_usdc_contract: Addr::unchecked("oracle_usdc_contract"),
             oracle_atom_contract: Addr::unchecked("oracle_atom_contract"),
             owner: Addr::unchecked("owner"),
+            denom: String::from("untrn"),
         };
         assert_eq!(
             cfg_empty_description.validate(),
             Err(ContractError::DescriptionIsEmpty {})
         );
+
+        let cfg_empty_denom = Config {
+            name: String::from("name"),
+            description: String::from("description"),
+            lockdrop_contract: Addr::unchecked("lockdrop_contract"),
+            oracle_usdc_contract: Addr::unchecked("oracle_usdc_contract"),
+            oracle_atom_contract: Addr::unchecked("oracle_atom_contract"),
+            owner: Addr::unchecked("owner"),
+            denom: String::from(""),
+        };
+        assert_eq!(
+            cfg_empty_denom.validate(),
+            Err(ContractError::DenomIsEmpty {})
+        );
+    }
+
+    #[test]
+    fn test_config_default_denom() {
+        // configs stored before the denom became configurable don't have it
+        let cfg: Config = from_json(
+            br#"{
+                "name": "name",
+                "description": "description",
+                "lockdrop_contract": "lockdrop_contract",
+                "oracle_usdc_contract": "oracle_usdc_contract",
+                "oracle_atom_contract": "oracle_atom_contract",
+                "owner": "owner"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.denom, "untrn");
     }
 }
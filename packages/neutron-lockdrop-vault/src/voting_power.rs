@@ -3,6 +3,7 @@ use cosmwasm_std::{Decimal256, Deps, StdResult, Uint128};
 use neutron_oracle::voting_power::voting_power_from_lp_tokens;
 use serde::Serialize;
 
+#[allow(clippy::too_many_arguments)]
 pub fn get_voting_power_for_address(
     deps: Deps,
     lp_contract: impl Into<String>,
@@ -10,6 +11,7 @@ pub fn get_voting_power_for_address(
     oracle_atom_contract: impl Into<String>,
     pool_type: PoolType,
     address: String,
+    denom: &str,
     height: u64,
 ) -> StdResult<Decimal256> {
     let oracle_contract: String = match pool_type {
@@ -26,6 +28,7 @@ pub fn get_voting_power_for_address(
             user_address: address,
             height,
         },
+        denom,
         height,
     )
 }
@@ -36,6 +39,7 @@ pub fn get_voting_power_total(
     oracle_usdc_contract: impl Into<String>,
     oracle_atom_contract: impl Into<String>,
     pool_type: PoolType,
+    denom: &str,
     height: u64,
 ) -> StdResult<Decimal256> {
     let oracle_contract: String = match pool_type {
@@ -48,6 +52,7 @@ pub fn get_voting_power_total(
         lp_contract,
         oracle_contract,
         &LockdropQueryMsg::QueryLockupTotalAtHeight { pool_type, height },
+        denom,
         height,
     )
 }
@@ -57,9 +62,16 @@ pub fn get_voting_power(
     lp_contract: impl Into<String>,
     oracle_contract: impl Into<String>,
     msg: &impl Serialize,
+    denom: &str,
     height: u64,
 ) -> StdResult<Decimal256> {
     let lp_tokens: Option<Uint128> = deps.querier.query_wasm_smart(lp_contract, msg)?;
 
-    voting_power_from_lp_tokens(deps, lp_tokens.unwrap_or_default(), oracle_contract, height)
+    voting_power_from_lp_tokens(
+        deps,
+        lp_tokens.unwrap_or_default(),
+        oracle_contract,
+        denom,
+        height,
+    )
 }
@@ -0,0 +1,46 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Overflow {0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Bonding is not available for this contract")]
+    BondingDisabled {},
+
+    #[error("Direct unbonding is not available for this contract")]
+    DirectUnbondingDisabled {},
+
+    #[error("config name cannot be empty.")]
+    NameIsEmpty {},
+
+    #[error("config description cannot be empty.")]
+    DescriptionIsEmpty {},
+
+    #[error("config denom cannot be empty.")]
+    DenomIsEmpty {},
+
+    #[error("config denom {denom} is not an asset of the {contract} pair.")]
+    DenomNotInPair { denom: String, contract: String },
+
+    #[error("config must contain at least one pool.")]
+    NoPools {},
+
+    #[error("config cannot contain more than {max} pools.")]
+    TooManyPools { max: usize },
+
+    #[error("LP source is already used by another pool.")]
+    DuplicatePool {},
+
+    #[error("no pool uses the LP source.")]
+    PoolNotFound {},
+}
+
+pub type ContractResult<T> = Result<T, ContractError>;
@@ -0,0 +1,4 @@
+pub mod error;
+pub mod msg;
+pub mod types;
+pub mod voting_power;
@@ -0,0 +1,53 @@
+use crate::types::{LpPool, LpSource};
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Uint128};
+use cwd_interface::voting::{
+    BondingStatusResponse, InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
+};
+use cwd_macros::{info_query, voting_query, voting_vault, voting_vault_query};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct InstantiateMsg {
+    /// Name contains the vault name which is used to ease the vault's recognition.
+    pub name: String,
+    /// Description contains information that characterizes the vault.
+    pub description: String,
+    /// Owner can update all configs including changing the owner. This will generally be a DAO.
+    pub owner: String,
+    /// Pools whose LP tokens give voting power.
+    pub pools: Vec<LpPool>,
+    /// The denom LP tokens are valued in. NTRN by default.
+    #[serde(default = "neutron_voting_power::voting_power::default_denom")]
+    pub denom: String,
+}
+
+#[voting_vault]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    UpdateConfig {
+        owner: Option<String>,
+        name: Option<String>,
+        description: Option<String>,
+        /// Pools to start counting voting power from.
+        add_pools: Option<Vec<LpPool>>,
+        /// LP sources of the pools to stop counting voting power from.
+        remove_pools: Option<Vec<LpSource>>,
+        denom: Option<String>,
+    },
+}
+
+#[voting_query]
+#[voting_vault_query]
+#[info_query]
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(crate::types::Config)]
+    Config {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MigrateMsg {}
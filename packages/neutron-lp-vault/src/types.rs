@@ -0,0 +1,235 @@
+use crate::error::{ContractError, ContractResult};
+use astroport_periphery::lockdrop::PoolType;
+use cosmwasm_std::{Addr, Api, StdResult};
+use neutron_voting_power::voting_power::ClPoolValuation;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Limits the number of pools so that voting power queries stay bounded.
+pub const MAX_POOLS: usize = 10;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Config {
+    pub name: String,
+    pub description: String,
+    pub owner: Addr,
+    pub pools: Vec<LpPool>,
+    #[serde(default = "neutron_voting_power::voting_power::default_denom")]
+    pub denom: String,
+}
+
+impl Config {
+    /// checks whether the config fields are valid.
+    pub fn validate(&self) -> ContractResult<()> {
+        if self.name.is_empty() {
+            return Err(ContractError::NameIsEmpty {});
+        }
+        if self.description.is_empty() {
+            return Err(ContractError::DescriptionIsEmpty {});
+        }
+        if self.denom.is_empty() {
+            return Err(ContractError::DenomIsEmpty {});
+        }
+        if self.pools.is_empty() {
+            return Err(ContractError::NoPools {});
+        }
+        if self.pools.len() > MAX_POOLS {
+            return Err(ContractError::TooManyPools { max: MAX_POOLS });
+        }
+        for (i, pool) in self.pools.iter().enumerate() {
+            if self.pools[..i]
+                .iter()
+                .any(|other| other.lp_source == pool.lp_source)
+            {
+                return Err(ContractError::DuplicatePool {});
+            }
+            if let PairKind::ClPool { valuation, .. } = &pool.pair_kind {
+                valuation.validate()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A pool whose LP tokens give voting power.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LpPool {
+    /// The contract holding LP tokens on behalf of users.
+    pub lp_source: LpSource,
+    /// How the LP tokens are turned into NTRN voting power.
+    pub pair_kind: PairKind,
+}
+
+impl LpPool {
+    /// checks whether the pool contract addresses are valid.
+    pub fn validate_addresses(&self, api: &dyn Api) -> StdResult<()> {
+        match &self.lp_source {
+            LpSource::Lockdrop { contract, .. } | LpSource::VestingLp { contract } => {
+                api.addr_validate(contract.as_str())?;
+            }
+        }
+        match &self.pair_kind {
+            PairKind::XykTwap { oracle_contract } => {
+                api.addr_validate(oracle_contract.as_str())?;
+            }
+            PairKind::ClPool { pool_contract, .. } => {
+                api.addr_validate(pool_contract.as_str())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum LpSource {
+    /// Lockdrop contract, LP tokens locked for the given pool type are counted.
+    Lockdrop { contract: Addr, pool_type: PoolType },
+    /// Vesting LP contract, unclaimed LP tokens are counted.
+    VestingLp { contract: Addr },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PairKind {
+    /// XYK pair, LP tokens are valued with the denom TWAP from the oracle contract.
+    XykTwap { oracle_contract: Addr },
+    /// CL pair, LP tokens are valued with the denom balance of the pool
+    /// according to the valuation, spot by default.
+    ClPool {
+        pool_contract: Addr,
+        #[serde(default)]
+        valuation: ClPoolValuation,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, LpPool, LpSource, PairKind, MAX_POOLS};
+    use crate::error::ContractError;
+    use astroport_periphery::lockdrop::PoolType;
+    use cosmwasm_std::{from_json, Addr, StdError};
+    use neutron_voting_power::voting_power::ClPoolValuation;
+
+    fn lockdrop_pool(pool_type: PoolType) -> LpPool {
+        LpPool {
+            lp_source: LpSource::Lockdrop {
+                contract: Addr::unchecked("lockdrop_contract"),
+                pool_type,
+            },
+            pair_kind: PairKind::XykTwap {
+                oracle_contract: Addr::unchecked("oracle_contract"),
+            },
+        }
+    }
+
+    #[test]
+    fn test_config_validate() {
+        let cfg_ok = Config {
+            name: String::from("name"),
+            description: String::from("description"),
+            owner: Addr::unchecked("owner"),
+            pools: vec![lockdrop_pool(PoolType::ATOM), lockdrop_pool(PoolType::USDC)],
+            denom: String::from("untrn"),
+        };
+        assert_eq!(cfg_ok.validate(), Ok(()));
+
+        let cfg_empty_name = Config {
+            name: String::from(""),
+            ..cfg_ok.clone()
+        };
+        assert_eq!(
+            cfg_empty_name.validate(),
+            Err(ContractError::NameIsEmpty {})
+        );
+
+        let cfg_empty_description = Config {
+            description: String::from(""),
+            ..cfg_ok.clone()
+        };
+        assert_eq!(
+            cfg_empty_description.validate(),
+            Err(ContractError::DescriptionIsEmpty {})
+        );
+
+        let cfg_empty_denom = Config {
+            denom: String::from(""),
+            ..cfg_ok.clone()
+        };
+        assert_eq!(
+            cfg_empty_denom.validate(),
+            Err(ContractError::DenomIsEmpty {})
+        );
+
+        let cfg_no_pools = Config {
+            pools: vec![],
+            ..cfg_ok.clone()
+        };
+        assert_eq!(cfg_no_pools.validate(), Err(ContractError::NoPools {}));
+
+        let cfg_duplicate_pool = Config {
+            pools: vec![lockdrop_pool(PoolType::ATOM), lockdrop_pool(PoolType::ATOM)],
+            ..cfg_ok.clone()
+        };
+        assert_eq!(
+            cfg_duplicate_pool.validate(),
+            Err(ContractError::DuplicatePool {})
+        );
+
+        let cfg_too_many_pools = Config {
+            pools: (0..=MAX_POOLS)
+                .map(|i| LpPool {
+                    lp_source: LpSource::VestingLp {
+                        contract: Addr::unchecked(format!("vesting_lp_{}", i)),
+                    },
+                    pair_kind: PairKind::ClPool {
+                        pool_contract: Addr::unchecked("cl_pool"),
+                        valuation: ClPoolValuation::default(),
+                    },
+                })
+                .collect(),
+            ..cfg_ok.clone()
+        };
+        assert_eq!(
+            cfg_too_many_pools.validate(),
+            Err(ContractError::TooManyPools { max: MAX_POOLS })
+        );
+
+        let cfg_invalid_valuation = Config {
+            pools: vec![LpPool {
+                lp_source: LpSource::VestingLp {
+                    contract: Addr::unchecked("vesting_lp"),
+                },
+                pair_kind: PairKind::ClPool {
+                    pool_contract: Addr::unchecked("cl_pool"),
+                    valuation: ClPoolValuation::Twap {
+                        window: 0,
+                        samples: 5,
+                    },
+                },
+            }],
+            ..cfg_ok
+        };
+        assert_eq!(
+            cfg_invalid_valuation.validate(),
+            Err(ContractError::Std(StdError::generic_err(
+                "valuation window must be between 1 and 100000"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_config_default_denom() {
+        // configs stored before the denom became configurable don't have it
+        let cfg: Config = from_json(
+            br#"{
+                "name": "name",
+                "description": "description",
+                "owner": "owner",
+                "pools": []
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.denom, "untrn");
+    }
+}
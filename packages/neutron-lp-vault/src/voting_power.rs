@@ -0,0 +1,117 @@
+use crate::types::{LpPool, LpSource, PairKind};
+use astroport_periphery::lockdrop::QueryMsg as LockdropQueryMsg;
+use cosmwasm_std::{Deps, Fraction, StdError, StdResult, Uint128};
+use vesting_base::msg::{QueryMsg as VestingLpQueryMsg, QueryMsgHistorical};
+
+pub fn get_voting_power_for_address(
+    deps: Deps,
+    pool: &LpPool,
+    address: String,
+    denom: &str,
+    height: u64,
+) -> StdResult<Uint128> {
+    let lp_tokens: Option<Uint128> = match pool.lp_source.clone() {
+        LpSource::Lockdrop {
+            contract,
+            pool_type,
+        } => deps.querier.query_wasm_smart(
+            contract,
+            &LockdropQueryMsg::QueryUserLockupTotalAtHeight {
+                pool_type,
+                user_address: address,
+                height,
+            },
+        )?,
+        LpSource::VestingLp { contract } => deps.querier.query_wasm_smart(
+            contract,
+            &VestingLpQueryMsg::HistoricalExtension {
+                msg: QueryMsgHistorical::UnclaimedAmountAtHeight { address, height },
+            },
+        )?,
+    };
+
+    voting_power_from_lp_tokens(
+        deps,
+        &pool.pair_kind,
+        lp_tokens.unwrap_or_default(),
+        denom,
+        height,
+    )
+}
+
+pub fn get_voting_power_total(
+    deps: Deps,
+    pool: &LpPool,
+    denom: &str,
+    height: u64,
+) -> StdResult<Uint128> {
+    let lp_tokens: Option<Uint128> = match pool.lp_source.clone() {
+        LpSource::Lockdrop {
+            contract,
+            pool_type,
+        } => deps.querier.query_wasm_smart(
+            contract,
+            &LockdropQueryMsg::QueryLockupTotalAtHeight { pool_type, height },
+        )?,
+        LpSource::VestingLp { contract } => deps.querier.query_wasm_smart(
+            contract,
+            &VestingLpQueryMsg::HistoricalExtension {
+                msg: QueryMsgHistorical::UnclaimedTotalAmountAtHeight { height },
+            },
+        )?,
+    };
+
+    voting_power_from_lp_tokens(
+        deps,
+        &pool.pair_kind,
+        lp_tokens.unwrap_or_default(),
+        denom,
+        height,
+    )
+}
+
+pub fn voting_power_from_lp_tokens(
+    deps: Deps,
+    pair_kind: &PairKind,
+    lp_tokens: Uint128,
+    denom: &str,
+    height: u64,
+) -> StdResult<Uint128> {
+    if lp_tokens.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    match pair_kind {
+        PairKind::XykTwap { oracle_contract } => {
+            neutron_oracle::voting_power::voting_power_from_lp_tokens(
+                deps,
+                lp_tokens,
+                oracle_contract,
+                denom,
+                height,
+            )?
+            .numerator()
+            .try_into()
+            .map_err(StdError::from)
+        }
+        PairKind::ClPool {
+            pool_contract,
+            valuation,
+        } => {
+            let pair_info: astroport::asset::PairInfo = deps.querier.query_wasm_smart(
+                pool_contract,
+                &astroport::pair_concentrated::QueryMsg::Pair {},
+            )?;
+
+            neutron_voting_power::voting_power::voting_power_from_lp_tokens_with_valuation(
+                deps,
+                lp_tokens,
+                &pair_info.liquidity_token,
+                pool_contract,
+                denom,
+                height,
+                valuation,
+            )
+        }
+    }
+}
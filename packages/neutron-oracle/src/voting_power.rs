@@ -1,10 +1,39 @@
 use astroport::{asset::AssetInfo, oracle::QueryMsg as OracleQueryMsg};
 use cosmwasm_std::{Decimal256, Deps, StdError, StdResult, Uint128, Uint256, Uint64};
 
+/// The error the oracle returns for a token outside of its pair.
+const INVALID_TOKEN_ERROR: &str = "Invalid Token";
+
+/// checks whether the native denom is one of the assets of the oracle's pair, the oracle doesn't
+/// price other tokens. Other oracle errors are returned as is.
+pub fn pair_has_denom(
+    deps: Deps,
+    oracle_contract: impl Into<String>,
+    denom: &str,
+    height: u64,
+) -> StdResult<bool> {
+    match deps
+        .querier
+        .query_wasm_smart::<Vec<(AssetInfo, Decimal256)>>(
+            oracle_contract,
+            &OracleQueryMsg::TWAPAtHeight {
+                token: AssetInfo::NativeToken {
+                    denom: denom.to_string(),
+                },
+                height: Uint64::new(height),
+            },
+        ) {
+        Ok(twaps) => Ok(!twaps.is_empty()),
+        Err(err) if err.to_string().contains(INVALID_TOKEN_ERROR) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
 pub fn voting_power_from_lp_tokens(
     deps: Deps,
     lp_tokens: Uint128,
     oracle_contract: impl Into<String>,
+    denom: &str,
     height: u64,
 ) -> StdResult<Decimal256> {
     Ok(if lp_tokens.is_zero() {
@@ -16,7 +45,7 @@ pub fn voting_power_from_lp_tokens(
                 oracle_contract,
                 &OracleQueryMsg::TWAPAtHeight {
                     token: AssetInfo::NativeToken {
-                        denom: "untrn".to_string(),
+                        denom: denom.to_string(),
                     },
                     height: Uint64::new(height),
                 },
@@ -1,3 +1,5 @@
+use crate::types::EmergencyAction;
+use cosmwasm_std::Addr;
 use cw_utils::Duration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -20,3 +22,36 @@ pub enum ExecuteMsgPauseUntypedDuration {
     /// Pauses the cntract for blocks
     Pause { duration: u64 },
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsgRemoveStrategy {
+    /// Removes the strategy of the address from the chain manager
+    RemoveStrategy { address: Addr },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteExt {
+    /// Sets the emergency actions the security subDAO is allowed to take
+    /// on the contract, replacing the existing ones. An empty list removes
+    /// the contract from the catalog. Can only be called by the main DAO.
+    SetEmergencyActions {
+        contract: String,
+        actions: Vec<EmergencyAction>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryExt {
+    /// Lists the contracts of the emergency catalog along with the actions
+    /// allowed on them. Returns `Vec<EmergencyCatalogEntry>`.
+    EmergencyCatalog {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the pause status of every contract of the pause registry.
+    /// Returns `Vec<ContractPauseStatus>`.
+    PauseStatus {},
+}
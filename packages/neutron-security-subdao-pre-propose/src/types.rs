@@ -1,4 +1,5 @@
-use cosmwasm_std::CosmosMsg;
+use cosmwasm_std::{Addr, CosmosMsg};
+use cw_utils::Expiration;
 use neutron_sdk::bindings::msg::NeutronMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -11,4 +12,48 @@ pub enum ProposeMessage {
         description: String,
         msgs: Vec<CosmosMsg<NeutronMsg>>,
     },
+    /// Creates a proposal pausing all the contracts of the pause registry
+    /// for `duration` blocks.
+    PauseAll {
+        title: String,
+        description: String,
+        duration: u64,
+    },
+}
+
+/// The pause registry is kept in the main DAO core items: every pausable
+/// contract has an item with the `pausable:<contract address>` key. The item
+/// value is either `typed` or `untyped` depending on the duration type of the
+/// `Pause` message of the contract.
+pub const PAUSABLE_ITEM_PREFIX: &str = "pausable:";
+/// The contract is paused with `ExecuteMsgPauseTypedDuration`.
+pub const PAUSE_TYPED_DURATION: &str = "typed";
+/// The contract is paused with `ExecuteMsgPauseUntypedDuration`.
+pub const PAUSE_UNTYPED_DURATION: &str = "untyped";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ContractPauseStatus {
+    pub contract: Addr,
+    /// None if the contract isn't paused or its pause info is unknown.
+    pub paused_until: Option<Expiration>,
+    /// Set if the pause info of the contract can't be queried.
+    pub error: Option<String>,
+}
+
+/// A bounded defensive action the security subDAO may take on a contract
+/// in an emergency, besides pausing it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EmergencyAction {
+    /// `RemoveStrategy` on the chain manager. The security subDAO core
+    /// needs an `AllowAll` strategy there to execute it.
+    RemoveStrategy {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct EmergencyCatalogEntry {
+    pub contract: Addr,
+    pub actions: Vec<EmergencyAction>,
 }
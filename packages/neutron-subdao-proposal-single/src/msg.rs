@@ -118,16 +118,34 @@ pub enum ExecuteMsg {
     /// Adds an address as a consumer of proposal hooks. Consumers of
     /// proposal hooks have hook messages executed on them whenever
     /// the status of a proposal changes or a proposal is created. If
-    /// a consumer contract errors when handling a hook message it
-    /// will be removed from the list of consumers.
-    AddProposalHook { address: String },
+    /// a consumer contract errors when handling a hook message more
+    /// than `failures_tolerated` times in a row it will be removed
+    /// from the list of consumers.
+    AddProposalHook {
+        address: String,
+        /// The number of consecutive failed hook executions tolerated
+        /// before the consumer is removed. Defaults to zero.
+        failures_tolerated: Option<u64>,
+        /// Restricts the hook messages sent to the consumer. Defaults
+        /// to sending every hook message.
+        filter: Option<cwd_hooks::HookFilter>,
+    },
     /// Removes a consumer of proposal hooks.
     RemoveProposalHook { address: String },
     /// Adds an address as a consumer of vote hooks. Consumers of vote
     /// hooks have hook messages executed on them whenever the a vote
     /// is cast. If a consumer contract errors when handling a hook
-    /// message it will be removed from the list of consumers.
-    AddVoteHook { address: String },
+    /// message more than `failures_tolerated` times in a row it will
+    /// be removed from the list of consumers.
+    AddVoteHook {
+        address: String,
+        /// The number of consecutive failed hook executions tolerated
+        /// before the consumer is removed. Defaults to zero.
+        failures_tolerated: Option<u64>,
+        /// Restricts the hook messages sent to the consumer. Defaults
+        /// to sending every hook message.
+        filter: Option<cwd_hooks::HookFilter>,
+    },
     /// Removed a consumer of vote hooks.
     RemoveVoteHook { address: String },
 }
@@ -203,6 +221,18 @@ pub enum QueryMsg {
     /// module. Returns cwd_hooks::HooksResponse.
     #[returns(cwd_hooks::HooksResponse)]
     VoteHooks {},
+    /// Gets the subscription settings and failure tracking
+    /// information of a proposal hook consumer. Returns
+    /// `Option<cwd_hooks::HookStatus>`, `None` if the address is not a
+    /// consumer.
+    #[returns(Option<cwd_hooks::HookStatus>)]
+    ProposalHookStatus { address: String },
+    /// Gets the subscription settings and failure tracking
+    /// information of a vote hook consumer. Returns
+    /// `Option<cwd_hooks::HookStatus>`, `None` if the address is not a
+    /// consumer.
+    #[returns(Option<cwd_hooks::HookStatus>)]
+    VoteHookStatus { address: String },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -1,6 +1,9 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::CosmosMsg;
+use cw_utils::{Duration, Expiration};
 use neutron_sdk::bindings::msg::NeutronMsg;
+
+use crate::types::RetryPolicy;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +11,15 @@ use serde::{Deserialize, Serialize};
 pub struct InstantiateMsg {
     // Overrule pre proposal module from the main DAO
     pub overrule_pre_propose: String,
+    /// The amount of time proposals stay timelocked before they can be
+    /// executed.
+    pub timelock_duration: Duration,
+    /// Allows retrying the execution of failed proposals.
+    pub retry_policy: Option<RetryPolicy>,
+    /// Addresses that may fast-track proposals in addition to the
+    /// owner.
+    #[serde(default)]
+    pub fast_track_approvers: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
@@ -20,12 +32,46 @@ pub enum ExecuteMsg {
     ExecuteProposal {
         proposal_id: u64,
     },
+    /// Executes a proposal which execution failed once again. Allowed
+    /// within the limits of the configured retry policy.
+    RetryProposal {
+        proposal_id: u64,
+    },
+    /// Marks a proposal which execution failed as abandoned so it can't
+    /// be retried anymore. Can only be called by the subDAO.
+    AbandonProposal {
+        proposal_id: u64,
+    },
+    /// Overrules the proposal. Can only be called by the owner, or by
+    /// anyone once the owner has declined to approve a proposal that
+    /// matched one of its overrule rules.
     OverruleProposal {
         proposal_id: u64,
     },
+    /// Allows the proposal to be executed without waiting for the
+    /// timelock to be over, and closes its pending overrule proposal.
+    /// Can only be called by the owner or one of the fast-track
+    /// approvers.
+    FastTrack {
+        proposal_id: u64,
+    },
+    /// Overrules the messages with the given indices, the rest of the
+    /// proposal messages are still executed. Indices are the ones
+    /// returned by `SurvivingMsgs`.
+    OverruleProposalMsgs {
+        proposal_id: u64,
+        msg_indices: Vec<u64>,
+    },
     UpdateConfig {
         owner: Option<String>,
         overrule_pre_propose: Option<String>,
+        /// Only applies to proposals timelocked after the update.
+        timelock_duration: Option<Duration>,
+        retry_policy: Option<RetryPolicy>,
+        /// Removes the retry policy, so that failed proposals can't be
+        /// retried anymore. Can't be combined with `retry_policy`.
+        clear_retry_policy: Option<bool>,
+        fast_track_approvers: Option<Vec<String>>,
     },
 }
 
@@ -54,12 +100,65 @@ pub enum QueryMsg {
         /// returned.
         limit: Option<u64>,
     },
+    /// Lists the proposals with the given status. Returns
+    /// `types::ProposalListResponse`.
+    #[returns(crate::types::ProposalListResponse)]
+    ListProposalsByStatus {
+        status: crate::types::ProposalStatus,
+        /// The proposal ID to start listing proposals after.
+        start_after: Option<u64>,
+        /// The maximum number of proposals to return as part of this
+        /// query. If no limit is set a max of 30 proposals will be
+        /// returned.
+        limit: Option<u64>,
+    },
+    /// Lists the proposals that can be executed right now. Proposals
+    /// which overrule state can't be queried are left out. Returns
+    /// `types::ExecutableProposalsResponse`.
+    #[returns(crate::types::ExecutableProposalsResponse)]
+    ListExecutable {
+        /// The proposal ID to start listing proposals after.
+        start_after: Option<u64>,
+        /// The maximum number of timelocked proposals to check as part
+        /// of this query, at most 30, which is also the default. Only
+        /// the executable ones among them are returned.
+        limit: Option<u64>,
+    },
     /// Returns errors of the failed proposal.
     /// Expected in the form of "codespace=? code=?".
     /// Returns `Option<String>`
     #[returns(Option<String>)]
     ProposalExecutionError { proposal_id: u64 },
+    /// Returns all the failed execution attempts of the proposal.
+    /// Returns `Vec<types::ExecutionFailure>`.
+    #[returns(Vec<crate::types::ExecutionFailure>)]
+    ProposalExecutionFailures { proposal_id: u64 },
+    /// Returns the moment the proposal becomes executable at, `None`
+    /// if it was timelocked before timelock durations were introduced.
+    /// Returns `Option<cw_utils::Expiration>`.
+    #[returns(Option<Expiration>)]
+    ExecutableAt { proposal_id: u64 },
+    /// Lists the messages of the proposal that haven't been overruled
+    /// along with their indices. Returns `types::SurvivingMsgsResponse`.
+    #[returns(crate::types::SurvivingMsgsResponse)]
+    SurvivingMsgs { proposal_id: u64 },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// The timelock duration to set in the config. When migrating from
+    /// a version without timelock durations, defaults to the voting
+    /// period of the main DAO overrule proposals, the time proposals
+    /// were implicitly timelocked for before. Otherwise the current
+    /// duration is kept if not set.
+    #[serde(default)]
+    pub timelock_duration: Option<Duration>,
+    /// The retry policy to set in the config. The current one is kept
+    /// if not set.
+    #[serde(default)]
+    pub retry_policy: Option<RetryPolicy>,
+    /// The fast-track approvers to set in the config. The current ones
+    /// are kept if not set.
+    #[serde(default)]
+    pub fast_track_approvers: Option<Vec<String>>,
+}
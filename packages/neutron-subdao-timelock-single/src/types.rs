@@ -1,5 +1,7 @@
 use cosmwasm_std::Addr;
 use cosmwasm_std::CosmosMsg;
+use cosmwasm_std::Timestamp;
+use cw_utils::{Duration, Expiration};
 use neutron_sdk::bindings::msg::NeutronMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -10,6 +12,49 @@ pub struct Config {
     pub overrule_pre_propose: Addr,
     // subDAO core module can timelock proposals.
     pub subdao: Addr,
+    /// The amount of time a proposal stays timelocked before it can be
+    /// executed, unless it's overruled by the main DAO in the meantime.
+    pub timelock_duration: Duration,
+    /// Allows retrying the execution of failed proposals. Failed
+    /// proposals can't be retried if not set.
+    #[serde(default)]
+    pub retry_policy: Option<RetryPolicy>,
+    /// Addresses that may fast-track proposals in addition to the
+    /// owner, e.g. the security subDAO.
+    #[serde(default)]
+    pub fast_track_approvers: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct RetryPolicy {
+    /// The maximum number of execution attempts of a proposal, the
+    /// first execution included.
+    pub max_attempts: u64,
+    /// The amount of time after the first failed execution within which
+    /// the proposal can be retried.
+    pub retry_window: Duration,
+}
+
+impl RetryPolicy {
+    /// Returns the moment a proposal that first failed with `failure`
+    /// can't be retried anymore.
+    pub fn retry_deadline(&self, failure: &ExecutionFailure) -> Expiration {
+        match self.retry_window {
+            Duration::Height(height) => Expiration::AtHeight(failure.height + height),
+            Duration::Time(time) => Expiration::AtTime(failure.time.plus_seconds(time)),
+        }
+    }
+}
+
+/// A failed execution attempt of a proposal.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+pub struct ExecutionFailure {
+    /// The height of the block the execution failed at.
+    pub height: u64,
+    /// The time of the block the execution failed at.
+    pub time: Timestamp,
+    /// The execution error, in the form of "codespace=? code=?".
+    pub error: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, JsonSchema, Debug, Eq, PartialEq)]
@@ -21,6 +66,29 @@ pub struct SingleChoiceProposal {
     pub msgs: Vec<CosmosMsg<NeutronMsg>>,
 
     pub status: ProposalStatus,
+
+    /// The height of the block the proposal was timelocked at. `None`
+    /// for proposals timelocked before timelock durations were
+    /// introduced.
+    pub timelocked_at: Option<u64>,
+
+    /// The moment the proposal becomes executable at. `None` for
+    /// proposals timelocked before timelock durations were introduced,
+    /// such proposals become executable once the main DAO declines to
+    /// overrule them.
+    pub executable_at: Option<Expiration>,
+
+    /// Indices of the messages overruled by the main DAO. Messages are
+    /// indexed in the order they are wrapped into the
+    /// `ExecuteTimelockedMsgs` messages of the proposal.
+    #[serde(default)]
+    pub overruled_msgs: Vec<u64>,
+
+    /// The address that fast-tracked the proposal, if any. Fast-tracked
+    /// proposals can be executed without waiting for the timelock to
+    /// be over.
+    #[serde(default)]
+    pub fast_tracked_by: Option<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Copy)]
@@ -35,6 +103,12 @@ pub enum ProposalStatus {
     Executed,
     /// The proposal's execution failed.
     ExecutionFailed,
+    /// Some of the proposal's messages have been overruled, the rest
+    /// will be executed.
+    PartiallyOverruled,
+    /// The proposal's execution failed and the subDAO gave up on
+    /// retrying it.
+    Abandoned,
 }
 
 impl std::fmt::Display for ProposalStatus {
@@ -44,6 +118,8 @@ impl std::fmt::Display for ProposalStatus {
             ProposalStatus::Overruled => write!(f, "overruled"),
             ProposalStatus::Executed => write!(f, "executed"),
             ProposalStatus::ExecutionFailed => write!(f, "execution_failed"),
+            ProposalStatus::PartiallyOverruled => write!(f, "partially_overruled"),
+            ProposalStatus::Abandoned => write!(f, "abandoned"),
         }
     }
 }
@@ -53,3 +129,26 @@ impl std::fmt::Display for ProposalStatus {
 pub struct ProposalListResponse {
     pub proposals: Vec<SingleChoiceProposal>,
 }
+
+/// A list of executable proposals returned by `ListExecutable`.
+#[derive(Serialize, Deserialize, Clone, JsonSchema, Debug)]
+pub struct ExecutableProposalsResponse {
+    pub proposals: Vec<SingleChoiceProposal>,
+    /// The ID to continue listing after, set unless all the proposals
+    /// have been checked.
+    pub next_start_after: Option<u64>,
+}
+
+/// A message of a timelocked proposal along with its index.
+#[derive(Serialize, Deserialize, Clone, JsonSchema, Debug, Eq, PartialEq)]
+pub struct IndexedMsg {
+    pub index: u64,
+    pub msg: CosmosMsg<NeutronMsg>,
+}
+
+/// The messages of a proposal that haven't been overruled, returned by
+/// `SurvivingMsgs`.
+#[derive(Serialize, Deserialize, Clone, JsonSchema, Debug, Eq, PartialEq)]
+pub struct SurvivingMsgsResponse {
+    pub msgs: Vec<IndexedMsg>,
+}
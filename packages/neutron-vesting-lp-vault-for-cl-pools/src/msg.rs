@@ -4,6 +4,7 @@ use cwd_interface::voting::{
     BondingStatusResponse, InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse,
 };
 use cwd_macros::{info_query, voting_query, voting_vault, voting_vault_query};
+use neutron_voting_power::voting_power::ClPoolValuation;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -23,6 +24,12 @@ pub struct InstantiateMsg {
     pub usdc_cl_pool_contract: String,
     /// Owner can update all configs including changing the owner. This will generally be a DAO.
     pub owner: String,
+    /// Defines how LP tokens of the CL pools are valued. Spot by default.
+    #[serde(default)]
+    pub valuation: ClPoolValuation,
+    /// The denom LP tokens are valued in. NTRN by default.
+    #[serde(default = "neutron_voting_power::voting_power::default_denom")]
+    pub denom: String,
 }
 
 #[voting_vault]
@@ -37,6 +44,8 @@ pub enum ExecuteMsg {
         usdc_cl_pool_contract: String,
         name: String,
         description: String,
+        valuation: Option<ClPoolValuation>,
+        denom: Option<String>,
     },
 }
 
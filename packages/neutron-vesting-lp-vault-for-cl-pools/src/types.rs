@@ -1,5 +1,6 @@
 use crate::error::{ContractError, ContractResult};
 use cosmwasm_std::Addr;
+use neutron_voting_power::voting_power::ClPoolValuation;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -12,6 +13,10 @@ pub struct Config {
     pub usdc_vesting_lp_contract: Addr,
     pub usdc_cl_pool_contract: Addr,
     pub owner: Addr,
+    #[serde(default)]
+    pub valuation: ClPoolValuation,
+    #[serde(default = "neutron_voting_power::voting_power::default_denom")]
+    pub denom: String,
 }
 
 impl Config {
@@ -23,6 +28,10 @@ impl Config {
         if self.description.is_empty() {
             return Err(ContractError::DescriptionIsEmpty {});
         }
+        if self.denom.is_empty() {
+            return Err(ContractError::DenomIsEmpty {});
+        }
+        self.valuation.validate()?;
         Ok(())
     }
 }
@@ -30,7 +39,8 @@ impl Config {
 #[cfg(test)]
 mod tests {
     use crate::{error::ContractError, types::Config};
-    use cosmwasm_std::Addr;
+    use cosmwasm_std::{from_json, Addr, StdError};
+    use neutron_voting_power::voting_power::ClPoolValuation;
 
     #[test]
     fn valid_config() {
@@ -42,6 +52,8 @@ mod tests {
             usdc_vesting_lp_contract: Addr::unchecked("usdc_vesting_lp_contract"),
             usdc_cl_pool_contract: Addr::unchecked("usdc_cl_pool_contract"),
             owner: Addr::unchecked("owner"),
+            valuation: ClPoolValuation::default(),
+            denom: String::from("untrn"),
         };
         assert!(cfg.validate().is_ok());
     }
@@ -56,6 +68,8 @@ mod tests {
             usdc_vesting_lp_contract: Addr::unchecked("usdc_vesting_lp_contract"),
             usdc_cl_pool_contract: Addr::unchecked("usdc_cl_pool_contract"),
             owner: Addr::unchecked("owner"),
+            valuation: ClPoolValuation::default(),
+            denom: String::from("untrn"),
         };
         assert_eq!(cfg.validate(), Err(ContractError::NameIsEmpty {}));
     }
@@ -70,7 +84,67 @@ mod tests {
             usdc_vesting_lp_contract: Addr::unchecked("usdc_vesting_lp_contract"),
             usdc_cl_pool_contract: Addr::unchecked("usdc_cl_pool_contract"),
             owner: Addr::unchecked("owner"),
+            valuation: ClPoolValuation::default(),
+            denom: String::from("untrn"),
         };
         assert_eq!(cfg.validate(), Err(ContractError::DescriptionIsEmpty {}));
     }
+
+    #[test]
+    fn empty_denom() {
+        let cfg = Config {
+            name: String::from("name"),
+            description: String::from("description"),
+            atom_vesting_lp_contract: Addr::unchecked("atom_vesting_lp_contract"),
+            atom_cl_pool_contract: Addr::unchecked("atom_cl_pool_contract"),
+            usdc_vesting_lp_contract: Addr::unchecked("usdc_vesting_lp_contract"),
+            usdc_cl_pool_contract: Addr::unchecked("usdc_cl_pool_contract"),
+            owner: Addr::unchecked("owner"),
+            valuation: ClPoolValuation::default(),
+            denom: String::from(""),
+        };
+        assert_eq!(cfg.validate(), Err(ContractError::DenomIsEmpty {}));
+    }
+
+    #[test]
+    fn invalid_valuation() {
+        let cfg = Config {
+            name: String::from("name"),
+            description: String::from("description"),
+            atom_vesting_lp_contract: Addr::unchecked("atom_vesting_lp_contract"),
+            atom_cl_pool_contract: Addr::unchecked("atom_cl_pool_contract"),
+            usdc_vesting_lp_contract: Addr::unchecked("usdc_vesting_lp_contract"),
+            usdc_cl_pool_contract: Addr::unchecked("usdc_cl_pool_contract"),
+            owner: Addr::unchecked("owner"),
+            valuation: ClPoolValuation::Twap {
+                window: 10,
+                samples: 100,
+            },
+            denom: String::from("untrn"),
+        };
+        assert_eq!(
+            cfg.validate(),
+            Err(ContractError::Std(StdError::generic_err(
+                "valuation samples must be between 2 and 10"
+            )))
+        );
+    }
+
+    #[test]
+    fn default_denom() {
+        // configs stored before the denom became configurable don't have it
+        let cfg: Config = from_json(
+            br#"{
+                "name": "name",
+                "description": "description",
+                "atom_vesting_lp_contract": "atom_vesting_lp_contract",
+                "atom_cl_pool_contract": "atom_cl_pool_contract",
+                "usdc_vesting_lp_contract": "usdc_vesting_lp_contract",
+                "usdc_cl_pool_contract": "usdc_cl_pool_contract",
+                "owner": "owner"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.denom, "untrn");
+    }
 }
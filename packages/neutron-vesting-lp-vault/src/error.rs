@@ -20,6 +20,12 @@ pub enum ContractError {
 
     #[error("config description cannot be empty.")]
     DescriptionIsEmpty {},
+
+    #[error("config denom cannot be empty.")]
+    DenomIsEmpty {},
+
+    #[error("config denom {denom} is not an asset of the {contract} pair.")]
+    DenomNotInPair { denom: String, contract: String },
 }
 
 pub type ContractResult<T> = Result<T, ContractError>;
@@ -23,6 +23,9 @@ pub struct InstantiateMsg {
     pub usdc_oracle_contract: String,
     /// Owner can update all configs including changing the owner. This will generally be a DAO.
     pub owner: String,
+    /// The denom LP tokens are valued in. NTRN by default.
+    #[serde(default = "neutron_voting_power::voting_power::default_denom")]
+    pub denom: String,
 }
 
 #[voting_vault]
@@ -37,6 +40,7 @@ pub enum ExecuteMsg {
         usdc_oracle_contract: String,
         name: String,
         description: String,
+        denom: Option<String>,
     },
 }
 
@@ -12,6 +12,8 @@ pub struct Config {
     pub usdc_vesting_lp_contract: Addr,
     pub usdc_oracle_contract: Addr,
     pub owner: Addr,
+    #[serde(default = "neutron_voting_power::voting_power::default_denom")]
+    pub denom: String,
 }
 
 impl Config {
@@ -23,6 +25,9 @@ impl Config {
         if self.description.is_empty() {
             return Err(ContractError::DescriptionIsEmpty {});
         }
+        if self.denom.is_empty() {
+            return Err(ContractError::DenomIsEmpty {});
+        }
         Ok(())
     }
 }
@@ -30,7 +35,7 @@ impl Config {
 #[cfg(test)]
 mod tests {
     use crate::{error::ContractError, types::Config};
-    use cosmwasm_std::Addr;
+    use cosmwasm_std::{from_json, Addr};
 
     #[test]
     fn valid_config() {
@@ -42,6 +47,7 @@ mod tests {
             usdc_vesting_lp_contract: Addr::unchecked("usdc_vesting_lp_contract"),
             usdc_oracle_contract: Addr::unchecked("usdc_oracle_contract"),
             owner: Addr::unchecked("owner"),
+            denom: String::from("untrn"),
         };
         assert!(cfg.validate().is_ok());
     }
@@ -56,6 +62,7 @@ mod tests {
             usdc_vesting_lp_contract: Addr::unchecked("usdc_vesting_lp_contract"),
             usdc_oracle_contract: Addr::unchecked("usdc_oracle_contract"),
             owner: Addr::unchecked("owner"),
+            denom: String::from("untrn"),
         };
         assert_eq!(cfg.validate(), Err(ContractError::NameIsEmpty {}));
     }
@@ -70,7 +77,41 @@ mod tests {
             usdc_vesting_lp_contract: Addr::unchecked("usdc_vesting_lp_contract"),
             usdc_oracle_contract: Addr::unchecked("usdc_oracle_contract"),
             owner: Addr::unchecked("owner"),
+            denom: String::from("untrn"),
         };
         assert_eq!(cfg.validate(), Err(ContractError::DescriptionIsEmpty {}));
     }
+
+    #[test]
+    fn empty_denom() {
+        let cfg = Config {
+            name: String::from("name"),
+            description: String::from("description"),
+            atom_vesting_lp_contract: Addr::unchecked("atom_vesting_lp_contract"),
+            atom_oracle_contract: Addr::unchecked("atom_oracle_contract"),
+            usdc_vesting_lp_contract: Addr::unchecked("usdc_vesting_lp_contract"),
+            usdc_oracle_contract: Addr::unchecked("usdc_oracle_contract"),
+            owner: Addr::unchecked("owner"),
+            denom: String::from(""),
+        };
+        assert_eq!(cfg.validate(), Err(ContractError::DenomIsEmpty {}));
+    }
+
+    #[test]
+    fn default_denom() {
+        // configs stored before the denom became configurable don't have it
+        let cfg: Config = from_json(
+            br#"{
+                "name": "name",
+                "description": "description",
+                "atom_vesting_lp_contract": "atom_vesting_lp_contract",
+                "atom_oracle_contract": "atom_oracle_contract",
+                "usdc_vesting_lp_contract": "usdc_vesting_lp_contract",
+                "usdc_oracle_contract": "usdc_oracle_contract",
+                "owner": "owner"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(cfg.denom, "untrn");
+    }
 }
@@ -0,0 +1,52 @@
+use cosmwasm_std::{StdResult, Storage};
+use cw_storage_plus::SnapshotItem;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Returns the value the item had at the height, so that config updates of a vault don't change
+/// the voting power at past heights. Heights before the item was first saved get the current
+/// value.
+pub fn load_at_height<T>(
+    storage: &dyn Storage,
+    item: &SnapshotItem<'_, T>,
+    height: u64,
+) -> StdResult<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    match item.may_load_at_height(storage, height)? {
+        Some(value) => Ok(value),
+        None => item.load(storage),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::load_at_height;
+    use cosmwasm_std::testing::MockStorage;
+    use cw_storage_plus::{Item, SnapshotItem, Strategy};
+
+    const CONFIG: SnapshotItem<String> = SnapshotItem::new(
+        "config",
+        "config__checkpoints",
+        "config__changelog",
+        Strategy::EveryBlock,
+    );
+
+    #[test]
+    fn test_load_at_height() {
+        let mut storage = MockStorage::new();
+
+        // saved before the item was snapshotted
+        Item::<String>::new("config")
+            .save(&mut storage, &String::from("legacy"))
+            .unwrap();
+        assert_eq!(load_at_height(&storage, &CONFIG, 5).unwrap(), "legacy");
+
+        CONFIG
+            .save(&mut storage, &String::from("updated"), 10)
+            .unwrap();
+        assert_eq!(load_at_height(&storage, &CONFIG, 5).unwrap(), "legacy");
+        assert_eq!(load_at_height(&storage, &CONFIG, 10).unwrap(), "legacy");
+        assert_eq!(load_at_height(&storage, &CONFIG, 11).unwrap(), "updated");
+    }
+}
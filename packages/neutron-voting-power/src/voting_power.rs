@@ -1,10 +1,79 @@
-use cosmwasm_std::{Addr, Deps, StdResult, Uint128, Uint64};
+use astroport::asset::{AssetInfo, PairInfo};
+use cosmwasm_std::{Addr, Decimal256, Deps, StdError, StdResult, Uint128, Uint256, Uint64};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Limits the number of heights sampled by a windowed valuation, each sample costs two queries.
+pub const MAX_VALUATION_SAMPLES: u64 = 10;
+/// Limits the number of blocks a windowed valuation looks back over.
+pub const MAX_VALUATION_WINDOW: u64 = 100_000;
+
+/// NTRN, the denom LP tokens were valued in before it became configurable.
+pub fn default_denom() -> String {
+    String::from("untrn")
+}
+
+/// Defines how LP tokens of a CL pool are valued in the base denom.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClPoolValuation {
+    /// Base denom balance of the pool at the snapshot height.
+    Spot {},
+    /// Average base denom amount per LP token, taken at `samples` heights evenly
+    /// spread over the `window` blocks preceding the snapshot height.
+    Twap { window: u64, samples: u64 },
+    /// Median base denom amount per LP token over the same heights as `Twap`.
+    /// A single manipulated block can't move it.
+    Median { window: u64, samples: u64 },
+}
+
+impl Default for ClPoolValuation {
+    fn default() -> Self {
+        ClPoolValuation::Spot {}
+    }
+}
+
+impl ClPoolValuation {
+    /// checks whether the valuation window is valid.
+    pub fn validate(&self) -> StdResult<()> {
+        match self {
+            ClPoolValuation::Spot {} => Ok(()),
+            ClPoolValuation::Twap { window, samples }
+            | ClPoolValuation::Median { window, samples } => {
+                if *window == 0 || *window > MAX_VALUATION_WINDOW {
+                    return Err(StdError::generic_err(format!(
+                        "valuation window must be between 1 and {}",
+                        MAX_VALUATION_WINDOW
+                    )));
+                }
+                if *samples < 2 || *samples > MAX_VALUATION_SAMPLES {
+                    return Err(StdError::generic_err(format!(
+                        "valuation samples must be between 2 and {}",
+                        MAX_VALUATION_SAMPLES
+                    )));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// checks whether the native denom is one of the CL pool assets.
+pub fn cl_pool_has_denom(deps: Deps, cl_pool: &Addr, denom: &str) -> StdResult<bool> {
+    let pair_info: PairInfo = deps
+        .querier
+        .query_wasm_smart(cl_pool, &astroport::pair_concentrated::QueryMsg::Pair {})?;
+    Ok(pair_info.asset_infos.contains(&AssetInfo::NativeToken {
+        denom: denom.to_string(),
+    }))
+}
 
 pub fn voting_power_from_lp_tokens(
     deps: Deps,
     lp_tokens: Uint128,
     total_lp_tokens: Uint128,
     cl_pool: &Addr,
+    denom: &str,
     height: u64,
 ) -> StdResult<Uint128> {
     if lp_tokens.is_zero() {
@@ -13,8 +82,8 @@ pub fn voting_power_from_lp_tokens(
         let balance_resp: Option<Uint128> = deps.querier.query_wasm_smart(
             cl_pool,
             &astroport::pair_concentrated::QueryMsg::AssetBalanceAt {
-                asset_info: astroport::asset::AssetInfo::NativeToken {
-                    denom: "untrn".to_string(),
+                asset_info: AssetInfo::NativeToken {
+                    denom: denom.to_string(),
                 },
                 block_height: Uint64::from(height),
             },
@@ -32,3 +101,306 @@ pub fn voting_power_from_lp_tokens(
         Ok(lp_tokens.multiply_ratio(ntrn_balance_in_pool, total_lp_tokens))
     }
 }
+
+/// Values LP tokens of a CL pool according to the given valuation.
+pub fn voting_power_from_lp_tokens_with_valuation(
+    deps: Deps,
+    lp_tokens: Uint128,
+    lp_token: &Addr,
+    cl_pool: &Addr,
+    denom: &str,
+    height: u64,
+    valuation: &ClPoolValuation,
+) -> StdResult<Uint128> {
+    if lp_tokens.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    let (window, samples) = match valuation {
+        ClPoolValuation::Spot {} => {
+            let total_lp_tokens = query_lp_total_supply(deps, lp_token, height)?;
+            return voting_power_from_lp_tokens(
+                deps,
+                lp_tokens,
+                total_lp_tokens,
+                cl_pool,
+                denom,
+                height,
+            );
+        }
+        ClPoolValuation::Twap { window, samples } | ClPoolValuation::Median { window, samples } => {
+            (*window, *samples)
+        }
+    };
+
+    let mut prices = (0..samples)
+        .map(|i| {
+            let offset = Uint64::from(window).multiply_ratio(i, samples - 1);
+            height.saturating_sub(offset.u64())
+        })
+        .map(|sample_height| denom_per_lp_token(deps, lp_token, cl_pool, denom, sample_height))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let price = match valuation {
+        ClPoolValuation::Median { .. } => {
+            prices.sort();
+            let middle = prices.len() / 2;
+            if prices.len() % 2 == 1 {
+                prices[middle]
+            } else {
+                (prices[middle - 1] + prices[middle])
+                    .checked_div(Decimal256::from_ratio(2u64, 1u64))
+                    .map_err(|err| StdError::generic_err(err.to_string()))?
+            }
+        }
+        _ => prices
+            .iter()
+            .sum::<Decimal256>()
+            .checked_div(Decimal256::from_ratio(prices.len() as u64, 1u64))
+            .map_err(|err| StdError::generic_err(err.to_string()))?,
+    };
+
+    (Uint256::from(lp_tokens) * price)
+        .try_into()
+        .map_err(StdError::from)
+}
+
+fn denom_per_lp_token(
+    deps: Deps,
+    lp_token: &Addr,
+    cl_pool: &Addr,
+    denom: &str,
+    height: u64,
+) -> StdResult<Decimal256> {
+    let total_lp_tokens = query_lp_total_supply(deps, lp_token, height)?;
+    if total_lp_tokens.is_zero() {
+        return Ok(Decimal256::zero());
+    }
+
+    let denom_balance: Option<Uint128> = deps.querier.query_wasm_smart(
+        cl_pool,
+        &astroport::pair_concentrated::QueryMsg::AssetBalanceAt {
+            asset_info: AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            },
+            block_height: Uint64::from(height),
+        },
+    )?;
+
+    Ok(Decimal256::from_ratio(
+        denom_balance.unwrap_or_default(),
+        total_lp_tokens,
+    ))
+}
+
+fn query_lp_total_supply(deps: Deps, lp_token: &Addr, height: u64) -> StdResult<Uint128> {
+    deps.querier.query_wasm_smart(
+        lp_token,
+        &astroport::xastro_token::QueryMsg::TotalSupplyAt { block: height },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cl_pool_has_denom, voting_power_from_lp_tokens_with_valuation, ClPoolValuation,
+        MAX_VALUATION_SAMPLES, MAX_VALUATION_WINDOW,
+    };
+    use astroport::asset::{AssetInfo, PairInfo};
+    use astroport::factory::PairType;
+    use cosmwasm_std::testing::{mock_dependencies, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{
+        from_json, to_json_binary, Addr, ContractResult, OwnedDeps, SystemResult, Uint128,
+        WasmQuery,
+    };
+
+    const LP_TOKEN: &str = "lp_token";
+    const CL_POOL: &str = "cl_pool";
+    const HEIGHT: u64 = 100;
+    const NTRN_DENOM: &str = "untrn";
+    const SUBDAO_DENOM: &str = "usubdao";
+
+    // The pool holds 1000 NTRN and 3000 SUBDAO for 1000 LP tokens, but a large swap
+    // at HEIGHT pushed its NTRN balance to 100_000 for a single block.
+    fn manipulated_pool_deps() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| {
+            let response = match query {
+                WasmQuery::Smart { contract_addr, msg } if contract_addr == LP_TOKEN => {
+                    match from_json(msg).unwrap() {
+                        astroport::xastro_token::QueryMsg::TotalSupplyAt { block: _ } => {
+                            to_json_binary(&Uint128::new(1_000))
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+                WasmQuery::Smart { contract_addr, msg } if contract_addr == CL_POOL => {
+                    match from_json(msg).unwrap() {
+                        astroport::pair_concentrated::QueryMsg::AssetBalanceAt {
+                            asset_info: AssetInfo::NativeToken { denom },
+                            block_height,
+                        } => to_json_binary(&Some(Uint128::new(match denom.as_str() {
+                            NTRN_DENOM if block_height.u64() == HEIGHT => 100_000,
+                            NTRN_DENOM => 1_000,
+                            SUBDAO_DENOM => 3_000,
+                            _ => 0,
+                        }))),
+                        astroport::pair_concentrated::QueryMsg::Pair {} => {
+                            to_json_binary(&PairInfo {
+                                asset_infos: vec![
+                                    AssetInfo::NativeToken {
+                                        denom: NTRN_DENOM.to_string(),
+                                    },
+                                    AssetInfo::NativeToken {
+                                        denom: SUBDAO_DENOM.to_string(),
+                                    },
+                                ],
+                                contract_addr: Addr::unchecked(CL_POOL),
+                                liquidity_token: Addr::unchecked(LP_TOKEN),
+                                pair_type: PairType::Custom("concentrated".to_string()),
+                            })
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+                _ => unreachable!(),
+            };
+            SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+        });
+        deps
+    }
+
+    fn power(valuation: ClPoolValuation, height: u64) -> Uint128 {
+        power_in_denom(valuation, NTRN_DENOM, height)
+    }
+
+    fn power_in_denom(valuation: ClPoolValuation, denom: &str, height: u64) -> Uint128 {
+        let deps = manipulated_pool_deps();
+        voting_power_from_lp_tokens_with_valuation(
+            deps.as_ref(),
+            Uint128::new(100),
+            &Addr::unchecked(LP_TOKEN),
+            &Addr::unchecked(CL_POOL),
+            denom,
+            height,
+            &valuation,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_spot_valuation() {
+        assert_eq!(
+            power(ClPoolValuation::Spot {}, HEIGHT - 1),
+            Uint128::new(100)
+        );
+        // the manipulated block multiplies the voting power by 100
+        assert_eq!(
+            power(ClPoolValuation::Spot {}, HEIGHT),
+            Uint128::new(10_000)
+        );
+    }
+
+    #[test]
+    fn test_twap_valuation() {
+        let valuation = ClPoolValuation::Twap {
+            window: 10,
+            samples: 6,
+        };
+        assert_eq!(power(valuation.clone(), HEIGHT - 1), Uint128::new(100));
+        // (100 + 5 * 1) / 6 NTRN per LP token
+        assert_eq!(power(valuation, HEIGHT), Uint128::new(1_750));
+    }
+
+    #[test]
+    fn test_median_valuation() {
+        let valuation = ClPoolValuation::Median {
+            window: 10,
+            samples: 5,
+        };
+        assert_eq!(power(valuation.clone(), HEIGHT - 1), Uint128::new(100));
+        assert_eq!(power(valuation, HEIGHT), Uint128::new(100));
+
+        // with an even number of samples the two middle prices are averaged
+        let valuation = ClPoolValuation::Median {
+            window: 10,
+            samples: 2,
+        };
+        assert_eq!(power(valuation, HEIGHT), Uint128::new(5_050));
+    }
+
+    #[test]
+    fn test_non_default_denom() {
+        assert_eq!(
+            power_in_denom(ClPoolValuation::Spot {}, SUBDAO_DENOM, HEIGHT),
+            Uint128::new(300)
+        );
+        let valuation = ClPoolValuation::Twap {
+            window: 10,
+            samples: 5,
+        };
+        assert_eq!(
+            power_in_denom(valuation, SUBDAO_DENOM, HEIGHT),
+            Uint128::new(300)
+        );
+        // the pool doesn't hold the denom at all
+        assert_eq!(
+            power_in_denom(ClPoolValuation::Spot {}, "uatom", HEIGHT),
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn test_validate_valuation() {
+        assert!(ClPoolValuation::Spot {}.validate().is_ok());
+        assert!(ClPoolValuation::Twap {
+            window: 10,
+            samples: 5
+        }
+        .validate()
+        .is_ok());
+        assert!(ClPoolValuation::Twap {
+            window: 0,
+            samples: 5
+        }
+        .validate()
+        .is_err());
+        assert!(ClPoolValuation::Median {
+            window: 10,
+            samples: 1
+        }
+        .validate()
+        .is_err());
+        assert!(ClPoolValuation::Median {
+            window: 10,
+            samples: MAX_VALUATION_SAMPLES + 1
+        }
+        .validate()
+        .is_err());
+        assert!(ClPoolValuation::Twap {
+            window: MAX_VALUATION_WINDOW + 1,
+            samples: 5
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_max_window_valuation() {
+        // sample heights don't overflow with the largest window and are capped at zero
+        let valuation = ClPoolValuation::Twap {
+            window: MAX_VALUATION_WINDOW,
+            samples: MAX_VALUATION_SAMPLES,
+        };
+        assert_eq!(power(valuation, HEIGHT - 1), Uint128::new(100));
+    }
+
+    #[test]
+    fn test_cl_pool_has_denom() {
+        let deps = manipulated_pool_deps();
+        let cl_pool = Addr::unchecked(CL_POOL);
+        assert!(cl_pool_has_denom(deps.as_ref(), &cl_pool, NTRN_DENOM).unwrap());
+        assert!(cl_pool_has_denom(deps.as_ref(), &cl_pool, SUBDAO_DENOM).unwrap());
+        assert!(!cl_pool_has_denom(deps.as_ref(), &cl_pool, "uatom").unwrap());
+    }
+}